@@ -1,6 +1,12 @@
+pub mod backend;
 pub mod data_access;
 pub mod display;
 pub mod graphics;
+pub mod instance;
 pub mod menus;
+pub mod naming;
+pub mod navigation;
 pub mod plugin;
+pub mod processing;
+pub mod scenery;
 pub mod utilities;
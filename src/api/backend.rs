@@ -0,0 +1,135 @@
+//! The layer between the crate and the XPLM library.
+//!
+//! Data access, command, basic window, aircraft, placement, AI plane, directory listing,
+//! logging and speech functions of the crate call X-Plane through [`Backend`]. By default
+//! it is implemented by plain FFI calls. With the `mock` feature enabled it is replaced by
+//! an in-memory fake, so plugin logic built on top of these functions can be unit-tested
+//! without X-Plane, see [`fake`] for the test controls.
+//!
+//! With the `trace` feature enabled the calls can be recorded at runtime, see [`trace`].
+//!
+//! Functions which are not routed through the backend still call X-Plane directly and
+//! must not be used in headless tests.
+
+#[cfg(feature = "mock")]
+pub mod fake;
+#[cfg(not(feature = "mock"))]
+mod ffi;
+#[cfg(feature = "trace")]
+pub mod trace;
+
+use std::ffi::CStr;
+
+/// XPLM functions the crate calls through the active backend.
+pub(crate) trait Backend {
+    fn find_data_ref(&self, name: &CStr) -> xplm_sys::XPLMDataRef;
+    fn can_write_data_ref(&self, data_ref: xplm_sys::XPLMDataRef) -> bool;
+    fn is_data_ref_good(&self, data_ref: xplm_sys::XPLMDataRef) -> bool;
+    fn get_data_ref_types(&self, data_ref: xplm_sys::XPLMDataRef) -> xplm_sys::XPLMDataTypeID;
+    fn get_datai(&self, data_ref: xplm_sys::XPLMDataRef) -> ::std::os::raw::c_int;
+    fn set_datai(&self, data_ref: xplm_sys::XPLMDataRef, value: ::std::os::raw::c_int);
+    fn get_dataf(&self, data_ref: xplm_sys::XPLMDataRef) -> f32;
+    fn set_dataf(&self, data_ref: xplm_sys::XPLMDataRef, value: f32);
+    fn get_datad(&self, data_ref: xplm_sys::XPLMDataRef) -> f64;
+    fn set_datad(&self, data_ref: xplm_sys::XPLMDataRef, value: f64);
+    fn get_datavi_len(&self, data_ref: xplm_sys::XPLMDataRef) -> usize;
+    fn get_datavi(
+        &self,
+        data_ref: xplm_sys::XPLMDataRef,
+        offset: usize,
+        values: &mut [::std::os::raw::c_int],
+    ) -> usize;
+    fn set_datavi(
+        &self,
+        data_ref: xplm_sys::XPLMDataRef,
+        offset: usize,
+        values: &[::std::os::raw::c_int],
+    );
+    fn get_datavf_len(&self, data_ref: xplm_sys::XPLMDataRef) -> usize;
+    fn get_datavf(
+        &self,
+        data_ref: xplm_sys::XPLMDataRef,
+        offset: usize,
+        values: &mut [f32],
+    ) -> usize;
+    fn set_datavf(&self, data_ref: xplm_sys::XPLMDataRef, offset: usize, values: &[f32]);
+    fn get_datab_len(&self, data_ref: xplm_sys::XPLMDataRef) -> usize;
+    fn get_datab(&self, data_ref: xplm_sys::XPLMDataRef, offset: usize, values: &mut [u8])
+        -> usize;
+    fn set_datab(&self, data_ref: xplm_sys::XPLMDataRef, offset: usize, values: &[u8]);
+
+    fn find_command(&self, name: &CStr) -> xplm_sys::XPLMCommandRef;
+    fn create_command(&self, name: &CStr, description: &CStr) -> xplm_sys::XPLMCommandRef;
+    fn command_begin(&self, command: xplm_sys::XPLMCommandRef);
+    fn command_end(&self, command: xplm_sys::XPLMCommandRef);
+    fn command_once(&self, command: xplm_sys::XPLMCommandRef);
+
+    fn create_window_ex(&self, params: &mut xplm_sys::XPLMCreateWindow_t)
+        -> xplm_sys::XPLMWindowID;
+    fn destroy_window(&self, id: xplm_sys::XPLMWindowID);
+    fn get_window_geometry(&self, id: xplm_sys::XPLMWindowID) -> [::std::os::raw::c_int; 4];
+    fn set_window_geometry(&self, id: xplm_sys::XPLMWindowID, geometry: [::std::os::raw::c_int; 4]);
+    fn get_window_is_visible(&self, id: xplm_sys::XPLMWindowID) -> bool;
+    fn set_window_is_visible(&self, id: xplm_sys::XPLMWindowID, visible: bool);
+
+    fn get_nth_aircraft_model(
+        &self,
+        index: ::std::os::raw::c_int,
+        file_name: &mut [::std::os::raw::c_char; 256],
+        path: &mut [::std::os::raw::c_char; 512],
+    );
+    fn set_users_aircraft(&self, path: &CStr);
+    fn get_directory_contents(
+        &self,
+        dir: &CStr,
+        offset: ::std::os::raw::c_int,
+        names: &mut [::std::os::raw::c_char],
+        indices: &mut [*mut ::std::os::raw::c_char],
+        total: &mut ::std::os::raw::c_int,
+        returned: &mut ::std::os::raw::c_int,
+    ) -> bool;
+
+    fn acquire_planes(&self) -> bool;
+    fn release_planes(&self);
+
+    fn place_user_at_location(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        elevation: f32,
+        heading: f32,
+        speed: f32,
+    );
+    fn place_user_at_airport(&self, icao: &CStr);
+
+    fn debug_string(&self, message: &CStr);
+    fn speak_string(&self, message: &CStr);
+}
+
+/// Returns the active backend.
+#[cfg(not(feature = "trace"))]
+pub(crate) fn backend() -> &'static dyn Backend {
+    inner()
+}
+
+/// Returns the active backend, the tracing one while a trace is recorded.
+#[cfg(feature = "trace")]
+pub(crate) fn backend() -> &'static dyn Backend {
+    if trace::is_tracing() {
+        &trace::Tracing
+    } else {
+        inner()
+    }
+}
+
+/// Returns the backend which talks to X-Plane or the fake.
+#[cfg(not(feature = "mock"))]
+fn inner() -> &'static dyn Backend {
+    &ffi::Ffi
+}
+
+/// Returns the backend which talks to X-Plane or the fake.
+#[cfg(feature = "mock")]
+fn inner() -> &'static dyn Backend {
+    &fake::Fake
+}
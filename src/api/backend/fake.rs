@@ -0,0 +1,721 @@
+//! An in-memory fake of X-Plane for headless unit tests.
+//! Available with the `mock` feature enabled.
+//!
+//! The fake state is kept per thread, so tests running in parallel don't see each other.
+//! Data refs and commands have to be defined before the code under test looks them up:
+//!
+//! ```ignore
+//! fake::reset();
+//! fake::define_data_ref("sim/cockpit2/controls/gear_handle_down", FakeData::Int(1));
+//! fake::define_command("sim/flight_controls/landing_gear_toggle");
+//!
+//! gear_toggle_logic()?;
+//!
+//! assert_eq!(fake::command_stats("sim/flight_controls/landing_gear_toggle").unwrap().once, 1);
+//! ```
+//!
+//! Command handlers registered by the plugin are not called by the fake. Directory listings
+//! read the real file system, so tests can point the user's aircraft at a temporary folder
+//! with [`set_aircraft_path`].
+
+use std::cell::RefCell;
+use std::ffi::CStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::api::display::{Rect, WindowId};
+use crate::api::utilities::os_path;
+
+use super::Backend;
+
+/// A value of a fake data ref.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FakeData {
+    /// An integer value.
+    Int(::std::os::raw::c_int),
+    /// A single precision floating point value.
+    Float(f32),
+    /// A double precision floating point value.
+    Double(f64),
+    /// An integer array.
+    IntArray(Vec<::std::os::raw::c_int>),
+    /// A single precision floating point array.
+    FloatArray(Vec<f32>),
+    /// A byte array.
+    Bytes(Vec<u8>),
+}
+
+impl FakeData {
+    fn type_id(&self) -> xplm_sys::XPLMDataTypeID {
+        let id = match self {
+            Self::Int(_) => xplm_sys::xplmType_Int,
+            Self::Float(_) => xplm_sys::xplmType_Float,
+            Self::Double(_) => xplm_sys::xplmType_Double,
+            Self::IntArray(_) => xplm_sys::xplmType_IntArray,
+            Self::FloatArray(_) => xplm_sys::xplmType_FloatArray,
+            Self::Bytes(_) => xplm_sys::xplmType_Data,
+        };
+        id as _
+    }
+
+    fn as_f64(&self) -> f64 {
+        match self {
+            Self::Int(value) => *value as _,
+            Self::Float(value) => *value as _,
+            Self::Double(value) => *value,
+            _ => 0.0,
+        }
+    }
+
+    fn set_scalar(&mut self, value: f64) {
+        match self {
+            Self::Int(current) => *current = value as _,
+            Self::Float(current) => *current = value as _,
+            Self::Double(current) => *current = value,
+            _ => {}
+        }
+    }
+}
+
+/// Execution counters of a fake command.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct CommandStats {
+    /// The number of times the command has been run once.
+    pub once: usize,
+    /// The number of times the command has begun.
+    pub begin: usize,
+    /// The number of times the command has ended.
+    pub end: usize,
+}
+
+impl CommandStats {
+    /// Checks wether the command has begun and not ended yet.
+    pub fn is_active(&self) -> bool {
+        self.begin > self.end
+    }
+}
+
+/// A placement of the user's aircraft made by the code under test.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FakePlacement {
+    /// The aircraft has been placed at a location.
+    Location {
+        /// The latitude in degrees.
+        latitude: f64,
+        /// The longitude in degrees.
+        longitude: f64,
+        /// The elevation in meters MSL.
+        elevation: f32,
+        /// The true heading in degrees.
+        heading: f32,
+        /// The speed in meters per second.
+        speed: f32,
+    },
+    /// The aircraft has been placed at an airport.
+    Airport(String),
+}
+
+struct FakeDataRef {
+    name: String,
+    value: FakeData,
+    writable: bool,
+}
+
+struct FakeCommand {
+    name: String,
+    stats: CommandStats,
+}
+
+struct FakeWindow {
+    geometry: [::std::os::raw::c_int; 4],
+    visible: bool,
+}
+
+#[derive(Default)]
+struct State {
+    data_refs: Vec<FakeDataRef>,
+    commands: Vec<FakeCommand>,
+    windows: Vec<Option<FakeWindow>>,
+    log: Vec<String>,
+    aircraft_path: Option<PathBuf>,
+    loaded_aircraft: Vec<PathBuf>,
+    planes_acquired: bool,
+    placements: Vec<FakePlacement>,
+    spoken: Vec<String>,
+}
+
+thread_local! {
+    static STATE: RefCell<State> = RefCell::new(State::default());
+}
+
+/// Removes all fake data refs, commands, windows and the aircraft of the calling thread.
+pub fn reset() {
+    STATE.with(|state| *state.borrow_mut() = State::default());
+}
+
+/// Defines a writable data ref or replaces the value of an existing one.
+///
+/// # Arguments
+/// * `name` - a data ref name.
+/// * `value` - a data ref value. See [`FakeData`] for more details.
+pub fn define_data_ref<N: Into<String>>(name: N, value: FakeData) {
+    define(name.into(), value, true);
+}
+
+/// Defines a read-only data ref, writes from the code under test are ignored.
+///
+/// # Arguments
+/// * `name` - a data ref name.
+/// * `value` - a data ref value. See [`FakeData`] for more details.
+pub fn define_read_only_data_ref<N: Into<String>>(name: N, value: FakeData) {
+    define(name.into(), value, false);
+}
+
+/// Returns the current value of a data ref, e.g. to check what the code under test wrote.
+///
+/// # Arguments
+/// * `name` - a data ref name.
+///
+/// # Returns
+/// Returns [`FakeData`] or [`None`] if the data ref is not defined.
+pub fn data(name: &str) -> Option<FakeData> {
+    STATE.with(|state| {
+        state
+            .borrow()
+            .data_refs
+            .iter()
+            .find(|data_ref| data_ref.name == name)
+            .map(|data_ref| data_ref.value.clone())
+    })
+}
+
+/// Defines a command, e.g. one provided by X-Plane.
+///
+/// # Arguments
+/// * `name` - a command name.
+pub fn define_command<N: Into<String>>(name: N) {
+    let name = name.into();
+    STATE.with(|state| {
+        command_index(&mut state.borrow_mut(), &name, true);
+    });
+}
+
+/// Returns execution counters of a command.
+///
+/// # Arguments
+/// * `name` - a command name.
+///
+/// # Returns
+/// Returns [`CommandStats`] or [`None`] if the command is not defined.
+pub fn command_stats(name: &str) -> Option<CommandStats> {
+    STATE.with(|state| {
+        state
+            .borrow()
+            .commands
+            .iter()
+            .find(|command| command.name == name)
+            .map(|command| command.stats)
+    })
+}
+
+/// Returns the number of windows which have not been destroyed.
+pub fn window_count() -> usize {
+    STATE.with(|state| state.borrow().windows.iter().flatten().count())
+}
+
+/// Returns the geometry of a window.
+///
+/// # Arguments
+/// * `id` - a window identifier.
+///
+/// # Returns
+/// Returns [`Rect`] or [`None`] if the window does not exist.
+pub fn window_geometry(id: &WindowId) -> Option<Rect> {
+    with_window(**id, |window| {
+        let [left, top, right, bottom] = window.geometry;
+        Rect::new(left, top, right, bottom)
+    })
+}
+
+/// Checks wether a window is visible.
+///
+/// # Arguments
+/// * `id` - a window identifier.
+///
+/// # Returns
+/// Returns `true` if the window exists and is visible. Otherwise returns `false`.
+pub fn is_window_visible(id: &WindowId) -> bool {
+    with_window(**id, |window| window.visible).unwrap_or(false)
+}
+
+/// Returns the messages written to the log, e.g. by [`crate::warn`], in the order they
+/// were written.
+pub fn log_messages() -> Vec<String> {
+    STATE.with(|state| state.borrow().log.clone())
+}
+
+/// Sets the `.acf` file of the user's aircraft.
+///
+/// # Arguments
+/// * `path` - a path to the `.acf` file.
+pub fn set_aircraft_path<P: Into<PathBuf>>(path: P) {
+    STATE.with(|state| state.borrow_mut().aircraft_path = Some(path.into()));
+}
+
+/// Returns the aircraft paths the code under test has loaded as the user's aircraft,
+/// in the order they were loaded.
+pub fn loaded_aircraft() -> Vec<PathBuf> {
+    STATE.with(|state| state.borrow().loaded_aircraft.clone())
+}
+
+/// Checks wether the code under test holds the AI planes.
+pub fn are_planes_acquired() -> bool {
+    STATE.with(|state| state.borrow().planes_acquired)
+}
+
+/// Returns the placements of the user's aircraft in the order they were made.
+pub fn placements() -> Vec<FakePlacement> {
+    STATE.with(|state| state.borrow().placements.clone())
+}
+
+/// Returns the messages passed to [`crate::api::utilities::speak_string`] in the order they
+/// were spoken.
+pub fn spoken_messages() -> Vec<String> {
+    STATE.with(|state| state.borrow().spoken.clone())
+}
+
+fn define(name: String, value: FakeData, writable: bool) {
+    STATE.with(|state| {
+        let data_refs = &mut state.borrow_mut().data_refs;
+        match data_refs.iter_mut().find(|data_ref| data_ref.name == name) {
+            Some(data_ref) => {
+                data_ref.value = value;
+                data_ref.writable = writable;
+            }
+            None => data_refs.push(FakeDataRef {
+                name,
+                value,
+                writable,
+            }),
+        }
+    });
+}
+
+fn command_index(state: &mut State, name: &str, create: bool) -> Option<usize> {
+    if let Some(index) = state
+        .commands
+        .iter()
+        .position(|command| command.name == name)
+    {
+        return Some(index);
+    }
+
+    if !create {
+        return None;
+    }
+
+    state.commands.push(FakeCommand {
+        name: name.to_string(),
+        stats: CommandStats::default(),
+    });
+    Some(state.commands.len() - 1)
+}
+
+fn to_handle(index: usize) -> *mut ::std::os::raw::c_void {
+    (index + 1) as _
+}
+
+fn from_handle(handle: *mut ::std::os::raw::c_void) -> Option<usize> {
+    (handle as usize).checked_sub(1)
+}
+
+fn with_data_ref<R>(
+    data_ref: xplm_sys::XPLMDataRef,
+    default: R,
+    f: impl FnOnce(&mut FakeDataRef) -> R,
+) -> R {
+    STATE.with(|state| {
+        from_handle(data_ref)
+            .and_then(|index| state.borrow_mut().data_refs.get_mut(index).map(f))
+            .unwrap_or(default)
+    })
+}
+
+fn with_command(command: xplm_sys::XPLMCommandRef, f: impl FnOnce(&mut CommandStats)) {
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        if let Some(command) = from_handle(command).and_then(|index| state.commands.get_mut(index))
+        {
+            f(&mut command.stats);
+        }
+    });
+}
+
+fn with_window<R>(id: xplm_sys::XPLMWindowID, f: impl FnOnce(&mut FakeWindow) -> R) -> Option<R> {
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        from_handle(id)
+            .and_then(|index| state.windows.get_mut(index))
+            .and_then(Option::as_mut)
+            .map(f)
+    })
+}
+
+fn read_slice<T: Copy>(source: &[T], offset: usize, values: &mut [T]) -> usize {
+    let source = source.get(offset..).unwrap_or_default();
+    let count = source.len().min(values.len());
+    values[..count].copy_from_slice(&source[..count]);
+    count
+}
+
+fn write_slice<T: Copy>(target: &mut [T], offset: usize, values: &[T]) {
+    let target = target.get_mut(offset..).unwrap_or_default();
+    let count = target.len().min(values.len());
+    target[..count].copy_from_slice(&values[..count]);
+}
+
+/// The backend which keeps the simulator state in memory.
+pub(crate) struct Fake;
+
+impl Backend for Fake {
+    fn find_data_ref(&self, name: &CStr) -> xplm_sys::XPLMDataRef {
+        let name = name.to_string_lossy();
+        STATE.with(|state| {
+            state
+                .borrow()
+                .data_refs
+                .iter()
+                .position(|data_ref| data_ref.name == name)
+                .map_or(std::ptr::null_mut(), to_handle)
+        })
+    }
+
+    fn can_write_data_ref(&self, data_ref: xplm_sys::XPLMDataRef) -> bool {
+        with_data_ref(data_ref, false, |data_ref| data_ref.writable)
+    }
+
+    fn is_data_ref_good(&self, data_ref: xplm_sys::XPLMDataRef) -> bool {
+        with_data_ref(data_ref, false, |_| true)
+    }
+
+    fn get_data_ref_types(&self, data_ref: xplm_sys::XPLMDataRef) -> xplm_sys::XPLMDataTypeID {
+        with_data_ref(data_ref, xplm_sys::xplmType_Unknown as _, |data_ref| {
+            data_ref.value.type_id()
+        })
+    }
+
+    fn get_datai(&self, data_ref: xplm_sys::XPLMDataRef) -> ::std::os::raw::c_int {
+        with_data_ref(data_ref, 0.0, |data_ref| data_ref.value.as_f64()) as _
+    }
+
+    fn set_datai(&self, data_ref: xplm_sys::XPLMDataRef, value: ::std::os::raw::c_int) {
+        set_scalar(data_ref, value as _);
+    }
+
+    fn get_dataf(&self, data_ref: xplm_sys::XPLMDataRef) -> f32 {
+        with_data_ref(data_ref, 0.0, |data_ref| data_ref.value.as_f64()) as _
+    }
+
+    fn set_dataf(&self, data_ref: xplm_sys::XPLMDataRef, value: f32) {
+        set_scalar(data_ref, value as _);
+    }
+
+    fn get_datad(&self, data_ref: xplm_sys::XPLMDataRef) -> f64 {
+        with_data_ref(data_ref, 0.0, |data_ref| data_ref.value.as_f64())
+    }
+
+    fn set_datad(&self, data_ref: xplm_sys::XPLMDataRef, value: f64) {
+        set_scalar(data_ref, value);
+    }
+
+    fn get_datavi_len(&self, data_ref: xplm_sys::XPLMDataRef) -> usize {
+        with_data_ref(data_ref, 0, |data_ref| match &data_ref.value {
+            FakeData::IntArray(array) => array.len(),
+            _ => 0,
+        })
+    }
+
+    fn get_datavi(
+        &self,
+        data_ref: xplm_sys::XPLMDataRef,
+        offset: usize,
+        values: &mut [::std::os::raw::c_int],
+    ) -> usize {
+        with_data_ref(data_ref, 0, |data_ref| match &data_ref.value {
+            FakeData::IntArray(array) => read_slice(array, offset, values),
+            _ => 0,
+        })
+    }
+
+    fn set_datavi(
+        &self,
+        data_ref: xplm_sys::XPLMDataRef,
+        offset: usize,
+        values: &[::std::os::raw::c_int],
+    ) {
+        with_data_ref(data_ref, (), |data_ref| match &mut data_ref.value {
+            FakeData::IntArray(array) if data_ref.writable => write_slice(array, offset, values),
+            _ => {}
+        });
+    }
+
+    fn get_datavf_len(&self, data_ref: xplm_sys::XPLMDataRef) -> usize {
+        with_data_ref(data_ref, 0, |data_ref| match &data_ref.value {
+            FakeData::FloatArray(array) => array.len(),
+            _ => 0,
+        })
+    }
+
+    fn get_datavf(
+        &self,
+        data_ref: xplm_sys::XPLMDataRef,
+        offset: usize,
+        values: &mut [f32],
+    ) -> usize {
+        with_data_ref(data_ref, 0, |data_ref| match &data_ref.value {
+            FakeData::FloatArray(array) => read_slice(array, offset, values),
+            _ => 0,
+        })
+    }
+
+    fn set_datavf(&self, data_ref: xplm_sys::XPLMDataRef, offset: usize, values: &[f32]) {
+        with_data_ref(data_ref, (), |data_ref| match &mut data_ref.value {
+            FakeData::FloatArray(array) if data_ref.writable => write_slice(array, offset, values),
+            _ => {}
+        });
+    }
+
+    fn get_datab_len(&self, data_ref: xplm_sys::XPLMDataRef) -> usize {
+        with_data_ref(data_ref, 0, |data_ref| match &data_ref.value {
+            FakeData::Bytes(bytes) => bytes.len(),
+            _ => 0,
+        })
+    }
+
+    fn get_datab(
+        &self,
+        data_ref: xplm_sys::XPLMDataRef,
+        offset: usize,
+        values: &mut [u8],
+    ) -> usize {
+        with_data_ref(data_ref, 0, |data_ref| match &data_ref.value {
+            FakeData::Bytes(bytes) => read_slice(bytes, offset, values),
+            _ => 0,
+        })
+    }
+
+    fn set_datab(&self, data_ref: xplm_sys::XPLMDataRef, offset: usize, values: &[u8]) {
+        with_data_ref(data_ref, (), |data_ref| match &mut data_ref.value {
+            FakeData::Bytes(bytes) if data_ref.writable => write_slice(bytes, offset, values),
+            _ => {}
+        });
+    }
+
+    fn find_command(&self, name: &CStr) -> xplm_sys::XPLMCommandRef {
+        let name = name.to_string_lossy();
+        STATE.with(|state| {
+            command_index(&mut state.borrow_mut(), &name, false)
+                .map_or(std::ptr::null_mut(), to_handle)
+        })
+    }
+
+    fn create_command(&self, name: &CStr, _description: &CStr) -> xplm_sys::XPLMCommandRef {
+        let name = name.to_string_lossy();
+        STATE.with(|state| {
+            command_index(&mut state.borrow_mut(), &name, true)
+                .map_or(std::ptr::null_mut(), to_handle)
+        })
+    }
+
+    fn command_begin(&self, command: xplm_sys::XPLMCommandRef) {
+        with_command(command, |stats| stats.begin += 1);
+    }
+
+    fn command_end(&self, command: xplm_sys::XPLMCommandRef) {
+        with_command(command, |stats| stats.end += 1);
+    }
+
+    fn command_once(&self, command: xplm_sys::XPLMCommandRef) {
+        with_command(command, |stats| stats.once += 1);
+    }
+
+    fn create_window_ex(
+        &self,
+        params: &mut xplm_sys::XPLMCreateWindow_t,
+    ) -> xplm_sys::XPLMWindowID {
+        let window = FakeWindow {
+            geometry: [params.left, params.top, params.right, params.bottom],
+            visible: params.visible != 0,
+        };
+        STATE.with(|state| {
+            let windows = &mut state.borrow_mut().windows;
+            windows.push(Some(window));
+            to_handle(windows.len() - 1)
+        })
+    }
+
+    fn destroy_window(&self, id: xplm_sys::XPLMWindowID) {
+        STATE.with(|state| {
+            let mut state = state.borrow_mut();
+            if let Some(window) = from_handle(id).and_then(|index| state.windows.get_mut(index)) {
+                *window = None;
+            }
+        });
+    }
+
+    fn get_window_geometry(&self, id: xplm_sys::XPLMWindowID) -> [::std::os::raw::c_int; 4] {
+        with_window(id, |window| window.geometry).unwrap_or_default()
+    }
+
+    fn set_window_geometry(
+        &self,
+        id: xplm_sys::XPLMWindowID,
+        geometry: [::std::os::raw::c_int; 4],
+    ) {
+        with_window(id, |window| window.geometry = geometry);
+    }
+
+    fn get_window_is_visible(&self, id: xplm_sys::XPLMWindowID) -> bool {
+        with_window(id, |window| window.visible).unwrap_or(false)
+    }
+
+    fn set_window_is_visible(&self, id: xplm_sys::XPLMWindowID, visible: bool) {
+        with_window(id, |window| window.visible = visible);
+    }
+
+    fn get_nth_aircraft_model(
+        &self,
+        index: ::std::os::raw::c_int,
+        file_name: &mut [::std::os::raw::c_char; 256],
+        path: &mut [::std::os::raw::c_char; 512],
+    ) {
+        file_name[0] = 0;
+        path[0] = 0;
+        if index != 0 {
+            return;
+        }
+
+        STATE.with(|state| {
+            if let Some(aircraft_path) = &state.borrow().aircraft_path {
+                if let Some(name) = aircraft_path.file_name() {
+                    write_c_str(file_name, Path::new(name));
+                }
+                write_c_str(path, aircraft_path);
+            }
+        });
+    }
+
+    fn set_users_aircraft(&self, path: &CStr) {
+        let path = os_path::path_from_c_str(path);
+        STATE.with(|state| {
+            let mut state = state.borrow_mut();
+            state.aircraft_path = Some(path.clone());
+            state.loaded_aircraft.push(path);
+        });
+    }
+
+    fn get_directory_contents(
+        &self,
+        dir: &CStr,
+        offset: ::std::os::raw::c_int,
+        names: &mut [::std::os::raw::c_char],
+        indices: &mut [*mut ::std::os::raw::c_char],
+        total: &mut ::std::os::raw::c_int,
+        returned: &mut ::std::os::raw::c_int,
+    ) -> bool {
+        *total = 0;
+        *returned = 0;
+        let Ok(entries) = fs::read_dir(os_path::path_from_c_str(dir)) else {
+            return true;
+        };
+
+        let mut files: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| PathBuf::from(entry.file_name()))
+            .collect();
+        files.sort();
+        *total = files.len() as _;
+
+        let mut position = 0;
+        for file in files.iter().skip(offset.max(0) as usize) {
+            let Some(bytes) = os_path::path_to_bytes(file) else {
+                continue;
+            };
+            let index = *returned as usize;
+            if index >= indices.len() || position + bytes.len() >= names.len() {
+                return false;
+            }
+
+            write_c_str(&mut names[position..], file);
+            indices[index] = names[position..].as_mut_ptr();
+            position += bytes.len() + 1;
+            *returned += 1;
+        }
+
+        true
+    }
+
+    fn acquire_planes(&self) -> bool {
+        STATE.with(|state| {
+            let mut state = state.borrow_mut();
+            !std::mem::replace(&mut state.planes_acquired, true)
+        })
+    }
+
+    fn release_planes(&self) {
+        STATE.with(|state| state.borrow_mut().planes_acquired = false);
+    }
+
+    fn place_user_at_location(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        elevation: f32,
+        heading: f32,
+        speed: f32,
+    ) {
+        let placement = FakePlacement::Location {
+            latitude,
+            longitude,
+            elevation,
+            heading,
+            speed,
+        };
+        STATE.with(|state| state.borrow_mut().placements.push(placement));
+    }
+
+    fn place_user_at_airport(&self, icao: &CStr) {
+        let placement = FakePlacement::Airport(icao.to_string_lossy().into_owned());
+        STATE.with(|state| state.borrow_mut().placements.push(placement));
+    }
+
+    fn debug_string(&self, message: &CStr) {
+        let message = message.to_string_lossy().into_owned();
+        STATE.with(|state| state.borrow_mut().log.push(message));
+    }
+
+    fn speak_string(&self, message: &CStr) {
+        let message = message.to_string_lossy().into_owned();
+        STATE.with(|state| state.borrow_mut().spoken.push(message));
+    }
+}
+
+fn write_c_str(target: &mut [::std::os::raw::c_char], path: &Path) {
+    let Some(bytes) = os_path::path_to_bytes(path) else {
+        target[0] = 0;
+        return;
+    };
+    let len = bytes.len().min(target.len() - 1);
+    for (target, byte) in target.iter_mut().zip(&bytes[..len]) {
+        *target = *byte as _;
+    }
+    target[len] = 0;
+}
+
+fn set_scalar(data_ref: xplm_sys::XPLMDataRef, value: f64) {
+    with_data_ref(data_ref, (), |data_ref| {
+        if data_ref.writable {
+            data_ref.value.set_scalar(value);
+        }
+    });
+}
@@ -0,0 +1,274 @@
+use std::ffi::CStr;
+
+use super::Backend;
+
+/// The backend which calls the XPLM library.
+pub(crate) struct Ffi;
+
+impl Backend for Ffi {
+    fn find_data_ref(&self, name: &CStr) -> xplm_sys::XPLMDataRef {
+        unsafe { xplm_sys::XPLMFindDataRef(name.as_ptr()) }
+    }
+
+    fn can_write_data_ref(&self, data_ref: xplm_sys::XPLMDataRef) -> bool {
+        unsafe { xplm_sys::XPLMCanWriteDataRef(data_ref) == 1 }
+    }
+
+    fn is_data_ref_good(&self, data_ref: xplm_sys::XPLMDataRef) -> bool {
+        unsafe { xplm_sys::XPLMIsDataRefGood(data_ref) == 1 }
+    }
+
+    fn get_data_ref_types(&self, data_ref: xplm_sys::XPLMDataRef) -> xplm_sys::XPLMDataTypeID {
+        unsafe { xplm_sys::XPLMGetDataRefTypes(data_ref) }
+    }
+
+    fn get_datai(&self, data_ref: xplm_sys::XPLMDataRef) -> ::std::os::raw::c_int {
+        unsafe { xplm_sys::XPLMGetDatai(data_ref) }
+    }
+
+    fn set_datai(&self, data_ref: xplm_sys::XPLMDataRef, value: ::std::os::raw::c_int) {
+        unsafe { xplm_sys::XPLMSetDatai(data_ref, value) }
+    }
+
+    fn get_dataf(&self, data_ref: xplm_sys::XPLMDataRef) -> f32 {
+        unsafe { xplm_sys::XPLMGetDataf(data_ref) }
+    }
+
+    fn set_dataf(&self, data_ref: xplm_sys::XPLMDataRef, value: f32) {
+        unsafe { xplm_sys::XPLMSetDataf(data_ref, value) }
+    }
+
+    fn get_datad(&self, data_ref: xplm_sys::XPLMDataRef) -> f64 {
+        unsafe { xplm_sys::XPLMGetDatad(data_ref) }
+    }
+
+    fn set_datad(&self, data_ref: xplm_sys::XPLMDataRef, value: f64) {
+        unsafe { xplm_sys::XPLMSetDatad(data_ref, value) }
+    }
+
+    fn get_datavi_len(&self, data_ref: xplm_sys::XPLMDataRef) -> usize {
+        unsafe { xplm_sys::XPLMGetDatavi(data_ref, std::ptr::null_mut(), 0, 0) as _ }
+    }
+
+    fn get_datavi(
+        &self,
+        data_ref: xplm_sys::XPLMDataRef,
+        offset: usize,
+        values: &mut [::std::os::raw::c_int],
+    ) -> usize {
+        unsafe {
+            xplm_sys::XPLMGetDatavi(
+                data_ref,
+                values.as_mut_ptr(),
+                offset as ::std::os::raw::c_int,
+                values.len() as ::std::os::raw::c_int,
+            ) as _
+        }
+    }
+
+    fn set_datavi(
+        &self,
+        data_ref: xplm_sys::XPLMDataRef,
+        offset: usize,
+        values: &[::std::os::raw::c_int],
+    ) {
+        unsafe {
+            xplm_sys::XPLMSetDatavi(
+                data_ref,
+                values.as_ptr() as *mut ::std::os::raw::c_int,
+                offset as ::std::os::raw::c_int,
+                values.len() as ::std::os::raw::c_int,
+            )
+        };
+    }
+
+    fn get_datavf_len(&self, data_ref: xplm_sys::XPLMDataRef) -> usize {
+        unsafe { xplm_sys::XPLMGetDatavf(data_ref, std::ptr::null_mut(), 0, 0) as _ }
+    }
+
+    fn get_datavf(
+        &self,
+        data_ref: xplm_sys::XPLMDataRef,
+        offset: usize,
+        values: &mut [f32],
+    ) -> usize {
+        unsafe {
+            xplm_sys::XPLMGetDatavf(
+                data_ref,
+                values.as_mut_ptr(),
+                offset as ::std::os::raw::c_int,
+                values.len() as ::std::os::raw::c_int,
+            ) as _
+        }
+    }
+
+    fn set_datavf(&self, data_ref: xplm_sys::XPLMDataRef, offset: usize, values: &[f32]) {
+        unsafe {
+            xplm_sys::XPLMSetDatavf(
+                data_ref,
+                values.as_ptr() as *mut f32,
+                offset as ::std::os::raw::c_int,
+                values.len() as ::std::os::raw::c_int,
+            )
+        };
+    }
+
+    fn get_datab_len(&self, data_ref: xplm_sys::XPLMDataRef) -> usize {
+        unsafe { xplm_sys::XPLMGetDatab(data_ref, std::ptr::null_mut(), 0, 0) as _ }
+    }
+
+    fn get_datab(
+        &self,
+        data_ref: xplm_sys::XPLMDataRef,
+        offset: usize,
+        values: &mut [u8],
+    ) -> usize {
+        unsafe {
+            xplm_sys::XPLMGetDatab(
+                data_ref,
+                values.as_mut_ptr() as *mut ::std::os::raw::c_void,
+                offset as ::std::os::raw::c_int,
+                values.len() as ::std::os::raw::c_int,
+            ) as _
+        }
+    }
+
+    fn set_datab(&self, data_ref: xplm_sys::XPLMDataRef, offset: usize, values: &[u8]) {
+        unsafe {
+            xplm_sys::XPLMSetDatab(
+                data_ref,
+                values.as_ptr() as *mut ::std::os::raw::c_void,
+                offset as ::std::os::raw::c_int,
+                values.len() as ::std::os::raw::c_int,
+            )
+        };
+    }
+
+    fn find_command(&self, name: &CStr) -> xplm_sys::XPLMCommandRef {
+        unsafe { xplm_sys::XPLMFindCommand(name.as_ptr()) }
+    }
+
+    fn create_command(&self, name: &CStr, description: &CStr) -> xplm_sys::XPLMCommandRef {
+        unsafe { xplm_sys::XPLMCreateCommand(name.as_ptr(), description.as_ptr()) }
+    }
+
+    fn command_begin(&self, command: xplm_sys::XPLMCommandRef) {
+        unsafe { xplm_sys::XPLMCommandBegin(command) };
+    }
+
+    fn command_end(&self, command: xplm_sys::XPLMCommandRef) {
+        unsafe { xplm_sys::XPLMCommandEnd(command) };
+    }
+
+    fn command_once(&self, command: xplm_sys::XPLMCommandRef) {
+        unsafe { xplm_sys::XPLMCommandOnce(command) };
+    }
+
+    fn create_window_ex(
+        &self,
+        params: &mut xplm_sys::XPLMCreateWindow_t,
+    ) -> xplm_sys::XPLMWindowID {
+        unsafe { xplm_sys::XPLMCreateWindowEx(params) }
+    }
+
+    fn destroy_window(&self, id: xplm_sys::XPLMWindowID) {
+        unsafe { xplm_sys::XPLMDestroyWindow(id) };
+    }
+
+    fn get_window_geometry(&self, id: xplm_sys::XPLMWindowID) -> [::std::os::raw::c_int; 4] {
+        let mut geometry = [0; 4];
+        let [left, top, right, bottom] = &mut geometry;
+        unsafe { xplm_sys::XPLMGetWindowGeometry(id, left, top, right, bottom) };
+        geometry
+    }
+
+    fn set_window_geometry(
+        &self,
+        id: xplm_sys::XPLMWindowID,
+        geometry: [::std::os::raw::c_int; 4],
+    ) {
+        let [left, top, right, bottom] = geometry;
+        unsafe { xplm_sys::XPLMSetWindowGeometry(id, left, top, right, bottom) };
+    }
+
+    fn get_window_is_visible(&self, id: xplm_sys::XPLMWindowID) -> bool {
+        unsafe { xplm_sys::XPLMGetWindowIsVisible(id) == 1 }
+    }
+
+    fn set_window_is_visible(&self, id: xplm_sys::XPLMWindowID, visible: bool) {
+        unsafe { xplm_sys::XPLMSetWindowIsVisible(id, visible as _) };
+    }
+
+    fn get_nth_aircraft_model(
+        &self,
+        index: ::std::os::raw::c_int,
+        file_name: &mut [::std::os::raw::c_char; 256],
+        path: &mut [::std::os::raw::c_char; 512],
+    ) {
+        unsafe {
+            xplm_sys::XPLMGetNthAircraftModel(index, file_name.as_mut_ptr(), path.as_mut_ptr())
+        };
+    }
+
+    fn set_users_aircraft(&self, path: &CStr) {
+        unsafe { xplm_sys::XPLMSetUsersAircraft(path.as_ptr()) };
+    }
+
+    fn get_directory_contents(
+        &self,
+        dir: &CStr,
+        offset: ::std::os::raw::c_int,
+        names: &mut [::std::os::raw::c_char],
+        indices: &mut [*mut ::std::os::raw::c_char],
+        total: &mut ::std::os::raw::c_int,
+        returned: &mut ::std::os::raw::c_int,
+    ) -> bool {
+        unsafe {
+            xplm_sys::XPLMGetDirectoryContents(
+                dir.as_ptr(),
+                offset,
+                names.as_mut_ptr(),
+                names.len() as _,
+                indices.as_mut_ptr(),
+                indices.len() as _,
+                total,
+                returned,
+            ) == 1
+        }
+    }
+
+    fn acquire_planes(&self) -> bool {
+        unsafe {
+            xplm_sys::XPLMAcquirePlanes(std::ptr::null_mut(), None, std::ptr::null_mut()) == 1
+        }
+    }
+
+    fn release_planes(&self) {
+        unsafe { xplm_sys::XPLMReleasePlanes() };
+    }
+
+    fn place_user_at_location(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        elevation: f32,
+        heading: f32,
+        speed: f32,
+    ) {
+        unsafe {
+            xplm_sys::XPLMPlaceUserAtLocation(latitude, longitude, elevation, heading, speed)
+        };
+    }
+
+    fn place_user_at_airport(&self, icao: &CStr) {
+        unsafe { xplm_sys::XPLMPlaceUserAtAirport(icao.as_ptr()) };
+    }
+
+    fn speak_string(&self, message: &CStr) {
+        unsafe { xplm_sys::XPLMSpeakString(message.as_ptr()) };
+    }
+
+    fn debug_string(&self, message: &CStr) {
+        unsafe { xplm_sys::XPLMDebugString(message.as_ptr()) };
+    }
+}
@@ -0,0 +1,566 @@
+//! Tracing of the calls the crate makes through the backend.
+//! Available with the `trace` feature enabled.
+//!
+//! While tracing is active every call of [`super::Backend`] is recorded with its arguments
+//! and result, either into an in-memory ring buffer or appended to a file. Tracing can be
+//! toggled at runtime with a command, so a user can record a trace of a hard to reproduce
+//! issue and send the file:
+//!
+//! ```ignore
+//! let _record = register_trace_command(
+//!     "my_plugin/debug/toggle_trace",
+//!     TraceSink::File(get_output_dir()?.join("my_plugin_trace.txt")),
+//! )?;
+//! ```
+//!
+//! Only the calls routed through the backend are traced: data access, commands, the basic
+//! window calls, the user's aircraft and its placement, AI planes, directory listings and
+//! speech. Every other XPLM call is made directly and does not appear in a trace. A trace
+//! is a log for reading, replaying it is not implemented.
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::ffi::CStr;
+use std::fmt;
+use std::fs;
+use std::io::{self, BufWriter, LineWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::api::utilities::{
+    create_command, os_path, register_command_handler, CommandExecutionTime, CommandHandler,
+    CommandHandlerRecord, CommandOutcome,
+};
+
+use super::{inner, Backend};
+
+/// Default number of calls kept in a ring buffer.
+pub const DEFAULT_TRACE_CAPACITY: usize = 10_000;
+
+/// A destination of traced calls.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TraceSink {
+    /// Keeps the latest calls in memory, older calls are dropped.
+    RingBuffer(usize),
+    /// Appends calls to a file, every call is flushed as soon as it is written.
+    File(PathBuf),
+}
+
+impl Default for TraceSink {
+    fn default() -> Self {
+        Self::RingBuffer(DEFAULT_TRACE_CAPACITY)
+    }
+}
+
+/// A traced call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceEntry {
+    /// Time since the tracing has started.
+    pub elapsed: Duration,
+    /// The XPLM function name, e.g. `XPLMGetDatai`.
+    pub function: &'static str,
+    /// Formatted call arguments.
+    pub arguments: String,
+    /// Formatted call result, empty for functions without a result.
+    pub result: String,
+}
+
+impl fmt::Display for TraceEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:>12.6} {}({})",
+            self.elapsed.as_secs_f64(),
+            self.function,
+            self.arguments
+        )?;
+        if !self.result.is_empty() {
+            write!(f, " -> {}", self.result)?;
+        }
+
+        Ok(())
+    }
+}
+
+enum Output {
+    RingBuffer(VecDeque<TraceEntry>, usize),
+    File(LineWriter<fs::File>),
+}
+
+struct Tracer {
+    started: Instant,
+    active: bool,
+    output: Output,
+}
+
+thread_local! {
+    static TRACER: RefCell<Option<Tracer>> = const { RefCell::new(None) };
+}
+
+/// Starts tracing, a running trace is stopped first.
+///
+/// # Arguments
+/// * `sink` - a trace destination. See [`TraceSink`] for more details.
+///
+/// # Returns
+/// Returns empty result on success. Otherwise returns an error if the trace file can't be opened.
+pub fn start_trace(sink: &TraceSink) -> io::Result<()> {
+    let output = match sink {
+        TraceSink::RingBuffer(capacity) => {
+            Output::RingBuffer(VecDeque::with_capacity(*capacity), (*capacity).max(1))
+        }
+        TraceSink::File(path) => {
+            let file = fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)?;
+            Output::File(LineWriter::new(file))
+        }
+    };
+
+    stop_trace();
+    crate::info!("XPLM call tracing started");
+    TRACER.with(|tracer| {
+        *tracer.borrow_mut() = Some(Tracer {
+            started: Instant::now(),
+            active: true,
+            output,
+        })
+    });
+    Ok(())
+}
+
+/// Stops tracing and flushes the trace file. A ring buffer is kept until
+/// the next [`start_trace`] or [`clear_trace`].
+pub fn stop_trace() {
+    TRACER.with(|tracer| {
+        let mut tracer = tracer.borrow_mut();
+        let Some(active) = tracer.as_mut().filter(|tracer| tracer.active) else {
+            return;
+        };
+
+        active.active = false;
+        if let Output::File(writer) = &mut active.output {
+            if let Err(err) = writer.flush() {
+                crate::error!("failed to flush XPLM call trace: {}", err);
+            }
+            // Only a ring buffer is kept for reading after the trace has stopped.
+            tracer.take();
+        }
+        crate::info!("XPLM call tracing stopped");
+    });
+}
+
+/// Checks wether the calls are being traced.
+pub fn is_tracing() -> bool {
+    TRACER.with(|tracer| tracer.borrow().as_ref().is_some_and(|tracer| tracer.active))
+}
+
+/// Returns the calls kept in the ring buffer, the oldest first.
+pub fn trace_entries() -> Vec<TraceEntry> {
+    TRACER.with(
+        |tracer| match tracer.borrow().as_ref().map(|tracer| &tracer.output) {
+            Some(Output::RingBuffer(entries, _)) => entries.iter().cloned().collect(),
+            _ => Vec::new(),
+        },
+    )
+}
+
+/// Removes the calls kept in the ring buffer and stops tracing.
+pub fn clear_trace() {
+    stop_trace();
+    TRACER.with(|tracer| tracer.borrow_mut().take());
+}
+
+/// Writes the calls kept in the ring buffer to a file.
+///
+/// # Arguments
+/// * `path` - a file path, an existing file is replaced.
+///
+/// # Returns
+/// Returns empty result on success. Otherwise returns an IO error.
+pub fn dump_trace<P: AsRef<Path>>(path: P) -> io::Result<()> {
+    let mut writer = BufWriter::new(fs::File::create(path)?);
+    for entry in trace_entries() {
+        writeln!(writer, "{}", entry)?;
+    }
+
+    writer.flush()
+}
+
+/// Creates a command which starts and stops tracing.
+///
+/// # Arguments
+/// * `name` - a command name.
+/// * `sink` - a trace destination used every time tracing starts.
+///
+/// # Returns
+/// Returns a [`CommandHandlerRecord`] which keeps the command working on success.
+/// Otherwise returns [`crate::XplmError`].
+pub fn register_trace_command(name: &str, sink: TraceSink) -> crate::Result<CommandHandlerRecord> {
+    let command = create_command(name, "Start or stop XPLM call tracing")?;
+    Ok(register_command_handler(
+        &command,
+        CommandExecutionTime::BeforeXPlane,
+        TraceToggle(sink),
+    ))
+}
+
+struct TraceToggle(TraceSink);
+
+impl CommandHandler for TraceToggle {
+    fn command_begin(&mut self) -> CommandOutcome {
+        if is_tracing() {
+            stop_trace();
+        } else if let Err(err) = start_trace(&self.0) {
+            crate::error!("failed to start XPLM call tracing: {}", err);
+        }
+
+        CommandOutcome::Consume
+    }
+
+    fn command_continue(&mut self) -> CommandOutcome {
+        CommandOutcome::Consume
+    }
+
+    fn command_end(&mut self) -> CommandOutcome {
+        CommandOutcome::Consume
+    }
+}
+
+fn record<R: fmt::Debug>(function: &'static str, arguments: String, result: &R) {
+    let result = format!("{:?}", result);
+    let result = if result == "()" {
+        String::new()
+    } else {
+        result
+    };
+    TRACER.with(|tracer| {
+        let mut tracer = tracer.borrow_mut();
+        let Some(tracer) = tracer.as_mut().filter(|tracer| tracer.active) else {
+            return;
+        };
+
+        let entry = TraceEntry {
+            elapsed: tracer.started.elapsed(),
+            function,
+            arguments,
+            result,
+        };
+
+        match &mut tracer.output {
+            Output::RingBuffer(entries, capacity) => {
+                if entries.len() == *capacity {
+                    entries.pop_front();
+                }
+                entries.push_back(entry);
+            }
+            Output::File(writer) => {
+                if let Err(err) = writeln!(writer, "{}", entry) {
+                    crate::error!("failed to write XPLM call trace: {}", err);
+                }
+            }
+        }
+    });
+}
+
+/// The backend which records calls and forwards them to the underlying backend.
+pub(crate) struct Tracing;
+
+impl Backend for Tracing {
+    fn find_data_ref(&self, name: &CStr) -> xplm_sys::XPLMDataRef {
+        let data_ref = inner().find_data_ref(name);
+        record("XPLMFindDataRef", format!("{:?}", name), &data_ref);
+        data_ref
+    }
+
+    fn can_write_data_ref(&self, data_ref: xplm_sys::XPLMDataRef) -> bool {
+        let writable = inner().can_write_data_ref(data_ref);
+        record("XPLMCanWriteDataRef", format!("{:?}", data_ref), &writable);
+        writable
+    }
+
+    fn is_data_ref_good(&self, data_ref: xplm_sys::XPLMDataRef) -> bool {
+        let good = inner().is_data_ref_good(data_ref);
+        record("XPLMIsDataRefGood", format!("{:?}", data_ref), &good);
+        good
+    }
+
+    fn get_data_ref_types(&self, data_ref: xplm_sys::XPLMDataRef) -> xplm_sys::XPLMDataTypeID {
+        let types = inner().get_data_ref_types(data_ref);
+        record("XPLMGetDataRefTypes", format!("{:?}", data_ref), &types);
+        types
+    }
+
+    fn get_datai(&self, data_ref: xplm_sys::XPLMDataRef) -> ::std::os::raw::c_int {
+        let value = inner().get_datai(data_ref);
+        record("XPLMGetDatai", format!("{:?}", data_ref), &value);
+        value
+    }
+
+    fn set_datai(&self, data_ref: xplm_sys::XPLMDataRef, value: ::std::os::raw::c_int) {
+        inner().set_datai(data_ref, value);
+        record("XPLMSetDatai", format!("{:?}, {}", data_ref, value), &());
+    }
+
+    fn get_dataf(&self, data_ref: xplm_sys::XPLMDataRef) -> f32 {
+        let value = inner().get_dataf(data_ref);
+        record("XPLMGetDataf", format!("{:?}", data_ref), &value);
+        value
+    }
+
+    fn set_dataf(&self, data_ref: xplm_sys::XPLMDataRef, value: f32) {
+        inner().set_dataf(data_ref, value);
+        record("XPLMSetDataf", format!("{:?}, {}", data_ref, value), &());
+    }
+
+    fn get_datad(&self, data_ref: xplm_sys::XPLMDataRef) -> f64 {
+        let value = inner().get_datad(data_ref);
+        record("XPLMGetDatad", format!("{:?}", data_ref), &value);
+        value
+    }
+
+    fn set_datad(&self, data_ref: xplm_sys::XPLMDataRef, value: f64) {
+        inner().set_datad(data_ref, value);
+        record("XPLMSetDatad", format!("{:?}, {}", data_ref, value), &());
+    }
+
+    fn get_datavi_len(&self, data_ref: xplm_sys::XPLMDataRef) -> usize {
+        let len = inner().get_datavi_len(data_ref);
+        record("XPLMGetDatavi", format!("{:?}, NULL", data_ref), &len);
+        len
+    }
+
+    fn get_datavi(
+        &self,
+        data_ref: xplm_sys::XPLMDataRef,
+        offset: usize,
+        values: &mut [::std::os::raw::c_int],
+    ) -> usize {
+        let count = inner().get_datavi(data_ref, offset, values);
+        let arguments = format!("{:?}, {:?}, {}", data_ref, &values[..count], offset);
+        record("XPLMGetDatavi", arguments, &count);
+        count
+    }
+
+    fn set_datavi(
+        &self,
+        data_ref: xplm_sys::XPLMDataRef,
+        offset: usize,
+        values: &[::std::os::raw::c_int],
+    ) {
+        inner().set_datavi(data_ref, offset, values);
+        let arguments = format!("{:?}, {:?}, {}", data_ref, values, offset);
+        record("XPLMSetDatavi", arguments, &());
+    }
+
+    fn get_datavf_len(&self, data_ref: xplm_sys::XPLMDataRef) -> usize {
+        let len = inner().get_datavf_len(data_ref);
+        record("XPLMGetDatavf", format!("{:?}, NULL", data_ref), &len);
+        len
+    }
+
+    fn get_datavf(
+        &self,
+        data_ref: xplm_sys::XPLMDataRef,
+        offset: usize,
+        values: &mut [f32],
+    ) -> usize {
+        let count = inner().get_datavf(data_ref, offset, values);
+        let arguments = format!("{:?}, {:?}, {}", data_ref, &values[..count], offset);
+        record("XPLMGetDatavf", arguments, &count);
+        count
+    }
+
+    fn set_datavf(&self, data_ref: xplm_sys::XPLMDataRef, offset: usize, values: &[f32]) {
+        inner().set_datavf(data_ref, offset, values);
+        let arguments = format!("{:?}, {:?}, {}", data_ref, values, offset);
+        record("XPLMSetDatavf", arguments, &());
+    }
+
+    fn get_datab_len(&self, data_ref: xplm_sys::XPLMDataRef) -> usize {
+        let len = inner().get_datab_len(data_ref);
+        record("XPLMGetDatab", format!("{:?}, NULL", data_ref), &len);
+        len
+    }
+
+    fn get_datab(
+        &self,
+        data_ref: xplm_sys::XPLMDataRef,
+        offset: usize,
+        values: &mut [u8],
+    ) -> usize {
+        let count = inner().get_datab(data_ref, offset, values);
+        let arguments = format!("{:?}, {:?}, {}", data_ref, &values[..count], offset);
+        record("XPLMGetDatab", arguments, &count);
+        count
+    }
+
+    fn set_datab(&self, data_ref: xplm_sys::XPLMDataRef, offset: usize, values: &[u8]) {
+        inner().set_datab(data_ref, offset, values);
+        let arguments = format!("{:?}, {:?}, {}", data_ref, values, offset);
+        record("XPLMSetDatab", arguments, &());
+    }
+
+    fn find_command(&self, name: &CStr) -> xplm_sys::XPLMCommandRef {
+        let command = inner().find_command(name);
+        record("XPLMFindCommand", format!("{:?}", name), &command);
+        command
+    }
+
+    fn create_command(&self, name: &CStr, description: &CStr) -> xplm_sys::XPLMCommandRef {
+        let command = inner().create_command(name, description);
+        let arguments = format!("{:?}, {:?}", name, description);
+        record("XPLMCreateCommand", arguments, &command);
+        command
+    }
+
+    fn command_begin(&self, command: xplm_sys::XPLMCommandRef) {
+        inner().command_begin(command);
+        record("XPLMCommandBegin", format!("{:?}", command), &());
+    }
+
+    fn command_end(&self, command: xplm_sys::XPLMCommandRef) {
+        inner().command_end(command);
+        record("XPLMCommandEnd", format!("{:?}", command), &());
+    }
+
+    fn command_once(&self, command: xplm_sys::XPLMCommandRef) {
+        inner().command_once(command);
+        record("XPLMCommandOnce", format!("{:?}", command), &());
+    }
+
+    fn create_window_ex(
+        &self,
+        params: &mut xplm_sys::XPLMCreateWindow_t,
+    ) -> xplm_sys::XPLMWindowID {
+        let id = inner().create_window_ex(params);
+        let arguments = format!(
+            "{{ left: {}, top: {}, right: {}, bottom: {}, layer: {} }}",
+            params.left, params.top, params.right, params.bottom, params.layer
+        );
+        record("XPLMCreateWindowEx", arguments, &id);
+        id
+    }
+
+    fn destroy_window(&self, id: xplm_sys::XPLMWindowID) {
+        inner().destroy_window(id);
+        record("XPLMDestroyWindow", format!("{:?}", id), &());
+    }
+
+    fn get_window_geometry(&self, id: xplm_sys::XPLMWindowID) -> [::std::os::raw::c_int; 4] {
+        let geometry = inner().get_window_geometry(id);
+        record("XPLMGetWindowGeometry", format!("{:?}", id), &geometry);
+        geometry
+    }
+
+    fn set_window_geometry(
+        &self,
+        id: xplm_sys::XPLMWindowID,
+        geometry: [::std::os::raw::c_int; 4],
+    ) {
+        inner().set_window_geometry(id, geometry);
+        record(
+            "XPLMSetWindowGeometry",
+            format!("{:?}, {:?}", id, geometry),
+            &(),
+        );
+    }
+
+    fn get_window_is_visible(&self, id: xplm_sys::XPLMWindowID) -> bool {
+        let visible = inner().get_window_is_visible(id);
+        record("XPLMGetWindowIsVisible", format!("{:?}", id), &visible);
+        visible
+    }
+
+    fn set_window_is_visible(&self, id: xplm_sys::XPLMWindowID, visible: bool) {
+        inner().set_window_is_visible(id, visible);
+        record(
+            "XPLMSetWindowIsVisible",
+            format!("{:?}, {}", id, visible),
+            &(),
+        );
+    }
+
+    fn get_nth_aircraft_model(
+        &self,
+        index: ::std::os::raw::c_int,
+        file_name: &mut [::std::os::raw::c_char; 256],
+        path: &mut [::std::os::raw::c_char; 512],
+    ) {
+        inner().get_nth_aircraft_model(index, file_name, path);
+        record(
+            "XPLMGetNthAircraftModel",
+            format!("{}", index),
+            &os_path::path_from_c_buf(path),
+        );
+    }
+
+    fn set_users_aircraft(&self, path: &CStr) {
+        inner().set_users_aircraft(path);
+        record("XPLMSetUsersAircraft", format!("{:?}", path), &());
+    }
+
+    fn get_directory_contents(
+        &self,
+        dir: &CStr,
+        offset: ::std::os::raw::c_int,
+        names: &mut [::std::os::raw::c_char],
+        indices: &mut [*mut ::std::os::raw::c_char],
+        total: &mut ::std::os::raw::c_int,
+        returned: &mut ::std::os::raw::c_int,
+    ) -> bool {
+        let result = inner().get_directory_contents(dir, offset, names, indices, total, returned);
+        record(
+            "XPLMGetDirectoryContents",
+            format!("{:?}, {}", dir, offset),
+            &(result, *total, *returned),
+        );
+        result
+    }
+
+    fn acquire_planes(&self) -> bool {
+        let result = inner().acquire_planes();
+        record("XPLMAcquirePlanes", String::new(), &result);
+        result
+    }
+
+    fn release_planes(&self) {
+        inner().release_planes();
+        record("XPLMReleasePlanes", String::new(), &());
+    }
+
+    fn place_user_at_location(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        elevation: f32,
+        heading: f32,
+        speed: f32,
+    ) {
+        inner().place_user_at_location(latitude, longitude, elevation, heading, speed);
+        record(
+            "XPLMPlaceUserAtLocation",
+            format!(
+                "{}, {}, {}, {}, {}",
+                latitude, longitude, elevation, heading, speed
+            ),
+            &(),
+        );
+    }
+
+    fn place_user_at_airport(&self, icao: &CStr) {
+        inner().place_user_at_airport(icao);
+        record("XPLMPlaceUserAtAirport", format!("{:?}", icao), &());
+    }
+
+    fn debug_string(&self, message: &CStr) {
+        // Log writes are not recorded, the log already contains them.
+        inner().debug_string(message);
+    }
+
+    fn speak_string(&self, message: &CStr) {
+        inner().speak_string(message);
+        record("XPLMSpeakString", format!("{:?}", message), &());
+    }
+}
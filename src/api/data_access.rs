@@ -1,18 +1,39 @@
+pub mod access;
+pub mod array;
+pub mod cache;
 pub mod data_ref;
 pub mod data_refs;
 pub mod data_type;
 pub mod error;
+pub mod lookup;
+pub mod owned;
+pub mod resilient;
+pub mod string;
+pub mod subscription;
+pub mod value;
 
 use std::ffi;
 use std::ops::Deref;
 
+pub use self::access::{DataWrite, ReadOnly, ReadWrite};
+pub use self::array::{ArrayValue, DataRefArray};
+pub use self::cache::CachedDataRef;
 pub use self::data_ref::DataRef;
 pub use self::data_ref::DataRefInfo;
+pub use self::data_ref::DataRefOwner;
 pub use self::data_ref::Info;
 pub use self::data_refs::DataRefsIter;
-pub use self::data_type::DataType;
-pub use self::data_type::DataTypeId;
+pub use self::data_type::{DataType, DataTypeId};
 pub use self::error::DataAccessError;
+pub use self::lookup::{find_cached, handle_lookup_message, invalidate_lookup_cache};
+pub use self::owned::{register_data_ref, OwnedDataRef, OwnedValue};
+pub use self::resilient::ResilientDataRef;
+pub use self::string::{DataRefString, WritableDataRefString};
+pub use self::subscription::DataRefSubscriptions;
+pub use self::value::{DataRefValue, DataValue};
+pub use super::naming::validate_dataref_name;
+
+use super::backend::backend;
 
 pub type Result<T> = std::result::Result<T, DataAccessError>;
 
@@ -82,10 +103,33 @@ pub type Result<T> = std::result::Result<T, DataAccessError>;
 /// Returns a [`DataRef`] in case of success. Otherwise returns [`DataAccessError`].
 pub fn find_data_ref<T: Into<String>>(name: T) -> Result<DataRef> {
     let name_c = ffi::CString::new(name.into()).map_err(DataAccessError::InvalidDataRefName)?;
-    let data_ref = unsafe { xplm_sys::XPLMFindDataRef(name_c.as_ptr()) };
+    let data_ref = backend().find_data_ref(&name_c);
     DataRef::try_from(data_ref)
 }
 
+/// Looks up an optional data ref, e.g. one published by a third-party plugin or aircraft.
+///
+/// # Arguments
+/// * `name` - a data ref name.
+///
+/// # Returns
+/// Returns a [`DataRef`] if the data ref exists. Otherwise returns [`None`],
+/// a malformed name is treated as a missing data ref.
+pub fn find_data_ref_opt<T: Into<String>>(name: T) -> Option<DataRef> {
+    find_data_ref(name).ok()
+}
+
+/// Checks wether a data ref with a given name exists.
+///
+/// # Arguments
+/// * `name` - a data ref name.
+///
+/// # Returns
+/// Returns `true` if the data ref exists. Otherwise returns `false`.
+pub fn exists<T: Into<String>>(name: T) -> bool {
+    find_data_ref_opt(name).is_some()
+}
+
 /// Checks wether a data ref can be written to.
 ///
 /// # Arguments
@@ -94,7 +138,7 @@ pub fn find_data_ref<T: Into<String>>(name: T) -> Result<DataRef> {
 /// # Returns
 /// Returns `true` if can write to data ref. Otherwise returns `false`.
 pub fn can_write_data_ref(data_ref: &DataRef) -> bool {
-    unsafe { xplm_sys::XPLMCanWriteDataRef(*data_ref.deref()) == 1 }
+    backend().can_write_data_ref(*data_ref.deref())
 }
 
 /// Check wether a data ref is a valid data ref that is not orphaned.
@@ -105,7 +149,7 @@ pub fn can_write_data_ref(data_ref: &DataRef) -> bool {
 /// # Returns
 /// Returns `true` if data ref is good and ready to use. Otherwise returns `false`.
 pub fn is_data_ref_good(data_ref: &DataRef) -> bool {
-    unsafe { xplm_sys::XPLMIsDataRefGood(*data_ref.deref()) == 1 }
+    backend().is_data_ref_good(*data_ref.deref())
 }
 
 /// Returns the types of the dataref for accessor use.
@@ -116,7 +160,7 @@ pub fn is_data_ref_good(data_ref: &DataRef) -> bool {
 /// # Returns
 /// Returns a [`DataTypeId`] for a given data ref.
 pub fn get_data_ref_types(data_ref: &DataRef) -> DataTypeId {
-    let id = unsafe { xplm_sys::XPLMGetDataRefTypes(*data_ref.deref()) };
+    let id = backend().get_data_ref_types(*data_ref.deref());
     DataTypeId::from(id)
 }
 
@@ -128,7 +172,7 @@ pub fn get_data_ref_types(data_ref: &DataRef) -> DataTypeId {
 /// # Returns
 /// Returns data ref value.
 pub fn get_data_i(data_ref: &DataRef) -> ::std::os::raw::c_int {
-    unsafe { xplm_sys::XPLMGetDatai(*data_ref.deref()) }
+    backend().get_datai(*data_ref.deref())
 }
 
 /// Writes a new value to an integer data ref.
@@ -137,7 +181,7 @@ pub fn get_data_i(data_ref: &DataRef) -> ::std::os::raw::c_int {
 /// * `data_ref` - a data ref.
 /// * `value` - a data ref value.
 pub fn set_data_i(data_ref: &DataRef, value: ::std::os::raw::c_int) {
-    unsafe { xplm_sys::XPLMSetDatai(*data_ref.deref(), value) }
+    backend().set_datai(*data_ref.deref(), value)
 }
 
 /// Reads an single precision floating point data ref and return its value.
@@ -148,7 +192,7 @@ pub fn set_data_i(data_ref: &DataRef, value: ::std::os::raw::c_int) {
 /// # Returns
 /// Returns data ref value.
 pub fn get_data_f(data_ref: &DataRef) -> f32 {
-    unsafe { xplm_sys::XPLMGetDataf(*data_ref.deref()) }
+    backend().get_dataf(*data_ref.deref())
 }
 
 /// Writes a new value to an single precision floating point data ref.
@@ -157,7 +201,7 @@ pub fn get_data_f(data_ref: &DataRef) -> f32 {
 /// * `data_ref` - a data ref.
 /// * `value` - a data ref value.
 pub fn set_data_f(data_ref: &DataRef, value: f32) {
-    unsafe { xplm_sys::XPLMSetDataf(*data_ref.deref(), value) }
+    backend().set_dataf(*data_ref.deref(), value)
 }
 
 /// Reads an double precision floating point data ref and return its value.
@@ -168,7 +212,7 @@ pub fn set_data_f(data_ref: &DataRef, value: f32) {
 /// # Returns
 /// Returns data ref value.
 pub fn get_data_d(data_ref: &DataRef) -> f64 {
-    unsafe { xplm_sys::XPLMGetDatad(*data_ref.deref()) }
+    backend().get_datad(*data_ref.deref())
 }
 
 /// Writes a new value to an double precision floating point data ref.
@@ -177,7 +221,18 @@ pub fn get_data_d(data_ref: &DataRef) -> f64 {
 /// * `data_ref` - a data ref.
 /// * `value` - a data ref value.
 pub fn set_data_d(data_ref: &DataRef, value: f64) {
-    unsafe { xplm_sys::XPLMSetDatad(*data_ref.deref(), value) }
+    backend().set_datad(*data_ref.deref(), value)
+}
+
+/// Returns the number of elements in an integer array data ref.
+///
+/// # Arguments
+/// * `data_ref` - a data ref.
+///
+/// # Returns
+/// Returns the array length.
+pub fn get_data_vi_len(data_ref: &DataRef) -> usize {
+    backend().get_datavi_len(*data_ref.deref())
 }
 
 /// Reads a part of an integer array data ref.
@@ -194,15 +249,7 @@ pub fn get_data_vi(
     offset: usize,
     array: &mut [::std::os::raw::c_int],
 ) -> usize {
-    let count = offset + array.len();
-    unsafe {
-        xplm_sys::XPLMGetDatavi(
-            *data_ref.deref(),
-            array.as_mut_ptr(),
-            offset as ::std::os::raw::c_int,
-            count as ::std::os::raw::c_int,
-        ) as _
-    }
+    backend().get_datavi(*data_ref.deref(), offset, array)
 }
 
 /// Write part or all of an integer array dataref.
@@ -212,14 +259,18 @@ pub fn get_data_vi(
 /// * `offset` - an offset to start write values to data ref.
 /// * `array` - an array which contains values.
 pub fn set_data_vi(data_ref: &DataRef, offset: usize, array: &[::std::os::raw::c_int]) {
-    unsafe {
-        xplm_sys::XPLMSetDatavi(
-            *data_ref.deref(),
-            array.as_ptr() as *mut ::std::os::raw::c_int,
-            offset as ::std::os::raw::c_int,
-            array.len() as ::std::os::raw::c_int,
-        )
-    };
+    backend().set_datavi(*data_ref.deref(), offset, array);
+}
+
+/// Returns the number of elements in a single precision floating point array data ref.
+///
+/// # Arguments
+/// * `data_ref` - a data ref.
+///
+/// # Returns
+/// Returns the array length.
+pub fn get_data_vf_len(data_ref: &DataRef) -> usize {
+    backend().get_datavf_len(*data_ref.deref())
 }
 
 /// Reads a part of a single precision floating point array data ref.
@@ -232,15 +283,7 @@ pub fn set_data_vi(data_ref: &DataRef, offset: usize, array: &[::std::os::raw::c
 /// # Return
 /// Return the number of values read into the `array` argument.
 pub fn get_data_vf(data_ref: &DataRef, offset: usize, array: &mut [f32]) -> usize {
-    let count = offset + array.len();
-    unsafe {
-        xplm_sys::XPLMGetDatavf(
-            *data_ref.deref(),
-            array.as_mut_ptr(),
-            offset as ::std::os::raw::c_int,
-            count as ::std::os::raw::c_int,
-        ) as _
-    }
+    backend().get_datavf(*data_ref.deref(), offset, array)
 }
 
 /// Write part or all of a single precision floating point array data ref.
@@ -250,14 +293,18 @@ pub fn get_data_vf(data_ref: &DataRef, offset: usize, array: &mut [f32]) -> usiz
 /// * `offset` - an offset to start write values to data ref.
 /// * `array` - an array which contains values.
 pub fn set_data_vf(data_ref: &DataRef, offset: usize, array: &[f32]) {
-    unsafe {
-        xplm_sys::XPLMSetDatavf(
-            *data_ref.deref(),
-            array.as_ptr() as *mut f32,
-            offset as ::std::os::raw::c_int,
-            array.len() as ::std::os::raw::c_int,
-        )
-    };
+    backend().set_datavf(*data_ref.deref(), offset, array);
+}
+
+/// Returns the number of bytes in a byte array data ref.
+///
+/// # Arguments
+/// * `data_ref` - a data ref.
+///
+/// # Returns
+/// Returns the array length.
+pub fn get_data_b_len(data_ref: &DataRef) -> usize {
+    backend().get_datab_len(*data_ref.deref())
 }
 
 /// Reads a part of a byte array data ref.
@@ -270,15 +317,7 @@ pub fn set_data_vf(data_ref: &DataRef, offset: usize, array: &[f32]) {
 /// # Return
 /// Return the number of values read into the `array` argument.
 pub fn get_data_b(data_ref: &DataRef, offset: usize, array: &mut [u8]) -> usize {
-    let count = offset + array.len();
-    unsafe {
-        xplm_sys::XPLMGetDatab(
-            *data_ref.deref(),
-            array.as_mut_ptr() as *mut ::std::os::raw::c_void,
-            offset as ::std::os::raw::c_int,
-            count as ::std::os::raw::c_int,
-        ) as _
-    }
+    backend().get_datab(*data_ref.deref(), offset, array)
 }
 
 /// Write part or all of a byte array data ref.
@@ -288,12 +327,29 @@ pub fn get_data_b(data_ref: &DataRef, offset: usize, array: &mut [u8]) -> usize
 /// * `offset` - an offset to start write values to data ref.
 /// * `array` - an array which contains values.
 pub fn set_data_b(data_ref: &DataRef, offset: usize, array: &[u8]) {
-    unsafe {
-        xplm_sys::XPLMSetDatab(
-            *data_ref.deref(),
-            array.as_ptr() as *mut ::std::os::raw::c_void,
-            offset as ::std::os::raw::c_int,
-            array.len() as ::std::os::raw::c_int,
-        )
-    };
+    backend().set_datab(*data_ref.deref(), offset, array);
+}
+
+/// Reads the whole byte array data ref, e.g. a binary structure published by an aircraft.
+///
+/// # Arguments
+/// * `data_ref` - a data ref.
+///
+/// # Returns
+/// Returns the bytes read, the length is queried from the data ref before reading.
+pub fn read_bytes(data_ref: &DataRef) -> Vec<u8> {
+    let mut bytes = vec![0; get_data_b_len(data_ref)];
+    let count = get_data_b(data_ref, 0, &mut bytes);
+    bytes.truncate(count);
+    bytes
+}
+
+/// Writes bytes to a byte array data ref.
+///
+/// # Arguments
+/// * `data_ref` - a data ref.
+/// * `offset` - an offset to start write bytes to data ref.
+/// * `bytes` - bytes to write.
+pub fn write_bytes(data_ref: &DataRef, offset: usize, bytes: &[u8]) {
+    set_data_b(data_ref, offset, bytes)
 }
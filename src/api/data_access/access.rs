@@ -0,0 +1,143 @@
+use std::marker::PhantomData;
+
+use super::{can_write_data_ref, find_data_ref, DataAccessError, DataRef, DataValue, Result};
+
+/// A data ref which accepts values of a given type.
+pub trait DataWrite<T> {
+    /// Writes a value to the data ref.
+    ///
+    /// # Arguments
+    /// * `value` - a value to write.
+    ///
+    /// # Returns
+    /// Returns empty result on success. Otherwise returns [`DataAccessError`].
+    fn write(&self, value: T) -> Result<()>;
+}
+
+/// A typed read only data ref.
+pub struct ReadOnly<T: DataValue> {
+    data_ref: DataRef,
+    value_type: PhantomData<T>,
+}
+
+impl<T: DataValue> ReadOnly<T> {
+    /// Looks up a read only data ref.
+    ///
+    /// # Arguments
+    /// * `name` - a data ref name.
+    ///
+    /// # Returns
+    /// Returns [`ReadOnly`] on success. Otherwise returns [`DataAccessError`].
+    pub fn find<N: Into<String>>(name: N) -> Result<Self> {
+        Ok(Self {
+            data_ref: find_data_ref(name)?,
+            value_type: PhantomData,
+        })
+    }
+
+    /// Reads the data ref value.
+    pub fn get(&self) -> T {
+        T::read(&self.data_ref)
+    }
+}
+
+/// A typed writable data ref.
+pub struct ReadWrite<T: DataValue> {
+    data_ref: DataRef,
+    value_type: PhantomData<T>,
+}
+
+impl<T: DataValue> ReadWrite<T> {
+    /// Looks up a writable data ref.
+    ///
+    /// # Arguments
+    /// * `name` - a data ref name.
+    ///
+    /// # Returns
+    /// Returns [`ReadWrite`] on success. Otherwise returns [`DataAccessError::NotWritable`]
+    /// if the data ref can't be written or other [`DataAccessError`].
+    pub fn find<N: Into<String>>(name: N) -> Result<Self> {
+        let name = name.into();
+        let data_ref = find_data_ref(name.as_str())?;
+        if can_write_data_ref(&data_ref) {
+            Ok(Self {
+                data_ref,
+                value_type: PhantomData,
+            })
+        } else {
+            Err(DataAccessError::NotWritable(name))
+        }
+    }
+
+    /// Reads the data ref value.
+    pub fn get(&self) -> T {
+        T::read(&self.data_ref)
+    }
+
+    /// Writes a new data ref value.
+    ///
+    /// # Arguments
+    /// * `value` - a data ref value.
+    pub fn set(&self, value: T) {
+        T::write(&self.data_ref, value)
+    }
+}
+
+impl<T: DataValue> DataWrite<T> for ReadWrite<T> {
+    fn write(&self, value: T) -> Result<()> {
+        self.set(value);
+        Ok(())
+    }
+}
+
+/// Declares a struct of typed data refs resolved all at once.
+///
+/// Every field is declared with an access mode (`r` for read only or `rw` for read/write),
+/// a value type implementing [`DataValue`] and a data ref name.
+/// The generated `find()` function resolves all data refs and fails on the first
+/// missing or non-writable `rw` data ref.
+///
+/// ```ignore
+/// xplm::dataref_struct! {
+///     pub struct Lights {
+///         landing: rw i32 = "sim/cockpit/electrical/landing_lights_on",
+///         beacon: r bool = "sim/cockpit/electrical/beacon_lights_on",
+///     }
+/// }
+///
+/// let lights = Lights::find()?;
+/// lights.landing.set(1);
+/// ```
+#[macro_export]
+macro_rules! dataref_struct {
+    (@access r $ty:ty) => { $crate::api::data_access::ReadOnly<$ty> };
+    (@access rw $ty:ty) => { $crate::api::data_access::ReadWrite<$ty> };
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $(
+                $(#[$field_meta:meta])*
+                $field:ident : $mode:ident $ty:ty = $data_ref:literal
+            ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis struct $name {
+            $(
+                $(#[$field_meta])*
+                pub $field: $crate::dataref_struct!(@access $mode $ty),
+            )*
+        }
+
+        impl $name {
+            /// Looks up all declared data refs.
+            pub fn find() -> $crate::api::data_access::Result<Self> {
+                Ok(Self {
+                    $(
+                        $field: <$crate::dataref_struct!(@access $mode $ty)>::find($data_ref)?,
+                    )*
+                })
+            }
+        }
+    };
+}
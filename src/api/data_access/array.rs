@@ -0,0 +1,202 @@
+use std::marker::PhantomData;
+
+use super::{
+    find_data_ref, get_data_b, get_data_b_len, get_data_vf, get_data_vf_len, get_data_vi,
+    get_data_vi_len, set_data_b, set_data_vf, set_data_vi, DataAccessError, DataRef, Result,
+};
+
+/// An element of an array data ref.
+pub trait ArrayValue: Sized + Copy + Default {
+    /// Reads a part of an array data ref.
+    ///
+    /// # Arguments
+    /// * `data_ref` - a data ref.
+    /// * `offset` - an offset to start read values from data ref.
+    /// * `array` - an array which will contain read values.
+    ///
+    /// # Returns
+    /// Returns the number of values read.
+    fn read(data_ref: &DataRef, offset: usize, array: &mut [Self]) -> usize;
+
+    /// Writes a part of an array data ref.
+    ///
+    /// # Arguments
+    /// * `data_ref` - a data ref.
+    /// * `offset` - an offset to start write values to data ref.
+    /// * `array` - an array which contains values.
+    fn write(data_ref: &DataRef, offset: usize, array: &[Self]);
+
+    /// Returns the number of elements in an array data ref.
+    ///
+    /// # Arguments
+    /// * `data_ref` - a data ref.
+    fn len(data_ref: &DataRef) -> usize;
+}
+
+impl ArrayValue for ::std::os::raw::c_int {
+    fn read(data_ref: &DataRef, offset: usize, array: &mut [Self]) -> usize {
+        get_data_vi(data_ref, offset, array)
+    }
+
+    fn write(data_ref: &DataRef, offset: usize, array: &[Self]) {
+        set_data_vi(data_ref, offset, array)
+    }
+
+    fn len(data_ref: &DataRef) -> usize {
+        get_data_vi_len(data_ref)
+    }
+}
+
+impl ArrayValue for f32 {
+    fn read(data_ref: &DataRef, offset: usize, array: &mut [Self]) -> usize {
+        get_data_vf(data_ref, offset, array)
+    }
+
+    fn write(data_ref: &DataRef, offset: usize, array: &[Self]) {
+        set_data_vf(data_ref, offset, array)
+    }
+
+    fn len(data_ref: &DataRef) -> usize {
+        get_data_vf_len(data_ref)
+    }
+}
+
+impl ArrayValue for u8 {
+    fn read(data_ref: &DataRef, offset: usize, array: &mut [Self]) -> usize {
+        get_data_b(data_ref, offset, array)
+    }
+
+    fn write(data_ref: &DataRef, offset: usize, array: &[Self]) {
+        set_data_b(data_ref, offset, array)
+    }
+
+    fn len(data_ref: &DataRef) -> usize {
+        get_data_b_len(data_ref)
+    }
+}
+
+/// A typed array data ref which size is queried from X-Plane.
+pub struct DataRefArray<T: ArrayValue> {
+    data_ref: DataRef,
+    value_type: PhantomData<T>,
+}
+
+impl<T: ArrayValue> DataRefArray<T> {
+    /// Wraps an existing data ref.
+    ///
+    /// # Arguments
+    /// * `data_ref` - an array data ref.
+    ///
+    /// # Returns
+    /// Returns a new [`DataRefArray`] instance.
+    pub fn new(data_ref: DataRef) -> Self {
+        Self {
+            data_ref,
+            value_type: PhantomData,
+        }
+    }
+
+    /// Looks up an array data ref.
+    ///
+    /// # Arguments
+    /// * `name` - a data ref name.
+    ///
+    /// # Returns
+    /// Returns [`DataRefArray`] on success. Otherwise returns [`super::DataAccessError`].
+    pub fn find<N: Into<String>>(name: N) -> Result<Self> {
+        Ok(Self::new(find_data_ref(name)?))
+    }
+
+    /// Returns the wrapped data ref.
+    pub fn data_ref(&self) -> &DataRef {
+        &self.data_ref
+    }
+
+    /// Returns the current number of elements reported by X-Plane.
+    pub fn len(&self) -> usize {
+        T::len(&self.data_ref)
+    }
+
+    /// Checks wether the array has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Reads a part of the array.
+    ///
+    /// # Arguments
+    /// * `offset` - an offset to start read values from.
+    /// * `array` - an array which will contain read values.
+    ///
+    /// # Returns
+    /// Returns the number of values read on success. Otherwise returns
+    /// [`DataAccessError::OffsetOutOfBounds`] if the offset is past the end of the array.
+    pub fn read_at(&self, offset: usize, array: &mut [T]) -> Result<usize> {
+        self.check_offset(offset)?;
+        Ok(T::read(&self.data_ref, offset, array))
+    }
+
+    /// Writes a part of the array.
+    ///
+    /// # Arguments
+    /// * `offset` - an offset to start write values to.
+    /// * `array` - an array which contains values.
+    ///
+    /// # Returns
+    /// Returns empty result on success. Otherwise returns [`DataAccessError::OffsetOutOfBounds`]
+    /// if the values do not fit into the array.
+    pub fn write_at(&self, offset: usize, array: &[T]) -> Result<()> {
+        let len = self.len();
+        if offset > len || array.len() > len - offset {
+            return Err(DataAccessError::OffsetOutOfBounds {
+                offset,
+                count: array.len(),
+                len,
+            });
+        }
+
+        T::write(&self.data_ref, offset, array);
+        Ok(())
+    }
+
+    fn check_offset(&self, offset: usize) -> Result<()> {
+        let len = self.len();
+        if offset > len {
+            Err(DataAccessError::OffsetOutOfBounds {
+                offset,
+                count: 0,
+                len,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Reads the whole array.
+    ///
+    /// # Returns
+    /// Returns all array elements.
+    pub fn to_vec(&self) -> Vec<T> {
+        let mut values = vec![T::default(); self.len()];
+        let read = T::read(&self.data_ref, 0, &mut values);
+        values.truncate(read);
+        values
+    }
+
+    /// Reads the whole array and iterates over its elements.
+    ///
+    /// # Returns
+    /// Returns an iterator over a snapshot of the array elements.
+    pub fn iter(&self) -> std::vec::IntoIter<T> {
+        self.to_vec().into_iter()
+    }
+}
+
+impl<T: ArrayValue> IntoIterator for &DataRefArray<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
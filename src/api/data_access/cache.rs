@@ -0,0 +1,70 @@
+use std::cell::Cell;
+
+use crate::api::processing::get_cycle_number;
+
+use super::{DataRef, DataValue};
+
+/// A data ref which reads its value at most once per sim cycle.
+///
+/// Useful when the same data ref is read from multiple callbacks within the same frame.
+pub struct CachedDataRef<T: DataValue + Copy> {
+    data_ref: DataRef,
+    cached: Cell<Option<(::std::os::raw::c_int, T)>>,
+}
+
+impl<T: DataValue + Copy> CachedDataRef<T> {
+    /// Creates a new cached data ref.
+    ///
+    /// # Arguments
+    /// * `data_ref` - a data ref to read values from.
+    ///
+    /// # Returns
+    /// Returns a new [`CachedDataRef`] instance.
+    pub fn new(data_ref: DataRef) -> Self {
+        Self {
+            data_ref,
+            cached: Cell::new(None),
+        }
+    }
+
+    /// Returns the data ref value. The value is read from X-Plane only
+    /// if the sim cycle has advanced since the last read.
+    ///
+    /// # Returns
+    /// Returns data ref value.
+    pub fn get(&self) -> T {
+        let cycle = get_cycle_number();
+        match self.cached.get() {
+            Some((cached_cycle, value)) if cached_cycle == cycle => value,
+            _ => self.read(cycle),
+        }
+    }
+
+    /// Reads the data ref value from X-Plane regardless of the cached value.
+    ///
+    /// # Returns
+    /// Returns data ref value.
+    pub fn force_refresh(&self) -> T {
+        self.read(get_cycle_number())
+    }
+
+    /// Writes a new data ref value and updates the cached value.
+    ///
+    /// # Arguments
+    /// * `value` - a data ref value.
+    pub fn set(&self, value: T) {
+        T::write(&self.data_ref, value);
+        self.cached.set(Some((get_cycle_number(), value)));
+    }
+
+    /// Drops the cached value so the next read goes to X-Plane.
+    pub fn invalidate(&self) {
+        self.cached.set(None);
+    }
+
+    fn read(&self, cycle: ::std::os::raw::c_int) -> T {
+        let value = T::read(&self.data_ref);
+        self.cached.set(Some((cycle, value)));
+        value
+    }
+}
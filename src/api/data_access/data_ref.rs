@@ -1,11 +1,13 @@
 use std::{ffi, ops::Deref};
 
-use crate::api::plugin::PluginId;
+use crate::api::plugin::{get_plugin_info, PluginId, PluginInfo};
+use crate::plugin::main_thread::MainThreadOnly;
 
 use super::{DataAccessError, DataTypeId};
 
 /// An opaque handle to data provided by the simulator or another plugin.
-pub struct DataRef(xplm_sys::XPLMDataRef);
+/// Must only be used on the main thread, see [`crate::plugin::main_thread`].
+pub struct DataRef(xplm_sys::XPLMDataRef, MainThreadOnly);
 
 impl Deref for DataRef {
     type Target = xplm_sys::XPLMDataRef;
@@ -22,7 +24,27 @@ impl TryFrom<xplm_sys::XPLMDataRef> for DataRef {
         if value.is_null() {
             Err(Self::Error::InvalidDataRefId)
         } else {
-            Ok(DataRef(value))
+            Ok(DataRef(value, MainThreadOnly::default()))
+        }
+    }
+}
+
+/// An owner of a data ref.
+pub enum DataRefOwner {
+    /// The data ref is provided by X-Plane itself.
+    XPlane,
+    /// The data ref is provided by a loaded plugin.
+    Plugin(PluginInfo),
+    /// The data ref is orphaned or its owning plugin can't be resolved.
+    Missing,
+}
+
+impl std::fmt::Display for DataRefOwner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::XPlane => write!(f, "X-Plane"),
+            Self::Plugin(info) => write!(f, "{}", info.name),
+            Self::Missing => write!(f, "<missing plugin>"),
         }
     }
 }
@@ -31,7 +53,26 @@ impl TryFrom<xplm_sys::XPLMDataRef> for DataRef {
 pub struct Info {
     pub name: String,
     pub data_type: DataTypeId,
-    pub owner: PluginId,
+    /// The owning plugin, [`None`] if the data ref is orphaned.
+    pub owner: Option<PluginId>,
+}
+
+impl Info {
+    /// Resolves the plugin which owns the data ref.
+    ///
+    /// # Returns
+    /// Returns [`DataRefOwner`] of the data ref.
+    pub fn owner_info(&self) -> DataRefOwner {
+        match self.owner {
+            Some(id) if *id == xplm_sys::XPLM_PLUGIN_XPLANE as xplm_sys::XPLMPluginID => {
+                DataRefOwner::XPlane
+            }
+            Some(id) => get_plugin_info(&id)
+                .map(DataRefOwner::Plugin)
+                .unwrap_or(DataRefOwner::Missing),
+            None => DataRefOwner::Missing,
+        }
+    }
 }
 
 impl TryFrom<xplm_sys::XPLMDataRefInfo_t> for Info {
@@ -46,7 +87,7 @@ impl TryFrom<xplm_sys::XPLMDataRefInfo_t> for Info {
                     .map_err(DataAccessError::InvalidInfoName)
             }?,
             data_type: DataTypeId::from(value.type_),
-            owner: PluginId::try_from(value.owner)?,
+            owner: PluginId::try_from(value.owner).ok(),
         })
     }
 }
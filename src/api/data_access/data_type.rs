@@ -1,4 +1,7 @@
+use std::ops::{BitAnd, BitOr, BitOrAssign};
+
 /// Enumeration that defines the type of the data behind a data reference.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum DataType {
     /// Data of a type the current XPLM doesn't do.
     Unknown,
@@ -16,10 +19,56 @@ pub enum DataType {
     Data,
 }
 
+impl DataType {
+    /// Returns all known data types except [`DataType::Unknown`].
+    ///
+    /// # Returns
+    /// Returns a slice of data types ordered by their flag bits.
+    pub fn all() -> &'static [DataType] {
+        &[
+            Self::Int,
+            Self::Float,
+            Self::Double,
+            Self::FloatArray,
+            Self::IntArray,
+            Self::Data,
+        ]
+    }
+}
+
+impl From<DataType> for xplm_sys::XPLMDataTypeID {
+    fn from(value: DataType) -> Self {
+        let id = match value {
+            DataType::Unknown => xplm_sys::xplmType_Unknown,
+            DataType::Int => xplm_sys::xplmType_Int,
+            DataType::Float => xplm_sys::xplmType_Float,
+            DataType::Double => xplm_sys::xplmType_Double,
+            DataType::FloatArray => xplm_sys::xplmType_FloatArray,
+            DataType::IntArray => xplm_sys::xplmType_IntArray,
+            DataType::Data => xplm_sys::xplmType_Data,
+        };
+        id as _
+    }
+}
+
 /// Data type flags bitmap.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct DataTypeId(xplm_sys::XPLMDataTypeID);
 
 impl DataTypeId {
+    /// Creates an empty flags bitmap which corresponds to [`DataType::Unknown`].
+    ///
+    /// # Returns
+    /// Returns an empty [`DataTypeId`].
+    pub fn empty() -> Self {
+        Self(xplm_sys::xplmType_Unknown as _)
+    }
+
+    /// Returns the raw flags bitmap.
+    pub fn bits(&self) -> xplm_sys::XPLMDataTypeID {
+        self.0
+    }
+
     /// Checks whether the flags bitmap contains a specific flag.
     ///
     /// # Arguments
@@ -39,6 +88,28 @@ impl DataTypeId {
         }
     }
 
+    /// Adds a flag to the flags bitmap.
+    ///
+    /// # Arguments
+    /// * `data_type` - a flag to add.
+    ///
+    /// # Returns
+    /// Returns a modified flags bitmap.
+    pub fn with(self, data_type: DataType) -> Self {
+        self | data_type
+    }
+
+    /// Lists all data types contained in the flags bitmap.
+    ///
+    /// # Returns
+    /// Returns an iterator over contained data types.
+    pub fn iter(&self) -> impl Iterator<Item = DataType> + '_ {
+        DataType::all()
+            .iter()
+            .copied()
+            .filter(|data_type| self.contains(*data_type))
+    }
+
     /// Checks whether the flags bitmap contains unknown type flag.
     ///
     /// # Returns
@@ -101,3 +172,45 @@ impl From<xplm_sys::XPLMDataTypeID> for DataTypeId {
         Self(value)
     }
 }
+
+impl From<DataType> for DataTypeId {
+    fn from(value: DataType) -> Self {
+        Self(value.into())
+    }
+}
+
+impl From<DataTypeId> for xplm_sys::XPLMDataTypeID {
+    fn from(value: DataTypeId) -> Self {
+        value.0
+    }
+}
+
+impl BitOr for DataTypeId {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOr<DataType> for DataTypeId {
+    type Output = Self;
+
+    fn bitor(self, rhs: DataType) -> Self::Output {
+        self | Self::from(rhs)
+    }
+}
+
+impl BitOrAssign<DataType> for DataTypeId {
+    fn bitor_assign(&mut self, rhs: DataType) {
+        *self = *self | rhs;
+    }
+}
+
+impl BitAnd for DataTypeId {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        Self(self.0 & rhs.0)
+    }
+}
@@ -1,5 +1,6 @@
-use std::ffi;
+use std::{ffi, string};
 
+use crate::api::naming::NameError;
 use crate::api::plugin::PluginError;
 
 /// An error returned from data access API calls.
@@ -17,11 +18,42 @@ pub enum DataAccessError {
     /// Invalid data ref name passed to X-Plane.
     #[error("invalid data ref name string {0}")]
     InvalidDataRefName(ffi::NulError),
+    /// Data ref name does not follow the SDK naming conventions.
+    #[error("invalid data ref name {0}")]
+    InvalidName(NameError),
+    /// Data ref is not writable.
+    #[error("data ref {0} is not writable")]
+    NotWritable(String),
+    /// Array offset is out of the data ref bounds.
+    #[error("{count} values at offset {offset} are out of bounds of array with length {len}")]
+    OffsetOutOfBounds {
+        /// The first accessed index.
+        offset: usize,
+        /// The number of accessed values.
+        count: usize,
+        /// The array length.
+        len: usize,
+    },
+    /// Data ref string contains invalid UTF-8 bytes.
+    #[error("invalid data ref string value {0}")]
+    InvalidUtf8(string::FromUtf8Error),
+    /// String passed to a data ref contains a nul byte.
+    #[error("invalid data ref string value {0}")]
+    InvalidStringValue(ffi::NulError),
+    /// String does not fit into a data ref.
+    #[error("string of {len} bytes does not fit into data ref of {capacity} bytes")]
+    StringTooLong { len: usize, capacity: usize },
     /// Plugin error.
     #[error("plugin error {0}")]
     Plugin(PluginError),
 }
 
+impl From<NameError> for DataAccessError {
+    fn from(value: NameError) -> Self {
+        Self::InvalidName(value)
+    }
+}
+
 impl From<PluginError> for DataAccessError {
     fn from(value: PluginError) -> Self {
         Self::Plugin(value)
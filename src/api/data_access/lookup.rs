@@ -0,0 +1,51 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::api::plugin::Message;
+
+use super::{find_data_ref, DataRef, Result};
+
+thread_local! {
+    /// Data refs found by name. Failed lookups are not cached.
+    static LOOKUP_CACHE: RefCell<HashMap<String, Rc<DataRef>>> = RefCell::new(HashMap::new());
+}
+
+/// Looks up a data ref by name, remembering the result for the following calls.
+/// Unlike [`find_data_ref`], a cached lookup neither allocates nor calls X-Plane.
+///
+/// # Arguments
+/// * `name` - a data ref name.
+///
+/// # Returns
+/// Returns a shared [`DataRef`] handle on success. Otherwise returns [`super::DataAccessError`].
+pub fn find_cached(name: &str) -> Result<Rc<DataRef>> {
+    if let Some(data_ref) = LOOKUP_CACHE.with(|cache| cache.borrow().get(name).cloned()) {
+        return Ok(data_ref);
+    }
+
+    let data_ref = Rc::new(find_data_ref(name)?);
+    LOOKUP_CACHE.with(|cache| {
+        cache
+            .borrow_mut()
+            .insert(name.to_string(), data_ref.clone())
+    });
+    Ok(data_ref)
+}
+
+/// Forgets every cached lookup, handles returned earlier stay valid as long as
+/// the data refs are registered.
+pub fn invalidate_lookup_cache() {
+    LOOKUP_CACHE.with(|cache| cache.borrow_mut().clear());
+}
+
+/// Forgets every cached lookup when data refs owned by aircraft or scenery plugins may
+/// have been re-registered. Should be called from [`crate::plugin::XPlugin::receive_message`].
+///
+/// # Arguments
+/// * `message` - the received message.
+pub fn handle_lookup_message(message: &Message) {
+    if matches!(message, Message::SceneryLoaded | Message::PlaneLoaded(_)) {
+        invalidate_lookup_cache();
+    }
+}
@@ -0,0 +1,196 @@
+use std::cell::Cell;
+use std::ffi;
+use std::ops::Deref;
+use std::rc::Rc;
+
+use super::{validate_dataref_name, DataAccessError, DataRef, Result};
+
+/// A scalar value which can be published as a data ref owned by the plugin.
+pub trait OwnedValue: Copy + 'static {
+    /// Registers data accessors which read and write a shared value.
+    ///
+    /// # Arguments
+    /// * `name` - a data ref name.
+    /// * `writable` - `true` if other plugins may write the value.
+    /// * `refcon` - a pointer to the shared [`Cell`].
+    ///
+    /// # Returns
+    /// Returns a raw data ref, null if registration failed.
+    fn register(
+        name: &ffi::CStr,
+        writable: bool,
+        refcon: *mut ::std::os::raw::c_void,
+    ) -> xplm_sys::XPLMDataRef;
+}
+
+impl OwnedValue for ::std::os::raw::c_int {
+    fn register(
+        name: &ffi::CStr,
+        writable: bool,
+        refcon: *mut ::std::os::raw::c_void,
+    ) -> xplm_sys::XPLMDataRef {
+        unsafe {
+            xplm_sys::XPLMRegisterDataAccessor(
+                name.as_ptr(),
+                xplm_sys::xplmType_Int as _,
+                writable as _,
+                Some(read_value::<Self>),
+                Some(write_value::<Self>),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                refcon,
+                refcon,
+            )
+        }
+    }
+}
+
+impl OwnedValue for f32 {
+    fn register(
+        name: &ffi::CStr,
+        writable: bool,
+        refcon: *mut ::std::os::raw::c_void,
+    ) -> xplm_sys::XPLMDataRef {
+        unsafe {
+            xplm_sys::XPLMRegisterDataAccessor(
+                name.as_ptr(),
+                xplm_sys::xplmType_Float as _,
+                writable as _,
+                None,
+                None,
+                Some(read_value::<Self>),
+                Some(write_value::<Self>),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                refcon,
+                refcon,
+            )
+        }
+    }
+}
+
+impl OwnedValue for f64 {
+    fn register(
+        name: &ffi::CStr,
+        writable: bool,
+        refcon: *mut ::std::os::raw::c_void,
+    ) -> xplm_sys::XPLMDataRef {
+        unsafe {
+            xplm_sys::XPLMRegisterDataAccessor(
+                name.as_ptr(),
+                xplm_sys::xplmType_Double as _,
+                writable as _,
+                None,
+                None,
+                None,
+                None,
+                Some(read_value::<Self>),
+                Some(write_value::<Self>),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                refcon,
+                refcon,
+            )
+        }
+    }
+}
+
+/// A data ref published by the plugin. Other plugins read and, if writable, write the shared
+/// value through X-Plane. The data ref is unregistered when dropped.
+pub struct OwnedDataRef<T: OwnedValue> {
+    name: String,
+    data_ref: DataRef,
+    value: Rc<Cell<T>>,
+}
+
+impl<T: OwnedValue> OwnedDataRef<T> {
+    /// Returns the data ref name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the current value.
+    pub fn get(&self) -> T {
+        self.value.get()
+    }
+
+    /// Changes the published value.
+    ///
+    /// # Arguments
+    /// * `value` - a new value.
+    pub fn set(&self, value: T) {
+        self.value.set(value)
+    }
+
+    /// Returns the shared value, e.g. to update it from a closure.
+    pub fn value(&self) -> Rc<Cell<T>> {
+        self.value.clone()
+    }
+}
+
+impl<T: OwnedValue> Deref for OwnedDataRef<T> {
+    type Target = DataRef;
+
+    fn deref(&self) -> &Self::Target {
+        &self.data_ref
+    }
+}
+
+impl<T: OwnedValue> Drop for OwnedDataRef<T> {
+    fn drop(&mut self) {
+        unsafe { xplm_sys::XPLMUnregisterDataAccessor(*self.data_ref) };
+    }
+}
+
+/// Publishes a scalar data ref owned by the plugin.
+///
+/// # Arguments
+/// * `name` - a data ref name, e.g. `my_company/my_plugin/landing_lights`.
+/// * `writable` - `true` if other plugins may write the value.
+/// * `initial` - an initial value.
+///
+/// # Returns
+/// Returns [`OwnedDataRef`] on success. Otherwise returns [`DataAccessError`].
+pub fn register_data_ref<N, T>(name: N, writable: bool, initial: T) -> Result<OwnedDataRef<T>>
+where
+    N: Into<String>,
+    T: OwnedValue,
+{
+    let name = name.into();
+    validate_dataref_name(&name)?;
+    let name_c = ffi::CString::new(name.as_str()).map_err(DataAccessError::InvalidDataRefName)?;
+    let value = Rc::new(Cell::new(initial));
+    let refcon = Rc::as_ptr(&value) as *mut ::std::os::raw::c_void;
+    let data_ref = DataRef::try_from(T::register(&name_c, writable, refcon))?;
+    Ok(OwnedDataRef {
+        name,
+        data_ref,
+        value,
+    })
+}
+
+unsafe extern "C" fn read_value<T: OwnedValue>(refcon: *mut ::std::os::raw::c_void) -> T {
+    (*(refcon as *const Cell<T>)).get()
+}
+
+unsafe extern "C" fn write_value<T: OwnedValue>(refcon: *mut ::std::os::raw::c_void, value: T) {
+    (*(refcon as *const Cell<T>)).set(value)
+}
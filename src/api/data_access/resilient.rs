@@ -0,0 +1,107 @@
+use std::cell::{Cell, RefCell};
+use std::marker::PhantomData;
+use std::time::{Duration, Instant};
+
+use super::{find_data_ref, is_data_ref_good, DataRef, DataValue, Result};
+
+/// Default interval between orphan checks.
+pub const DEFAULT_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A typed data ref which survives the reload of the aircraft or plugin publishing it.
+///
+/// The data ref is looked up on first use. Afterwards it is checked with
+/// [`is_data_ref_good`] at most once per check interval and looked up again
+/// once it becomes orphaned.
+pub struct ResilientDataRef<T: DataValue> {
+    name: String,
+    data_ref: RefCell<Option<DataRef>>,
+    check_interval: Duration,
+    last_check: Cell<Option<Instant>>,
+    value_type: PhantomData<T>,
+}
+
+impl<T: DataValue> ResilientDataRef<T> {
+    /// Creates a new lazily resolved data ref checked every [`DEFAULT_CHECK_INTERVAL`].
+    ///
+    /// # Arguments
+    /// * `name` - a data ref name.
+    ///
+    /// # Returns
+    /// Returns a new [`ResilientDataRef`] instance.
+    pub fn new<N: Into<String>>(name: N) -> Self {
+        Self {
+            name: name.into(),
+            data_ref: RefCell::new(None),
+            check_interval: DEFAULT_CHECK_INTERVAL,
+            last_check: Cell::new(None),
+            value_type: PhantomData,
+        }
+    }
+
+    /// Sets how often the data ref is checked for being orphaned.
+    ///
+    /// # Arguments
+    /// * `interval` - a check interval, zero checks on every access.
+    ///
+    /// # Returns
+    /// Returns a modified data ref.
+    pub fn check_interval(mut self, interval: Duration) -> Self {
+        self.check_interval = interval;
+        self
+    }
+
+    /// Returns the data ref name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Reads the data ref value.
+    ///
+    /// # Returns
+    /// Returns data ref value on success. Otherwise returns [`super::DataAccessError`]
+    /// if the data ref can't be found.
+    pub fn get(&self) -> Result<T> {
+        self.access(T::read)
+    }
+
+    /// Writes a new data ref value.
+    ///
+    /// # Arguments
+    /// * `value` - a data ref value.
+    ///
+    /// # Returns
+    /// Returns empty result on success. Otherwise returns [`super::DataAccessError`]
+    /// if the data ref can't be found.
+    pub fn set(&self, value: T) -> Result<()> {
+        self.access(|data_ref| T::write(data_ref, value))
+    }
+
+    /// Makes sure the data ref is found and not orphaned and passes it to a closure.
+    fn access<R, F: FnOnce(&DataRef) -> R>(&self, f: F) -> Result<R> {
+        let now = Instant::now();
+        let mut data_ref = self.data_ref.borrow_mut();
+        if let Some(current) = data_ref.as_ref() {
+            let is_due = self
+                .last_check
+                .get()
+                .is_none_or(|last| now.duration_since(last) >= self.check_interval);
+            if !is_due {
+                return Ok(f(current));
+            }
+
+            self.last_check.set(Some(now));
+            if is_data_ref_good(current) {
+                return Ok(f(current));
+            }
+
+            crate::warn!("data ref {} is orphaned, looking it up again", self.name);
+            *data_ref = None;
+        }
+
+        let found = find_data_ref(self.name.as_str())?;
+        let result = f(&found);
+        *data_ref = Some(found);
+        self.last_check.set(Some(now));
+        Ok(result)
+    }
+}
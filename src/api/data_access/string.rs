@@ -0,0 +1,119 @@
+use std::ffi;
+
+use super::{
+    can_write_data_ref, find_data_ref, get_data_b_len, read_bytes, set_data_b, DataAccessError,
+    DataRef, DataWrite, Result,
+};
+
+/// Reads a nul-terminated string from a byte array data ref.
+fn read_string_bytes(data_ref: &DataRef) -> Vec<u8> {
+    let mut bytes = read_bytes(data_ref);
+    if let Some(end) = bytes.iter().position(|byte| *byte == 0) {
+        bytes.truncate(end);
+    }
+
+    bytes
+}
+
+/// A read only string data ref backed by a byte array.
+pub struct DataRefString {
+    data_ref: DataRef,
+}
+
+impl DataRefString {
+    /// Looks up a string data ref.
+    ///
+    /// # Arguments
+    /// * `name` - a data ref name.
+    ///
+    /// # Returns
+    /// Returns [`DataRefString`] on success. Otherwise returns [`DataAccessError`].
+    pub fn find<N: Into<String>>(name: N) -> Result<Self> {
+        Ok(Self {
+            data_ref: find_data_ref(name)?,
+        })
+    }
+
+    /// Reads the string value.
+    ///
+    /// # Returns
+    /// Returns the string on success. Otherwise returns [`DataAccessError::InvalidUtf8`]
+    /// if the data ref contains invalid UTF-8 bytes.
+    pub fn read(&self) -> Result<String> {
+        String::from_utf8(read_string_bytes(&self.data_ref)).map_err(DataAccessError::InvalidUtf8)
+    }
+
+    /// Reads the string value replacing invalid UTF-8 sequences, e.g. Latin-1 characters,
+    /// with the replacement character.
+    ///
+    /// # Returns
+    /// Returns the string value.
+    pub fn read_lossy(&self) -> String {
+        String::from_utf8_lossy(&read_string_bytes(&self.data_ref)).into_owned()
+    }
+}
+
+/// A writable string data ref backed by a byte array.
+pub struct WritableDataRefString {
+    data_ref: DataRef,
+}
+
+impl WritableDataRefString {
+    /// Looks up a writable string data ref.
+    ///
+    /// # Arguments
+    /// * `name` - a data ref name.
+    ///
+    /// # Returns
+    /// Returns [`WritableDataRefString`] on success. Otherwise returns [`DataAccessError::NotWritable`]
+    /// if the data ref can't be written or other [`DataAccessError`].
+    pub fn find<N: Into<String>>(name: N) -> Result<Self> {
+        let name = name.into();
+        let data_ref = find_data_ref(name.as_str())?;
+        if can_write_data_ref(&data_ref) {
+            Ok(Self { data_ref })
+        } else {
+            Err(DataAccessError::NotWritable(name))
+        }
+    }
+
+    /// Reads the string value.
+    ///
+    /// # Returns
+    /// Returns the string on success. Otherwise returns [`DataAccessError::InvalidUtf8`]
+    /// if the data ref contains invalid UTF-8 bytes.
+    pub fn read(&self) -> Result<String> {
+        String::from_utf8(read_string_bytes(&self.data_ref)).map_err(DataAccessError::InvalidUtf8)
+    }
+
+    /// Reads the string value replacing invalid UTF-8 sequences with the replacement character.
+    ///
+    /// # Returns
+    /// Returns the string value.
+    pub fn read_lossy(&self) -> String {
+        String::from_utf8_lossy(&read_string_bytes(&self.data_ref)).into_owned()
+    }
+}
+
+impl DataWrite<&str> for WritableDataRefString {
+    fn write(&self, value: &str) -> Result<()> {
+        let value_c = ffi::CString::new(value).map_err(DataAccessError::InvalidStringValue)?;
+        let bytes = value_c.as_bytes_with_nul();
+        let capacity = get_data_b_len(&self.data_ref);
+        if capacity > 0 && bytes.len() > capacity {
+            return Err(DataAccessError::StringTooLong {
+                len: bytes.len(),
+                capacity,
+            });
+        }
+
+        set_data_b(&self.data_ref, 0, bytes);
+        Ok(())
+    }
+}
+
+impl DataWrite<String> for WritableDataRefString {
+    fn write(&self, value: String) -> Result<()> {
+        <Self as DataWrite<&str>>::write(self, value.as_str())
+    }
+}
@@ -0,0 +1,77 @@
+use crate::api::plugin::{enable_feature, Feature, Message};
+
+use super::{find_data_ref, DataRef};
+
+type DataRefCallback = Box<dyn FnOnce(DataRef)>;
+
+/// Waits for data refs registered by other plugins.
+///
+/// Enables [`Feature::WantsDatarefNotifications`] so X-Plane notifies the plugin
+/// with [`Message::DataRefsAdded`] whenever new data refs are registered.
+/// Pending subscriptions are resolved when a notification arrives.
+pub struct DataRefSubscriptions {
+    pending: Vec<(String, DataRefCallback)>,
+}
+
+impl DataRefSubscriptions {
+    /// Creates a new subscriptions registry and enables data ref notifications.
+    ///
+    /// # Returns
+    /// Returns a new [`DataRefSubscriptions`] instance.
+    pub fn new() -> Self {
+        enable_feature(Feature::WantsDatarefNotifications);
+        Self {
+            pending: Vec::new(),
+        }
+    }
+
+    /// Calls a callback once the data ref becomes available. The callback is called
+    /// immediately if the data ref already exists.
+    ///
+    /// # Arguments
+    /// * `name` - a data ref name.
+    /// * `callback` - a callback which receives the found data ref.
+    pub fn on_dataref_available<N, F>(&mut self, name: N, callback: F)
+    where
+        N: Into<String>,
+        F: FnOnce(DataRef) + 'static,
+    {
+        let name = name.into();
+        match find_data_ref(name.as_str()) {
+            Ok(data_ref) => callback(data_ref),
+            Err(_) => self.pending.push((name, Box::new(callback))),
+        }
+    }
+
+    /// Handles a plugin message. Should be called from [`crate::plugin::XPlugin::receive_message`].
+    ///
+    /// # Arguments
+    /// * `message` - the received message.
+    pub fn handle_message(&mut self, message: &Message) {
+        if matches!(message, Message::DataRefsAdded(_)) {
+            self.resolve();
+        }
+    }
+
+    /// Tries to resolve all pending subscriptions.
+    pub fn resolve(&mut self) {
+        let pending = std::mem::take(&mut self.pending);
+        for (name, callback) in pending {
+            match find_data_ref(name.as_str()) {
+                Ok(data_ref) => callback(data_ref),
+                Err(_) => self.pending.push((name, callback)),
+            }
+        }
+    }
+
+    /// Returns the number of data refs which are not available yet.
+    pub fn pending_count(&self) -> usize {
+        self.pending.len()
+    }
+}
+
+impl Default for DataRefSubscriptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
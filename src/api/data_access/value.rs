@@ -0,0 +1,128 @@
+use std::cell::OnceCell;
+use std::marker::PhantomData;
+
+use super::{
+    find_data_ref, get_data_d, get_data_f, get_data_i, set_data_d, set_data_f, set_data_i,
+};
+use super::{DataRef, Result};
+
+/// A scalar value which can be read from and written to a data ref.
+pub trait DataValue: Sized {
+    /// Reads a value from a data ref.
+    ///
+    /// # Arguments
+    /// * `data_ref` - a data ref.
+    ///
+    /// # Returns
+    /// Returns data ref value.
+    fn read(data_ref: &DataRef) -> Self;
+
+    /// Writes a value to a data ref.
+    ///
+    /// # Arguments
+    /// * `data_ref` - a data ref.
+    /// * `value` - a data ref value.
+    fn write(data_ref: &DataRef, value: Self);
+}
+
+impl DataValue for ::std::os::raw::c_int {
+    fn read(data_ref: &DataRef) -> Self {
+        get_data_i(data_ref)
+    }
+
+    fn write(data_ref: &DataRef, value: Self) {
+        set_data_i(data_ref, value)
+    }
+}
+
+impl DataValue for f32 {
+    fn read(data_ref: &DataRef) -> Self {
+        get_data_f(data_ref)
+    }
+
+    fn write(data_ref: &DataRef, value: Self) {
+        set_data_f(data_ref, value)
+    }
+}
+
+impl DataValue for f64 {
+    fn read(data_ref: &DataRef) -> Self {
+        get_data_d(data_ref)
+    }
+
+    fn write(data_ref: &DataRef, value: Self) {
+        set_data_d(data_ref, value)
+    }
+}
+
+impl DataValue for bool {
+    fn read(data_ref: &DataRef) -> Self {
+        get_data_i(data_ref) != 0
+    }
+
+    fn write(data_ref: &DataRef, value: Self) {
+        set_data_i(data_ref, value as _)
+    }
+}
+
+/// A typed data ref which is looked up by name on first use and cached afterwards.
+/// A failed lookup is not cached, so the next access tries to find the data ref again.
+pub struct DataRefValue<T: DataValue> {
+    name: String,
+    data_ref: OnceCell<DataRef>,
+    value_type: PhantomData<T>,
+}
+
+impl<T: DataValue> DataRefValue<T> {
+    /// Creates a new lazily resolved data ref.
+    ///
+    /// # Arguments
+    /// * `name` - a data ref name.
+    ///
+    /// # Returns
+    /// Returns a new [`DataRefValue`] instance.
+    pub fn new<N: Into<String>>(name: N) -> Self {
+        Self {
+            name: name.into(),
+            data_ref: OnceCell::new(),
+            value_type: PhantomData,
+        }
+    }
+
+    /// Returns the data ref name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the data ref, looking it up if necessary.
+    ///
+    /// # Returns
+    /// Returns a [`DataRef`] on success. Otherwise returns [`super::DataAccessError`].
+    pub fn data_ref(&self) -> Result<&DataRef> {
+        if let Some(data_ref) = self.data_ref.get() {
+            return Ok(data_ref);
+        }
+
+        let data_ref = find_data_ref(self.name.as_str())?;
+        Ok(self.data_ref.get_or_init(|| data_ref))
+    }
+
+    /// Reads the data ref value.
+    ///
+    /// # Returns
+    /// Returns data ref value on success. Otherwise returns [`super::DataAccessError`].
+    pub fn get(&self) -> Result<T> {
+        self.data_ref().map(T::read)
+    }
+
+    /// Writes a new data ref value.
+    ///
+    /// # Arguments
+    /// * `value` - a data ref value.
+    ///
+    /// # Returns
+    /// Returns empty result on success. Otherwise returns [`super::DataAccessError`].
+    pub fn set(&self, value: T) -> Result<()> {
+        self.data_ref().map(|data_ref| T::write(data_ref, value))
+    }
+}
@@ -1,29 +1,52 @@
+pub mod animation;
 pub mod color;
 pub mod coord;
+pub mod coords;
+pub mod draggable;
+pub mod draw;
 pub mod error;
 pub mod event;
+pub mod gesture;
 pub mod gravity;
+pub mod hot_key;
 pub mod key;
+pub mod layer;
+pub mod monitor;
 pub mod mouse;
 pub mod rect;
+pub mod scaled;
 pub mod size;
 pub mod window;
 
 use std::ffi;
 use std::ops::{Deref, DerefMut};
 
+pub use self::animation::{
+    animate_window_geometry, animate_window_geometry_with, cancel_window_animation,
+    is_window_animating, stop_window_animations, Easing,
+};
 pub use self::color::Color;
 pub use self::coord::Coord;
+pub use self::coords::{GlobalBoxel, OsPixel, WindowLocal};
+pub use self::draggable::DraggableWindow;
+pub use self::draw::{DrawCallbackRecord, DrawHandler, DrawLink, DrawPhase};
 pub use self::error::DisplayError;
 pub use self::event::EventState;
+pub use self::gesture::{GestureHandler, GestureWindow};
 use self::gravity::GravityRect;
-pub use self::key::KeyFlags;
+pub use self::hot_key::{HotKeyHandler, HotKeyLink, HotKeyRecord};
+pub use self::key::{KeyFlag, KeyFlags};
+pub use self::layer::WindowLayer;
+pub use self::monitor::Monitor;
 pub use self::mouse::{MouseStatus, WheelAxis};
 pub use self::rect::Rect;
+pub use self::scaled::ScaledRect;
 pub use self::size::Size;
-pub use self::window::PositioningMode;
+pub use self::window::{PositioningMode, WindowDecoration};
 pub use self::window::{WindowHandler, WindowHandlerRecord, WindowId, WindowLink};
 
+use super::backend::backend;
+use super::data_access::DataRefValue;
 use super::utilities::VirtualKey;
 
 pub type Result<T> = std::result::Result<T, DisplayError>;
@@ -37,28 +60,72 @@ pub type Result<T> = std::result::Result<T, DisplayError>;
 /// # Returns
 /// Returns [`WindowHandlerRecord`] on success. Otherwise returns [`DisplayError`].
 pub fn create_window_ex<H: WindowHandler>(rect: &Rect, handler: H) -> Result<WindowHandlerRecord> {
+    create_window_in_layer(rect, WindowLayer::FloatingWindows, handler)
+}
+
+/// This routine creates a new “modern” window in a specific layer.
+///
+/// # Arguments
+/// * `rect` - window rectangle.
+/// * `layer` - a window layer. See [`WindowLayer`] for more details.
+/// * `handler` - window events handler.
+///
+/// # Returns
+/// Returns [`WindowHandlerRecord`] on success. Otherwise returns [`DisplayError`].
+pub fn create_window_in_layer<H: WindowHandler>(
+    rect: &Rect,
+    layer: WindowLayer,
+    handler: H,
+) -> Result<WindowHandlerRecord> {
+    create_window_with_decoration(rect, layer, WindowDecoration::RoundRectangle, handler)
+}
+
+/// This routine creates a new “modern” window in a specific layer with a specific decoration.
+///
+/// # Arguments
+/// * `rect` - window rectangle.
+/// * `layer` - a window layer. See [`WindowLayer`] for more details.
+/// * `decoration` - a window decoration. See [`WindowDecoration`] for more details.
+/// * `handler` - window events handler.
+///
+/// # Returns
+/// Returns [`WindowHandlerRecord`] on success. Otherwise returns [`DisplayError`].
+pub fn create_window_with_decoration<H: WindowHandler>(
+    rect: &Rect,
+    layer: WindowLayer,
+    decoration: WindowDecoration,
+    handler: H,
+) -> Result<WindowHandlerRecord> {
     unsafe extern "C" fn draw_window(
-        id: xplm_sys::XPLMWindowID,
+        _: xplm_sys::XPLMWindowID,
         refcon: *mut ::std::os::raw::c_void,
     ) {
-        if let (Ok(id), false) = (WindowId::try_from(id), refcon.is_null()) {
+        #[cfg(feature = "metrics")]
+        let _scope = crate::metrics::Scope::enter("window_draw");
+        if !refcon.is_null() {
             let link = refcon as *mut WindowLink;
-            (*link).draw(&id);
+            (*link).draw();
         }
     }
 
     unsafe extern "C" fn mouse_click(
-        _: xplm_sys::XPLMWindowID,
+        id: xplm_sys::XPLMWindowID,
         x: ::std::os::raw::c_int,
         y: ::std::os::raw::c_int,
         mouse: xplm_sys::XPLMMouseStatus,
         refcon: *mut ::std::os::raw::c_void,
     ) -> ::std::os::raw::c_int {
+        #[cfg(feature = "metrics")]
+        let _scope = crate::metrics::Scope::enter("window_mouse");
         match MouseStatus::try_from(mouse) {
             Ok(status) => {
                 let link = refcon as *mut WindowLink;
                 let coord = Coord::default().x(x).y(y);
-                (*link).mouse_click(coord, status).into()
+                let state = (*link).mouse_click(coord, status);
+                if let (true, Ok(id)) = (state.wants_focus(), WindowId::try_from(id)) {
+                    take_keyboard_focus(&id);
+                }
+                state.into()
             }
             Err(err) => {
                 crate::error!("{}", err);
@@ -75,6 +142,8 @@ pub fn create_window_ex<H: WindowHandler>(rect: &Rect, handler: H) -> Result<Win
         refcon: *mut ::std::os::raw::c_void,
         _: ::std::os::raw::c_int,
     ) {
+        #[cfg(feature = "metrics")]
+        let _scope = crate::metrics::Scope::enter("window_key");
         let link = refcon as *mut WindowLink;
         match VirtualKey::try_from(virtual_key) {
             Ok(virtual_key) => {
@@ -92,6 +161,8 @@ pub fn create_window_ex<H: WindowHandler>(rect: &Rect, handler: H) -> Result<Win
         y: ::std::os::raw::c_int,
         refcon: *mut ::std::os::raw::c_void,
     ) -> xplm_sys::XPLMCursorStatus {
+        #[cfg(feature = "metrics")]
+        let _scope = crate::metrics::Scope::enter("window_cursor");
         let link = refcon as *mut WindowLink;
         let coord = Coord::default().x(x).y(y);
         (*link).handle_cursor(coord);
@@ -106,6 +177,8 @@ pub fn create_window_ex<H: WindowHandler>(rect: &Rect, handler: H) -> Result<Win
         clicks: ::std::os::raw::c_int,
         refcon: *mut ::std::os::raw::c_void,
     ) -> ::std::os::raw::c_int {
+        #[cfg(feature = "metrics")]
+        let _scope = crate::metrics::Scope::enter("window_wheel");
         let link = refcon as *mut WindowLink;
         match WheelAxis::try_from(wheel) {
             Ok(wheel_axis) => {
@@ -134,13 +207,141 @@ pub fn create_window_ex<H: WindowHandler>(rect: &Rect, handler: H) -> Result<Win
         handleCursorFunc: Some(handle_cursor),
         handleMouseWheelFunc: Some(handle_mouse_wheel),
         refcon: link_ptr as _,
-        decorateAsFloatingWindow: xplm_sys::xplm_WindowDecorationRoundRectangle as _,
-        layer: xplm_sys::xplm_WindowLayerFloatingWindows as _,
+        decorateAsFloatingWindow: decoration.into(),
+        layer: layer.into(),
         handleRightClickFunc: Some(mouse_click),
     };
 
-    let id = unsafe { xplm_sys::XPLMCreateWindowEx(&mut params) };
-    Ok(WindowHandlerRecord::new(WindowId::try_from(id)?, link))
+    let id = backend().create_window_ex(&mut params);
+    let id = WindowId::try_from(id)?;
+    link.attach(*id);
+    layer::track(*id, layer);
+    Ok(WindowHandlerRecord::new(id, link))
+}
+
+unsafe extern "C" fn draw_callback(
+    phase: xplm_sys::XPLMDrawingPhase,
+    is_before: ::std::os::raw::c_int,
+    refcon: *mut ::std::os::raw::c_void,
+) -> ::std::os::raw::c_int {
+    #[cfg(feature = "metrics")]
+    let _scope = crate::metrics::Scope::enter("draw");
+    match DrawPhase::try_from(phase) {
+        Ok(phase) if !refcon.is_null() => {
+            let link = refcon as *mut DrawLink;
+            (*link).handler.draw(phase, is_before == 1) as _
+        }
+        Ok(_) => 1,
+        Err(err) => {
+            crate::error!("{}", err);
+            1
+        }
+    }
+}
+
+/// Registers a callback which is called every frame during a drawing phase.
+///
+/// # Arguments
+/// * `phase` - a drawing phase. See [`DrawPhase`] for more details.
+/// * `is_before` - `true` to be called before X-Plane draws the phase.
+/// * `handler` - a draw handler. See [`DrawHandler`] for more details.
+///
+/// # Returns
+/// Returns [`DrawCallbackRecord`] which keeps the callback registered.
+pub fn register_draw_callback<H: DrawHandler>(
+    phase: DrawPhase,
+    is_before: bool,
+    handler: H,
+) -> DrawCallbackRecord {
+    let mut link = Box::new(DrawLink {
+        phase,
+        is_before,
+        handler: Box::new(handler),
+    });
+    let link_ptr: *mut DrawLink = link.deref_mut();
+    unsafe {
+        xplm_sys::XPLMRegisterDrawCallback(
+            Some(draw_callback),
+            phase.into(),
+            is_before as _,
+            link_ptr as *mut _,
+        )
+    };
+    DrawCallbackRecord { link }
+}
+
+/// Unregisters a draw callback. Normally a callback is unregistered when its record is dropped.
+///
+/// # Arguments
+/// * `record` - a draw callback record.
+pub fn unregister_draw_callback(record: &mut DrawCallbackRecord) {
+    let link_ptr: *mut DrawLink = record.link.deref_mut();
+    unsafe {
+        xplm_sys::XPLMUnregisterDrawCallback(
+            Some(draw_callback),
+            record.link.phase.into(),
+            record.link.is_before as _,
+            link_ptr as *mut _,
+        )
+    };
+}
+
+unsafe extern "C" fn hot_key_callback(refcon: *mut ::std::os::raw::c_void) {
+    if !refcon.is_null() {
+        let link = refcon as *mut HotKeyLink;
+        (*link).handler.hot_key();
+    }
+}
+
+/// Registers a hot key. The user can remap hot keys in the X-Plane keyboard settings.
+///
+/// # Arguments
+/// * `key` - a virtual key. See [`VirtualKey`] for more details.
+/// * `flags` - modifier keys, the handler is called on key press.
+/// * `description` - a hot key description shown to the user.
+/// * `handler` - a hot key handler. See [`HotKeyHandler`] for more details.
+///
+/// # Returns
+/// Returns [`HotKeyRecord`] which keeps the hot key registered on success.
+/// Otherwise returns [`DisplayError`].
+pub fn register_hot_key<D: Into<String>, H: HotKeyHandler>(
+    key: VirtualKey,
+    flags: KeyFlags,
+    description: D,
+    handler: H,
+) -> Result<HotKeyRecord> {
+    let description_c =
+        ffi::CString::new(description.into()).map_err(DisplayError::InvalidHotKeyDescription)?;
+    let mut link = Box::new(HotKeyLink {
+        handler: Box::new(handler),
+    });
+    let link_ptr: *mut HotKeyLink = link.deref_mut();
+    let id = unsafe {
+        xplm_sys::XPLMRegisterHotKey(
+            key as u32 as ::std::os::raw::c_char,
+            flags.with(KeyFlags::DOWN).into(),
+            description_c.as_ptr(),
+            Some(hot_key_callback),
+            link_ptr as *mut _,
+        )
+    };
+
+    if id.is_null() {
+        Err(DisplayError::InvalidHotKeyId)
+    } else {
+        Ok(HotKeyRecord { id, link })
+    }
+}
+
+/// Unregisters a hot key. Normally a hot key is unregistered when its record is dropped.
+///
+/// # Arguments
+/// * `record` - a hot key record.
+pub fn unregister_hot_key(record: &mut HotKeyRecord) {
+    if !record.id.is_null() {
+        unsafe { xplm_sys::XPLMUnregisterHotKey(record.id) };
+        record.id = std::ptr::null_mut();
+    }
 }
 
 /// Destroys a window.
@@ -148,7 +349,9 @@ pub fn create_window_ex<H: WindowHandler>(rect: &Rect, handler: H) -> Result<Win
 /// # Arguments
 /// * `id` - a window identifier. See [`WindowId`] for more details.
 pub fn destroy_window(id: &WindowId) {
-    unsafe { xplm_sys::XPLMDestroyWindow(*id.deref()) };
+    layer::untrack(*id.deref());
+    animation::forget(*id.deref());
+    backend().destroy_window(*id.deref());
 }
 
 /// Returns the size of the main X-Plane OpenGL window in pixels.
@@ -164,6 +367,31 @@ pub fn get_screen_size() -> Size {
     Size::default().width(width).height(height)
 }
 
+/// Returns the user interface scale configured in X-Plane settings.
+/// Falls back to the ratio between the main window width in pixels and the
+/// screen width in boxels when the scale data ref is not available.
+///
+/// # Returns
+/// Returns the UI scale factor, 1.0 means no scaling.
+pub fn get_ui_scale() -> f32 {
+    thread_local! {
+        static UI_SCALE: DataRefValue<f32> = DataRefValue::new("sim/graphics/misc/user_interface_scale");
+        static WINDOW_WIDTH: DataRefValue<::std::os::raw::c_int> = DataRefValue::new("sim/graphics/view/window_width");
+    }
+
+    if let Ok(scale) = UI_SCALE.with(|scale| scale.get()) {
+        if scale > 0.0 {
+            return scale;
+        }
+    }
+
+    let boxels = get_screen_size().width;
+    match WINDOW_WIDTH.with(|width| width.get()) {
+        Ok(pixels) if pixels > 0 && boxels > 0 => pixels as f32 / boxels as f32,
+        _ => 1.0,
+    }
+}
+
 /// Returns the bounds of the “global” X-Plane desktop, in boxels.
 /// Unlike the non-global version [`get_screen_size`], this is multi-monitor aware.
 ///
@@ -182,6 +410,36 @@ pub fn get_screen_bounds_global() -> Rect {
         .bottom(bottom)
 }
 
+/// Returns the bounds of every monitor X-Plane is running on, in global desktop boxels.
+/// Monitors used by popped-out windows are included, full screen or not.
+///
+/// # Returns
+/// Returns the monitors ordered by the X-Plane monitor index.
+pub fn get_all_monitor_bounds_global() -> Vec<Monitor> {
+    unsafe extern "C" fn receive_monitor(
+        index: ::std::os::raw::c_int,
+        left: ::std::os::raw::c_int,
+        top: ::std::os::raw::c_int,
+        right: ::std::os::raw::c_int,
+        bottom: ::std::os::raw::c_int,
+        refcon: *mut ::std::os::raw::c_void,
+    ) {
+        let monitors = refcon as *mut Vec<Monitor>;
+        let bounds = Rect::new(left, top, right, bottom);
+        (*monitors).push(Monitor { index, bounds });
+    }
+
+    let mut monitors = Vec::new();
+    unsafe {
+        xplm_sys::XPLMGetAllMonitorBoundsGlobal(
+            Some(receive_monitor),
+            &mut monitors as *mut Vec<Monitor> as *mut ::std::os::raw::c_void,
+        )
+    };
+    monitors.sort_by_key(|monitor| monitor.index);
+    monitors
+}
+
 /// Returns the current mouse location in global desktop boxels.
 ///
 /// # Returns
@@ -209,13 +467,7 @@ pub fn get_mouse_location_global() -> Coord {
 /// # Returns
 /// Returns the bounding rect on a window.
 pub fn get_window_geometry(id: &WindowId) -> Rect {
-    let mut left = 0;
-    let mut top = 0;
-    let mut right = 0;
-    let mut bottom = 0;
-    unsafe {
-        xplm_sys::XPLMGetWindowGeometry(*id.deref(), &mut left, &mut top, &mut right, &mut bottom)
-    };
+    let [left, top, right, bottom] = backend().get_window_geometry(*id.deref());
     Rect::default()
         .left(left)
         .top(top)
@@ -236,9 +488,7 @@ pub fn get_window_geometry(id: &WindowId) -> Rect {
 /// * `id` - a window identifier.
 /// * `rect` - a bounding box rect of a window.
 pub fn set_window_geometry(id: &WindowId, rect: &Rect) {
-    unsafe {
-        xplm_sys::XPLMSetWindowGeometry(*id.deref(), rect.left, rect.top, rect.right, rect.bottom)
-    };
+    backend().set_window_geometry(*id.deref(), [rect.left, rect.top, rect.right, rect.bottom]);
 }
 
 /// This routine returns the position and size of a “popped out” window,
@@ -283,7 +533,7 @@ pub fn set_window_geometry_os(id: &WindowId, rect: &Rect) {
 /// # Returns
 /// Returns `true` if window is visible. Otherwise returns false.
 pub fn get_window_is_visible(id: &WindowId) -> bool {
-    1 == unsafe { xplm_sys::XPLMGetWindowIsVisible(*id.deref()) }
+    backend().get_window_is_visible(*id.deref())
 }
 
 /// Sets a window visible.
@@ -291,7 +541,7 @@ pub fn get_window_is_visible(id: &WindowId) -> bool {
 /// # Arguments
 /// * `id` - a window identifier
 pub fn set_window_visible(id: &WindowId) {
-    unsafe { xplm_sys::XPLMSetWindowIsVisible(*id.deref(), 1) };
+    backend().set_window_is_visible(*id.deref(), true);
 }
 
 /// Sets a window hidden.
@@ -299,7 +549,7 @@ pub fn set_window_visible(id: &WindowId) {
 /// # Arguments
 /// * `id` - a window identifier
 pub fn set_window_hidden(id: &WindowId) {
-    unsafe { xplm_sys::XPLMSetWindowIsVisible(*id.deref(), 0) };
+    backend().set_window_is_visible(*id.deref(), false);
 }
 
 /// Checks wether a window is poppet-out.
@@ -425,3 +675,28 @@ pub fn bring_window_to_front(id: &WindowId) {
 pub fn is_window_in_front(id: &WindowId) -> bool {
     unsafe { xplm_sys::XPLMIsWindowInFront(*id.deref()) == 1 }
 }
+
+/// Moves the window behind the other windows of its layer.
+/// X-Plane has no such call, so every other window this plugin created in the same layer
+/// is brought to the front instead. Windows of other plugins are not affected and
+/// the relative order of the other windows is not preserved.
+///
+/// # Arguments
+/// * `id` - a window identifier.
+pub fn send_window_to_back(id: &WindowId) {
+    for window in layer::siblings_of(*id.deref()) {
+        unsafe { xplm_sys::XPLMBringWindowToFront(window) };
+    }
+}
+
+/// Returns the layer a window was created in.
+///
+/// # Arguments
+/// * `id` - a window identifier.
+///
+/// # Returns
+/// Returns [`WindowLayer`] or [`None`] if the window was not created by [`create_window_in_layer`]
+/// or has been destroyed.
+pub fn get_window_layer(id: &WindowId) -> Option<WindowLayer> {
+    layer::layer_of(*id.deref())
+}
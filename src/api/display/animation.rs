@@ -0,0 +1,206 @@
+use std::cell::RefCell;
+use std::time::{Duration, Instant};
+
+use crate::api::processing::{
+    create_flight_loop, schedule_flight_loop, FlightLoopPhase, FlightLoopRecord,
+};
+
+use super::{get_window_geometry, set_window_geometry, Rect, WindowId};
+
+/// An easing curve of a window animation.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum Easing {
+    /// Constant speed.
+    Linear,
+    /// Starts slowly and accelerates.
+    EaseIn,
+    /// Starts fast and decelerates.
+    EaseOut,
+    /// Accelerates and then decelerates.
+    #[default]
+    EaseInOut,
+}
+
+impl Easing {
+    /// Applies the curve.
+    ///
+    /// # Arguments
+    /// * `t` - an animation progress in range 0.0..=1.0.
+    ///
+    /// # Returns
+    /// Returns the eased progress in range 0.0..=1.0.
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Self::Linear => t,
+            Self::EaseIn => t * t,
+            Self::EaseOut => t * (2.0 - t),
+            Self::EaseInOut => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+struct Animation {
+    id: xplm_sys::XPLMWindowID,
+    from: Rect,
+    to: Rect,
+    started: Instant,
+    duration: Duration,
+    easing: Easing,
+}
+
+impl Animation {
+    /// Moves the window to the current frame geometry.
+    ///
+    /// # Returns
+    /// Returns `true` once the animation has finished.
+    fn step(&self, now: Instant) -> bool {
+        let t = if self.duration.is_zero() {
+            1.0
+        } else {
+            now.duration_since(self.started).as_secs_f32() / self.duration.as_secs_f32()
+        };
+
+        let eased = self.easing.apply(t);
+        let rect = Rect::new(
+            lerp(self.from.left, self.to.left, eased),
+            lerp(self.from.top, self.to.top, eased),
+            lerp(self.from.right, self.to.right, eased),
+            lerp(self.from.bottom, self.to.bottom, eased),
+        );
+
+        if let Ok(id) = WindowId::try_from(self.id) {
+            set_window_geometry(&id, &rect);
+        }
+
+        t >= 1.0
+    }
+}
+
+thread_local! {
+    /// Running window animations.
+    static ANIMATIONS: RefCell<Vec<Animation>> = const { RefCell::new(Vec::new()) };
+    /// The flight loop which advances the animations, created on demand. It can't be
+    /// destroyed from its own callback, so a finished flight loop is only deactivated, it is
+    /// reused by the next animation and destroyed when an animation is cancelled or by
+    /// [`stop_window_animations`].
+    static FLIGHT_LOOP: RefCell<Option<FlightLoopRecord>> = const { RefCell::new(None) };
+}
+
+/// Animates a window to a new geometry with [`Easing::EaseInOut`], e.g. to slide a panel
+/// in or collapse a sidebar. The animation is advanced by a flight loop managed by the crate.
+///
+/// # Arguments
+/// * `id` - a window identifier.
+/// * `target` - a target geometry in the units of [`get_window_geometry`].
+/// * `duration` - an animation duration, zero moves the window at once.
+pub fn animate_window_geometry(id: &WindowId, target: &Rect, duration: Duration) {
+    animate_window_geometry_with(id, target, duration, Easing::default());
+}
+
+/// Animates a window to a new geometry with an easing curve.
+/// A running animation of the same window continues from its current geometry.
+///
+/// # Arguments
+/// * `id` - a window identifier.
+/// * `target` - a target geometry in the units of [`get_window_geometry`].
+/// * `duration` - an animation duration, zero moves the window at once.
+/// * `easing` - an easing curve. See [`Easing`] for more details.
+pub fn animate_window_geometry_with(
+    id: &WindowId,
+    target: &Rect,
+    duration: Duration,
+    easing: Easing,
+) {
+    let animation = Animation {
+        id: **id,
+        from: get_window_geometry(id),
+        to: *target,
+        started: Instant::now(),
+        duration,
+        easing,
+    };
+
+    ANIMATIONS.with(|animations| {
+        let mut animations = animations.borrow_mut();
+        animations.retain(|animation| animation.id != **id);
+        animations.push(animation);
+    });
+
+    FLIGHT_LOOP.with(|flight_loop| {
+        let mut flight_loop = flight_loop.borrow_mut();
+        let record = flight_loop
+            .get_or_insert_with(|| create_flight_loop(FlightLoopPhase::BeforeFlightModel, advance));
+        schedule_flight_loop(record, -1.0, true);
+    });
+}
+
+/// Stops a window animation, the window keeps its current geometry.
+///
+/// # Arguments
+/// * `id` - a window identifier.
+pub fn cancel_window_animation(id: &WindowId) {
+    forget(**id);
+}
+
+/// Checks wether a window is being animated.
+///
+/// # Arguments
+/// * `id` - a window identifier.
+///
+/// # Returns
+/// Returns `true` if the window is being animated. Otherwise returns `false`.
+pub fn is_window_animating(id: &WindowId) -> bool {
+    ANIMATIONS.with(|animations| {
+        animations
+            .borrow()
+            .iter()
+            .any(|animation| animation.id == **id)
+    })
+}
+
+/// Stops all window animations and destroys the flight loop which advances them.
+///
+/// Called by [`crate::register_plugin`] when the plugin is stopped, plugins which export
+/// the entry points themselves should call it from `XPluginStop`, so the flight loop isn't
+/// destroyed at thread exit after the plugin is unloaded.
+pub fn stop_window_animations() {
+    ANIMATIONS.with(|animations| animations.borrow_mut().clear());
+    FLIGHT_LOOP.with(|flight_loop| flight_loop.borrow_mut().take());
+}
+
+/// Forgets the animation of a window, e.g. when it is destroyed.
+/// The flight loop is destroyed once no animation is left.
+pub(super) fn forget(id: xplm_sys::XPLMWindowID) {
+    ANIMATIONS.with(|animations| {
+        animations
+            .borrow_mut()
+            .retain(|animation| animation.id != id)
+    });
+    release_idle_flight_loop();
+}
+
+/// Destroys the flight loop if no animation is left. Must not be called from the flight loop.
+fn release_idle_flight_loop() {
+    let is_empty = ANIMATIONS.with(|animations| animations.borrow().is_empty());
+    if is_empty {
+        FLIGHT_LOOP.with(|flight_loop| flight_loop.borrow_mut().take());
+    }
+}
+
+fn advance(_elapsed: f32) -> f32 {
+    let now = Instant::now();
+    ANIMATIONS.with(|animations| {
+        let mut animations = animations.borrow_mut();
+        animations.retain(|animation| !animation.step(now));
+        if animations.is_empty() {
+            0.0
+        } else {
+            -1.0
+        }
+    })
+}
+
+fn lerp(from: ::std::os::raw::c_int, to: ::std::os::raw::c_int, t: f32) -> ::std::os::raw::c_int {
+    (from as f32 + (to - from) as f32 * t).round() as _
+}
@@ -1,6 +1,11 @@
+use super::DisplayError;
+
 /// Display color representation.
 /// A default color is white.
-#[derive(Debug, Copy, Clone)]
+///
+/// The alpha channel is only respected by the APIs which support transparency,
+/// e.g. the shape drawing. Text drawing ignores it.
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Color {
     /// Red color value.
     pub r: f32,
@@ -8,19 +13,110 @@ pub struct Color {
     pub g: f32,
     /// Blue color value.
     pub b: f32,
+    /// Alpha channel value, 0.0 is fully transparent and 1.0 is opaque.
+    pub a: f32,
 }
 
 impl Color {
+    /// Creates a new opaque color.
+    ///
+    /// # Arguments
+    /// * `r` - red color value in range 0.0..=1.0.
+    /// * `g` - green color value in range 0.0..=1.0.
+    /// * `b` - blue color value in range 0.0..=1.0.
+    ///
+    /// # Returns
+    /// Returns a new color.
+    pub fn new(r: f32, g: f32, b: f32) -> Self {
+        Self { r, g, b, a: 1.0 }
+    }
+
+    /// Creates a new color with alpha channel.
+    ///
+    /// # Arguments
+    /// * `r` - red color value in range 0.0..=1.0.
+    /// * `g` - green color value in range 0.0..=1.0.
+    /// * `b` - blue color value in range 0.0..=1.0.
+    /// * `a` - alpha channel value in range 0.0..=1.0.
+    ///
+    /// # Returns
+    /// Returns a new color.
+    pub fn new_rgba(r: f32, g: f32, b: f32, a: f32) -> Self {
+        Self { r, g, b, a }
+    }
+
+    /// Creates a new opaque color from 8 bit components.
+    ///
+    /// # Arguments
+    /// * `r` - red color value.
+    /// * `g` - green color value.
+    /// * `b` - blue color value.
+    ///
+    /// # Returns
+    /// Returns a new color.
+    pub fn from_rgb(r: u8, g: u8, b: u8) -> Self {
+        Self::from_rgba(r, g, b, u8::MAX)
+    }
+
+    /// Creates a new color from 8 bit components with alpha channel.
+    ///
+    /// # Arguments
+    /// * `r` - red color value.
+    /// * `g` - green color value.
+    /// * `b` - blue color value.
+    /// * `a` - alpha channel value.
+    ///
+    /// # Returns
+    /// Returns a new color.
+    pub fn from_rgba(r: u8, g: u8, b: u8, a: u8) -> Self {
+        let max = u8::MAX as f32;
+        Self {
+            r: r as f32 / max,
+            g: g as f32 / max,
+            b: b as f32 / max,
+            a: a as f32 / max,
+        }
+    }
+
+    /// Parses a color from a hex string in `#RRGGBB` or `#RRGGBBAA` format.
+    /// The leading `#` is optional, every other character must be a hex digit.
+    ///
+    /// # Arguments
+    /// * `value` - a hex color string.
+    ///
+    /// # Returns
+    /// Returns a color on success. Otherwise returns [`DisplayError::InvalidHexColor`].
+    pub fn from_hex<T: AsRef<str>>(value: T) -> Result<Self, DisplayError> {
+        let value = value.as_ref();
+        let hex = value.strip_prefix('#').unwrap_or(value);
+        if !hex.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+            return Err(DisplayError::InvalidHexColor(value.to_string()));
+        }
+
+        let component = |index: usize| {
+            hex.get(index..index + 2)
+                .and_then(|part| u8::from_str_radix(part, 16).ok())
+                .ok_or_else(|| DisplayError::InvalidHexColor(value.to_string()))
+        };
+
+        match hex.len() {
+            6 => Ok(Self::from_rgb(component(0)?, component(2)?, component(4)?)),
+            8 => Ok(Self::from_rgba(
+                component(0)?,
+                component(2)?,
+                component(4)?,
+                component(6)?,
+            )),
+            _ => Err(DisplayError::InvalidHexColor(value.to_string())),
+        }
+    }
+
     /// Creates a new white color.
     ///
     /// # Returns
     /// Return a white color.
     pub fn white() -> Self {
-        Self {
-            r: 1.0,
-            g: 1.0,
-            b: 1.0,
-        }
+        Self::new(1.0, 1.0, 1.0)
     }
 
     /// Creates a new black color.
@@ -28,12 +124,142 @@ impl Color {
     /// # Returns
     /// Return a black color.
     pub fn black() -> Self {
+        Self::new(0.0, 0.0, 0.0)
+    }
+
+    /// Creates a new red color.
+    ///
+    /// # Returns
+    /// Return a red color.
+    pub fn red() -> Self {
+        Self::new(1.0, 0.0, 0.0)
+    }
+
+    /// Creates a new green color.
+    ///
+    /// # Returns
+    /// Return a green color.
+    pub fn green() -> Self {
+        Self::new(0.0, 1.0, 0.0)
+    }
+
+    /// Creates a new blue color.
+    ///
+    /// # Returns
+    /// Return a blue color.
+    pub fn blue() -> Self {
+        Self::new(0.0, 0.0, 1.0)
+    }
+
+    /// Creates a new yellow color.
+    ///
+    /// # Returns
+    /// Return a yellow color.
+    pub fn yellow() -> Self {
+        Self::new(1.0, 1.0, 0.0)
+    }
+
+    /// Creates a new amber color commonly used for cautions.
+    ///
+    /// # Returns
+    /// Return an amber color.
+    pub fn amber() -> Self {
+        Self::new(1.0, 0.75, 0.0)
+    }
+
+    /// Creates a new cyan color.
+    ///
+    /// # Returns
+    /// Return a cyan color.
+    pub fn cyan() -> Self {
+        Self::new(0.0, 1.0, 1.0)
+    }
+
+    /// Creates a new magenta color.
+    ///
+    /// # Returns
+    /// Return a magenta color.
+    pub fn magenta() -> Self {
+        Self::new(1.0, 0.0, 1.0)
+    }
+
+    /// Creates a new gray color.
+    ///
+    /// # Returns
+    /// Return a gray color.
+    pub fn gray() -> Self {
+        Self::new(0.5, 0.5, 0.5)
+    }
+
+    /// Sets the alpha channel of the color.
+    ///
+    /// # Arguments
+    /// * `value` - alpha channel value in range 0.0..=1.0.
+    ///
+    /// # Returns
+    /// Returns new instance of the color with modified alpha channel.
+    pub fn alpha(mut self, value: f32) -> Self {
+        self.a = value;
+        self
+    }
+
+    /// Linearly interpolates between two colors including alpha channel.
+    ///
+    /// # Arguments
+    /// * `other` - a color to interpolate to.
+    /// * `t` - an interpolation factor. Clamped to range 0.0..=1.0.
+    ///
+    /// # Returns
+    /// Returns `self` if `t` is 0.0, `other` if `t` is 1.0 or a mixed color otherwise.
+    pub fn lerp(&self, other: &Color, t: f32) -> Self {
+        let t = t.clamp(0.0, 1.0);
+        let mix = |from: f32, to: f32| from + (to - from) * t;
         Self {
-            r: 0.0,
-            g: 0.0,
-            b: 0.0,
+            r: mix(self.r, other.r),
+            g: mix(self.g, other.g),
+            b: mix(self.b, other.b),
+            a: mix(self.a, other.a),
+        }
+    }
+
+    /// Picks a color from a gradient defined by evenly distributed color stops.
+    /// Useful for warning-gradient gauges, e.g. green to yellow to red.
+    ///
+    /// # Arguments
+    /// * `stops` - gradient color stops.
+    /// * `t` - a position in the gradient. Clamped to range 0.0..=1.0.
+    ///
+    /// # Returns
+    /// Returns an interpolated color or [`None`] if no stops are provided.
+    pub fn gradient(stops: &[Color], t: f32) -> Option<Self> {
+        match stops {
+            [] => None,
+            [single] => Some(*single),
+            _ => {
+                let segments = (stops.len() - 1) as f32;
+                let position = t.clamp(0.0, 1.0) * segments;
+                let index = std::cmp::min(position.floor() as usize, stops.len() - 2);
+                let local = position - index as f32;
+                Some(stops[index].lerp(&stops[index + 1], local))
+            }
         }
     }
+
+    /// Returns red, green and blue components as an array.
+    ///
+    /// # Returns
+    /// Returns an array of color components.
+    pub fn to_rgb_array(&self) -> [f32; 3] {
+        [self.r, self.g, self.b]
+    }
+
+    /// Returns red, green, blue and alpha components as an array.
+    ///
+    /// # Returns
+    /// Returns an array of color components.
+    pub fn to_rgba_array(&self) -> [f32; 4] {
+        [self.r, self.g, self.b, self.a]
+    }
 }
 
 impl Default for Color {
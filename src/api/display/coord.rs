@@ -2,7 +2,7 @@
 pub type CoordType = ::std::os::raw::c_int;
 
 /// X-Plane 2D coordinate definition.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
 pub struct Coord {
     /// The X coordinate.
     pub x: CoordType,
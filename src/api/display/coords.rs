@@ -0,0 +1,192 @@
+//! Explicit coordinate spaces.
+//!
+//! X-Plane reports mouse events of modern windows in global desktop boxels, draws them
+//! in the same space and positions popped-out windows in operating system pixels.
+//! The newtypes below keep the spaces apart, conversions take the window they are
+//! relative to:
+//!
+//! ```ignore
+//! fn mouse_click(&mut self, id: &WindowId, coord: Coord, status: MouseStatus) -> EventState {
+//!     let local = GlobalBoxel(coord).to_local(id);
+//!     if self.button.contains(&local.0) { ... }
+//! }
+//! ```
+
+use super::{
+    get_mouse_location_global, get_ui_scale, get_window_geometry, get_window_geometry_os,
+    is_window_popped_out, Coord, Rect, WindowId,
+};
+
+/// A point on the global X-Plane desktop in boxels, the origin is the bottom left
+/// corner of the main monitor. Mouse events and drawing of modern windows use this space.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct GlobalBoxel(pub Coord);
+
+/// A point in operating system pixels, the origin is the bottom left corner of the
+/// window X-Plane draws to.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct OsPixel(pub Coord);
+
+/// A point in boxels relative to the bottom left corner of a window.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct WindowLocal(pub Coord);
+
+impl GlobalBoxel {
+    /// Returns the current mouse location.
+    pub fn mouse() -> Self {
+        Self(get_mouse_location_global())
+    }
+
+    /// Converts the point to window local coordinates.
+    ///
+    /// # Arguments
+    /// * `id` - a window identifier.
+    ///
+    /// # Returns
+    /// Returns [`WindowLocal`] coordinates.
+    pub fn to_local(self, id: &WindowId) -> WindowLocal {
+        let geometry = get_window_geometry(id);
+        WindowLocal(Coord::new(
+            self.0.x - geometry.left,
+            self.0.y - geometry.bottom,
+        ))
+    }
+
+    /// Converts the point to operating system pixels of the window it is drawn in.
+    /// Popped-out windows are scaled by their operating system geometry, other windows
+    /// by the X-Plane UI scale.
+    ///
+    /// # Arguments
+    /// * `id` - a window identifier.
+    ///
+    /// # Returns
+    /// Returns [`OsPixel`] coordinates.
+    pub fn to_os_pixel(self, id: &WindowId) -> OsPixel {
+        let (boxels, pixels) = spaces(id);
+        OsPixel(Coord::new(
+            map(
+                self.0.x,
+                boxels.left,
+                boxels.right,
+                pixels.left,
+                pixels.right,
+            ),
+            map(
+                self.0.y,
+                boxels.bottom,
+                boxels.top,
+                pixels.bottom,
+                pixels.top,
+            ),
+        ))
+    }
+
+    /// Checks wether the point lies within a window.
+    ///
+    /// # Arguments
+    /// * `id` - a window identifier.
+    ///
+    /// # Returns
+    /// Returns `true` if the point lies within the window. Otherwise returns `false`.
+    pub fn is_inside(self, id: &WindowId) -> bool {
+        get_window_geometry(id).contains(&self.0)
+    }
+}
+
+impl WindowLocal {
+    /// Converts the point to global desktop boxels.
+    ///
+    /// # Arguments
+    /// * `id` - a window identifier.
+    ///
+    /// # Returns
+    /// Returns [`GlobalBoxel`] coordinates.
+    pub fn to_global(self, id: &WindowId) -> GlobalBoxel {
+        let geometry = get_window_geometry(id);
+        GlobalBoxel(Coord::new(
+            self.0.x + geometry.left,
+            self.0.y + geometry.bottom,
+        ))
+    }
+}
+
+impl OsPixel {
+    /// Converts the point to global desktop boxels.
+    ///
+    /// # Arguments
+    /// * `id` - a window identifier.
+    ///
+    /// # Returns
+    /// Returns [`GlobalBoxel`] coordinates.
+    pub fn to_global(self, id: &WindowId) -> GlobalBoxel {
+        let (boxels, pixels) = spaces(id);
+        GlobalBoxel(Coord::new(
+            map(
+                self.0.x,
+                pixels.left,
+                pixels.right,
+                boxels.left,
+                boxels.right,
+            ),
+            map(
+                self.0.y,
+                pixels.bottom,
+                pixels.top,
+                boxels.bottom,
+                boxels.top,
+            ),
+        ))
+    }
+}
+
+impl From<GlobalBoxel> for Coord {
+    fn from(value: GlobalBoxel) -> Self {
+        value.0
+    }
+}
+
+impl From<OsPixel> for Coord {
+    fn from(value: OsPixel) -> Self {
+        value.0
+    }
+}
+
+impl From<WindowLocal> for Coord {
+    fn from(value: WindowLocal) -> Self {
+        value.0
+    }
+}
+
+/// Returns the window geometry in boxels and the matching geometry in pixels.
+fn spaces(id: &WindowId) -> (Rect, Rect) {
+    let boxels = get_window_geometry(id);
+    if is_window_popped_out(id) {
+        return (boxels, get_window_geometry_os(id));
+    }
+
+    let scale = get_ui_scale();
+    let pixels = Rect::new(
+        (boxels.left as f32 * scale).round() as _,
+        (boxels.top as f32 * scale).round() as _,
+        (boxels.right as f32 * scale).round() as _,
+        (boxels.bottom as f32 * scale).round() as _,
+    );
+    (boxels, pixels)
+}
+
+/// Maps a value from one range to another.
+fn map(
+    value: ::std::os::raw::c_int,
+    from_start: ::std::os::raw::c_int,
+    from_end: ::std::os::raw::c_int,
+    to_start: ::std::os::raw::c_int,
+    to_end: ::std::os::raw::c_int,
+) -> ::std::os::raw::c_int {
+    let from = (from_end - from_start) as f32;
+    if from == 0.0 {
+        return to_start;
+    }
+
+    let t = (value - from_start) as f32 / from;
+    (to_start as f32 + t * (to_end - to_start) as f32).round() as _
+}
@@ -0,0 +1,209 @@
+use crate::api::utilities::VirtualKey;
+
+use super::{
+    get_window_geometry, set_window_geometry, set_window_resizing_limits, Coord, EventState,
+    KeyFlags, MouseStatus, Rect, Size, WheelAxis, WindowHandler, WindowId,
+};
+
+/// Default size of the resize handle in the bottom right window corner in boxels.
+pub const DEFAULT_RESIZE_HANDLE: ::std::os::raw::c_int = 12;
+
+/// An active mouse gesture.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Gesture {
+    Move { last: Coord },
+    Resize { last: Coord },
+}
+
+/// A window handler decorator which lets users move an undecorated window by dragging
+/// and optionally resize it with a handle in the bottom right corner.
+///
+/// Mouse clicks are offered to the wrapped handler first, a gesture starts only
+/// when the wrapped handler propagates the click.
+pub struct DraggableWindow<H: WindowHandler> {
+    handler: H,
+    drag_area_height: Option<::std::os::raw::c_int>,
+    resize_handle: Option<::std::os::raw::c_int>,
+    min_size: Size,
+    max_size: Size,
+    limits_applied: bool,
+    gesture: Option<Gesture>,
+}
+
+impl<H: WindowHandler> DraggableWindow<H> {
+    /// Wraps a window handler. The whole window can be dragged and resizing is disabled.
+    ///
+    /// # Arguments
+    /// * `handler` - a window handler to decorate.
+    ///
+    /// # Returns
+    /// Returns a new [`DraggableWindow`] instance.
+    pub fn new(handler: H) -> Self {
+        Self {
+            handler,
+            drag_area_height: None,
+            resize_handle: None,
+            min_size: Size::new(0, 0),
+            max_size: Size::new(::std::os::raw::c_int::MAX, ::std::os::raw::c_int::MAX),
+            limits_applied: true,
+            gesture: None,
+        }
+    }
+
+    /// Limits dragging to a strip at the top of the window, e.g. a title bar.
+    ///
+    /// # Arguments
+    /// * `height` - a drag area height in boxels.
+    ///
+    /// # Returns
+    /// Returns a modified window decorator.
+    pub fn drag_area_height(mut self, height: ::std::os::raw::c_int) -> Self {
+        self.drag_area_height = Some(height);
+        self
+    }
+
+    /// Enables resizing with a handle in the bottom right window corner.
+    ///
+    /// # Arguments
+    /// * `handle` - a resize handle size in boxels, see [`DEFAULT_RESIZE_HANDLE`].
+    /// * `min` - a minimum window size.
+    /// * `max` - a maximum window size.
+    ///
+    /// # Returns
+    /// Returns a modified window decorator.
+    pub fn resizable(mut self, handle: ::std::os::raw::c_int, min: Size, max: Size) -> Self {
+        self.resize_handle = Some(handle);
+        self.min_size = min;
+        self.max_size = max;
+        self.limits_applied = false;
+        self
+    }
+
+    /// Returns the wrapped window handler.
+    pub fn handler(&self) -> &H {
+        &self.handler
+    }
+
+    /// Returns the wrapped window handler.
+    pub fn handler_mut(&mut self) -> &mut H {
+        &mut self.handler
+    }
+
+    /// Checks wether the user is moving or resizing the window.
+    pub fn is_dragging(&self) -> bool {
+        self.gesture.is_some()
+    }
+
+    fn in_drag_area(&self, geometry: &Rect, coord: &Coord) -> bool {
+        match self.drag_area_height {
+            Some(height) => coord.y > geometry.top - height,
+            None => true,
+        }
+    }
+
+    fn in_resize_handle(&self, geometry: &Rect, coord: &Coord) -> bool {
+        match self.resize_handle {
+            Some(handle) => coord.x > geometry.right - handle && coord.y < geometry.bottom + handle,
+            None => false,
+        }
+    }
+
+    fn begin_gesture(&mut self, id: &WindowId, coord: Coord) -> bool {
+        let geometry = get_window_geometry(id);
+        self.gesture = if self.in_resize_handle(&geometry, &coord) {
+            Some(Gesture::Resize { last: coord })
+        } else if self.in_drag_area(&geometry, &coord) {
+            Some(Gesture::Move { last: coord })
+        } else {
+            None
+        };
+
+        self.gesture.is_some()
+    }
+
+    fn continue_gesture(&mut self, id: &WindowId, coord: Coord) {
+        let Some(gesture) = self.gesture else {
+            return;
+        };
+
+        let geometry = get_window_geometry(id);
+        match gesture {
+            Gesture::Move { last } => {
+                let dx = coord.x - last.x;
+                let dy = coord.y - last.y;
+                let rect = Rect::new(
+                    geometry.left + dx,
+                    geometry.top + dy,
+                    geometry.right + dx,
+                    geometry.bottom + dy,
+                );
+                set_window_geometry(id, &rect);
+                self.gesture = Some(Gesture::Move { last: coord });
+            }
+            Gesture::Resize { last } => {
+                let width = (geometry.right - geometry.left + coord.x - last.x)
+                    .clamp(self.min_size.width, self.max_size.width);
+                let height = (geometry.top - geometry.bottom + last.y - coord.y)
+                    .clamp(self.min_size.height, self.max_size.height);
+                let rect = geometry
+                    .right(geometry.left + width)
+                    .bottom(geometry.top - height);
+                set_window_geometry(id, &rect);
+                self.gesture = Some(Gesture::Resize { last: coord });
+            }
+        }
+    }
+}
+
+impl<H: WindowHandler> WindowHandler for DraggableWindow<H> {
+    fn draw(&mut self, id: &WindowId) {
+        if !self.limits_applied {
+            set_window_resizing_limits(id, &self.min_size, &self.max_size);
+            self.limits_applied = true;
+        }
+
+        self.handler.draw(id);
+    }
+
+    fn mouse_click(&mut self, id: &WindowId, coord: Coord, status: MouseStatus) -> EventState {
+        match status {
+            MouseStatus::Down => {
+                let state = self.handler.mouse_click(id, coord, status);
+                if state == EventState::Propagate && self.begin_gesture(id, coord) {
+                    EventState::Consume
+                } else {
+                    state
+                }
+            }
+            MouseStatus::Drag if self.gesture.is_some() => {
+                self.continue_gesture(id, coord);
+                EventState::Consume
+            }
+            MouseStatus::Up if self.gesture.is_some() => {
+                self.continue_gesture(id, coord);
+                self.gesture = None;
+                EventState::Consume
+            }
+            _ => self.handler.mouse_click(id, coord, status),
+        }
+    }
+
+    fn handle_key(&mut self, id: &WindowId, key: char, virtual_key: VirtualKey, flags: KeyFlags) {
+        self.handler.handle_key(id, key, virtual_key, flags);
+    }
+
+    fn handle_cursor(&mut self, id: &WindowId, coord: Coord) {
+        self.handler.handle_cursor(id, coord);
+    }
+
+    fn handle_mouse_wheel(
+        &mut self,
+        id: &WindowId,
+        coord: Coord,
+        wheel_axis: WheelAxis,
+        clicks: i32,
+    ) -> EventState {
+        self.handler
+            .handle_mouse_wheel(id, coord, wheel_axis, clicks)
+    }
+}
@@ -0,0 +1,91 @@
+use super::{unregister_draw_callback, DisplayError};
+
+/// A phase of the X-Plane rendering pipeline a draw callback is attached to.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DrawPhase {
+    /// The 3d scene in modern rendering mode, no drawing is allowed except for custom effects.
+    Modern3d,
+    /// The first phase of the cockpit drawing.
+    FirstCockpit,
+    /// The non-moving parts of the 2d panel. Legacy, not called in Vulkan/Metal mode.
+    Panel,
+    /// The moving parts of the 2d panel. Legacy, not called in Vulkan/Metal mode.
+    Gauges,
+    /// The floating windows drawing phase.
+    Window,
+    /// The last phase of the cockpit drawing.
+    LastCockpit,
+}
+
+impl From<DrawPhase> for xplm_sys::XPLMDrawingPhase {
+    fn from(value: DrawPhase) -> Self {
+        let phase = match value {
+            DrawPhase::Modern3d => xplm_sys::xplm_Phase_Modern3D,
+            DrawPhase::FirstCockpit => xplm_sys::xplm_Phase_FirstCockpit,
+            DrawPhase::Panel => xplm_sys::xplm_Phase_Panel,
+            DrawPhase::Gauges => xplm_sys::xplm_Phase_Gauges,
+            DrawPhase::Window => xplm_sys::xplm_Phase_Window,
+            DrawPhase::LastCockpit => xplm_sys::xplm_Phase_LastCockpit,
+        };
+        phase as _
+    }
+}
+
+impl TryFrom<xplm_sys::XPLMDrawingPhase> for DrawPhase {
+    type Error = DisplayError;
+
+    fn try_from(value: xplm_sys::XPLMDrawingPhase) -> std::result::Result<Self, Self::Error> {
+        match value as _ {
+            xplm_sys::xplm_Phase_Modern3D => Ok(Self::Modern3d),
+            xplm_sys::xplm_Phase_FirstCockpit => Ok(Self::FirstCockpit),
+            xplm_sys::xplm_Phase_Panel => Ok(Self::Panel),
+            xplm_sys::xplm_Phase_Gauges => Ok(Self::Gauges),
+            xplm_sys::xplm_Phase_Window => Ok(Self::Window),
+            xplm_sys::xplm_Phase_LastCockpit => Ok(Self::LastCockpit),
+            _ => Err(Self::Error::UnknownDrawPhase(value as _)),
+        }
+    }
+}
+
+/// Draw callback handler.
+pub trait DrawHandler: 'static {
+    /// Called during the drawing phase the handler is registered for.
+    ///
+    /// # Arguments
+    /// * `phase` - the current drawing phase.
+    /// * `is_before` - `true` if called before X-Plane draws the phase.
+    ///
+    /// # Returns
+    /// Returns `true` to let X-Plane draw the phase. Returning `false`
+    /// suppresses X-Plane drawing, it only has effect before the phase.
+    fn draw(&mut self, phase: DrawPhase, is_before: bool) -> bool;
+}
+
+impl<F: FnMut(DrawPhase, bool) -> bool + 'static> DrawHandler for F {
+    fn draw(&mut self, phase: DrawPhase, is_before: bool) -> bool {
+        self(phase, is_before)
+    }
+}
+
+/// A link to [`DrawHandler`] for a given draw callback.
+pub struct DrawLink {
+    /// A drawing phase.
+    pub phase: DrawPhase,
+    /// `true` if the callback is called before X-Plane draws the phase.
+    pub is_before: bool,
+    /// A draw handler.
+    pub handler: Box<dyn DrawHandler>,
+}
+
+/// A draw callback record to keep the callback registered.
+/// The callback is unregistered when dropped.
+pub struct DrawCallbackRecord {
+    /// A link to the draw handler.
+    pub link: Box<DrawLink>,
+}
+
+impl Drop for DrawCallbackRecord {
+    fn drop(&mut self) {
+        unregister_draw_callback(self);
+    }
+}
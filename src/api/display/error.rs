@@ -18,4 +18,19 @@ pub enum DisplayError {
     /// Invalid window title string passed to X-Plane.
     #[error("invalid windiw title {0}")]
     InvalidWindowTitle(ffi::NulError),
+    /// Unknown drawing phase passed from X-Plane.
+    #[error("unknown drawing phase {0}")]
+    UnknownDrawPhase(::std::os::raw::c_int),
+    /// Invalid hex color string.
+    #[error("invalid hex color {0}")]
+    InvalidHexColor(String),
+    /// Monitor index is out of range.
+    #[error("monitor {0} not found")]
+    MonitorNotFound(usize),
+    /// Invalid hot key description string passed to X-Plane.
+    #[error("invalid hot key description {0}")]
+    InvalidHotKeyDescription(ffi::NulError),
+    /// Invalid hot key id returned from X-Plane.
+    #[error("invalid hot key id")]
+    InvalidHotKeyId,
 }
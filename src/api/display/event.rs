@@ -1,13 +1,38 @@
 /// Event propagation state function.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum EventState {
     /// Consume click.
-    Consume = 1,
+    Consume,
     /// Propagate click to other consumers.
-    Propagate = 0,
+    Propagate,
+    /// Consume click and take keyboard focus for the window.
+    /// Only meaningful for mouse clicks, other events treat it as [`EventState::Consume`].
+    ConsumeAndFocus,
+}
+
+impl EventState {
+    /// Checks wether the event is consumed and won't be passed to other consumers.
+    ///
+    /// # Returns
+    /// Returns `true` if the event is consumed. Otherwise returns `false`.
+    pub fn is_consumed(&self) -> bool {
+        match self {
+            Self::Consume | Self::ConsumeAndFocus => true,
+            Self::Propagate => false,
+        }
+    }
+
+    /// Checks wether the window should take keyboard focus.
+    ///
+    /// # Returns
+    /// Returns `true` if the keyboard focus is requested. Otherwise returns `false`.
+    pub fn wants_focus(&self) -> bool {
+        matches!(self, Self::ConsumeAndFocus)
+    }
 }
 
 impl From<EventState> for ::std::os::raw::c_int {
     fn from(value: EventState) -> Self {
-        value as ::std::os::raw::c_int
+        value.is_consumed() as ::std::os::raw::c_int
     }
 }
@@ -0,0 +1,266 @@
+use std::time::{Duration, Instant};
+
+use crate::api::utilities::VirtualKey;
+
+use super::{
+    get_mouse_location_global, get_window_geometry, Coord, EventState, KeyFlags, MouseStatus,
+    WheelAxis, WindowHandler, WindowId,
+};
+
+/// Default maximum interval between two clicks of a double-click.
+pub const DEFAULT_DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(400);
+/// Default distance in boxels the mouse has to move before a press turns into a drag.
+pub const DEFAULT_DRAG_THRESHOLD: ::std::os::raw::c_int = 3;
+
+/// A window handler which receives high level mouse gestures.
+/// Every method has a default no-op implementation.
+pub trait GestureHandler: WindowHandler {
+    /// Called when the mouse button is pressed and released without dragging.
+    ///
+    /// # Arguments
+    /// * `id` - the window identifier.
+    /// * `coord` - coordinates of the click.
+    fn click(&mut self, _id: &WindowId, _coord: Coord) {}
+
+    /// Called instead of the second [`GestureHandler::click`] of a double-click.
+    ///
+    /// # Arguments
+    /// * `id` - the window identifier.
+    /// * `coord` - coordinates of the second click.
+    fn double_click(&mut self, _id: &WindowId, _coord: Coord) {}
+
+    /// Called when the mouse moves far enough with the button pressed.
+    ///
+    /// # Arguments
+    /// * `id` - the window identifier.
+    /// * `coord` - coordinates where the button has been pressed.
+    fn drag_begin(&mut self, _id: &WindowId, _coord: Coord) {}
+
+    /// Called every time the mouse moves during a drag.
+    ///
+    /// # Arguments
+    /// * `id` - the window identifier.
+    /// * `coord` - current mouse coordinates.
+    /// * `delta` - movement since the previous drag event.
+    fn drag(&mut self, _id: &WindowId, _coord: Coord, _delta: Coord) {}
+
+    /// Called when the mouse button is released at the end of a drag.
+    ///
+    /// # Arguments
+    /// * `id` - the window identifier.
+    /// * `coord` - coordinates where the button has been released.
+    fn drag_end(&mut self, _id: &WindowId, _coord: Coord) {}
+
+    /// Called when the mouse enters the window.
+    ///
+    /// # Arguments
+    /// * `id` - the window identifier.
+    /// * `coord` - current mouse coordinates.
+    fn hover_enter(&mut self, _id: &WindowId, _coord: Coord) {}
+
+    /// Called when the mouse leaves the window.
+    ///
+    /// # Arguments
+    /// * `id` - the window identifier.
+    fn hover_leave(&mut self, _id: &WindowId) {}
+}
+
+/// A pressed mouse button.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct Press {
+    origin: Coord,
+    last: Coord,
+    dragging: bool,
+}
+
+/// A window handler decorator which turns raw mouse events into gestures.
+///
+/// Mouse clicks are offered to the wrapped handler first, a gesture starts only
+/// when the wrapped handler propagates the click. The window captures the mouse
+/// until the button is released, so a drag continues outside the window.
+pub struct GestureWindow<H: GestureHandler> {
+    handler: H,
+    double_click_interval: Duration,
+    drag_threshold: ::std::os::raw::c_int,
+    press: Option<Press>,
+    last_click: Option<(Instant, Coord)>,
+    hovering: bool,
+}
+
+impl<H: GestureHandler> GestureWindow<H> {
+    /// Wraps a gesture handler.
+    ///
+    /// # Arguments
+    /// * `handler` - a gesture handler.
+    ///
+    /// # Returns
+    /// Returns a new [`GestureWindow`] instance.
+    pub fn new(handler: H) -> Self {
+        Self {
+            handler,
+            double_click_interval: DEFAULT_DOUBLE_CLICK_INTERVAL,
+            drag_threshold: DEFAULT_DRAG_THRESHOLD,
+            press: None,
+            last_click: None,
+            hovering: false,
+        }
+    }
+
+    /// Sets the maximum interval between two clicks of a double-click.
+    ///
+    /// # Arguments
+    /// * `interval` - an interval, see [`DEFAULT_DOUBLE_CLICK_INTERVAL`].
+    ///
+    /// # Returns
+    /// Returns a modified window decorator.
+    pub fn double_click_interval(mut self, interval: Duration) -> Self {
+        self.double_click_interval = interval;
+        self
+    }
+
+    /// Sets the distance the mouse has to move before a press turns into a drag.
+    ///
+    /// # Arguments
+    /// * `threshold` - a distance in boxels, see [`DEFAULT_DRAG_THRESHOLD`].
+    ///
+    /// # Returns
+    /// Returns a modified window decorator.
+    pub fn drag_threshold(mut self, threshold: ::std::os::raw::c_int) -> Self {
+        self.drag_threshold = threshold.max(0);
+        self
+    }
+
+    /// Returns the wrapped gesture handler.
+    pub fn handler(&self) -> &H {
+        &self.handler
+    }
+
+    /// Returns the wrapped gesture handler.
+    pub fn handler_mut(&mut self) -> &mut H {
+        &mut self.handler
+    }
+
+    /// Checks wether the user is dragging the mouse.
+    pub fn is_dragging(&self) -> bool {
+        self.press.is_some_and(|press| press.dragging)
+    }
+
+    /// Checks wether the mouse is over the window.
+    pub fn is_hovering(&self) -> bool {
+        self.hovering
+    }
+
+    fn move_press(&mut self, id: &WindowId, coord: Coord) {
+        let Some(mut press) = self.press else {
+            return;
+        };
+
+        if !press.dragging {
+            let dx = (coord.x - press.origin.x).abs();
+            let dy = (coord.y - press.origin.y).abs();
+            if dx.max(dy) < self.drag_threshold {
+                return;
+            }
+
+            press.dragging = true;
+            self.handler.drag_begin(id, press.origin);
+        }
+
+        let delta = Coord::new(coord.x - press.last.x, coord.y - press.last.y);
+        press.last = coord;
+        self.press = Some(press);
+        if delta != Coord::default() {
+            self.handler.drag(id, coord, delta);
+        }
+    }
+
+    fn release(&mut self, id: &WindowId, coord: Coord) {
+        self.move_press(id, coord);
+        let Some(press) = self.press.take() else {
+            return;
+        };
+
+        if press.dragging {
+            self.last_click = None;
+            return self.handler.drag_end(id, coord);
+        }
+
+        let now = Instant::now();
+        let is_double = self.last_click.is_some_and(|(time, last)| {
+            now.duration_since(time) <= self.double_click_interval
+                && (coord.x - last.x).abs().max((coord.y - last.y).abs()) <= self.drag_threshold
+        });
+
+        if is_double {
+            self.last_click = None;
+            self.handler.double_click(id, coord);
+        } else {
+            self.last_click = Some((now, coord));
+            self.handler.click(id, coord);
+        }
+    }
+}
+
+impl<H: GestureHandler> WindowHandler for GestureWindow<H> {
+    fn draw(&mut self, id: &WindowId) {
+        // X-Plane stops sending cursor events once the mouse leaves the window,
+        // so leaving is detected while drawing.
+        if self.hovering && !get_window_geometry(id).contains(&get_mouse_location_global()) {
+            self.hovering = false;
+            self.handler.hover_leave(id);
+        }
+
+        self.handler.draw(id);
+    }
+
+    fn mouse_click(&mut self, id: &WindowId, coord: Coord, status: MouseStatus) -> EventState {
+        match status {
+            MouseStatus::Down => {
+                let state = self.handler.mouse_click(id, coord, status);
+                if state == EventState::Propagate {
+                    self.press = Some(Press {
+                        origin: coord,
+                        last: coord,
+                        dragging: false,
+                    });
+                    EventState::Consume
+                } else {
+                    state
+                }
+            }
+            MouseStatus::Drag if self.press.is_some() => {
+                self.move_press(id, coord);
+                EventState::Consume
+            }
+            MouseStatus::Up if self.press.is_some() => {
+                self.release(id, coord);
+                EventState::Consume
+            }
+            _ => self.handler.mouse_click(id, coord, status),
+        }
+    }
+
+    fn handle_key(&mut self, id: &WindowId, key: char, virtual_key: VirtualKey, flags: KeyFlags) {
+        self.handler.handle_key(id, key, virtual_key, flags);
+    }
+
+    fn handle_cursor(&mut self, id: &WindowId, coord: Coord) {
+        if !self.hovering {
+            self.hovering = true;
+            self.handler.hover_enter(id, coord);
+        }
+
+        self.handler.handle_cursor(id, coord);
+    }
+
+    fn handle_mouse_wheel(
+        &mut self,
+        id: &WindowId,
+        coord: Coord,
+        wheel_axis: WheelAxis,
+        clicks: i32,
+    ) -> EventState {
+        self.handler
+            .handle_mouse_wheel(id, coord, wheel_axis, clicks)
+    }
+}
@@ -0,0 +1,34 @@
+use super::unregister_hot_key;
+
+/// Hot key handler.
+pub trait HotKeyHandler: 'static {
+    /// Called when the hot key combination is pressed.
+    fn hot_key(&mut self);
+}
+
+impl<F: FnMut() + 'static> HotKeyHandler for F {
+    fn hot_key(&mut self) {
+        self()
+    }
+}
+
+/// A link to [`HotKeyHandler`] for a given hot key.
+pub struct HotKeyLink {
+    /// A hot key handler.
+    pub handler: Box<dyn HotKeyHandler>,
+}
+
+/// A hot key record to keep the hot key registered.
+/// The hot key is unregistered when dropped.
+pub struct HotKeyRecord {
+    /// A hot key identifier.
+    pub id: xplm_sys::XPLMHotKeyID,
+    /// A link to the hot key handler.
+    pub link: Box<HotKeyLink>,
+}
+
+impl Drop for HotKeyRecord {
+    fn drop(&mut self) {
+        unregister_hot_key(self);
+    }
+}
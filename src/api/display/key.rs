@@ -1,4 +1,8 @@
+use std::fmt;
+use std::ops::{BitAnd, BitOr, BitOrAssign};
+
 /// Modifier key variants.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum KeyFlag {
     Shift,
     OptionAlt,
@@ -7,66 +11,177 @@ pub enum KeyFlag {
     Up,
 }
 
+impl KeyFlag {
+    /// Returns all key flags.
+    pub fn all() -> &'static [KeyFlag] {
+        &[
+            KeyFlag::Shift,
+            KeyFlag::OptionAlt,
+            KeyFlag::Control,
+            KeyFlag::Down,
+            KeyFlag::Up,
+        ]
+    }
+}
+
 /// Modifier key flags bitmap.
-#[derive(Debug)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct KeyFlags(xplm_sys::XPLMKeyFlags);
 
 impl KeyFlags {
-    /// Checks whether the flags bitmap contains a specific flag.
+    /// The shift key is down.
+    pub const SHIFT: Self = Self(xplm_sys::xplm_ShiftFlag as _);
+    /// The option or alt key is down.
+    pub const OPTION_ALT: Self = Self(xplm_sys::xplm_OptionAltFlag as _);
+    /// The control key is down.
+    pub const CONTROL: Self = Self(xplm_sys::xplm_ControlFlag as _);
+    /// The key is being pressed down.
+    pub const DOWN: Self = Self(xplm_sys::xplm_DownFlag as _);
+    /// The key is being released.
+    pub const UP: Self = Self(xplm_sys::xplm_UpFlag as _);
+
+    /// Creates an empty flags bitmap.
+    ///
+    /// # Returns
+    /// Returns an empty [`KeyFlags`].
+    pub fn empty() -> Self {
+        Self(0)
+    }
+
+    /// Creates a flags bitmap from raw bits.
     ///
     /// # Arguments
-    /// * `flag` - a flag to check.
+    /// * `bits` - raw flags bits.
     ///
     /// # Returns
-    /// Return `true` if flags contains specific flag. Otherwise returns `false`.
-    pub fn contains(&self, flag: KeyFlag) -> bool {
-        match flag {
-            KeyFlag::Shift => self.contains_shift_flag(),
-            KeyFlag::OptionAlt => self.contains_option_alt_flag(),
-            KeyFlag::Control => self.contains_control_flag(),
-            KeyFlag::Down => self.contains_down_flag(),
-            KeyFlag::Up => self.contains_up_flag(),
-        }
+    /// Returns a new [`KeyFlags`].
+    pub fn from_bits(bits: xplm_sys::XPLMKeyFlags) -> Self {
+        Self(bits)
+    }
+
+    /// Returns the raw flags bitmap.
+    pub fn bits(&self) -> xplm_sys::XPLMKeyFlags {
+        self.0
+    }
+
+    /// Checks whether the flags bitmap is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Checks whether the flags bitmap contains all the given flags.
+    ///
+    /// # Arguments
+    /// * `flags` - a flag or flags to check.
+    ///
+    /// # Returns
+    /// Return `true` if flags contains all the given flags. Otherwise returns `false`.
+    pub fn contains<F: Into<KeyFlags>>(&self, flags: F) -> bool {
+        let flags = flags.into();
+        self.0 & flags.0 == flags.0
+    }
+
+    /// Adds flags to the flags bitmap.
+    ///
+    /// # Arguments
+    /// * `flags` - a flag or flags to add.
+    ///
+    /// # Returns
+    /// Returns a modified flags bitmap.
+    pub fn with<F: Into<KeyFlags>>(self, flags: F) -> Self {
+        Self(self.0 | flags.into().0)
+    }
+
+    /// Removes flags from the flags bitmap.
+    ///
+    /// # Arguments
+    /// * `flags` - a flag or flags to remove.
+    ///
+    /// # Returns
+    /// Returns a modified flags bitmap.
+    pub fn without<F: Into<KeyFlags>>(self, flags: F) -> Self {
+        Self(self.0 & !flags.into().0)
+    }
+
+    /// Lists all key flags contained in the flags bitmap.
+    ///
+    /// # Returns
+    /// Returns an iterator over contained key flags.
+    pub fn iter(&self) -> impl Iterator<Item = KeyFlag> + '_ {
+        KeyFlag::all()
+            .iter()
+            .copied()
+            .filter(|flag| self.contains(*flag))
+    }
+
+    /// Checks whether the shift key is down.
+    pub fn is_shift(&self) -> bool {
+        self.contains(Self::SHIFT)
+    }
+
+    /// Checks whether the option or alt key is down.
+    pub fn is_option_alt(&self) -> bool {
+        self.contains(Self::OPTION_ALT)
+    }
+
+    /// Checks whether the control key is down.
+    pub fn is_control(&self) -> bool {
+        self.contains(Self::CONTROL)
+    }
+
+    /// Checks whether the key is being pressed down.
+    pub fn is_down(&self) -> bool {
+        self.contains(Self::DOWN)
+    }
+
+    /// Checks whether the key is being released.
+    pub fn is_up(&self) -> bool {
+        self.contains(Self::UP)
     }
 
     /// Checks whether the flags bitmsap contains shift flag.
     ///
     /// # Returns
     /// Return `true` if flags contains shift. Otherwise returns `false`.
+    #[deprecated(note = "use `KeyFlags::is_shift` instead")]
     pub fn contains_shift_flag(&self) -> bool {
-        self.0 & (xplm_sys::xplm_ShiftFlag as xplm_sys::XPLMKeyFlags) != 0
+        self.is_shift()
     }
 
     /// Checks whether the flags bitmap contains option or alt flag.
     ///
     /// # Returns
     /// Return `true` if flags contains option or alt. Otherwise returns `false`.
+    #[deprecated(note = "use `KeyFlags::is_option_alt` instead")]
     pub fn contains_option_alt_flag(&self) -> bool {
-        self.0 & (xplm_sys::xplm_OptionAltFlag as xplm_sys::XPLMKeyFlags) != 0
+        self.is_option_alt()
     }
 
     /// Checks whether the flags bitmap contains control flag.
     ///
     /// # Returns
     /// Return `true` if flags contains control. Otherwise returns `false`.
+    #[deprecated(note = "use `KeyFlags::is_control` instead")]
     pub fn contains_control_flag(&self) -> bool {
-        self.0 & (xplm_sys::xplm_OptionAltFlag as xplm_sys::XPLMKeyFlags) != 0
+        self.is_control()
     }
 
     /// Checks whether the flags bitmap contains down flag.
     ///
     /// # Returns
     /// Return `true` if flags contains down. Otherwise returns `false`.
+    #[deprecated(note = "use `KeyFlags::is_down` instead")]
     pub fn contains_down_flag(&self) -> bool {
-        self.0 & (xplm_sys::xplm_DownFlag as xplm_sys::XPLMKeyFlags) != 0
+        self.is_down()
     }
 
     /// Checks whether the flags bitmap contains up flag.
     ///
     /// # Returns
     /// Return `true` if flags contains up. Otherwise returns `false`.
+    #[deprecated(note = "use `KeyFlags::is_up` instead")]
     pub fn contains_up_flag(&self) -> bool {
-        self.0 & (xplm_sys::xplm_UpFlag as xplm_sys::XPLMKeyFlags) != 0
+        self.is_up()
     }
 }
 
@@ -75,3 +190,59 @@ impl From<xplm_sys::XPLMKeyFlags> for KeyFlags {
         Self(value)
     }
 }
+
+impl From<KeyFlag> for KeyFlags {
+    fn from(value: KeyFlag) -> Self {
+        match value {
+            KeyFlag::Shift => Self::SHIFT,
+            KeyFlag::OptionAlt => Self::OPTION_ALT,
+            KeyFlag::Control => Self::CONTROL,
+            KeyFlag::Down => Self::DOWN,
+            KeyFlag::Up => Self::UP,
+        }
+    }
+}
+
+impl From<KeyFlags> for xplm_sys::XPLMKeyFlags {
+    fn from(value: KeyFlags) -> Self {
+        value.0
+    }
+}
+
+impl BitOr for KeyFlags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for KeyFlags {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl BitAnd for KeyFlags {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        Self(self.0 & rhs.0)
+    }
+}
+
+impl fmt::Display for KeyFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<&str> = self
+            .iter()
+            .map(|flag| match flag {
+                KeyFlag::Shift => "Shift",
+                KeyFlag::OptionAlt => "Alt",
+                KeyFlag::Control => "Ctrl",
+                KeyFlag::Down => "Down",
+                KeyFlag::Up => "Up",
+            })
+            .collect();
+        f.write_str(&names.join("+"))
+    }
+}
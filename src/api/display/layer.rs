@@ -0,0 +1,67 @@
+use std::cell::RefCell;
+
+/// A window layer. Windows in higher layers are drawn above and receive clicks
+/// before windows in lower layers, regardless of their z-order.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum WindowLayer {
+    /// The lowest layer, used for HUD-like displays while flying.
+    FlightOverlay = 0,
+    /// Windows that "float" over the sim, like the X-Plane 11+ map. Most windows belong here.
+    #[default]
+    FloatingWindows = 1,
+    /// An interruptive modal that covers the sim with a transparent black overlay
+    /// to draw the user's focus to the alert.
+    Modal = 2,
+    /// "Growl"-style notifications that are visible in a corner of the screen,
+    /// even over modals.
+    GrowlNotifications = 3,
+}
+
+impl From<WindowLayer> for xplm_sys::XPLMWindowLayer {
+    fn from(value: WindowLayer) -> Self {
+        value as xplm_sys::XPLMWindowLayer
+    }
+}
+
+thread_local! {
+    /// Windows created by this plugin in creation order. X-Plane can't report the window layer.
+    static WINDOW_LAYERS: RefCell<Vec<(xplm_sys::XPLMWindowID, WindowLayer)>> =
+        const { RefCell::new(Vec::new()) };
+}
+
+/// Remembers the layer of a created window.
+pub(super) fn track(id: xplm_sys::XPLMWindowID, layer: WindowLayer) {
+    WINDOW_LAYERS.with(|layers| layers.borrow_mut().push((id, layer)));
+}
+
+/// Forgets a destroyed window.
+pub(super) fn untrack(id: xplm_sys::XPLMWindowID) {
+    WINDOW_LAYERS.with(|layers| layers.borrow_mut().retain(|(window, _)| *window != id));
+}
+
+/// Returns the layer a window was created in.
+pub(super) fn layer_of(id: xplm_sys::XPLMWindowID) -> Option<WindowLayer> {
+    WINDOW_LAYERS.with(|layers| {
+        layers
+            .borrow()
+            .iter()
+            .find(|(window, _)| *window == id)
+            .map(|(_, layer)| *layer)
+    })
+}
+
+/// Returns the windows created in the same layer as the given window, except the window itself.
+pub(super) fn siblings_of(id: xplm_sys::XPLMWindowID) -> Vec<xplm_sys::XPLMWindowID> {
+    let Some(layer) = layer_of(id) else {
+        return Vec::new();
+    };
+
+    WINDOW_LAYERS.with(|layers| {
+        layers
+            .borrow()
+            .iter()
+            .filter(|(window, window_layer)| *window != id && *window_layer == layer)
+            .map(|(window, _)| *window)
+            .collect()
+    })
+}
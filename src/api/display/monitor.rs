@@ -0,0 +1,23 @@
+use super::{Coord, Rect};
+
+/// A monitor X-Plane is running on, in global desktop boxels.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Monitor {
+    /// The X-Plane monitor index passed to [`super::set_window_positioning_mode`].
+    pub index: ::std::os::raw::c_int,
+    /// The monitor bounds in global desktop boxels.
+    pub bounds: Rect,
+}
+
+impl Monitor {
+    /// Checks wether a point in global desktop boxels lies on the monitor.
+    ///
+    /// # Arguments
+    /// * `coord` - global desktop coordinates.
+    ///
+    /// # Returns
+    /// Returns `true` if the point lies on the monitor. Otherwise returns `false`.
+    pub fn contains(&self, coord: &Coord) -> bool {
+        self.bounds.contains(coord)
+    }
+}
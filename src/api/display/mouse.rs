@@ -1,6 +1,7 @@
 use super::DisplayError;
 
 /// The mouse status.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum MouseStatus {
     /// The mouse button is up.
     Up,
@@ -24,6 +25,7 @@ impl TryFrom<xplm_sys::XPLMMouseStatus> for MouseStatus {
 }
 
 /// The mouse wheel axis.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum WheelAxis {
     /// Vertical mouse wheel axis.
     Vertical,
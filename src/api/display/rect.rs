@@ -4,7 +4,7 @@ use super::{Coord, Size};
 pub type RectCoordType = ::std::os::raw::c_int;
 
 /// X-Plane 2D rectangle definiton.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
 pub struct Rect {
     /// The left coordinate.
     pub left: RectCoordType,
@@ -99,6 +99,17 @@ impl Rect {
         Coord::default().x(x).y(y)
     }
 
+    /// Checks whether a coordinate is inside the rectangle, borders included.
+    ///
+    /// # Arguments
+    /// * `coord` - a coordinate to check.
+    ///
+    /// # Returns
+    /// Returns `true` if the coordinate is inside the rectangle. Otherwise returns `false`.
+    pub fn contains(&self, coord: &Coord) -> bool {
+        (self.left..=self.right).contains(&coord.x) && (self.bottom..=self.top).contains(&coord.y)
+    }
+
     /// Shrinks rectangle to a size
     ///
     /// # Argumets
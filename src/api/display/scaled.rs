@@ -0,0 +1,91 @@
+use super::{Coord, Rect};
+
+/// A rectangle defined in unscaled UI units together with a content scale.
+///
+/// Plugin UIs can lay out elements at scale 1.0 and convert them into
+/// the actual drawing coordinates using the window content scale.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ScaledRect {
+    /// A rectangle in unscaled UI units.
+    pub rect: Rect,
+    /// A content scale factor.
+    pub scale: f32,
+}
+
+impl ScaledRect {
+    /// Creates a new scaled rectangle.
+    ///
+    /// # Arguments
+    /// * `rect` - a rectangle in unscaled UI units. See [`Rect`] for more details.
+    /// * `scale` - a content scale factor, e.g. [`WindowId::content_scale`](super::WindowId::content_scale).
+    ///
+    /// # Returns
+    /// Returns a new [`ScaledRect`] instance.
+    pub fn new(rect: Rect, scale: f32) -> Self {
+        Self { rect, scale }
+    }
+
+    /// Scales a single value.
+    ///
+    /// # Arguments
+    /// * `value` - a value in unscaled UI units.
+    ///
+    /// # Returns
+    /// Returns the value multiplied by the scale and rounded.
+    pub fn scale_value(&self, value: ::std::os::raw::c_int) -> ::std::os::raw::c_int {
+        (value as f32 * self.scale).round() as _
+    }
+
+    /// Scales a coordinate relative to the rectangle origin at its left top corner.
+    ///
+    /// # Arguments
+    /// * `coord` - an offset in unscaled UI units, Y grows downwards.
+    ///
+    /// # Returns
+    /// Returns [`Coord`] in drawing coordinates.
+    pub fn scale_coord(&self, coord: &Coord) -> Coord {
+        let origin = self.to_scaled();
+        Coord::new(
+            origin.left + self.scale_value(coord.x),
+            origin.top - self.scale_value(coord.y),
+        )
+    }
+
+    /// Converts the rectangle into drawing coordinates keeping its left top corner in place.
+    ///
+    /// # Returns
+    /// Returns a scaled [`Rect`].
+    pub fn to_scaled(&self) -> Rect {
+        let width = self.scale_value(self.rect.right - self.rect.left);
+        let height = self.scale_value(self.rect.top - self.rect.bottom);
+        Rect::new(
+            self.rect.left,
+            self.rect.top,
+            self.rect.left + width,
+            self.rect.top - height,
+        )
+    }
+
+    /// Converts a rectangle in drawing coordinates back into unscaled UI units
+    /// keeping its left top corner in place.
+    ///
+    /// # Arguments
+    /// * `rect` - a rectangle in drawing coordinates. See [`Rect`] for more details.
+    /// * `scale` - a content scale factor.
+    ///
+    /// # Returns
+    /// Returns a new [`ScaledRect`] instance.
+    pub fn from_scaled(rect: &Rect, scale: f32) -> Self {
+        let scale = if scale > 0.0 { scale } else { 1.0 };
+        let width = ((rect.right - rect.left) as f32 / scale).round() as ::std::os::raw::c_int;
+        let height = ((rect.top - rect.bottom) as f32 / scale).round() as ::std::os::raw::c_int;
+        let rect = Rect::new(rect.left, rect.top, rect.left + width, rect.top - height);
+        Self::new(rect, scale)
+    }
+}
+
+impl Default for ScaledRect {
+    fn default() -> Self {
+        Self::new(Rect::default(), 1.0)
+    }
+}
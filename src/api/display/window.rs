@@ -1,11 +1,17 @@
 use std::ops::Deref;
 
 use crate::api::utilities::VirtualKey;
+use crate::plugin::main_thread::MainThreadOnly;
 
-use super::{destroy_window, Coord, DisplayError, EventState, KeyFlags, MouseStatus, WheelAxis};
+use super::{
+    destroy_window, get_all_monitor_bounds_global, get_ui_scale, get_window_geometry,
+    get_window_geometry_os, is_window_popped_out, set_window_positioning_mode, Coord, DisplayError,
+    EventState, KeyFlags, Monitor, MouseStatus, Result, WheelAxis,
+};
 
 /// X-Plane window identifier.
-pub struct WindowId(xplm_sys::XPLMWindowID);
+/// Must only be used on the main thread, see [`crate::plugin::main_thread`].
+pub struct WindowId(xplm_sys::XPLMWindowID, MainThreadOnly);
 
 impl Deref for WindowId {
     type Target = xplm_sys::XPLMWindowID;
@@ -15,6 +21,111 @@ impl Deref for WindowId {
     }
 }
 
+impl WindowId {
+    /// Returns the scale between window boxels and the pixels it is rendered with.
+    /// For popped-out windows it is calculated from the operating system window geometry,
+    /// otherwise the X-Plane UI scale is used.
+    ///
+    /// # Returns
+    /// Returns the content scale factor, 1.0 means no scaling.
+    pub fn content_scale(&self) -> f32 {
+        if is_window_popped_out(self) {
+            let boxels = get_window_geometry(self);
+            let pixels = get_window_geometry_os(self);
+            let width = boxels.right - boxels.left;
+            if width > 0 {
+                return (pixels.right - pixels.left) as f32 / width as f32;
+            }
+        }
+
+        get_ui_scale()
+    }
+
+    /// Checks wether the window is popped out into its own operating system window.
+    pub fn is_popped_out(&self) -> bool {
+        is_window_popped_out(self)
+    }
+
+    /// Pops the window out into its own operating system window.
+    pub fn pop_out(&self) {
+        set_window_positioning_mode(self, PositioningMode::WindowPopOut, -1);
+    }
+
+    /// Returns a popped-out, centered or full screen window back into the X-Plane window.
+    pub fn pop_in(&self) {
+        set_window_positioning_mode(self, PositioningMode::Free, -1);
+    }
+
+    /// Pops the window out or back in depending on its current state.
+    ///
+    /// # Returns
+    /// Returns `true` if the window has been popped out.
+    pub fn toggle_pop_out(&self) -> bool {
+        if self.is_popped_out() {
+            self.pop_in();
+            false
+        } else {
+            self.pop_out();
+            true
+        }
+    }
+
+    /// Keeps the window full screen on a monitor.
+    ///
+    /// # Arguments
+    /// * `index` - a zero based index in the list returned by [`get_all_monitor_bounds_global`].
+    ///
+    /// # Returns
+    /// Returns empty result on success. Otherwise returns [`DisplayError::MonitorNotFound`].
+    pub fn fullscreen_on_monitor(&self, index: usize) -> Result<()> {
+        let monitor = monitor(index)?;
+        set_window_positioning_mode(self, PositioningMode::FullScreenOnMonitor, monitor.index);
+        Ok(())
+    }
+
+    /// Keeps the window centered on a monitor.
+    ///
+    /// # Arguments
+    /// * `index` - a zero based index in the list returned by [`get_all_monitor_bounds_global`].
+    ///
+    /// # Returns
+    /// Returns empty result on success. Otherwise returns [`DisplayError::MonitorNotFound`].
+    pub fn center_on_monitor(&self, index: usize) -> Result<()> {
+        let monitor = monitor(index)?;
+        set_window_positioning_mode(self, PositioningMode::CenterOnMonitor, monitor.index);
+        Ok(())
+    }
+
+    /// Finds the monitor the window center lies on. Popped-out windows are not on
+    /// a monitor known to X-Plane unless it is used full screen.
+    ///
+    /// # Returns
+    /// Returns the zero based monitor index and [`Monitor`], or [`None`] if the window
+    /// is outside every monitor.
+    pub fn current_monitor(&self) -> Option<(usize, Monitor)> {
+        let center = get_window_geometry(self).center();
+        get_all_monitor_bounds_global()
+            .into_iter()
+            .enumerate()
+            .find(|(_, monitor)| monitor.contains(&center))
+    }
+
+    /// Checks wether the window covers a whole monitor.
+    pub fn is_fullscreen(&self) -> bool {
+        let geometry = get_window_geometry(self);
+        get_all_monitor_bounds_global()
+            .iter()
+            .any(|monitor| monitor.bounds == geometry)
+    }
+}
+
+fn monitor(index: usize) -> Result<Monitor> {
+    get_all_monitor_bounds_global()
+        .get(index)
+        .copied()
+        .ok_or(DisplayError::MonitorNotFound(index))
+}
+
 impl TryFrom<xplm_sys::XPLMWindowID> for WindowId {
     type Error = DisplayError;
 
@@ -22,14 +133,19 @@ impl TryFrom<xplm_sys::XPLMWindowID> for WindowId {
         if value.is_null() {
             Err(Self::Error::InvalidWindowId)
         } else {
-            Ok(WindowId(value))
+            Ok(WindowId(value, MainThreadOnly::default()))
         }
     }
 }
 
 /// Window handler trait.
+/// Every callback receives the identifier of the window it is called for,
+/// so a single handler type can serve several windows.
 pub trait WindowHandler: 'static {
     /// A callback to handle 2-D drawing of a window.
+    ///
+    /// # Arguments
+    /// * `id` - the window identifier.
     fn draw(&mut self, id: &WindowId);
 
     /// A callback for one of three events:
@@ -38,31 +154,35 @@ pub trait WindowHandler: 'static {
     /// - When the user releases the down-clicked mouse button.
     ///
     /// # Arguments
+    /// * `id` - the window identifier.
     /// * `coord` - coordinates at which mouse event occured.
     /// * `status` - the mouse status.
     ///
     /// # Returns
     /// Returns an event state telling X-Plane what to do with this event.
-    fn mouse_click(&mut self, coord: Coord, status: MouseStatus) -> EventState;
+    fn mouse_click(&mut self, id: &WindowId, coord: Coord, status: MouseStatus) -> EventState;
 
     /// This function is called when a key is pressed or keyboard focus is taken away from your window.
     ///
     /// # Arguments
+    /// * `id` - the window identifier.
     /// * `key` - the key character which has been pressed or released.
     /// * `virtual_key` - the virtual key which has been pressed or released.
     /// * `flags` - the key flags bitmap which contains state for special keys and wether the key
     /// has been pressed or released.
-    fn handle_key(&mut self, key: char, virtual_key: VirtualKey, flags: KeyFlags);
+    fn handle_key(&mut self, id: &WindowId, key: char, virtual_key: VirtualKey, flags: KeyFlags);
 
     /// Get's called when the mouse is over the plugin window.
     ///
     /// # Arguments
+    /// * `id` - the window identifier.
     /// * `coord` - coordinates at which cursor event occured.
-    fn handle_cursor(&mut self, coord: Coord);
+    fn handle_cursor(&mut self, id: &WindowId, coord: Coord);
 
     /// Get's called when one of the mouse wheels is scrolled within the window.
     ///
     /// # Arguments
+    /// * `id` - the window identifier.
     /// * `coord` - coordinates at which mouse event occured.
     /// * `wheel_axis` - the direction of wheel axis.
     /// * `clicks` - number of clicks wheel performed after the last event.
@@ -71,6 +191,7 @@ pub trait WindowHandler: 'static {
     /// Returns an event state telling X-Plane what to do with this event.
     fn handle_mouse_wheel(
         &mut self,
+        id: &WindowId,
         coord: Coord,
         wheel_axis: WheelAxis,
         clicks: i32,
@@ -78,7 +199,11 @@ pub trait WindowHandler: 'static {
 }
 
 /// A link to [`WindowHandler`] for a given window.
-pub struct WindowLink(Box<dyn WindowHandler>);
+/// The link keeps the window identifier, so every callback is delivered with it.
+pub struct WindowLink {
+    id: Option<WindowId>,
+    handler: Box<dyn WindowHandler>,
+}
 
 impl WindowLink {
     /// Creates a new [`WindowLink`] instance.
@@ -89,34 +214,73 @@ impl WindowLink {
     /// # Returns
     /// Return the window link instance.
     pub fn new(value: Box<dyn WindowHandler>) -> Self {
-        Self(value)
+        Self {
+            id: None,
+            handler: value,
+        }
     }
-}
 
-impl WindowHandler for WindowLink {
-    fn draw(&mut self, id: &WindowId) {
-        self.0.draw(id);
+    /// Returns the linked window identifier or [`None`] if the window is not created yet.
+    pub fn id(&self) -> Option<&WindowId> {
+        self.id.as_ref()
     }
 
-    fn mouse_click(&mut self, coord: Coord, status: MouseStatus) -> EventState {
-        self.0.mouse_click(coord, status)
+    /// Links the window identifier. Called once the window is created.
+    ///
+    /// # Arguments
+    /// * `id` - the window identifier.
+    pub fn attach(&mut self, id: xplm_sys::XPLMWindowID) {
+        self.id = WindowId::try_from(id).ok();
     }
 
-    fn handle_key(&mut self, key: char, virtual_key: VirtualKey, flags: KeyFlags) {
-        self.0.handle_key(key, virtual_key, flags);
+    /// Delivers the draw callback.
+    pub fn draw(&mut self) {
+        if let Some(id) = self.id.as_ref() {
+            self.handler.draw(id);
+        }
     }
 
-    fn handle_cursor(&mut self, coord: Coord) {
-        self.0.handle_cursor(coord);
+    /// Delivers the mouse click callback.
+    ///
+    /// # Returns
+    /// Returns the handler event state or [`EventState::Propagate`] if the window is not created yet.
+    pub fn mouse_click(&mut self, coord: Coord, status: MouseStatus) -> EventState {
+        match self.id.as_ref() {
+            Some(id) => self.handler.mouse_click(id, coord, status),
+            None => EventState::Propagate,
+        }
     }
 
-    fn handle_mouse_wheel(
+    /// Delivers the key callback.
+    pub fn handle_key(&mut self, key: char, virtual_key: VirtualKey, flags: KeyFlags) {
+        if let Some(id) = self.id.as_ref() {
+            self.handler.handle_key(id, key, virtual_key, flags);
+        }
+    }
+
+    /// Delivers the cursor callback.
+    pub fn handle_cursor(&mut self, coord: Coord) {
+        if let Some(id) = self.id.as_ref() {
+            self.handler.handle_cursor(id, coord);
+        }
+    }
+
+    /// Delivers the mouse wheel callback.
+    ///
+    /// # Returns
+    /// Returns the handler event state or [`EventState::Propagate`] if the window is not created yet.
+    pub fn handle_mouse_wheel(
         &mut self,
         coord: Coord,
         wheel_axis: WheelAxis,
         clicks: i32,
     ) -> EventState {
-        self.0.handle_mouse_wheel(coord, wheel_axis, clicks)
+        match self.id.as_ref() {
+            Some(id) => self
+                .handler
+                .handle_mouse_wheel(id, coord, wheel_axis, clicks),
+            None => EventState::Propagate,
+        }
     }
 }
 
@@ -148,6 +312,27 @@ impl Drop for WindowHandlerRecord {
     }
 }
 
+/// A window decoration drawn by X-Plane.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum WindowDecoration {
+    /// No decoration, the window draws everything itself and can't be moved or resized
+    /// by the user.
+    None = 0,
+    /// The standard X-Plane 11 floating window style with a title bar.
+    #[default]
+    RoundRectangle = 1,
+    /// The window draws its own decoration and can't be resized by the user.
+    SelfDecorated = 2,
+    /// The window draws its own decoration and can be resized by the user.
+    SelfDecoratedResizable = 3,
+}
+
+impl From<WindowDecoration> for xplm_sys::XPLMWindowDecoration {
+    fn from(value: WindowDecoration) -> Self {
+        value as xplm_sys::XPLMWindowDecoration
+    }
+}
+
 /// A window positioning mode.
 pub enum PositioningMode {
     /// The default positioning mode. Set the window geometry and its
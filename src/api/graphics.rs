@@ -1,14 +1,29 @@
+pub mod capture;
+pub mod clip;
 pub mod error;
 pub mod font;
+mod gl;
+pub mod heading;
+pub mod offscreen;
 pub mod position;
+pub mod shapes;
 pub mod state;
+pub mod text;
 
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::ffi;
 
+pub use capture::Capture;
+pub use clip::{push_clip_rect, ClipGuard};
 pub use error::GraphicsError;
-pub use font::Font;
+pub use font::{Font, FontDimensions};
+pub use heading::Heading;
+pub use offscreen::{OffscreenPanel, OffscreenTarget};
 pub use position::{LocalPosition, WorldPosition};
+pub use shapes::GradientDirection;
 pub use state::GraphicsState;
+pub use text::{PreparedText, TextCache};
 
 use super::display::{Color, Coord, Rect};
 
@@ -21,13 +36,13 @@ pub type Result<T> = std::result::Result<T, GraphicsError>;
 pub fn set_graphics_state(state: &GraphicsState) {
     unsafe {
         xplm_sys::XPLMSetGraphicsState(
-            state.enable_fog,
-            state.number_tex_units,
-            state.enable_lighting,
-            state.enable_alpha_testing,
-            state.enable_alpha_blending,
-            state.enable_depth_testing,
-            state.enable_depth_writing,
+            state.fog as _,
+            state.tex_units as _,
+            state.lighting as _,
+            state.alpha_testing as _,
+            state.alpha_blending as _,
+            state.depth_testing as _,
+            state.depth_writing as _,
         )
     };
 }
@@ -47,6 +62,19 @@ pub fn bind_texture_2d(num: ::std::os::raw::c_int, unit: ::std::os::raw::c_int)
     unsafe { xplm_sys::XPLMBindTexture2d(num, unit) };
 }
 
+/// Generates unused texture numbers which can be bound with [`bind_texture_2d`].
+///
+/// # Arguments
+/// * `count` - a number of textures to generate.
+///
+/// # Returns
+/// Returns generated texture numbers.
+pub fn generate_texture_numbers(count: usize) -> Vec<::std::os::raw::c_int> {
+    let mut textures = vec![0; count];
+    unsafe { xplm_sys::XPLMGenerateTextureNumbers(textures.as_mut_ptr(), count as _) };
+    textures
+}
+
 /// Translates coordinates from latitude, longitude, and altitude to local scene coordinates.
 /// Latitude and longitude are in decimal degrees, and altitude is in meters MSL (mean sea level).
 /// The XYZ coordinates are in meters in the local OpenGL coordinate system.
@@ -98,6 +126,41 @@ pub fn local_to_world(local: &LocalPosition) -> WorldPosition {
     world
 }
 
+/// Returns X-Plane's simulated magnetic variation (declination) at the given location.
+///
+/// # Arguments
+/// * `world` - a world position, the altitude is ignored. See [`WorldPosition`] for more details.
+///
+/// # Returns
+/// Returns magnetic variation in degrees, positive values are east variation.
+pub fn get_magnetic_variation(world: &WorldPosition) -> f32 {
+    unsafe { xplm_sys::XPLMGetMagneticVariation(world.latitude, world.longitude) }
+}
+
+/// Converts a heading in degrees relative to true north into a value relative
+/// to magnetic north at the user's current location.
+///
+/// # Arguments
+/// * `heading` - a true heading in degrees.
+///
+/// # Returns
+/// Returns a magnetic heading in degrees.
+pub fn deg_true_to_deg_magnetic(heading: f32) -> f32 {
+    unsafe { xplm_sys::XPLMDegTrueToDegMagnetic(heading) }
+}
+
+/// Converts a heading in degrees relative to magnetic north at the user's location
+/// into a value relative to true north.
+///
+/// # Arguments
+/// * `heading` - a magnetic heading in degrees.
+///
+/// # Returns
+/// Returns a true heading in degrees.
+pub fn deg_magnetic_to_deg_true(heading: f32) -> f32 {
+    unsafe { xplm_sys::XPLMDegMagneticToDegTrue(heading) }
+}
+
 /// Draws a translucent dark box, partially obscuring parts of the screen
 /// but making text easy to read. This is the same graphics primitive used
 /// by X-Plane to show text files.
@@ -109,24 +172,166 @@ pub fn draw_translucent_dark_box(rect: &Rect) {
 }
 
 /// Draws a string in a given font.
-pub fn draw_string<T: Into<String>>(
+/// The string is converted using a reusable buffer, so drawing does not allocate every frame.
+///
+/// # Arguments
+/// * `value` - a string to draw.
+/// * `font` - a font to draw the string with. See [`Font`] for more details.
+/// * `color` - a text color. See [`Color`] for more details.
+/// * `coord` - a coordinate of the upper left corner of the text. See [`Coord`] for more details.
+///
+/// # Returns
+/// Returns empty result on success. Otherwise returns [`GraphicsError`].
+pub fn draw_string<T: AsRef<str>>(
+    value: T,
+    font: Font,
+    color: &Color,
+    coord: &Coord,
+) -> Result<()> {
+    text::with_c_str(value.as_ref(), |value_c| {
+        draw_c_str(value_c, font, color, coord)
+    })
+}
+
+/// Draws a pre-converted C string in a given font.
+///
+/// # Arguments
+/// * `value` - a string to draw.
+/// * `font` - a font to draw the string with. See [`Font`] for more details.
+/// * `color` - a text color. See [`Color`] for more details.
+/// * `coord` - a coordinate of the upper left corner of the text. See [`Coord`] for more details.
+pub fn draw_c_str(value: &ffi::CStr, font: Font, color: &Color, coord: &Coord) {
+    draw_c_str_raw(value, font, color, coord, core::ptr::null_mut());
+}
+
+/// Draws a prepared text in a given font.
+///
+/// # Arguments
+/// * `text` - a text to draw. See [`PreparedText`] for more details.
+/// * `font` - a font to draw the string with. See [`Font`] for more details.
+/// * `color` - a text color. See [`Color`] for more details.
+/// * `coord` - a coordinate of the upper left corner of the text. See [`Coord`] for more details.
+pub fn draw_prepared(text: &PreparedText, font: Font, color: &Color, coord: &Coord) {
+    draw_c_str(text.as_c_str(), font, color, coord);
+}
+
+/// Draws a string in a given font wrapping it into multiple lines.
+/// The text is word-wrapped to fit into the given width, lines are drawn
+/// downwards from the given coordinate.
+///
+/// # Arguments
+/// * `value` - a string to draw.
+/// * `font` - a font to draw the string with. See [`Font`] for more details.
+/// * `color` - a text color. See [`Color`] for more details.
+/// * `coord` - a coordinate of the upper left corner of the text. See [`Coord`] for more details.
+/// * `wrap_width` - a maximum width of a line in pixels.
+///
+/// # Returns
+/// Returns empty result on success. Otherwise returns [`GraphicsError`].
+pub fn draw_string_wrapped<T: AsRef<str>>(
     value: T,
     font: Font,
     color: &Color,
     coord: &Coord,
+    wrap_width: ::std::os::raw::c_int,
 ) -> Result<()> {
-    let value_c = ffi::CString::new(value.into()).map_err(GraphicsError::InvalidString)?;
-    let mut xplm_color = [color.r, color.g, color.b];
+    let mut wrap_width = wrap_width;
+    text::with_c_str(value.as_ref(), |value_c| {
+        draw_c_str_raw(value_c, font, color, coord, &mut wrap_width)
+    })
+}
+
+fn draw_c_str_raw(
+    value: &ffi::CStr,
+    font: Font,
+    color: &Color,
+    coord: &Coord,
+    wrap_width: *mut ::std::os::raw::c_int,
+) {
+    let mut xplm_color = color.to_rgb_array();
     unsafe {
         xplm_sys::XPLMDrawString(
             xplm_color.as_mut_ptr(),
             coord.x,
             coord.y,
-            value_c.as_ptr() as _,
-            core::ptr::null_mut(),
+            value.as_ptr() as _,
+            wrap_width,
+            font.into(),
+        )
+    };
+}
+
+/// Returns the dimensions of a font.
+///
+/// # Arguments
+/// * `font` - a font to query. See [`Font`] for more details.
+///
+/// # Returns
+/// Returns [`FontDimensions`] of the font.
+pub fn get_font_dimensions(font: Font) -> FontDimensions {
+    let mut char_width = 0;
+    let mut char_height = 0;
+    let mut digits_only = 0;
+    unsafe {
+        xplm_sys::XPLMGetFontDimensions(
             font.into(),
+            &mut char_width,
+            &mut char_height,
+            &mut digits_only,
         )
     };
 
-    Ok(())
+    FontDimensions {
+        char_width,
+        char_height,
+        digits_only: digits_only == 1,
+    }
+}
+
+/// Returns the dimensions of a font queried once and cached afterwards.
+/// Cheap enough to call from draw callbacks every frame.
+///
+/// # Arguments
+/// * `font` - a font to query. See [`Font`] for more details.
+///
+/// # Returns
+/// Returns [`FontDimensions`] of the font.
+pub fn font_metrics(font: Font) -> FontDimensions {
+    thread_local! {
+        static FONT_METRICS: RefCell<HashMap<Font, FontDimensions>> = RefCell::new(HashMap::new());
+    }
+
+    FONT_METRICS.with(|metrics| {
+        *metrics
+            .borrow_mut()
+            .entry(font)
+            .or_insert_with(|| get_font_dimensions(font))
+    })
+}
+
+/// Measures the width of a string in a given font.
+///
+/// # Arguments
+/// * `font` - a font to measure the string with. See [`Font`] for more details.
+/// * `value` - a string to measure.
+///
+/// # Returns
+/// Returns the width of the string in pixels on success. Otherwise returns [`GraphicsError`].
+pub fn measure_string<T: AsRef<str>>(font: Font, value: T) -> Result<f32> {
+    text::with_c_str(value.as_ref(), |value_c| measure_c_str(font, value_c))
+}
+
+/// Measures the width of a pre-converted C string in a given font.
+///
+/// # Arguments
+/// * `font` - a font to measure the string with. See [`Font`] for more details.
+/// * `value` - a string to measure.
+///
+/// # Returns
+/// Returns the width of the string in pixels.
+pub fn measure_c_str(font: Font, value: &ffi::CStr) -> f32 {
+    let len = value.to_bytes().len();
+    unsafe {
+        xplm_sys::XPLMMeasureString(font.into(), value.as_ptr(), len as ::std::os::raw::c_int)
+    }
 }
@@ -0,0 +1,173 @@
+//! Framebuffer capture for bug-report tooling and instructor stations.
+
+use std::io::Write;
+use std::{fs, io, path};
+
+use crate::api::display::Rect;
+use crate::api::utilities::get_output_dir;
+
+use super::gl;
+use super::{GraphicsError, Result};
+
+/// An RGBA image captured from the framebuffer. Rows are stored top to bottom.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Capture {
+    /// The image width in pixels.
+    pub width: usize,
+    /// The image height in pixels.
+    pub height: usize,
+    /// The RGBA pixels, 4 bytes per pixel.
+    pub pixels: Vec<u8>,
+}
+
+/// Reads a region of the current framebuffer. Must be called from a draw callback.
+///
+/// # Arguments
+/// * `rect` - a region in framebuffer pixels, the origin is the bottom left corner.
+///
+/// # Returns
+/// Returns the captured [`Capture`] image.
+pub fn capture_region(rect: &Rect) -> Capture {
+    let width = (rect.right - rect.left).max(0) as usize;
+    let height = (rect.top - rect.bottom).max(0) as usize;
+    let mut pixels = vec![0u8; width * height * 4];
+    if !pixels.is_empty() {
+        unsafe {
+            gl::glPixelStorei(gl::GL_PACK_ALIGNMENT, 1);
+            gl::glReadPixels(
+                rect.left,
+                rect.bottom,
+                width as _,
+                height as _,
+                gl::GL_RGBA,
+                gl::GL_UNSIGNED_BYTE,
+                pixels.as_mut_ptr() as *mut _,
+            );
+        }
+    }
+
+    // OpenGL returns rows bottom to top.
+    let row = width * 4;
+    for y in 0..height / 2 {
+        let (top, bottom) = pixels.split_at_mut((height - 1 - y) * row);
+        top[y * row..(y + 1) * row].swap_with_slice(&mut bottom[..row]);
+    }
+
+    Capture {
+        width,
+        height,
+        pixels,
+    }
+}
+
+impl Capture {
+    /// Encodes the image as an uncompressed PNG.
+    ///
+    /// # Returns
+    /// Returns the PNG file bytes.
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut raw = Vec::with_capacity((self.width * 4 + 1) * self.height);
+        for row in self.pixels.chunks(self.width * 4).take(self.height) {
+            raw.push(0);
+            raw.extend_from_slice(row);
+        }
+
+        let mut header = Vec::with_capacity(13);
+        header.extend_from_slice(&(self.width as u32).to_be_bytes());
+        header.extend_from_slice(&(self.height as u32).to_be_bytes());
+        header.extend_from_slice(&[8, 6, 0, 0, 0]);
+
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        write_chunk(&mut png, b"IHDR", &header);
+        write_chunk(&mut png, b"IDAT", &zlib_stored(&raw));
+        write_chunk(&mut png, b"IEND", &[]);
+        png
+    }
+
+    /// Writes the image as a PNG file.
+    ///
+    /// # Arguments
+    /// * `path` - a file path.
+    ///
+    /// # Returns
+    /// Returns empty result on success. Otherwise returns [`GraphicsError`].
+    pub fn write_png<P: AsRef<path::Path>>(&self, path: P) -> Result<()> {
+        fs::File::create(path)
+            .and_then(|mut file| file.write_all(&self.to_png()))
+            .map_err(GraphicsError::WriteImage)
+    }
+
+    /// Writes the image as a PNG file into the X-Plane `Output` folder.
+    ///
+    /// # Arguments
+    /// * `name` - a file name, e.g. `capture.png`.
+    ///
+    /// # Returns
+    /// Returns the written file path on success. Otherwise returns [`GraphicsError`].
+    pub fn save_to_output<T: AsRef<path::Path>>(&self, name: T) -> Result<path::PathBuf> {
+        let path = get_output_dir()
+            .map_err(|err| GraphicsError::WriteImage(io::Error::new(io::ErrorKind::NotFound, err)))?
+            .join(name);
+        self.write_png(&path)?;
+        Ok(path)
+    }
+}
+
+fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// Wraps data into a zlib stream made of uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    const MAX_BLOCK: usize = 0xFFFF;
+    let mut out = Vec::with_capacity(data.len() + data.len() / MAX_BLOCK * 5 + 11);
+    out.extend_from_slice(&[0x78, 0x01]);
+    let mut blocks = data.chunks(MAX_BLOCK).peekable();
+    if blocks.peek().is_none() {
+        out.extend_from_slice(&[1, 0, 0, 0xFF, 0xFF]);
+    }
+
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none() as u8;
+        let len = block.len() as u16;
+        out.push(last);
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&(!len).to_le_bytes());
+        out.extend_from_slice(block);
+    }
+
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for chunk in data.chunks(5552) {
+        for byte in chunk {
+            a += *byte as u32;
+            b += a;
+        }
+        a %= 65521;
+        b %= 65521;
+    }
+
+    (b << 16) | a
+}
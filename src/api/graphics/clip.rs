@@ -0,0 +1,96 @@
+use crate::api::display::Rect;
+
+use super::gl;
+
+/// An active clip rectangle. The previous scissor state is restored when dropped.
+#[must_use = "the clip rectangle is removed when the guard is dropped"]
+pub struct ClipGuard {
+    was_enabled: bool,
+    previous_box: [gl::GLint; 4],
+}
+
+impl Drop for ClipGuard {
+    fn drop(&mut self) {
+        let [x, y, width, height] = self.previous_box;
+        unsafe {
+            gl::glScissor(x, y, width, height);
+            if !self.was_enabled {
+                gl::glDisable(gl::GL_SCISSOR_TEST);
+            }
+        }
+    }
+}
+
+/// Restricts drawing to a rectangle until the returned guard is dropped.
+/// Nested clip rectangles are intersected with the enclosing ones.
+///
+/// Window coordinates are converted to framebuffer pixels with the current
+/// transformation matrices, so it must be called from a draw callback.
+///
+/// # Arguments
+/// * `rect` - a rectangle in window coordinates. See [`Rect`] for more details.
+///
+/// # Returns
+/// Returns a [`ClipGuard`] which keeps the clip rectangle active.
+pub fn push_clip_rect(rect: &Rect) -> ClipGuard {
+    let mut previous_box = [0; 4];
+    let was_enabled = unsafe {
+        gl::glGetIntegerv(gl::GL_SCISSOR_BOX, previous_box.as_mut_ptr());
+        gl::glIsEnabled(gl::GL_SCISSOR_TEST) != 0
+    };
+
+    let [mut left, mut bottom, mut right, mut top] = window_to_pixels(rect);
+    if was_enabled {
+        let [x, y, width, height] = previous_box;
+        left = left.max(x);
+        bottom = bottom.max(y);
+        right = right.min(x + width);
+        top = top.min(y + height);
+    }
+
+    unsafe {
+        gl::glEnable(gl::GL_SCISSOR_TEST);
+        gl::glScissor(left, bottom, (right - left).max(0), (top - bottom).max(0));
+    }
+
+    ClipGuard {
+        was_enabled,
+        previous_box,
+    }
+}
+
+/// Converts a rectangle in window coordinates to `[left, bottom, right, top]` framebuffer pixels.
+fn window_to_pixels(rect: &Rect) -> [gl::GLint; 4] {
+    let mut modelview = [0.0; 16];
+    let mut projection = [0.0; 16];
+    let mut viewport = [0; 4];
+    unsafe {
+        gl::glGetFloatv(gl::GL_MODELVIEW_MATRIX, modelview.as_mut_ptr());
+        gl::glGetFloatv(gl::GL_PROJECTION_MATRIX, projection.as_mut_ptr());
+        gl::glGetIntegerv(gl::GL_VIEWPORT, viewport.as_mut_ptr());
+    }
+
+    let to_pixels = |x: i32, y: i32| {
+        let eye = transform(&modelview, [x as f32, y as f32, 0.0, 1.0]);
+        let clip = transform(&projection, eye);
+        let w = if clip[3] == 0.0 { 1.0 } else { clip[3] };
+        let px = viewport[0] as f32 + (clip[0] / w + 1.0) * 0.5 * viewport[2] as f32;
+        let py = viewport[1] as f32 + (clip[1] / w + 1.0) * 0.5 * viewport[3] as f32;
+        (px.round() as gl::GLint, py.round() as gl::GLint)
+    };
+
+    let (x1, y1) = to_pixels(rect.left, rect.bottom);
+    let (x2, y2) = to_pixels(rect.right, rect.top);
+    [x1.min(x2), y1.min(y2), x1.max(x2), y1.max(y2)]
+}
+
+/// Multiplies a column-major OpenGL matrix by a vector.
+fn transform(matrix: &[f32; 16], vector: [f32; 4]) -> [f32; 4] {
+    let mut result = [0.0; 4];
+    for (row, value) in result.iter_mut().enumerate() {
+        *value = (0..4)
+            .map(|column| matrix[column * 4 + row] * vector[column])
+            .sum();
+    }
+    result
+}
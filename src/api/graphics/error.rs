@@ -1,4 +1,4 @@
-use std::ffi;
+use std::{ffi, io};
 
 /// An error returned from graphics API calls.
 #[derive(thiserror::Error, Debug)]
@@ -6,4 +6,13 @@ pub enum GraphicsError {
     /// Invalid window title string passed to X-Plane.
     #[error("invalid string {0}")]
     InvalidString(ffi::NulError),
+    /// Unable to write a captured image.
+    #[error("unable to write image {0}")]
+    WriteImage(io::Error),
+    /// Framebuffer objects are not supported by the OpenGL context.
+    #[error("framebuffer objects are not supported")]
+    FramebufferUnsupported,
+    /// Framebuffer is incomplete and can't be drawn into.
+    #[error("framebuffer is incomplete, status {0:#x}")]
+    FramebufferIncomplete(::std::os::raw::c_uint),
 }
@@ -1,15 +1,102 @@
 /// An X-Plane font.
+///
+/// Only [`Font::Basic`] and [`Font::Proportional`] are guaranteed to be available in
+/// modern versions of the SDK, the other fonts are legacy panel fonts kept by X-Plane
+/// for compatibility.
 #[repr(u32)]
-#[derive(Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum Font {
     /// Mono-spaced font for user interface. Available in all versions of the SDK.
     Basic = xplm_sys::xplmFont_Basic,
+    /// Legacy mono-spaced menus font.
+    Menus = 1,
+    /// Legacy mono-spaced metal font.
+    Metal = 2,
+    /// Legacy mono-spaced LED font.
+    Led = 3,
+    /// Legacy mono-spaced wide LED font.
+    LedWide = 4,
+    /// Legacy mono-spaced panel HUD font.
+    PanelHud = 5,
+    /// Legacy mono-spaced panel EFIS font.
+    PanelEfis = 6,
+    /// Legacy mono-spaced panel GPS font.
+    PanelGps = 7,
+    /// Legacy mono-spaced general aviation radios font.
+    RadiosGa = 8,
+    /// Legacy mono-spaced Bendix/King radios font.
+    RadiosBc = 9,
+    /// Legacy mono-spaced helicopter radios font.
+    RadiosHm = 10,
+    /// Legacy mono-spaced narrow general aviation radios font.
+    RadiosGaNarrow = 11,
+    /// Legacy mono-spaced narrow Bendix/King radios font.
+    RadiosBcNarrow = 12,
+    /// Legacy mono-spaced narrow helicopter radios font.
+    RadiosHmNarrow = 13,
+    /// Legacy mono-spaced timer font.
+    Timer = 14,
+    /// Legacy mono-spaced full round font.
+    FullRound = 15,
+    /// Legacy mono-spaced small round font.
+    SmallRound = 16,
+    /// Legacy mono-spaced localized menus font.
+    MenusLocalized = 17,
     /// Proportional UI font.
     Proportional = xplm_sys::xplmFont_Proportional,
 }
 
+impl Font {
+    /// Returns all fonts known to the SDK.
+    ///
+    /// # Returns
+    /// Returns a slice of all fonts ordered by their identifiers.
+    pub fn all() -> &'static [Font] {
+        &[
+            Self::Basic,
+            Self::Menus,
+            Self::Metal,
+            Self::Led,
+            Self::LedWide,
+            Self::PanelHud,
+            Self::PanelEfis,
+            Self::PanelGps,
+            Self::RadiosGa,
+            Self::RadiosBc,
+            Self::RadiosHm,
+            Self::RadiosGaNarrow,
+            Self::RadiosBcNarrow,
+            Self::RadiosHmNarrow,
+            Self::Timer,
+            Self::FullRound,
+            Self::SmallRound,
+            Self::MenusLocalized,
+            Self::Proportional,
+        ]
+    }
+
+    /// Checks wether the font is proportional.
+    ///
+    /// # Returns
+    /// Returns `true` if the font is proportional. Otherwise returns `false`.
+    pub fn is_proportional(&self) -> bool {
+        matches!(self, Self::Proportional)
+    }
+}
+
 impl From<Font> for xplm_sys::XPLMFontID {
     fn from(value: Font) -> Self {
         value as _
     }
 }
+
+/// Font dimensions returned by [`get_font_dimensions`](super::get_font_dimensions).
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct FontDimensions {
+    /// The width of a character in pixels, for proportional fonts it is the maximum width.
+    pub char_width: ::std::os::raw::c_int,
+    /// The height of a character in pixels.
+    pub char_height: ::std::os::raw::c_int,
+    /// `true` if the font only contains digits.
+    pub digits_only: bool,
+}
@@ -0,0 +1,178 @@
+//! Minimal OpenGL bindings used by the drawing helpers.
+//! X-Plane provides a compatibility profile context to plugins drawing in window callbacks.
+
+#![allow(non_snake_case)]
+
+pub type GLenum = ::std::os::raw::c_uint;
+pub type GLint = ::std::os::raw::c_int;
+pub type GLfloat = f32;
+pub type GLsizei = ::std::os::raw::c_int;
+pub type GLuint = ::std::os::raw::c_uint;
+pub type GLboolean = ::std::os::raw::c_uchar;
+
+pub const GL_LINE_LOOP: GLenum = 0x0002;
+pub const GL_LINE_STRIP: GLenum = 0x0003;
+pub const GL_QUADS: GLenum = 0x0007;
+pub const GL_UNSIGNED_BYTE: GLenum = 0x1401;
+pub const GL_RGBA: GLenum = 0x1908;
+pub const GL_PACK_ALIGNMENT: GLenum = 0x0D05;
+pub const GL_VIEWPORT: GLenum = 0x0BA2;
+pub const GL_MODELVIEW_MATRIX: GLenum = 0x0BA6;
+pub const GL_PROJECTION_MATRIX: GLenum = 0x0BA7;
+pub const GL_SCISSOR_BOX: GLenum = 0x0C10;
+pub const GL_SCISSOR_TEST: GLenum = 0x0C11;
+pub const GL_TEXTURE_2D: GLenum = 0x0DE1;
+pub const GL_TEXTURE_MIN_FILTER: GLenum = 0x2801;
+pub const GL_TEXTURE_MAG_FILTER: GLenum = 0x2800;
+pub const GL_LINEAR: GLint = 0x2601;
+pub const GL_FRAMEBUFFER: GLenum = 0x8D40;
+pub const GL_FRAMEBUFFER_BINDING: GLenum = 0x8CA6;
+pub const GL_FRAMEBUFFER_COMPLETE: GLenum = 0x8CD5;
+pub const GL_COLOR_ATTACHMENT0: GLenum = 0x8CE0;
+
+#[cfg_attr(target_os = "windows", link(name = "opengl32"))]
+#[cfg_attr(target_os = "macos", link(name = "OpenGL", kind = "framework"))]
+#[cfg_attr(target_os = "linux", link(name = "GL"))]
+extern "system" {
+    pub fn glBegin(mode: GLenum);
+    pub fn glEnd();
+    pub fn glVertex2i(x: GLint, y: GLint);
+    pub fn glColor4f(red: GLfloat, green: GLfloat, blue: GLfloat, alpha: GLfloat);
+    pub fn glLineWidth(width: GLfloat);
+    pub fn glPixelStorei(pname: GLenum, param: GLint);
+    pub fn glReadPixels(
+        x: GLint,
+        y: GLint,
+        width: GLsizei,
+        height: GLsizei,
+        format: GLenum,
+        kind: GLenum,
+        pixels: *mut ::std::os::raw::c_void,
+    );
+    pub fn glViewport(x: GLint, y: GLint, width: GLsizei, height: GLsizei);
+    pub fn glGetIntegerv(pname: GLenum, data: *mut GLint);
+    pub fn glGetFloatv(pname: GLenum, data: *mut GLfloat);
+    pub fn glEnable(cap: GLenum);
+    pub fn glDisable(cap: GLenum);
+    pub fn glIsEnabled(cap: GLenum) -> GLboolean;
+    pub fn glScissor(x: GLint, y: GLint, width: GLsizei, height: GLsizei);
+    pub fn glTexParameteri(target: GLenum, pname: GLenum, param: GLint);
+    pub fn glTexImage2D(
+        target: GLenum,
+        level: GLint,
+        internal_format: GLint,
+        width: GLsizei,
+        height: GLsizei,
+        border: GLint,
+        format: GLenum,
+        kind: GLenum,
+        pixels: *const ::std::os::raw::c_void,
+    );
+    pub fn glDeleteTextures(n: GLsizei, textures: *const GLuint);
+}
+
+/// Framebuffer objects are exported directly by the OpenGL libraries on Linux and macOS.
+#[cfg(not(target_os = "windows"))]
+mod framebuffer {
+    use super::*;
+
+    #[cfg_attr(target_os = "macos", link(name = "OpenGL", kind = "framework"))]
+    #[cfg_attr(target_os = "linux", link(name = "GL"))]
+    extern "system" {
+        pub fn glGenFramebuffers(n: GLsizei, ids: *mut GLuint);
+        pub fn glDeleteFramebuffers(n: GLsizei, ids: *const GLuint);
+        pub fn glBindFramebuffer(target: GLenum, framebuffer: GLuint);
+        pub fn glFramebufferTexture2D(
+            target: GLenum,
+            attachment: GLenum,
+            textarget: GLenum,
+            texture: GLuint,
+            level: GLint,
+        );
+        pub fn glCheckFramebufferStatus(target: GLenum) -> GLenum;
+    }
+
+    pub fn is_supported() -> bool {
+        true
+    }
+}
+
+/// Framebuffer objects are extension functions on Windows and are resolved at runtime.
+#[cfg(target_os = "windows")]
+mod framebuffer {
+    use std::sync::OnceLock;
+
+    use super::*;
+
+    #[link(name = "opengl32")]
+    extern "system" {
+        fn wglGetProcAddress(name: *const ::std::os::raw::c_char) -> *const ::std::os::raw::c_void;
+    }
+
+    struct Functions {
+        gen: unsafe extern "system" fn(GLsizei, *mut GLuint),
+        delete: unsafe extern "system" fn(GLsizei, *const GLuint),
+        bind: unsafe extern "system" fn(GLenum, GLuint),
+        texture_2d: unsafe extern "system" fn(GLenum, GLenum, GLenum, GLuint, GLint),
+        check_status: unsafe extern "system" fn(GLenum) -> GLenum,
+    }
+
+    fn functions() -> Option<&'static Functions> {
+        static FUNCTIONS: OnceLock<Option<Functions>> = OnceLock::new();
+        FUNCTIONS
+            .get_or_init(|| unsafe {
+                let load = |name: &[u8]| {
+                    let ptr = wglGetProcAddress(name.as_ptr() as *const _);
+                    (!ptr.is_null()).then_some(ptr)
+                };
+                Some(Functions {
+                    gen: std::mem::transmute(load(b"glGenFramebuffers\0")?),
+                    delete: std::mem::transmute(load(b"glDeleteFramebuffers\0")?),
+                    bind: std::mem::transmute(load(b"glBindFramebuffer\0")?),
+                    texture_2d: std::mem::transmute(load(b"glFramebufferTexture2D\0")?),
+                    check_status: std::mem::transmute(load(b"glCheckFramebufferStatus\0")?),
+                })
+            })
+            .as_ref()
+    }
+
+    pub fn is_supported() -> bool {
+        functions().is_some()
+    }
+
+    pub unsafe fn glGenFramebuffers(n: GLsizei, ids: *mut GLuint) {
+        if let Some(f) = functions() {
+            (f.gen)(n, ids)
+        }
+    }
+
+    pub unsafe fn glDeleteFramebuffers(n: GLsizei, ids: *const GLuint) {
+        if let Some(f) = functions() {
+            (f.delete)(n, ids)
+        }
+    }
+
+    pub unsafe fn glBindFramebuffer(target: GLenum, framebuffer: GLuint) {
+        if let Some(f) = functions() {
+            (f.bind)(target, framebuffer)
+        }
+    }
+
+    pub unsafe fn glFramebufferTexture2D(
+        target: GLenum,
+        attachment: GLenum,
+        textarget: GLenum,
+        texture: GLuint,
+        level: GLint,
+    ) {
+        if let Some(f) = functions() {
+            (f.texture_2d)(target, attachment, textarget, texture, level)
+        }
+    }
+
+    pub unsafe fn glCheckFramebufferStatus(target: GLenum) -> GLenum {
+        functions().map_or(0, |f| (f.check_status)(target))
+    }
+}
+
+pub use self::framebuffer::*;
@@ -0,0 +1,60 @@
+use super::{deg_magnetic_to_deg_true, deg_true_to_deg_magnetic};
+
+/// A heading in degrees which keeps track of its reference north.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Heading {
+    /// A heading relative to the true north.
+    True(f32),
+    /// A heading relative to the magnetic north at the user's aircraft location.
+    Magnetic(f32),
+}
+
+impl Heading {
+    /// Returns the heading value in degrees regardless of its reference north.
+    ///
+    /// # Returns
+    /// Returns heading degrees normalized to range 0.0..360.0.
+    pub fn degrees(&self) -> f32 {
+        match self {
+            Self::True(value) | Self::Magnetic(value) => value.rem_euclid(360.0),
+        }
+    }
+
+    /// Converts the heading to the true north reference.
+    ///
+    /// # Returns
+    /// Returns a [`Heading::True`] heading.
+    pub fn to_true(self) -> Self {
+        match self {
+            Self::True(_) => self,
+            Self::Magnetic(value) => Self::True(deg_magnetic_to_deg_true(value)),
+        }
+    }
+
+    /// Converts the heading to the magnetic north reference at the user's aircraft location.
+    ///
+    /// # Returns
+    /// Returns a [`Heading::Magnetic`] heading.
+    pub fn to_magnetic(self) -> Self {
+        match self {
+            Self::True(value) => Self::Magnetic(deg_true_to_deg_magnetic(value)),
+            Self::Magnetic(_) => self,
+        }
+    }
+
+    /// Checks wether the heading is relative to the true north.
+    ///
+    /// # Returns
+    /// Returns `true` if the heading is true. Otherwise returns `false`.
+    pub fn is_true(&self) -> bool {
+        matches!(self, Self::True(_))
+    }
+
+    /// Checks wether the heading is relative to the magnetic north.
+    ///
+    /// # Returns
+    /// Returns `true` if the heading is magnetic. Otherwise returns `false`.
+    pub fn is_magnetic(&self) -> bool {
+        matches!(self, Self::Magnetic(_))
+    }
+}
@@ -0,0 +1,203 @@
+//! Off-screen rendering into textures, e.g. for glass cockpit displays.
+
+use std::{cell::RefCell, rc::Rc};
+
+use crate::api::display::{register_draw_callback, DrawCallbackRecord, DrawPhase};
+
+use super::gl;
+use super::{bind_texture_2d, generate_texture_numbers, GraphicsError, Result};
+
+/// An off-screen framebuffer backed by an RGBA texture.
+/// The framebuffer and the texture are deleted when dropped.
+pub struct OffscreenTarget {
+    width: ::std::os::raw::c_int,
+    height: ::std::os::raw::c_int,
+    texture: ::std::os::raw::c_int,
+    framebuffer: gl::GLuint,
+}
+
+impl OffscreenTarget {
+    /// Creates an off-screen framebuffer. Must be called with a current OpenGL context,
+    /// e.g. from a draw callback or plugin start.
+    ///
+    /// # Arguments
+    /// * `width` - a texture width in pixels.
+    /// * `height` - a texture height in pixels.
+    ///
+    /// # Returns
+    /// Returns [`OffscreenTarget`] on success. Otherwise returns [`GraphicsError`].
+    pub fn new(width: ::std::os::raw::c_int, height: ::std::os::raw::c_int) -> Result<Self> {
+        if !gl::is_supported() {
+            return Err(GraphicsError::FramebufferUnsupported);
+        }
+
+        let texture = generate_texture_numbers(1)
+            .first()
+            .copied()
+            .ok_or(GraphicsError::FramebufferUnsupported)?;
+        bind_texture_2d(texture, 0);
+        unsafe {
+            gl::glTexParameteri(gl::GL_TEXTURE_2D, gl::GL_TEXTURE_MIN_FILTER, gl::GL_LINEAR);
+            gl::glTexParameteri(gl::GL_TEXTURE_2D, gl::GL_TEXTURE_MAG_FILTER, gl::GL_LINEAR);
+            gl::glTexImage2D(
+                gl::GL_TEXTURE_2D,
+                0,
+                gl::GL_RGBA as _,
+                width,
+                height,
+                0,
+                gl::GL_RGBA,
+                gl::GL_UNSIGNED_BYTE,
+                std::ptr::null(),
+            );
+        }
+
+        let mut target = Self {
+            width,
+            height,
+            texture,
+            framebuffer: 0,
+        };
+
+        let status = target.bound(|target| unsafe {
+            gl::glFramebufferTexture2D(
+                gl::GL_FRAMEBUFFER,
+                gl::GL_COLOR_ATTACHMENT0,
+                gl::GL_TEXTURE_2D,
+                target.texture as _,
+                0,
+            );
+            gl::glCheckFramebufferStatus(gl::GL_FRAMEBUFFER)
+        });
+
+        if status == gl::GL_FRAMEBUFFER_COMPLETE {
+            Ok(target)
+        } else {
+            Err(GraphicsError::FramebufferIncomplete(status))
+        }
+    }
+
+    /// Returns the texture width in pixels.
+    pub fn width(&self) -> ::std::os::raw::c_int {
+        self.width
+    }
+
+    /// Returns the texture height in pixels.
+    pub fn height(&self) -> ::std::os::raw::c_int {
+        self.height
+    }
+
+    /// Returns the texture number to bind with [`bind_texture_2d`].
+    pub fn texture(&self) -> ::std::os::raw::c_int {
+        self.texture
+    }
+
+    /// Draws into the framebuffer. The viewport is set to the texture size and
+    /// the previously bound framebuffer and viewport are restored afterwards.
+    ///
+    /// # Arguments
+    /// * `f` - a drawing closure.
+    ///
+    /// # Returns
+    /// Returns the closure result.
+    pub fn draw<T, F: FnOnce(&Self) -> T>(&mut self, f: F) -> T {
+        self.bound(|target| f(target))
+    }
+
+    fn bound<T, F: FnOnce(&Self) -> T>(&mut self, f: F) -> T {
+        let mut previous_framebuffer = 0;
+        let mut previous_viewport = [0; 4];
+        unsafe {
+            gl::glGetIntegerv(gl::GL_FRAMEBUFFER_BINDING, &mut previous_framebuffer);
+            gl::glGetIntegerv(gl::GL_VIEWPORT, previous_viewport.as_mut_ptr());
+            if self.framebuffer == 0 {
+                gl::glGenFramebuffers(1, &mut self.framebuffer);
+            }
+            gl::glBindFramebuffer(gl::GL_FRAMEBUFFER, self.framebuffer);
+            gl::glViewport(0, 0, self.width, self.height);
+        }
+
+        let result = f(self);
+
+        unsafe {
+            gl::glBindFramebuffer(gl::GL_FRAMEBUFFER, previous_framebuffer as _);
+            gl::glViewport(
+                previous_viewport[0],
+                previous_viewport[1],
+                previous_viewport[2],
+                previous_viewport[3],
+            );
+        }
+
+        result
+    }
+}
+
+impl Drop for OffscreenTarget {
+    fn drop(&mut self) {
+        let texture = self.texture as gl::GLuint;
+        unsafe {
+            if self.framebuffer != 0 {
+                gl::glDeleteFramebuffers(1, &self.framebuffer);
+            }
+            gl::glDeleteTextures(1, &texture);
+        }
+    }
+}
+
+/// A panel texture redrawn every frame from a draw callback.
+///
+/// The drawing closure renders into an [`OffscreenTarget`], the resulting texture
+/// can be bound with [`bind_texture_2d`] and mapped onto cockpit displays.
+/// The draw callback is unregistered when dropped.
+pub struct OffscreenPanel {
+    target: Rc<RefCell<OffscreenTarget>>,
+    _record: DrawCallbackRecord,
+}
+
+impl OffscreenPanel {
+    /// Creates an off-screen panel and starts redrawing it.
+    ///
+    /// # Arguments
+    /// * `width` - a texture width in pixels.
+    /// * `height` - a texture height in pixels.
+    /// * `phase` - a drawing phase to redraw the texture in. See [`DrawPhase`] for more details.
+    /// * `draw` - a drawing closure called every frame while the framebuffer is bound.
+    ///
+    /// # Returns
+    /// Returns [`OffscreenPanel`] on success. Otherwise returns [`GraphicsError`].
+    pub fn new<F>(
+        width: ::std::os::raw::c_int,
+        height: ::std::os::raw::c_int,
+        phase: DrawPhase,
+        mut draw: F,
+    ) -> Result<Self>
+    where
+        F: FnMut(&OffscreenTarget) + 'static,
+    {
+        let target = Rc::new(RefCell::new(OffscreenTarget::new(width, height)?));
+        let draw_target = target.clone();
+        let record = register_draw_callback(phase, true, move |_: DrawPhase, _: bool| {
+            if let Ok(mut target) = draw_target.try_borrow_mut() {
+                target.draw(&mut draw);
+            }
+            true
+        });
+
+        Ok(Self {
+            target,
+            _record: record,
+        })
+    }
+
+    /// Returns the texture number to bind with [`bind_texture_2d`].
+    pub fn texture(&self) -> ::std::os::raw::c_int {
+        self.target.borrow().texture()
+    }
+
+    /// Returns the texture size in pixels.
+    pub fn size(&self) -> (::std::os::raw::c_int, ::std::os::raw::c_int) {
+        let target = self.target.borrow();
+        (target.width(), target.height())
+    }
+}
@@ -1,5 +1,5 @@
 /// An X-Plane world position.
-#[derive(Default)]
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
 pub struct WorldPosition {
     /// World position latitude.
     pub latitude: f64,
@@ -48,7 +48,7 @@ impl WorldPosition {
 }
 
 /// An X-Plane local position.
-#[derive(Default)]
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
 pub struct LocalPosition {
     /// Local X coordinate.
     pub x: f64,
@@ -0,0 +1,138 @@
+use crate::api::display::{Color, Coord, Rect};
+
+use super::gl;
+use super::{set_graphics_state, GraphicsState};
+
+/// A direction of a color gradient.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum GradientDirection {
+    /// A gradient from the left edge to the right edge.
+    Horizontal,
+    /// A gradient from the top edge to the bottom edge.
+    Vertical,
+}
+
+/// Draws a rectangle filled with a solid color.
+///
+/// # Arguments
+/// * `rect` - a rectangle in window coordinates. See [`Rect`] for more details.
+/// * `color` - a fill color. See [`Color`] for more details.
+pub fn draw_rect_filled(rect: &Rect, color: &Color) {
+    draw_quad(&rect_corners(rect), color);
+}
+
+/// Draws a rectangle outline.
+///
+/// # Arguments
+/// * `rect` - a rectangle in window coordinates. See [`Rect`] for more details.
+/// * `color` - a stroke color. See [`Color`] for more details.
+/// * `width` - a stroke width in pixels.
+pub fn draw_rect_stroked(rect: &Rect, color: &Color, width: f32) {
+    prepare(&[*color]);
+    unsafe {
+        gl::glLineWidth(width);
+        gl::glBegin(gl::GL_LINE_LOOP);
+        set_color(color);
+        for corner in rect_corners(rect) {
+            gl::glVertex2i(corner.x, corner.y);
+        }
+        gl::glEnd();
+    }
+}
+
+/// Draws a straight line.
+///
+/// # Arguments
+/// * `from` - a line start in window coordinates.
+/// * `to` - a line end in window coordinates.
+/// * `color` - a line color. See [`Color`] for more details.
+/// * `width` - a line width in pixels.
+pub fn draw_line(from: &Coord, to: &Coord, color: &Color, width: f32) {
+    draw_polyline(&[*from, *to], color, width);
+}
+
+/// Draws connected line segments through the given points.
+///
+/// # Arguments
+/// * `points` - line points in window coordinates.
+/// * `color` - a line color. See [`Color`] for more details.
+/// * `width` - a line width in pixels.
+pub fn draw_polyline(points: &[Coord], color: &Color, width: f32) {
+    if points.len() < 2 {
+        return;
+    }
+
+    prepare(&[*color]);
+    unsafe {
+        gl::glLineWidth(width);
+        gl::glBegin(gl::GL_LINE_STRIP);
+        set_color(color);
+        for point in points {
+            gl::glVertex2i(point.x, point.y);
+        }
+        gl::glEnd();
+    }
+}
+
+/// Draws a quadrilateral filled with a solid color.
+///
+/// # Arguments
+/// * `corners` - quad corners in window coordinates in drawing order.
+/// * `color` - a fill color. See [`Color`] for more details.
+pub fn draw_quad(corners: &[Coord; 4], color: &Color) {
+    prepare(&[*color]);
+    unsafe {
+        gl::glBegin(gl::GL_QUADS);
+        set_color(color);
+        for corner in corners {
+            gl::glVertex2i(corner.x, corner.y);
+        }
+        gl::glEnd();
+    }
+}
+
+/// Draws a rectangle filled with a linear two color gradient.
+///
+/// # Arguments
+/// * `rect` - a rectangle in window coordinates. See [`Rect`] for more details.
+/// * `from` - a color at the left or top edge.
+/// * `to` - a color at the right or bottom edge.
+/// * `direction` - a gradient direction. See [`GradientDirection`] for more details.
+pub fn draw_rect_gradient(rect: &Rect, from: &Color, to: &Color, direction: GradientDirection) {
+    let [top_left, top_right, bottom_right, bottom_left] = rect_corners(rect);
+    let colors = match direction {
+        GradientDirection::Horizontal => [from, to, to, from],
+        GradientDirection::Vertical => [from, from, to, to],
+    };
+
+    prepare(&[*from, *to]);
+    unsafe {
+        gl::glBegin(gl::GL_QUADS);
+        for (corner, color) in [top_left, top_right, bottom_right, bottom_left]
+            .iter()
+            .zip(colors)
+        {
+            set_color(color);
+            gl::glVertex2i(corner.x, corner.y);
+        }
+        gl::glEnd();
+    }
+}
+
+fn rect_corners(rect: &Rect) -> [Coord; 4] {
+    [
+        Coord::new(rect.left, rect.top),
+        Coord::new(rect.right, rect.top),
+        Coord::new(rect.right, rect.bottom),
+        Coord::new(rect.left, rect.bottom),
+    ]
+}
+
+fn prepare(colors: &[Color]) {
+    let translucent = colors.iter().any(|color| color.a < 1.0);
+    set_graphics_state(&GraphicsState::ui().alpha_blending(translucent));
+}
+
+unsafe fn set_color(color: &Color) {
+    gl::glColor4f(color.r, color.g, color.b, color.a);
+}
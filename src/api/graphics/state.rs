@@ -1,33 +1,142 @@
-/// Graphics state configuration used in [`set_graphics_state`].
+/// Graphics state configuration used in [`set_graphics_state`](super::set_graphics_state).
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
 pub struct GraphicsState {
     /// Enables or disables fog, equivalent to: glEnable(GL_FOG).
-    pub enable_fog: ::std::os::raw::c_int,
+    pub fog: bool,
     /// Enables or disables a number of multitexturing units.
     /// If the number is 0, 2d texturing is disabled entirely, as in glDisable(GL_TEXTURE_2D).
     /// Otherwise, 2d texturing is enabled.
-    pub number_tex_units: ::std::os::raw::c_int,
+    pub tex_units: u8,
     /// Enables or disables OpenGL lighting, e.g. glEnable(GL_LIGHTING).
-    pub enable_lighting: ::std::os::raw::c_int,
+    pub lighting: bool,
     /// Enables or disables the alpha test per pixel.
-    pub enable_alpha_testing: ::std::os::raw::c_int,
+    pub alpha_testing: bool,
     /// Enables or disables alpha blending per pixel, e.g. glEnable(GL_BLEND).
-    pub enable_alpha_blending: ::std::os::raw::c_int,
+    pub alpha_blending: bool,
     /// Enables per pixel depth testing, as in glEnable(GL_DEPTH_TEST).
-    pub enable_depth_testing: ::std::os::raw::c_int,
+    pub depth_testing: bool,
     /// Enables writing back of depth information to the depth buffer, as in glDepthMask(GL_TRUE).
-    pub enable_depth_writing: ::std::os::raw::c_int,
+    pub depth_writing: bool,
 }
 
 impl GraphicsState {
+    /// Creates a state for drawing untextured user interface primitives.
+    /// Everything is disabled.
+    ///
+    /// # Returns
+    /// Returns a new [`GraphicsState`] instance.
     pub fn ui() -> Self {
-        Self {
-            enable_fog: 0,
-            number_tex_units: 0,
-            enable_lighting: 0,
-            enable_alpha_testing: 0,
-            enable_alpha_blending: 0,
-            enable_depth_testing: 0,
-            enable_depth_writing: 0,
-        }
+        Self::default()
+    }
+
+    /// Creates a state for drawing textured 2d user interface elements
+    /// with one texture unit and alpha blending enabled.
+    ///
+    /// # Returns
+    /// Returns a new [`GraphicsState`] instance.
+    pub fn textured_2d() -> Self {
+        Self::default()
+            .tex_units(1)
+            .alpha_testing(true)
+            .alpha_blending(true)
+    }
+
+    /// Creates a state for drawing lit and textured geometry in the 3d world.
+    ///
+    /// # Returns
+    /// Returns a new [`GraphicsState`] instance.
+    pub fn world_3d() -> Self {
+        Self::default()
+            .fog(true)
+            .tex_units(1)
+            .lighting(true)
+            .alpha_testing(true)
+            .alpha_blending(true)
+            .depth_testing(true)
+            .depth_writing(true)
+    }
+
+    /// Enables or disables fog.
+    ///
+    /// # Arguments
+    /// * `value` - `true` to enable fog.
+    ///
+    /// # Returns
+    /// Returns a modified graphics state.
+    pub fn fog(mut self, value: bool) -> Self {
+        self.fog = value;
+        self
+    }
+
+    /// Sets the number of multitexturing units.
+    ///
+    /// # Arguments
+    /// * `value` - a number of texture units, 0 disables 2d texturing.
+    ///
+    /// # Returns
+    /// Returns a modified graphics state.
+    pub fn tex_units(mut self, value: u8) -> Self {
+        self.tex_units = value;
+        self
+    }
+
+    /// Enables or disables lighting.
+    ///
+    /// # Arguments
+    /// * `value` - `true` to enable lighting.
+    ///
+    /// # Returns
+    /// Returns a modified graphics state.
+    pub fn lighting(mut self, value: bool) -> Self {
+        self.lighting = value;
+        self
+    }
+
+    /// Enables or disables the alpha test.
+    ///
+    /// # Arguments
+    /// * `value` - `true` to enable alpha testing.
+    ///
+    /// # Returns
+    /// Returns a modified graphics state.
+    pub fn alpha_testing(mut self, value: bool) -> Self {
+        self.alpha_testing = value;
+        self
+    }
+
+    /// Enables or disables alpha blending.
+    ///
+    /// # Arguments
+    /// * `value` - `true` to enable alpha blending.
+    ///
+    /// # Returns
+    /// Returns a modified graphics state.
+    pub fn alpha_blending(mut self, value: bool) -> Self {
+        self.alpha_blending = value;
+        self
+    }
+
+    /// Enables or disables depth testing.
+    ///
+    /// # Arguments
+    /// * `value` - `true` to enable depth testing.
+    ///
+    /// # Returns
+    /// Returns a modified graphics state.
+    pub fn depth_testing(mut self, value: bool) -> Self {
+        self.depth_testing = value;
+        self
+    }
+
+    /// Enables or disables depth writing.
+    ///
+    /// # Arguments
+    /// * `value` - `true` to enable depth writing.
+    ///
+    /// # Returns
+    /// Returns a modified graphics state.
+    pub fn depth_writing(mut self, value: bool) -> Self {
+        self.depth_writing = value;
+        self
     }
 }
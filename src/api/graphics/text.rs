@@ -0,0 +1,153 @@
+use std::cell::RefCell;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::ffi::{CStr, CString};
+use std::hash::{Hash, Hasher};
+
+use super::{measure_c_str, Font, GraphicsError, Result};
+
+thread_local! {
+    static TEXT_BUFFER: RefCell<Vec<u8>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Converts a string into a nul-terminated C string using a reusable per-thread buffer.
+/// The buffer grows to the longest string seen, so repeated calls do not allocate.
+///
+/// # Arguments
+/// * `value` - a string to convert.
+/// * `f` - a closure which receives the converted C string.
+///
+/// # Returns
+/// Returns the closure result on success. Otherwise returns [`GraphicsError`].
+pub(super) fn with_c_str<R, F: FnOnce(&CStr) -> R>(value: &str, f: F) -> Result<R> {
+    if value.as_bytes().contains(&0) {
+        let err = CString::new(value).expect_err("string contains nul byte");
+        return Err(GraphicsError::InvalidString(err));
+    }
+
+    TEXT_BUFFER.with(|buffer| match buffer.try_borrow_mut() {
+        Ok(mut buffer) => {
+            buffer.clear();
+            buffer.extend_from_slice(value.as_bytes());
+            buffer.push(0);
+            let value_c = CStr::from_bytes_with_nul(&buffer).expect("buffer is nul-terminated");
+            Ok(f(value_c))
+        }
+        // Re-entrant call from within the closure, fall back to allocation.
+        Err(_) => {
+            let value_c = CString::new(value).map_err(GraphicsError::InvalidString)?;
+            Ok(f(&value_c))
+        }
+    })
+}
+
+/// A text converted into a C string once and reused across frames.
+pub struct PreparedText {
+    value: CString,
+    widths: RefCell<Vec<(xplm_sys::XPLMFontID, f32)>>,
+}
+
+impl PreparedText {
+    /// Prepares a text for drawing.
+    ///
+    /// # Arguments
+    /// * `value` - a text to prepare.
+    ///
+    /// # Returns
+    /// Returns [`PreparedText`] on success. Otherwise returns [`GraphicsError`].
+    pub fn new<T: Into<String>>(value: T) -> Result<Self> {
+        let value = CString::new(value.into()).map_err(GraphicsError::InvalidString)?;
+        Ok(Self {
+            value,
+            widths: RefCell::new(Vec::new()),
+        })
+    }
+
+    /// Returns the prepared C string.
+    pub fn as_c_str(&self) -> &CStr {
+        &self.value
+    }
+
+    /// Returns the prepared text as a string slice.
+    pub fn as_str(&self) -> &str {
+        self.value.to_str().unwrap_or_default()
+    }
+
+    /// Measures the text width in a given font, the result is cached per font.
+    ///
+    /// # Arguments
+    /// * `font` - a font to measure the text with. See [`Font`] for more details.
+    ///
+    /// # Returns
+    /// Returns the width of the text in pixels.
+    pub fn width(&self, font: Font) -> f32 {
+        let font_id = xplm_sys::XPLMFontID::from(font);
+        let mut widths = self.widths.borrow_mut();
+        match widths.iter().find(|(id, _)| *id == font_id) {
+            Some((_, width)) => *width,
+            None => {
+                let width = measure_c_str(font, &self.value);
+                widths.push((font_id, width));
+                width
+            }
+        }
+    }
+}
+
+/// A cache of prepared texts keyed by the string hash.
+/// Useful for labels which change rarely but are drawn every frame.
+#[derive(Default)]
+pub struct TextCache {
+    texts: HashMap<u64, PreparedText>,
+}
+
+impl TextCache {
+    /// Creates an empty text cache.
+    ///
+    /// # Returns
+    /// Returns a new [`TextCache`] instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a prepared text for a string, preparing it on the first use.
+    ///
+    /// # Arguments
+    /// * `value` - a text to look up.
+    ///
+    /// # Returns
+    /// Returns [`PreparedText`] on success. Otherwise returns [`GraphicsError`].
+    pub fn get(&mut self, value: &str) -> Result<&PreparedText> {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        let key = hasher.finish();
+        let stale = self
+            .texts
+            .get(&key)
+            .is_some_and(|text| text.as_str() != value);
+        if stale {
+            self.texts.remove(&key);
+        }
+
+        if !self.texts.contains_key(&key) {
+            self.texts.insert(key, PreparedText::new(value)?);
+        }
+
+        Ok(&self.texts[&key])
+    }
+
+    /// Returns the number of cached texts.
+    pub fn len(&self) -> usize {
+        self.texts.len()
+    }
+
+    /// Checks wether the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.texts.is_empty()
+    }
+
+    /// Removes all cached texts.
+    pub fn clear(&mut self) {
+        self.texts.clear();
+    }
+}
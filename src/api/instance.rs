@@ -0,0 +1,70 @@
+pub mod draw_info;
+pub mod error;
+pub mod handle;
+
+use std::{ffi, ops::Deref};
+
+pub use self::draw_info::DrawInfo;
+pub use self::error::InstanceError;
+pub use self::handle::Instance;
+
+use super::scenery::Object;
+use super::utilities::{require_xplm_version, XplmVersion};
+
+pub type Result<T> = std::result::Result<T, InstanceError>;
+
+/// Creates an instance of a loaded object. The instance is not drawn until its position is set.
+/// Requires X-Plane 11.10 or newer.
+///
+/// # Arguments
+/// * `object` - a loaded object. See [`Object`] for more details.
+/// * `data_refs` - names of the datarefs the object animations are driven by.
+///
+/// # Returns
+/// Returns [`Instance`] on success. Otherwise returns [`InstanceError`].
+pub fn create_instance<T: AsRef<str>>(object: &Object, data_refs: &[T]) -> Result<Instance> {
+    require_xplm_version(XplmVersion::V300)?;
+    let names_c = data_refs
+        .iter()
+        .map(|name| ffi::CString::new(name.as_ref()))
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(InstanceError::InvalidDataRefName)?;
+    let mut names_ptr = names_c.iter().map(|name| name.as_ptr()).collect::<Vec<_>>();
+    names_ptr.push(std::ptr::null());
+    let instance = unsafe { xplm_sys::XPLMCreateInstance(*object.deref(), names_ptr.as_mut_ptr()) };
+    Instance::new(instance, data_refs.len())
+}
+
+/// Destroys an instance. Normally an instance is destroyed when dropped.
+///
+/// # Arguments
+/// * `instance` - an instance to destroy.
+pub fn destroy_instance(instance: &Instance) {
+    unsafe { xplm_sys::XPLMDestroyInstance(*instance.deref()) };
+}
+
+/// Updates the position, orientation and dataref values of an instance.
+///
+/// # Arguments
+/// * `instance` - an instance to update.
+/// * `draw_info` - a position and orientation. See [`DrawInfo`] for more details.
+/// * `values` - dataref values in the order the datarefs were passed to [`create_instance`].
+///
+/// # Returns
+/// Returns empty result on success. Otherwise returns [`InstanceError`].
+pub fn instance_set_position(
+    instance: &Instance,
+    draw_info: &DrawInfo,
+    values: &[f32],
+) -> Result<()> {
+    if values.len() != instance.data_refs_count() {
+        return Err(InstanceError::DataRefValuesMismatch {
+            expected: instance.data_refs_count(),
+            actual: values.len(),
+        });
+    }
+
+    let draw_info = xplm_sys::XPLMDrawInfo_t::from(draw_info);
+    unsafe { xplm_sys::XPLMInstanceSetPosition(*instance.deref(), &draw_info, values.as_ptr()) };
+    Ok(())
+}
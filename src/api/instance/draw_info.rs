@@ -0,0 +1,78 @@
+use crate::api::graphics::LocalPosition;
+
+/// An instance position and orientation in local OpenGL coordinates.
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct DrawInfo {
+    /// A local position of the instance.
+    pub position: LocalPosition,
+    /// A pitch in degrees, positive is nose up.
+    pub pitch: f32,
+    /// A true heading in degrees.
+    pub heading: f32,
+    /// A roll in degrees, positive is right wing down.
+    pub roll: f32,
+}
+
+impl DrawInfo {
+    /// Sets the local position of the instance.
+    ///
+    /// # Arguments
+    /// * `value` - a local position to set. See [`LocalPosition`] for more details.
+    ///
+    /// # Returns
+    /// Returns a modified draw info with new position.
+    pub fn position(mut self, value: LocalPosition) -> Self {
+        self.position = value;
+        self
+    }
+
+    /// Sets the pitch of the instance.
+    ///
+    /// # Arguments
+    /// * `value` - a pitch in degrees.
+    ///
+    /// # Returns
+    /// Returns a modified draw info with new pitch.
+    pub fn pitch(mut self, value: f32) -> Self {
+        self.pitch = value;
+        self
+    }
+
+    /// Sets the heading of the instance.
+    ///
+    /// # Arguments
+    /// * `value` - a true heading in degrees.
+    ///
+    /// # Returns
+    /// Returns a modified draw info with new heading.
+    pub fn heading(mut self, value: f32) -> Self {
+        self.heading = value;
+        self
+    }
+
+    /// Sets the roll of the instance.
+    ///
+    /// # Arguments
+    /// * `value` - a roll in degrees.
+    ///
+    /// # Returns
+    /// Returns a modified draw info with new roll.
+    pub fn roll(mut self, value: f32) -> Self {
+        self.roll = value;
+        self
+    }
+}
+
+impl From<&DrawInfo> for xplm_sys::XPLMDrawInfo_t {
+    fn from(value: &DrawInfo) -> Self {
+        Self {
+            structSize: std::mem::size_of::<xplm_sys::XPLMDrawInfo_t>() as _,
+            x: value.position.x as f32,
+            y: value.position.y as f32,
+            z: value.position.z as f32,
+            pitch: value.pitch,
+            heading: value.heading,
+            roll: value.roll,
+        }
+    }
+}
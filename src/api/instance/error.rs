@@ -0,0 +1,26 @@
+use std::ffi;
+
+use crate::api::utilities::UtilitiesError;
+
+/// An error returned from instance API calls.
+#[derive(thiserror::Error, Debug)]
+pub enum InstanceError {
+    /// Invalid dataref name string passed to X-Plane.
+    #[error("invalid dataref name {0}")]
+    InvalidDataRefName(ffi::NulError),
+    /// Invalid instance reference returned from X-Plane.
+    #[error("invalid instance reference")]
+    InvalidInstance,
+    /// Dataref values count does not match the instance datarefs count.
+    #[error("expected {expected} dataref values, got {actual}")]
+    DataRefValuesMismatch { expected: usize, actual: usize },
+    /// Utilities error, e.g. instancing is not supported by the running X-Plane.
+    #[error("utilities error {0}")]
+    Utilities(UtilitiesError),
+}
+
+impl From<UtilitiesError> for InstanceError {
+    fn from(value: UtilitiesError) -> Self {
+        Self::Utilities(value)
+    }
+}
@@ -0,0 +1,52 @@
+use std::ops::Deref;
+
+use super::{destroy_instance, InstanceError};
+
+/// An object instance reference. The instance is destroyed when dropped.
+pub struct Instance {
+    instance: xplm_sys::XPLMInstanceRef,
+    data_refs_count: usize,
+}
+
+impl Instance {
+    /// Creates an instance wrapper from a raw reference.
+    ///
+    /// # Arguments
+    /// * `instance` - a raw instance reference.
+    /// * `data_refs_count` - a number of datarefs the instance was created with.
+    ///
+    /// # Returns
+    /// Returns [`Instance`] on success. Otherwise returns [`InstanceError`].
+    pub(super) fn new(
+        instance: xplm_sys::XPLMInstanceRef,
+        data_refs_count: usize,
+    ) -> Result<Self, InstanceError> {
+        if instance.is_null() {
+            Err(InstanceError::InvalidInstance)
+        } else {
+            Ok(Self {
+                instance,
+                data_refs_count,
+            })
+        }
+    }
+
+    /// Returns the number of datarefs the instance was created with.
+    pub fn data_refs_count(&self) -> usize {
+        self.data_refs_count
+    }
+}
+
+impl Deref for Instance {
+    type Target = xplm_sys::XPLMInstanceRef;
+
+    fn deref(&self) -> &Self::Target {
+        &self.instance
+    }
+}
+
+impl Drop for Instance {
+    fn drop(&mut self) {
+        destroy_instance(self);
+    }
+}
@@ -1,10 +1,14 @@
 pub mod error;
+pub mod handler;
+pub mod items;
 pub mod menu;
 pub mod state;
 
 use std::{ffi, ops::Deref};
 
 pub use self::error::MenusError;
+pub use self::handler::{DataMenu, MenuHandler};
+pub use self::items::{Menu, MenuItem};
 pub use self::menu::MenuId;
 pub use self::menu::MenuItemId;
 pub use self::state::MenuItemState;
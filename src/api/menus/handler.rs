@@ -0,0 +1,167 @@
+use std::ffi;
+use std::ops::{Deref, DerefMut};
+
+use super::{destroy_menu, Menu, MenuId, MenuItem, MenuItemId, MenusError, Result};
+
+/// Menu click handler which receives the user data of the clicked item.
+pub trait MenuHandler<T>: 'static {
+    /// Called when the user selects a menu item.
+    ///
+    /// # Arguments
+    /// * `data` - the user data the item was appended with.
+    fn handle_click(&mut self, data: &T);
+}
+
+impl<T, F: FnMut(&T) + 'static> MenuHandler<T> for F {
+    fn handle_click(&mut self, data: &T) {
+        self(data)
+    }
+}
+
+/// A link between X-Plane menu callbacks and a [`MenuHandler`].
+pub struct MenuLink<T> {
+    handler: Box<dyn MenuHandler<T>>,
+    data: Vec<Option<T>>,
+}
+
+unsafe extern "C" fn menu_handler<T: 'static>(
+    menu_ref: *mut ::std::os::raw::c_void,
+    item_ref: *mut ::std::os::raw::c_void,
+) {
+    #[cfg(feature = "metrics")]
+    let _scope = crate::metrics::Scope::enter("menu");
+    if menu_ref.is_null() {
+        return;
+    }
+
+    let link = menu_ref as *mut MenuLink<T>;
+    let index = item_ref as usize;
+    if let Some(Some(data)) = (*link).data.get(index) {
+        (*link).handler.handle_click(data);
+    }
+}
+
+/// A menu which items carry typed user data passed to a single [`MenuHandler`].
+/// Useful for dynamic menus, e.g. a list of discovered liveries.
+///
+/// The menu is destroyed when dropped.
+pub struct DataMenu<T: 'static> {
+    menu: Menu,
+    link: Box<MenuLink<T>>,
+}
+
+impl<T: 'static> DataMenu<T> {
+    /// Creates a top level menu.
+    ///
+    /// # Arguments
+    /// * `name` - a menu name.
+    /// * `handler` - a menu click handler. See [`MenuHandler`] for more details.
+    ///
+    /// # Returns
+    /// Returns [`DataMenu`] on success. Otherwise returns [`MenusError`].
+    pub fn new<N: Into<String>, H: MenuHandler<T>>(name: N, handler: H) -> Result<Self> {
+        let name_c = ffi::CString::new(name.into()).map_err(MenusError::InvalidMenuName)?;
+        Self::create(Some(name_c), std::ptr::null_mut(), 0, handler)
+    }
+
+    /// Creates a sub-menu attached to a menu item.
+    ///
+    /// # Arguments
+    /// * `parent_menu` - a parent menu.
+    /// * `parent_item` - a parent menu item.
+    /// * `handler` - a menu click handler. See [`MenuHandler`] for more details.
+    ///
+    /// # Returns
+    /// Returns [`DataMenu`] on success. Otherwise returns [`MenusError`].
+    pub fn new_sub_menu<H: MenuHandler<T>>(
+        parent_menu: &MenuId,
+        parent_item: &MenuItemId,
+        handler: H,
+    ) -> Result<Self> {
+        Self::create(None, **parent_menu, **parent_item, handler)
+    }
+
+    fn create<H: MenuHandler<T>>(
+        name: Option<ffi::CString>,
+        parent_menu: xplm_sys::XPLMMenuID,
+        parent_item: ::std::os::raw::c_int,
+        handler: H,
+    ) -> Result<Self> {
+        let mut link = Box::new(MenuLink {
+            handler: Box::new(handler),
+            data: Vec::new(),
+        });
+        let link_ptr: *mut MenuLink<T> = link.deref_mut();
+        let id = unsafe {
+            xplm_sys::XPLMCreateMenu(
+                name.as_ref().map_or(std::ptr::null(), |name| name.as_ptr()),
+                parent_menu,
+                parent_item,
+                Some(menu_handler::<T>),
+                link_ptr as *mut _,
+            )
+        };
+
+        Ok(Self {
+            menu: Menu::new(MenuId::try_from(id)?),
+            link,
+        })
+    }
+
+    /// Returns the tracked menu, e.g. to enable or check items.
+    pub fn menu(&self) -> &Menu {
+        &self.menu
+    }
+
+    /// Appends a menu item carrying user data.
+    ///
+    /// # Arguments
+    /// * `text` - a menu item text.
+    /// * `data` - the user data passed to the handler when the item is clicked.
+    ///
+    /// # Returns
+    /// Returns appended [`MenuItem`] on success. Otherwise returns [`MenusError`].
+    pub fn append_item_with_data<N: Into<String>>(&mut self, text: N, data: T) -> Result<MenuItem> {
+        let text_c = ffi::CString::new(text.into()).map_err(MenusError::InvalidMenuName)?;
+        let index = self.link.data.len();
+        let id = unsafe {
+            xplm_sys::XPLMAppendMenuItem(
+                *self.menu.id().deref(),
+                text_c.as_ptr(),
+                index as *mut _,
+                0,
+            )
+        };
+        let id = MenuItemId::try_from(id)?;
+        self.link.data.push(Some(data));
+        Ok(self.menu.track(id, false))
+    }
+
+    /// Appends a separator.
+    pub fn append_separator(&mut self) {
+        self.menu.append_separator();
+    }
+
+    /// Returns the user data of an item.
+    ///
+    /// # Arguments
+    /// * `index` - an item index, separators are not counted.
+    ///
+    /// # Returns
+    /// Returns the item data or [`None`] if the item does not exist.
+    pub fn data(&self, index: usize) -> Option<&T> {
+        self.link.data.get(index).and_then(Option::as_ref)
+    }
+
+    /// Removes all items and their user data.
+    pub fn clear(&mut self) {
+        self.menu.clear();
+        self.link.data.clear();
+    }
+}
+
+impl<T: 'static> Drop for DataMenu<T> {
+    fn drop(&mut self) {
+        destroy_menu(self.menu.id());
+    }
+}
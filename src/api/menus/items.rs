@@ -0,0 +1,250 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use super::{
+    append_menu_item, append_menu_item_with_command, append_menu_separator, check_menu_item,
+    check_menu_item_state, clear_all_menu_items, disable_menu_item, enable_menu_item,
+    remove_menu_item, set_menu_item_name, uncheck_menu_item, MenuId, MenuItemId, MenuItemState,
+    MenusError, Result,
+};
+use crate::api::utilities::Command;
+
+/// A menu item which remembers its parent menu.
+///
+/// The item index is shared with the [`Menu`] which created it, so the item follows
+/// the index changes made by [`Menu::remove_item`]. Once the item itself is removed,
+/// its methods do nothing or return [`MenusError::InvalidMenuItemId`].
+#[derive(Debug, Clone)]
+pub struct MenuItem {
+    menu: MenuId,
+    id: Rc<Cell<Option<MenuItemId>>>,
+    is_separator: bool,
+}
+
+impl MenuItem {
+    /// Returns the parent menu identifier.
+    pub fn menu(&self) -> &MenuId {
+        &self.menu
+    }
+
+    /// Returns the current menu item identifier.
+    ///
+    /// # Returns
+    /// Returns [`MenuItemId`] or [`None`] if the item has been removed.
+    pub fn id(&self) -> Option<MenuItemId> {
+        self.id.get()
+    }
+
+    /// Checks wether the item is a separator.
+    pub fn is_separator(&self) -> bool {
+        self.is_separator
+    }
+
+    /// Checks or unchecks the menu item.
+    ///
+    /// # Arguments
+    /// * `checked` - `true` to check the item.
+    pub fn set_checked(&self, checked: bool) {
+        let Some(id) = self.id() else {
+            return;
+        };
+
+        if checked {
+            check_menu_item(&self.menu, &id);
+        } else {
+            uncheck_menu_item(&self.menu, &id);
+        }
+    }
+
+    /// Returns the menu item check state.
+    ///
+    /// # Returns
+    /// Returns [`MenuItemState`] on success. Otherwise returns [`super::MenusError`].
+    pub fn state(&self) -> Result<MenuItemState> {
+        check_menu_item_state(&self.menu, &self.current_id()?)
+    }
+
+    /// Enables or disables the menu item.
+    ///
+    /// # Arguments
+    /// * `enabled` - `true` to enable the item.
+    pub fn set_enabled(&self, enabled: bool) {
+        let Some(id) = self.id() else {
+            return;
+        };
+
+        if enabled {
+            enable_menu_item(&self.menu, &id);
+        } else {
+            disable_menu_item(&self.menu, &id);
+        }
+    }
+
+    /// Changes the menu item text.
+    ///
+    /// # Arguments
+    /// * `text` - a new menu item text.
+    ///
+    /// # Returns
+    /// Returns empty result on success. Otherwise returns [`super::MenusError`].
+    pub fn set_name<T: Into<String>>(&self, text: T) -> Result<()> {
+        set_menu_item_name(&self.menu, &self.current_id()?, text)
+    }
+
+    fn current_id(&self) -> Result<MenuItemId> {
+        self.id().ok_or(MenusError::InvalidMenuItemId)
+    }
+}
+
+/// A menu which keeps track of its items, X-Plane doesn't report the number of menu items.
+/// Items must be added and removed through the menu to keep track of them.
+pub struct Menu {
+    id: MenuId,
+    items: Vec<MenuItem>,
+}
+
+impl Menu {
+    /// Wraps an existing menu, e.g. one returned from [`super::create_menu`].
+    ///
+    /// # Arguments
+    /// * `id` - a menu identifier. The menu is expected to be empty.
+    ///
+    /// # Returns
+    /// Returns a new [`Menu`] instance.
+    pub fn new(id: MenuId) -> Self {
+        Self {
+            id,
+            items: Vec::new(),
+        }
+    }
+
+    /// Returns the menu identifier.
+    pub fn id(&self) -> &MenuId {
+        &self.id
+    }
+
+    /// Appends a menu item.
+    ///
+    /// # Arguments
+    /// * `text` - a menu item text.
+    ///
+    /// # Returns
+    /// Returns appended [`MenuItem`] on success. Otherwise returns [`super::MenusError`].
+    pub fn append_item<T: Into<String>>(&mut self, text: T) -> Result<MenuItem> {
+        let id = append_menu_item(&self.id, text)?;
+        Ok(self.track(id, false))
+    }
+
+    /// Appends a menu item which executes a command.
+    ///
+    /// # Arguments
+    /// * `text` - a menu item text.
+    /// * `command` - a command to execute.
+    ///
+    /// # Returns
+    /// Returns appended [`MenuItem`] on success. Otherwise returns [`super::MenusError`].
+    pub fn append_item_with_command<T: Into<String>>(
+        &mut self,
+        text: T,
+        command: &Command,
+    ) -> Result<MenuItem> {
+        let id = append_menu_item_with_command(&self.id, text, command)?;
+        Ok(self.track(id, false))
+    }
+
+    /// Appends a separator. Separators occupy an item index.
+    pub fn append_separator(&mut self) {
+        append_menu_separator(&self.id);
+        let index = self.items.len() as ::std::os::raw::c_int;
+        if let Ok(id) = MenuItemId::try_from(index) {
+            self.track(id, true);
+        }
+    }
+
+    pub(super) fn track(&mut self, id: MenuItemId, is_separator: bool) -> MenuItem {
+        let item = MenuItem {
+            menu: self.id,
+            id: Rc::new(Cell::new(Some(id))),
+            is_separator,
+        };
+        self.items.push(item.clone());
+        item
+    }
+
+    /// Returns the number of items including separators.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Checks wether the menu has no items.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Returns a menu item by index.
+    ///
+    /// # Arguments
+    /// * `index` - a menu item index.
+    ///
+    /// # Returns
+    /// Returns [`MenuItem`] or [`None`] if the index is out of range.
+    pub fn item(&self, index: usize) -> Option<&MenuItem> {
+        self.items.get(index)
+    }
+
+    /// Iterates over the menu items including separators.
+    pub fn items(&self) -> impl Iterator<Item = &MenuItem> {
+        self.items.iter()
+    }
+
+    /// Enables or disables all menu items except separators.
+    ///
+    /// # Arguments
+    /// * `enabled` - `true` to enable the items.
+    pub fn set_enabled(&self, enabled: bool) {
+        self.items
+            .iter()
+            .filter(|item| !item.is_separator)
+            .for_each(|item| item.set_enabled(enabled));
+    }
+
+    /// Checks or unchecks all menu items except separators.
+    ///
+    /// # Arguments
+    /// * `checked` - `true` to check the items.
+    pub fn set_checked(&self, checked: bool) {
+        self.items
+            .iter()
+            .filter(|item| !item.is_separator)
+            .for_each(|item| item.set_checked(checked));
+    }
+
+    /// Removes a menu item, the following items are shifted up.
+    /// Handles of the following items are updated and the handle of the removed item
+    /// is invalidated.
+    ///
+    /// # Arguments
+    /// * `index` - a menu item index.
+    pub fn remove_item(&mut self, index: usize) {
+        if index >= self.items.len() {
+            return;
+        }
+
+        let item = self.items.remove(index);
+        if let Some(id) = item.id.take() {
+            remove_menu_item(&self.id, &id);
+        }
+
+        for (index, item) in self.items.iter().enumerate().skip(index) {
+            item.id
+                .set(MenuItemId::try_from(index as ::std::os::raw::c_int).ok());
+        }
+    }
+
+    /// Removes all menu items and invalidates their handles.
+    pub fn clear(&mut self) {
+        clear_all_menu_items(&self.id);
+        self.items.iter().for_each(|item| item.id.set(None));
+        self.items.clear();
+    }
+}
@@ -3,6 +3,7 @@ use std::ops::Deref;
 use super::MenusError;
 
 /// Menu idenitifier.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct MenuId(xplm_sys::XPLMMenuID);
 
 impl Deref for MenuId {
@@ -26,6 +27,7 @@ impl TryFrom<xplm_sys::XPLMMenuID> for MenuId {
 }
 
 /// Menu item identifier.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct MenuItemId(::std::os::raw::c_int);
 
 impl Deref for MenuItemId {
@@ -1,6 +1,7 @@
 use super::MenusError;
 
 /// Menu item state.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum MenuItemState {
     /// The menu has a mark next to it that is checked (lit).
     Checked,
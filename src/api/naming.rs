@@ -0,0 +1,74 @@
+//! Validation of data ref and command names against the SDK naming conventions.
+//!
+//! Names are slash-separated paths with at least a namespace prefix and a leaf,
+//! e.g. `my_company/my_plugin/landing_lights`. Only printable ASCII characters
+//! without spaces are allowed.
+
+/// Maximum length of a data ref or command name in bytes.
+pub const MAX_NAME_LENGTH: usize = 255;
+
+/// An error returned when a name does not follow the SDK naming conventions.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum NameError {
+    /// The name is empty.
+    #[error("name is empty")]
+    Empty,
+    /// The name is longer than [`MAX_NAME_LENGTH`].
+    #[error("name is {0} bytes long, maximum is {MAX_NAME_LENGTH}")]
+    TooLong(usize),
+    /// The name contains a space, a control or a non-ASCII character.
+    #[error("name contains invalid character {0:?}")]
+    InvalidCharacter(char),
+    /// The name has no namespace prefix, e.g. `my_plugin/`.
+    #[error("name has no namespace prefix")]
+    MissingPrefix,
+    /// The name starts or ends with a slash or contains `//`.
+    #[error("name contains an empty path segment")]
+    EmptySegment,
+}
+
+fn validate_name(name: &str) -> Result<(), NameError> {
+    if name.is_empty() {
+        return Err(NameError::Empty);
+    }
+
+    if name.len() > MAX_NAME_LENGTH {
+        return Err(NameError::TooLong(name.len()));
+    }
+
+    if let Some(c) = name.chars().find(|c| !c.is_ascii_graphic()) {
+        return Err(NameError::InvalidCharacter(c));
+    }
+
+    if !name.contains('/') {
+        return Err(NameError::MissingPrefix);
+    }
+
+    if name.split('/').any(str::is_empty) {
+        return Err(NameError::EmptySegment);
+    }
+
+    Ok(())
+}
+
+/// Checks that a data ref name follows the SDK naming conventions.
+///
+/// # Arguments
+/// * `name` - a data ref name.
+///
+/// # Returns
+/// Returns empty result if the name is valid. Otherwise returns [`NameError`].
+pub fn validate_dataref_name(name: &str) -> Result<(), NameError> {
+    validate_name(name)
+}
+
+/// Checks that a command name follows the SDK naming conventions.
+///
+/// # Arguments
+/// * `name` - a command name.
+///
+/// # Returns
+/// Returns empty result if the name is valid. Otherwise returns [`NameError`].
+pub fn validate_command_name(name: &str) -> Result<(), NameError> {
+    validate_name(name)
+}
@@ -0,0 +1,256 @@
+pub mod airport;
+pub mod error;
+pub mod nav_aid;
+
+use std::ffi;
+use std::ops::Deref;
+
+pub use self::airport::{distance, Airport, Approach};
+pub use self::error::NavigationError;
+pub use self::nav_aid::{NavAidInfo, NavRef, NavType};
+
+use super::graphics::WorldPosition;
+
+pub type Result<T> = std::result::Result<T, NavigationError>;
+
+/// The radius around an airport to look for its ILS and localizer approaches in meters.
+const APPROACH_SEARCH_RADIUS: f64 = 10_000.0;
+
+/// Returns the first navaid of a type in the database. Navaids of a type are stored
+/// sequentially, so use [`get_next_nav_aid`] to iterate over them.
+///
+/// # Arguments
+/// * `nav_type` - a navaid type. See [`NavType`] for more details.
+///
+/// # Returns
+/// Returns [`NavRef`] on success. Otherwise returns [`NavigationError::NotFound`].
+pub fn get_first_nav_aid_of_type(nav_type: NavType) -> Result<NavRef> {
+    NavRef::try_from(unsafe { xplm_sys::XPLMFindFirstNavAidOfType(nav_type.into()) })
+}
+
+/// Returns the last navaid of a type in the database.
+///
+/// # Arguments
+/// * `nav_type` - a navaid type. See [`NavType`] for more details.
+///
+/// # Returns
+/// Returns [`NavRef`] on success. Otherwise returns [`NavigationError::NotFound`].
+pub fn get_last_nav_aid_of_type(nav_type: NavType) -> Result<NavRef> {
+    NavRef::try_from(unsafe { xplm_sys::XPLMFindLastNavAidOfType(nav_type.into()) })
+}
+
+/// Returns the navaid which follows the given one in the database.
+///
+/// # Arguments
+/// * `nav_ref` - a navaid reference.
+///
+/// # Returns
+/// Returns [`NavRef`] on success. Otherwise returns [`NavigationError::NotFound`].
+pub fn get_next_nav_aid(nav_ref: &NavRef) -> Result<NavRef> {
+    NavRef::try_from(unsafe { xplm_sys::XPLMGetNextNavAid(*nav_ref.deref()) })
+}
+
+/// Iterates over all navaids of a type.
+///
+/// # Arguments
+/// * `nav_type` - a navaid type. See [`NavType`] for more details.
+///
+/// # Returns
+/// Returns an iterator over navaid references.
+pub fn nav_aids_of_type(nav_type: NavType) -> impl Iterator<Item = NavRef> {
+    let first = get_first_nav_aid_of_type(nav_type).ok();
+    let last = get_last_nav_aid_of_type(nav_type).ok();
+    std::iter::successors(first, move |current| {
+        if Some(*current) == last {
+            None
+        } else {
+            get_next_nav_aid(current).ok()
+        }
+    })
+}
+
+/// Searches the navigation database. The search is case-insensitive and matches fragments,
+/// e.g. `"BOS"` matches `"KBOS"`. If several navaids match and a position is given,
+/// the nearest one is returned.
+///
+/// # Arguments
+/// * `name` - a name fragment to match or [`None`].
+/// * `id` - an identifier fragment to match or [`None`].
+/// * `position` - a position to find the nearest navaid to or [`None`].
+/// * `frequency` - a frequency to match or [`None`]. See [`NavAidInfo::frequency`] for the units.
+/// * `nav_type` - a navaid type. See [`NavType`] for more details.
+///
+/// # Returns
+/// Returns [`NavRef`] on success. Otherwise returns [`NavigationError`].
+pub fn find_nav_aid(
+    name: Option<&str>,
+    id: Option<&str>,
+    position: Option<&WorldPosition>,
+    frequency: Option<i32>,
+    nav_type: NavType,
+) -> Result<NavRef> {
+    let name_c = name
+        .map(ffi::CString::new)
+        .transpose()
+        .map_err(NavigationError::InvalidFragment)?;
+    let id_c = id
+        .map(ffi::CString::new)
+        .transpose()
+        .map_err(NavigationError::InvalidFragment)?;
+    let mut latitude = position.map(|position| position.latitude as f32);
+    let mut longitude = position.map(|position| position.longitude as f32);
+    let mut frequency = frequency;
+    let nav_ref = unsafe {
+        xplm_sys::XPLMFindNavAid(
+            name_c
+                .as_ref()
+                .map_or(std::ptr::null(), |name| name.as_ptr()),
+            id_c.as_ref().map_or(std::ptr::null(), |id| id.as_ptr()),
+            latitude
+                .as_mut()
+                .map_or(std::ptr::null_mut(), |value| value as *mut _),
+            longitude
+                .as_mut()
+                .map_or(std::ptr::null_mut(), |value| value as *mut _),
+            frequency
+                .as_mut()
+                .map_or(std::ptr::null_mut(), |value| value as *mut _),
+            nav_type.into(),
+        )
+    };
+    NavRef::try_from(nav_ref)
+}
+
+/// Returns information about a navaid.
+///
+/// # Arguments
+/// * `nav_ref` - a navaid reference.
+///
+/// # Returns
+/// Returns [`NavAidInfo`] on success. Otherwise returns [`NavigationError`].
+pub fn get_nav_aid_info(nav_ref: &NavRef) -> Result<NavAidInfo> {
+    let mut nav_type = 0;
+    let mut latitude = 0.0;
+    let mut longitude = 0.0;
+    let mut height = 0.0;
+    let mut frequency = 0;
+    let mut heading = 0.0;
+    let mut id = [0; 32];
+    let mut name = [0; 256];
+    unsafe {
+        xplm_sys::XPLMGetNavAidInfo(
+            *nav_ref.deref(),
+            &mut nav_type,
+            &mut latitude,
+            &mut longitude,
+            &mut height,
+            &mut frequency,
+            &mut heading,
+            id.as_mut_ptr(),
+            name.as_mut_ptr(),
+            std::ptr::null_mut(),
+        )
+    };
+
+    Ok(NavAidInfo {
+        nav_ref: *nav_ref,
+        nav_type: NavType::try_from(nav_type)?,
+        position: WorldPosition::default()
+            .latitude(latitude as f64)
+            .longitude(longitude as f64)
+            .altitude(height as f64),
+        frequency,
+        heading,
+        id: string_from_c_buf(&id),
+        name: string_from_c_buf(&name),
+    })
+}
+
+/// Looks up an airport by its ICAO code. The code must match exactly, ignoring case.
+///
+/// X-Plane searches by an ID fragment and returns a single match, e.g. `"KBOS"` for `"KBO"`.
+/// When that match is not exact, the airport database is scanned for the exact code.
+///
+/// # Arguments
+/// * `icao` - an airport ICAO code, e.g. `"KBOS"`.
+///
+/// # Returns
+/// Returns [`Airport`] or [`None`] if the airport is not found.
+pub fn find_airport<T: AsRef<str>>(icao: T) -> Option<Airport> {
+    let icao = icao.as_ref();
+    let nav_ref = find_nav_aid(None, Some(icao), None, None, NavType::Airport).ok()?;
+    let info = get_nav_aid_info(&nav_ref).ok()?;
+    if info.id.eq_ignore_ascii_case(icao) {
+        return Some(airport_from_info(info));
+    }
+
+    nav_aids_of_type(NavType::Airport)
+        .filter_map(|nav_ref| get_nav_aid_info(&nav_ref).ok())
+        .find(|info| info.id.eq_ignore_ascii_case(icao))
+        .map(airport_from_info)
+}
+
+/// Finds the airports nearest to a position, sorted by distance.
+/// Scans the whole airport database, so avoid calling it every frame.
+///
+/// # Arguments
+/// * `position` - a position to search around.
+/// * `count` - the maximum number of airports to return.
+///
+/// # Returns
+/// Returns nearest airports.
+pub fn nearest_airports(position: &WorldPosition, count: usize) -> Vec<Airport> {
+    if count == 0 {
+        return Vec::new();
+    }
+
+    let mut nearest: Vec<(f64, NavAidInfo)> = Vec::with_capacity(count + 1);
+    for nav_ref in nav_aids_of_type(NavType::Airport) {
+        let Ok(info) = get_nav_aid_info(&nav_ref) else {
+            continue;
+        };
+
+        let distance = distance(&info.position, position);
+        if nearest.len() == count && distance >= nearest[count - 1].0 {
+            continue;
+        }
+
+        let index = nearest.partition_point(|(other, _)| *other <= distance);
+        nearest.insert(index, (distance, info));
+        nearest.truncate(count);
+    }
+
+    nearest
+        .into_iter()
+        .map(|(_, info)| airport_from_info(info))
+        .collect()
+}
+
+fn airport_from_info(info: NavAidInfo) -> Airport {
+    Airport {
+        approaches: find_approaches(&info.position),
+        nav_ref: info.nav_ref,
+        icao: info.id,
+        name: info.name,
+        position: info.position,
+    }
+}
+
+fn find_approaches(position: &WorldPosition) -> Vec<Approach> {
+    [NavType::Ils, NavType::Localizer]
+        .into_iter()
+        .flat_map(nav_aids_of_type)
+        .filter_map(|nav_ref| get_nav_aid_info(&nav_ref).ok())
+        .filter(|info| distance(&info.position, position) <= APPROACH_SEARCH_RADIUS)
+        .map(Approach::from)
+        .collect()
+}
+
+fn string_from_c_buf(buf: &[::std::os::raw::c_char]) -> String {
+    let bytes: Vec<u8> = buf
+        .iter()
+        .take_while(|byte| **byte != 0)
+        .map(|byte| *byte as u8)
+        .collect();
+    String::from_utf8_lossy(&bytes).into_owned()
+}
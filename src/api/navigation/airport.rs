@@ -0,0 +1,83 @@
+use crate::api::graphics::WorldPosition;
+
+use super::{NavAidInfo, NavRef, NavType};
+
+/// The mean Earth radius in meters.
+const EARTH_RADIUS: f64 = 6_371_008.8;
+
+/// An airport from the X-Plane navigation database.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Airport {
+    /// A navaid reference of the airport.
+    pub nav_ref: NavRef,
+    /// An airport ICAO code or identifier.
+    pub icao: String,
+    /// An airport name.
+    pub name: String,
+    /// An airport reference point. The altitude is the field elevation in meters MSL.
+    pub position: WorldPosition,
+    /// Instrument approaches serving the airport runways.
+    pub approaches: Vec<Approach>,
+}
+
+impl Airport {
+    /// Returns the great circle distance to a position.
+    ///
+    /// # Arguments
+    /// * `position` - a position to measure the distance to.
+    ///
+    /// # Returns
+    /// Returns the distance in meters.
+    pub fn distance_to(&self, position: &WorldPosition) -> f64 {
+        distance(&self.position, position)
+    }
+}
+
+/// An ILS or localizer approach to a runway.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Approach {
+    /// An approach type, either [`NavType::Ils`] or [`NavType::Localizer`].
+    pub nav_type: NavType,
+    /// A localizer identifier.
+    pub id: String,
+    /// A localizer name.
+    pub name: String,
+    /// A localizer frequency in MHz.
+    pub frequency: f32,
+    /// A localizer course in degrees true, approximately the runway heading.
+    pub course: f32,
+    /// A localizer antenna position.
+    pub position: WorldPosition,
+}
+
+impl From<NavAidInfo> for Approach {
+    fn from(value: NavAidInfo) -> Self {
+        Self {
+            nav_type: value.nav_type,
+            id: value.id,
+            name: value.name,
+            frequency: value.frequency as f32 / 100.0,
+            course: value.heading,
+            position: value.position,
+        }
+    }
+}
+
+/// Calculates the great circle distance between two positions using the haversine formula.
+/// The altitude is ignored.
+///
+/// # Arguments
+/// * `from` - a start position.
+/// * `to` - an end position.
+///
+/// # Returns
+/// Returns the distance in meters.
+pub fn distance(from: &WorldPosition, to: &WorldPosition) -> f64 {
+    let lat_from = from.latitude.to_radians();
+    let lat_to = to.latitude.to_radians();
+    let d_lat = lat_to - lat_from;
+    let d_lon = (to.longitude - from.longitude).to_radians();
+    let a =
+        (d_lat / 2.0).sin().powi(2) + lat_from.cos() * lat_to.cos() * (d_lon / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS * a.sqrt().asin()
+}
@@ -0,0 +1,15 @@
+use std::ffi;
+
+/// An error returned from navigation API calls.
+#[derive(thiserror::Error, Debug)]
+pub enum NavigationError {
+    /// Navaid is not found in the database.
+    #[error("navaid not found")]
+    NotFound,
+    /// Invalid search fragment passed to X-Plane.
+    #[error("invalid search fragment {0}")]
+    InvalidFragment(ffi::NulError),
+    /// Unknown navaid type returned from X-Plane.
+    #[error("unknown navaid type {0}")]
+    UnknownNavType(xplm_sys::XPLMNavType),
+}
@@ -0,0 +1,124 @@
+use std::ops::Deref;
+
+use crate::api::graphics::WorldPosition;
+
+use super::NavigationError;
+
+/// An opaque reference to a navaid in the X-Plane navigation database.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct NavRef(xplm_sys::XPLMNavRef);
+
+impl Deref for NavRef {
+    type Target = xplm_sys::XPLMNavRef;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl TryFrom<xplm_sys::XPLMNavRef> for NavRef {
+    type Error = NavigationError;
+
+    fn try_from(value: xplm_sys::XPLMNavRef) -> std::result::Result<Self, Self::Error> {
+        if value == xplm_sys::XPLM_NAV_NOT_FOUND as xplm_sys::XPLMNavRef {
+            Err(Self::Error::NotFound)
+        } else {
+            Ok(NavRef(value))
+        }
+    }
+}
+
+/// A navaid type.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum NavType {
+    /// An airport.
+    Airport,
+    /// A non-directional beacon.
+    Ndb,
+    /// A VHF omnidirectional range.
+    Vor,
+    /// An instrument landing system.
+    Ils,
+    /// A stand-alone localizer.
+    Localizer,
+    /// An ILS glide slope.
+    GlideSlope,
+    /// An outer marker.
+    OuterMarker,
+    /// A middle marker.
+    MiddleMarker,
+    /// An inner marker.
+    InnerMarker,
+    /// A named intersection.
+    Fix,
+    /// A distance measuring equipment.
+    Dme,
+    /// A latitude/longitude fix, only found in flight plans.
+    LatLon,
+    /// A tactical air navigation station.
+    Tacan,
+}
+
+impl From<NavType> for xplm_sys::XPLMNavType {
+    fn from(value: NavType) -> Self {
+        let nav_type = match value {
+            NavType::Airport => xplm_sys::xplm_Nav_Airport,
+            NavType::Ndb => xplm_sys::xplm_Nav_NDB,
+            NavType::Vor => xplm_sys::xplm_Nav_VOR,
+            NavType::Ils => xplm_sys::xplm_Nav_ILS,
+            NavType::Localizer => xplm_sys::xplm_Nav_Localizer,
+            NavType::GlideSlope => xplm_sys::xplm_Nav_GlideSlope,
+            NavType::OuterMarker => xplm_sys::xplm_Nav_OuterMarker,
+            NavType::MiddleMarker => xplm_sys::xplm_Nav_MiddleMarker,
+            NavType::InnerMarker => xplm_sys::xplm_Nav_InnerMarker,
+            NavType::Fix => xplm_sys::xplm_Nav_Fix,
+            NavType::Dme => xplm_sys::xplm_Nav_DME,
+            NavType::LatLon => xplm_sys::xplm_Nav_LatLon,
+            NavType::Tacan => xplm_sys::xplm_Nav_TACAN,
+        };
+        nav_type as _
+    }
+}
+
+impl TryFrom<xplm_sys::XPLMNavType> for NavType {
+    type Error = NavigationError;
+
+    fn try_from(value: xplm_sys::XPLMNavType) -> std::result::Result<Self, Self::Error> {
+        match value as _ {
+            xplm_sys::xplm_Nav_Airport => Ok(Self::Airport),
+            xplm_sys::xplm_Nav_NDB => Ok(Self::Ndb),
+            xplm_sys::xplm_Nav_VOR => Ok(Self::Vor),
+            xplm_sys::xplm_Nav_ILS => Ok(Self::Ils),
+            xplm_sys::xplm_Nav_Localizer => Ok(Self::Localizer),
+            xplm_sys::xplm_Nav_GlideSlope => Ok(Self::GlideSlope),
+            xplm_sys::xplm_Nav_OuterMarker => Ok(Self::OuterMarker),
+            xplm_sys::xplm_Nav_MiddleMarker => Ok(Self::MiddleMarker),
+            xplm_sys::xplm_Nav_InnerMarker => Ok(Self::InnerMarker),
+            xplm_sys::xplm_Nav_Fix => Ok(Self::Fix),
+            xplm_sys::xplm_Nav_DME => Ok(Self::Dme),
+            xplm_sys::xplm_Nav_LatLon => Ok(Self::LatLon),
+            xplm_sys::xplm_Nav_TACAN => Ok(Self::Tacan),
+            _ => Err(Self::Error::UnknownNavType(value)),
+        }
+    }
+}
+
+/// Information about a navaid.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NavAidInfo {
+    /// A navaid reference.
+    pub nav_ref: NavRef,
+    /// A navaid type.
+    pub nav_type: NavType,
+    /// A navaid position. The altitude is in meters MSL.
+    pub position: WorldPosition,
+    /// A frequency. NDB frequencies are in kHz, other frequencies are in 10 kHz units,
+    /// e.g. 110.30 MHz is reported as `11030`. Zero for navaids without a frequency.
+    pub frequency: i32,
+    /// A heading in degrees true, e.g. a localizer course. Zero if not applicable.
+    pub heading: f32,
+    /// A navaid identifier, e.g. an airport ICAO code.
+    pub id: String,
+    /// A navaid name.
+    pub name: String,
+}
@@ -3,6 +3,10 @@ pub mod feature;
 pub mod id;
 pub mod info;
 pub mod message;
+#[cfg(feature = "reload-state")]
+pub mod reload_state;
+#[cfg(feature = "typed-messages")]
+pub mod typed;
 
 use std::{ffi, ops::Deref};
 
@@ -10,7 +14,11 @@ pub use self::error::PluginError;
 pub use self::feature::Feature;
 pub use self::id::PluginId;
 pub use self::info::PluginInfo;
-pub use self::message::AsMessageParam;
+pub use self::message::{AsMessageParam, Message};
+#[cfg(feature = "reload-state")]
+pub use self::reload_state::{stash_state, take_state};
+#[cfg(feature = "typed-messages")]
+pub use self::typed::{send_typed_message, send_typed_message_to_all, TypedEnvelope};
 
 pub type Result<T> = std::result::Result<T, PluginError>;
 
@@ -170,7 +178,7 @@ pub fn reload_plugins() {
 /// * `id` - the plugin identifier.
 /// * `message` - the unique message identifier.
 /// * `param` - the message param.
-pub fn send_message_to_plugin<P: AsMessageParam>(id: &PluginId, message: i32, param: P) {
+pub fn send_message_to_plugin<P: AsMessageParam>(id: &PluginId, message: i32, mut param: P) {
     unsafe { xplm_sys::XPLMSendMessageToPlugin(*id.deref(), message, param.as_message_param()) };
 }
 
@@ -180,7 +188,7 @@ pub fn send_message_to_plugin<P: AsMessageParam>(id: &PluginId, message: i32, pa
 /// # Arguments
 /// * `message` - the unique message identifier.
 /// * `param` - the message param.
-pub fn send_message_to_all_plugins<P: AsMessageParam>(message: i32, param: P) {
+pub fn send_message_to_all_plugins<P: AsMessageParam>(message: i32, mut param: P) {
     unsafe {
         xplm_sys::XPLMSendMessageToPlugin(
             xplm_sys::XPLM_NO_PLUGIN_ID,
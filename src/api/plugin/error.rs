@@ -1,4 +1,6 @@
 use std::ffi;
+#[cfg(feature = "reload-state")]
+use std::io;
 
 /// An error returned from plugin API calls.
 #[derive(thiserror::Error, Debug)]
@@ -24,4 +26,19 @@ pub enum PluginError {
     /// Invalid plugin info description passed from X-Plane.
     #[error("invalid plugin info description string {0}")]
     InvalidInfoDescription(ffi::IntoStringError),
+    /// Invalid API name passed to another plugin.
+    #[error("invalid api name string {0}")]
+    InvalidApiName(ffi::NulError),
+    /// Typed message can't be serialized or deserialized.
+    #[cfg(feature = "typed-messages")]
+    #[error("invalid typed message {0}")]
+    InvalidTypedMessage(serde_json::Error),
+    /// Reload state can't be serialized or deserialized.
+    #[cfg(feature = "reload-state")]
+    #[error("invalid reload state {0}")]
+    InvalidReloadState(serde_json::Error),
+    /// Reload state file can't be accessed.
+    #[cfg(feature = "reload-state")]
+    #[error("reload state io error {0}")]
+    ReloadStateIo(io::Error),
 }
@@ -1,14 +1,86 @@
 /// A trait which declares convertion to message parameter.
 pub trait AsMessageParam {
     /// Return the memory pointer to the message parameter.
-    fn as_message_param(&self) -> *mut ::std::os::raw::c_void;
+    /// The receiver may write through the pointer, so it is taken from a mutable borrow.
+    fn as_message_param(&mut self) -> *mut ::std::os::raw::c_void;
 }
 
 /// A message parameter that gets ignored when sending messages.
 pub struct NoMessageParam;
 
 impl AsMessageParam for NoMessageParam {
-    fn as_message_param(&self) -> *mut std::os::raw::c_void {
+    fn as_message_param(&mut self) -> *mut std::os::raw::c_void {
         std::ptr::null_mut()
     }
 }
+
+/// A message received by the plugin from X-Plane or another plugin.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Message {
+    /// The user's plane has crashed.
+    PlaneCrashed,
+    /// A new plane has been loaded. Contains the index of the loaded plane, 0 is the user's plane.
+    PlaneLoaded(::std::os::raw::c_int),
+    /// The user's plane has been positioned at a new airport.
+    AirportLoaded,
+    /// New scenery has been loaded.
+    SceneryLoaded,
+    /// The user has adjusted the number of X-Plane aircraft models.
+    AirplaneCountChanged,
+    /// A plane has been unloaded. Contains the index of the unloaded plane, 0 is the user's plane.
+    PlaneUnloaded(::std::os::raw::c_int),
+    /// X-Plane is going to write its preferences file.
+    WillWritePrefs,
+    /// A livery has been loaded. Contains the index of the plane, 0 is the user's plane.
+    LiveryLoaded(::std::os::raw::c_int),
+    /// X-Plane has entered virtual reality.
+    EnteredVr,
+    /// X-Plane is exiting virtual reality.
+    ExitingVr,
+    /// Another plugin wants to take over AI planes.
+    ReleasePlanes,
+    /// New datarefs have been registered. Contains the total number of datarefs.
+    DataRefsAdded(::std::os::raw::c_int),
+    /// A message which is not known to this library, e.g. a custom inter-plugin message.
+    Other {
+        /// The message identifier.
+        id: ::std::os::raw::c_int,
+        /// The raw message parameter.
+        param: *mut ::std::os::raw::c_void,
+    },
+}
+
+impl Message {
+    /// Converts a raw message received by `XPluginReceiveMessage` into a [`Message`].
+    ///
+    /// # Arguments
+    /// * `id` - the message identifier.
+    /// * `param` - the message parameter.
+    ///
+    /// # Returns
+    /// Returns a typed message.
+    pub fn new(id: ::std::os::raw::c_int, param: *mut ::std::os::raw::c_void) -> Self {
+        let index = param as isize as ::std::os::raw::c_int;
+        match id as u32 {
+            xplm_sys::XPLM_MSG_PLANE_CRASHED => Self::PlaneCrashed,
+            xplm_sys::XPLM_MSG_PLANE_LOADED => Self::PlaneLoaded(index),
+            xplm_sys::XPLM_MSG_AIRPORT_LOADED => Self::AirportLoaded,
+            xplm_sys::XPLM_MSG_SCENERY_LOADED => Self::SceneryLoaded,
+            xplm_sys::XPLM_MSG_AIRPLANE_COUNT_CHANGED => Self::AirplaneCountChanged,
+            xplm_sys::XPLM_MSG_PLANE_UNLOADED => Self::PlaneUnloaded(index),
+            xplm_sys::XPLM_MSG_WILL_WRITE_PREFS => Self::WillWritePrefs,
+            xplm_sys::XPLM_MSG_LIVERY_LOADED => Self::LiveryLoaded(index),
+            xplm_sys::XPLM_MSG_ENTERED_VR => Self::EnteredVr,
+            xplm_sys::XPLM_MSG_EXITING_VR => Self::ExitingVr,
+            xplm_sys::XPLM_MSG_RELEASE_PLANES => Self::ReleasePlanes,
+            xplm_sys::XPLM_MSG_DATAREFS_ADDED => Self::DataRefsAdded(index),
+            _ => Self::Other { id, param },
+        }
+    }
+}
+
+impl<T> AsMessageParam for &mut T {
+    fn as_message_param(&mut self) -> *mut std::os::raw::c_void {
+        std::ptr::addr_of_mut!(**self) as *mut _
+    }
+}
@@ -0,0 +1,103 @@
+//! Keeps plugin state across plugin reloads.
+//!
+//! A plugin stashes a serializable value before [`super::reload_plugins`] or in `stop`,
+//! and takes it back in the next `start`. The value is stored as JSON in the system
+//! temporary folder keyed by the plugin signature, so it survives the plugin being unloaded.
+
+use std::{fs, io, path};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::{get_my_id, get_plugin_info, PluginError, Result};
+
+/// The folder inside the system temporary folder the state files are stored in.
+const STATE_DIR: &str = "xplm-reload-state";
+
+/// Stashes the calling plugin state.
+///
+/// # Arguments
+/// * `value` - a value to stash. A previously stashed value is replaced.
+///
+/// # Returns
+/// Returns empty result on success. Otherwise returns [`PluginError`].
+pub fn stash_state<T: Serialize>(value: &T) -> Result<()> {
+    stash_state_for(&my_signature()?, value)
+}
+
+/// Takes the calling plugin state stashed before the reload. The stash is removed,
+/// so the state is restored only once.
+///
+/// # Returns
+/// Returns the stashed value or [`None`] if nothing was stashed.
+/// Otherwise returns [`PluginError`].
+pub fn take_state<T: DeserializeOwned>() -> Result<Option<T>> {
+    take_state_for(&my_signature()?)
+}
+
+/// Stashes a state under a plugin signature.
+///
+/// # Arguments
+/// * `signature` - a plugin signature.
+/// * `value` - a value to stash.
+///
+/// # Returns
+/// Returns empty result on success. Otherwise returns [`PluginError`].
+pub fn stash_state_for<T: Serialize>(signature: &str, value: &T) -> Result<()> {
+    let data = serde_json::to_vec(value).map_err(PluginError::InvalidReloadState)?;
+    let path = state_path(signature);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(PluginError::ReloadStateIo)?;
+    }
+
+    fs::write(path, data).map_err(PluginError::ReloadStateIo)
+}
+
+/// Takes a state stashed under a plugin signature.
+///
+/// # Arguments
+/// * `signature` - a plugin signature.
+///
+/// # Returns
+/// Returns the stashed value or [`None`] if nothing was stashed.
+/// Otherwise returns [`PluginError`].
+pub fn take_state_for<T: DeserializeOwned>(signature: &str) -> Result<Option<T>> {
+    let path = state_path(signature);
+    let data = match fs::read(&path) {
+        Ok(data) => data,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(PluginError::ReloadStateIo(err)),
+    };
+
+    fs::remove_file(&path).map_err(PluginError::ReloadStateIo)?;
+    serde_json::from_slice(&data)
+        .map(Some)
+        .map_err(PluginError::InvalidReloadState)
+}
+
+/// Removes a state stashed under a plugin signature, e.g. after an incompatible update.
+///
+/// # Arguments
+/// * `signature` - a plugin signature.
+pub fn discard_state_for(signature: &str) {
+    let _ = fs::remove_file(state_path(signature));
+}
+
+fn my_signature() -> Result<String> {
+    Ok(get_plugin_info(&get_my_id()?)?.signature)
+}
+
+fn state_path(signature: &str) -> path::PathBuf {
+    let file_name: String = signature
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    std::env::temp_dir()
+        .join(STATE_DIR)
+        .join(format!("{}.json", file_name))
+}
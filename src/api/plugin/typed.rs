@@ -0,0 +1,102 @@
+//! Structured messages exchanged between plugins built on this crate.
+//!
+//! A typed message is serialized into a buffer owned by the sender and passed as
+//! a pointer to [`TypedEnvelope`]. X-Plane delivers plugin messages synchronously,
+//! so the buffer stays valid while the receivers handle the message. Receivers must
+//! decode the message inside `receive_message` and must not keep the parameter pointer.
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::{Message, PluginError, PluginId, Result};
+
+/// A magic number identifying [`TypedEnvelope`] parameters.
+pub const TYPED_MESSAGE_MAGIC: u32 = 0x7870_6D73;
+
+/// An envelope format version.
+pub const TYPED_MESSAGE_VERSION: u32 = 1;
+
+/// A message parameter carrying a serialized value.
+#[repr(C)]
+pub struct TypedEnvelope {
+    /// Always [`TYPED_MESSAGE_MAGIC`].
+    pub magic: u32,
+    /// Always [`TYPED_MESSAGE_VERSION`].
+    pub version: u32,
+    /// A pointer to the serialized JSON value.
+    pub data: *const u8,
+    /// A length of the serialized value in bytes.
+    pub len: usize,
+}
+
+fn encode<T: Serialize>(value: &T) -> Result<Vec<u8>> {
+    serde_json::to_vec(value).map_err(PluginError::InvalidTypedMessage)
+}
+
+fn send(target: xplm_sys::XPLMPluginID, message_id: i32, data: &[u8]) {
+    let mut envelope = TypedEnvelope {
+        magic: TYPED_MESSAGE_MAGIC,
+        version: TYPED_MESSAGE_VERSION,
+        data: data.as_ptr(),
+        len: data.len(),
+    };
+    let param = &mut envelope as *mut TypedEnvelope;
+    unsafe { xplm_sys::XPLMSendMessageToPlugin(target, message_id, param as *mut _) };
+}
+
+/// Sends a structured message to another plugin.
+///
+/// # Arguments
+/// * `id` - the plugin identifier.
+/// * `message_id` - the unique message identifier agreed between plugins.
+/// * `value` - a value to send.
+///
+/// # Returns
+/// Returns empty result on success. Otherwise returns [`PluginError`].
+pub fn send_typed_message<T: Serialize>(id: &PluginId, message_id: i32, value: &T) -> Result<()> {
+    let data = encode(value)?;
+    send(**id, message_id, &data);
+    Ok(())
+}
+
+/// Broadcasts a structured message to all plugins.
+///
+/// # Arguments
+/// * `message_id` - the unique message identifier agreed between plugins.
+/// * `value` - a value to send.
+///
+/// # Returns
+/// Returns empty result on success. Otherwise returns [`PluginError`].
+pub fn send_typed_message_to_all<T: Serialize>(message_id: i32, value: &T) -> Result<()> {
+    let data = encode(value)?;
+    send(xplm_sys::XPLM_NO_PLUGIN_ID, message_id, &data);
+    Ok(())
+}
+
+impl Message {
+    /// Decodes a structured message sent with [`send_typed_message`].
+    ///
+    /// # Returns
+    /// Returns [`None`] if the message is not a custom message or does not carry
+    /// a [`TypedEnvelope`]. Otherwise returns the decoded value or [`PluginError`].
+    ///
+    /// # Safety
+    /// The message parameter is dereferenced, call it only for message ids which are
+    /// agreed to carry typed envelopes and only while handling the message.
+    pub unsafe fn decode_param<T: DeserializeOwned>(&self) -> Option<Result<T>> {
+        let param = match self {
+            Self::Other { param, .. } if !param.is_null() => *param as *const TypedEnvelope,
+            _ => return None,
+        };
+
+        let envelope = &*param;
+        if envelope.magic != TYPED_MESSAGE_MAGIC
+            || envelope.version != TYPED_MESSAGE_VERSION
+            || envelope.data.is_null()
+        {
+            return None;
+        }
+
+        let data = std::slice::from_raw_parts(envelope.data, envelope.len);
+        Some(serde_json::from_slice(data).map_err(PluginError::InvalidTypedMessage))
+    }
+}
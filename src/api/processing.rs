@@ -0,0 +1,126 @@
+/// Returns the elapsed time since the sim started up in decimal seconds.
+/// This is a wall timer, it keeps counting upward even if the sim is paused.
+///
+/// # Returns
+/// Returns elapsed time in seconds.
+pub fn get_elapsed_time() -> f32 {
+    unsafe { xplm_sys::XPLMGetElapsedTime() }
+}
+
+/// Returns a counter starting at zero for each sim cycle computed/video frame rendered.
+///
+/// # Returns
+/// Returns the current cycle number.
+pub fn get_cycle_number() -> ::std::os::raw::c_int {
+    unsafe { xplm_sys::XPLMGetCycleNumber() }
+}
+
+/// A phase of the simulation cycle a flight loop is called in.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum FlightLoopPhase {
+    /// Called before X-Plane integrates the flight model.
+    #[default]
+    BeforeFlightModel = 0,
+    /// Called after X-Plane integrates the flight model.
+    AfterFlightModel = 1,
+}
+
+impl From<FlightLoopPhase> for xplm_sys::XPLMFlightLoopPhaseType {
+    fn from(value: FlightLoopPhase) -> Self {
+        value as xplm_sys::XPLMFlightLoopPhaseType
+    }
+}
+
+/// Flight loop handler.
+pub trait FlightLoopHandler: 'static {
+    /// Called when the flight loop is due.
+    ///
+    /// # Arguments
+    /// * `elapsed` - seconds since the previous call of this flight loop.
+    ///
+    /// # Returns
+    /// Returns when to call the flight loop next: a positive number of seconds,
+    /// a negative number of frames, e.g. `-1.0` for the next frame, or `0.0`
+    /// to deactivate the flight loop until it is scheduled again.
+    fn flight_loop(&mut self, elapsed: f32) -> f32;
+}
+
+impl<F: FnMut(f32) -> f32 + 'static> FlightLoopHandler for F {
+    fn flight_loop(&mut self, elapsed: f32) -> f32 {
+        self(elapsed)
+    }
+}
+
+/// A link to [`FlightLoopHandler`] for a given flight loop.
+pub struct FlightLoopLink {
+    /// A flight loop handler.
+    pub handler: Box<dyn FlightLoopHandler>,
+}
+
+/// A flight loop record to keep the flight loop alive.
+/// The flight loop is destroyed when dropped.
+pub struct FlightLoopRecord {
+    /// A flight loop identifier.
+    pub id: xplm_sys::XPLMFlightLoopID,
+    /// A link to the flight loop handler.
+    pub link: Box<FlightLoopLink>,
+}
+
+impl Drop for FlightLoopRecord {
+    fn drop(&mut self) {
+        unsafe { xplm_sys::XPLMDestroyFlightLoop(self.id) };
+    }
+}
+
+unsafe extern "C" fn flight_loop_callback(
+    elapsed_since_last_call: f32,
+    _elapsed_since_last_flight_loop: f32,
+    _counter: ::std::os::raw::c_int,
+    refcon: *mut ::std::os::raw::c_void,
+) -> f32 {
+    #[cfg(feature = "metrics")]
+    let _scope = crate::metrics::Scope::enter("flight_loop");
+    if refcon.is_null() {
+        return 0.0;
+    }
+
+    let link = refcon as *mut FlightLoopLink;
+    (*link).handler.flight_loop(elapsed_since_last_call)
+}
+
+/// Creates an inactive flight loop, see [`schedule_flight_loop`] to activate it.
+///
+/// # Arguments
+/// * `phase` - a simulation cycle phase. See [`FlightLoopPhase`] for more details.
+/// * `handler` - a flight loop handler. See [`FlightLoopHandler`] for more details.
+///
+/// # Returns
+/// Returns [`FlightLoopRecord`] which keeps the flight loop alive.
+pub fn create_flight_loop<H: FlightLoopHandler>(
+    phase: FlightLoopPhase,
+    handler: H,
+) -> FlightLoopRecord {
+    let mut link = Box::new(FlightLoopLink {
+        handler: Box::new(handler),
+    });
+    let link_ptr: *mut FlightLoopLink = &mut *link;
+    let mut params = xplm_sys::XPLMCreateFlightLoop_t {
+        structSize: std::mem::size_of::<xplm_sys::XPLMCreateFlightLoop_t>() as _,
+        phase: phase.into(),
+        callbackFunc: Some(flight_loop_callback),
+        refcon: link_ptr as *mut _,
+    };
+    let id = unsafe { xplm_sys::XPLMCreateFlightLoop(&mut params) };
+    FlightLoopRecord { id, link }
+}
+
+/// Schedules a flight loop.
+///
+/// # Arguments
+/// * `record` - a flight loop record.
+/// * `interval` - when to call the flight loop, see [`FlightLoopHandler::flight_loop`].
+/// * `relative_to_now` - `true` to count the interval from now, `false` to count it
+///   from the previous call of the flight loop.
+pub fn schedule_flight_loop(record: &FlightLoopRecord, interval: f32, relative_to_now: bool) {
+    unsafe { xplm_sys::XPLMScheduleFlightLoop(record.id, interval, relative_to_now as _) };
+}
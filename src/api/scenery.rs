@@ -0,0 +1,188 @@
+pub mod error;
+pub mod loading;
+pub mod object;
+pub mod placed;
+pub mod probe;
+
+use std::cell::RefCell;
+use std::ffi;
+use std::ops::Deref;
+
+pub use self::error::SceneryError;
+pub use self::loading::{is_scenery_loading, SceneryLoadedCallback, SceneryLoader};
+pub use self::object::{Object, ObjectLoadedCallback};
+pub use self::placed::PlacedObject;
+pub use self::probe::{Probe, ProbeInfo};
+
+use super::graphics::{world_to_local, LocalPosition, WorldPosition};
+
+pub type Result<T> = std::result::Result<T, SceneryError>;
+
+/// Creates a new Y probe which looks for the terrain directly below a point.
+///
+/// # Returns
+/// Returns a [`Probe`] on success. Otherwise returns [`SceneryError`].
+pub fn create_probe() -> Result<Probe> {
+    let probe = unsafe { xplm_sys::XPLMCreateProbe(xplm_sys::xplm_ProbeY as _) };
+    Probe::try_from(probe)
+}
+
+/// Deallocates an existing probe. Normally a probe is destroyed when dropped.
+///
+/// # Arguments
+/// * `probe` - a probe to destroy.
+pub fn destroy_probe(probe: &Probe) {
+    unsafe { xplm_sys::XPLMDestroyProbe(*probe.deref()) };
+}
+
+/// Probes the terrain below a point in local OpenGL coordinates.
+///
+/// # Arguments
+/// * `probe` - a probe to use.
+/// * `local` - a point to probe from. See [`LocalPosition`] for more details.
+///
+/// # Returns
+/// Returns [`ProbeInfo`] if the probe hits the terrain. Otherwise returns [`SceneryError`].
+pub fn probe_terrain_xyz(probe: &Probe, local: &LocalPosition) -> Result<ProbeInfo> {
+    let mut info = xplm_sys::XPLMProbeInfo_t {
+        structSize: std::mem::size_of::<xplm_sys::XPLMProbeInfo_t>() as _,
+        locationX: 0.0,
+        locationY: 0.0,
+        locationZ: 0.0,
+        normalX: 0.0,
+        normalY: 0.0,
+        normalZ: 0.0,
+        velocityX: 0.0,
+        velocityY: 0.0,
+        velocityZ: 0.0,
+        is_wet: 0,
+    };
+
+    let result = unsafe {
+        xplm_sys::XPLMProbeTerrainXYZ(
+            *probe.deref(),
+            local.x as f32,
+            local.y as f32,
+            local.z as f32,
+            &mut info,
+        )
+    };
+
+    match result as ::std::os::raw::c_uint {
+        xplm_sys::xplm_ProbeHitTerrain => Ok(ProbeInfo::from(info)),
+        xplm_sys::xplm_ProbeMissed => Err(SceneryError::ProbeMissed),
+        _ => Err(SceneryError::ProbeError),
+    }
+}
+
+thread_local! {
+    static SHARED_PROBE: RefCell<Option<Probe>> = const { RefCell::new(None) };
+}
+
+/// Probes the terrain with a probe shared by all helpers of this module.
+/// The probe is created on first use.
+fn probe_shared(local: &LocalPosition) -> Result<ProbeInfo> {
+    SHARED_PROBE.with(|probe| {
+        let mut probe = probe.borrow_mut();
+        if probe.is_none() {
+            *probe = Some(create_probe()?);
+        }
+
+        match probe.as_ref() {
+            Some(probe) => probe_terrain_xyz(probe, local),
+            None => Err(SceneryError::InvalidProbe),
+        }
+    })
+}
+
+/// Destroys the probe shared by [`height_above_ground`] and [`clamp_to_ground`].
+/// The next call creates a new probe.
+///
+/// Called by [`crate::register_plugin`] when the plugin is stopped, plugins which export
+/// the entry points themselves should call it from `XPluginStop`, so the probe isn't
+/// destroyed at thread exit after the plugin is unloaded.
+pub fn release_shared_probe() {
+    SHARED_PROBE.with(|probe| probe.borrow_mut().take());
+}
+
+/// Calculates the height of a world position above the terrain.
+///
+/// # Arguments
+/// * `world` - a world position. See [`WorldPosition`] for more details.
+///
+/// # Returns
+/// Returns the height above the terrain in meters on success. Otherwise returns [`SceneryError`].
+pub fn height_above_ground(world: &WorldPosition) -> Result<f64> {
+    let local = world_to_local(world);
+    let info = probe_shared(&local)?;
+    Ok(local.y - info.y as f64)
+}
+
+/// Moves a local position vertically so it is placed at the given height above the terrain.
+///
+/// # Arguments
+/// * `local` - a local position. See [`LocalPosition`] for more details.
+/// * `offset` - a height above the terrain in meters.
+///
+/// # Returns
+/// Returns the clamped position on success. Otherwise returns [`SceneryError`].
+pub fn clamp_to_ground(local: &LocalPosition, offset: f64) -> Result<LocalPosition> {
+    let info = probe_shared(local)?;
+    Ok(local.y(info.y as f64 + offset))
+}
+
+/// Loads an OBJ file and returns a reference to it. The path is relative to the X-System folder.
+/// This call blocks the sim while loading, prefer [`load_object_async`] during flight.
+///
+/// # Arguments
+/// * `path` - an object file path.
+///
+/// # Returns
+/// Returns loaded [`Object`] on success. Otherwise returns [`SceneryError`].
+pub fn load_object<T: Into<String>>(path: T) -> Result<Object> {
+    let path_c = ffi::CString::new(path.into()).map_err(SceneryError::InvalidObjectPath)?;
+    let object = unsafe { xplm_sys::XPLMLoadObject(path_c.as_ptr()) };
+    Object::try_from(object)
+}
+
+/// Loads an OBJ file asynchronously. The path is relative to the X-System folder.
+/// The callback is invoked from the main thread once the object is loaded or failed to load.
+///
+/// # Arguments
+/// * `path` - an object file path.
+/// * `callback` - a callback which receives loaded [`Object`] or [`SceneryError`].
+///
+/// # Returns
+/// Returns empty result when the loading is started. Otherwise returns [`SceneryError`].
+pub fn load_object_async<T, F>(path: T, callback: F) -> Result<()>
+where
+    T: Into<String>,
+    F: FnOnce(Result<Object>) + 'static,
+{
+    let path_c = ffi::CString::new(path.into()).map_err(SceneryError::InvalidObjectPath)?;
+    let callback: Box<ObjectLoadedCallback> = Box::new(Box::new(callback));
+    let callback_ptr = Box::into_raw(callback);
+    unsafe {
+        xplm_sys::XPLMLoadObjectAsync(path_c.as_ptr(), Some(object_loaded), callback_ptr as *mut _)
+    };
+
+    Ok(())
+}
+
+unsafe extern "C" fn object_loaded(
+    object: xplm_sys::XPLMObjectRef,
+    refcon: *mut ::std::os::raw::c_void,
+) {
+    #[cfg(feature = "metrics")]
+    let _scope = crate::metrics::Scope::enter("object_loaded");
+    let callback = Box::from_raw(refcon as *mut ObjectLoadedCallback);
+    callback(Object::try_from(object));
+}
+
+/// Unloads an object. Normally an object is unloaded when dropped.
+///
+/// # Arguments
+/// * `object` - an object to unload.
+pub fn unload_object(object: &Object) {
+    unsafe { xplm_sys::XPLMUnloadObject(*object.deref()) };
+}
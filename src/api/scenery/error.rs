@@ -0,0 +1,32 @@
+use std::ffi;
+
+use crate::api::data_access::DataAccessError;
+
+/// An error returned from scenery API calls.
+#[derive(thiserror::Error, Debug)]
+pub enum SceneryError {
+    /// Invalid probe reference returned from X-Plane.
+    #[error("invalid probe reference")]
+    InvalidProbe,
+    /// Probe failed, e.g. it was given invalid arguments.
+    #[error("probe error")]
+    ProbeError,
+    /// Probe did not hit the terrain.
+    #[error("probe missed the terrain")]
+    ProbeMissed,
+    /// Invalid object path string passed to X-Plane.
+    #[error("invalid object path {0}")]
+    InvalidObjectPath(ffi::NulError),
+    /// Invalid object reference returned from X-Plane, e.g. the object failed to load.
+    #[error("invalid object reference")]
+    InvalidObject,
+    /// Data access error.
+    #[error("data access error {0}")]
+    DataAccess(DataAccessError),
+}
+
+impl From<DataAccessError> for SceneryError {
+    fn from(value: DataAccessError) -> Self {
+        Self::DataAccess(value)
+    }
+}
@@ -0,0 +1,88 @@
+use crate::api::data_access::DataRefValue;
+use crate::api::plugin::Message;
+use crate::api::utilities;
+
+use super::Result;
+
+/// A callback invoked once scenery loading finishes.
+pub type SceneryLoadedCallback = Box<dyn FnOnce()>;
+
+/// Tracks scenery reloads and notifies callbacks once X-Plane reports the scenery is loaded.
+///
+/// X-Plane loads scenery asynchronously, so [`utilities::reload_scenery`] returns
+/// before the new scenery is available. The loader waits for the following
+/// [`Message::SceneryLoaded`] message to fire the pending callbacks.
+pub struct SceneryLoader {
+    pending: Vec<SceneryLoadedCallback>,
+    loading: DataRefValue<bool>,
+}
+
+impl SceneryLoader {
+    /// Creates a new scenery loader.
+    ///
+    /// # Returns
+    /// Returns a new [`SceneryLoader`] instance.
+    pub fn new() -> Self {
+        Self {
+            pending: Vec::new(),
+            loading: DataRefValue::new("sim/graphics/scenery/async_scenery_load_in_progress"),
+        }
+    }
+
+    /// Reloads the current set of scenery and calls the callback once loading finishes.
+    ///
+    /// # Arguments
+    /// * `callback` - a callback to call when the scenery is loaded.
+    pub fn reload<F: FnOnce() + 'static>(&mut self, callback: F) {
+        self.on_loaded(callback);
+        utilities::reload_scenery();
+    }
+
+    /// Calls the callback when the next scenery load finishes, without triggering a reload.
+    ///
+    /// # Arguments
+    /// * `callback` - a callback to call when the scenery is loaded.
+    pub fn on_loaded<F: FnOnce() + 'static>(&mut self, callback: F) {
+        self.pending.push(Box::new(callback));
+    }
+
+    /// Checks whether any callbacks are waiting for scenery loading to finish.
+    pub fn is_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Checks whether X-Plane is loading scenery in the background.
+    ///
+    /// # Returns
+    /// Returns `true` while scenery is loading. Otherwise returns [`super::SceneryError`].
+    pub fn is_loading(&self) -> Result<bool> {
+        Ok(self.loading.get()?)
+    }
+
+    /// Handles a plugin message. Should be called from [`crate::plugin::XPlugin::receive_message`].
+    ///
+    /// # Arguments
+    /// * `message` - the received message.
+    pub fn handle_message(&mut self, message: &Message) {
+        if matches!(message, Message::SceneryLoaded) {
+            for callback in std::mem::take(&mut self.pending) {
+                callback();
+            }
+        }
+    }
+}
+
+impl Default for SceneryLoader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Checks whether X-Plane is loading scenery in the background.
+/// Useful to show progress UI during long operations.
+///
+/// # Returns
+/// Returns `true` while scenery is loading. Otherwise returns [`super::SceneryError`].
+pub fn is_scenery_loading() -> Result<bool> {
+    Ok(DataRefValue::<bool>::new("sim/graphics/scenery/async_scenery_load_in_progress").get()?)
+}
@@ -0,0 +1,35 @@
+use std::ops::Deref;
+
+use super::{unload_object, SceneryError};
+
+/// A loaded scenery object reference. The object is unloaded when dropped.
+pub struct Object(xplm_sys::XPLMObjectRef);
+
+impl Deref for Object {
+    type Target = xplm_sys::XPLMObjectRef;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl TryFrom<xplm_sys::XPLMObjectRef> for Object {
+    type Error = SceneryError;
+
+    fn try_from(value: xplm_sys::XPLMObjectRef) -> std::result::Result<Self, Self::Error> {
+        if value.is_null() {
+            Err(Self::Error::InvalidObject)
+        } else {
+            Ok(Object(value))
+        }
+    }
+}
+
+impl Drop for Object {
+    fn drop(&mut self) {
+        unload_object(self);
+    }
+}
+
+/// A callback invoked when an object finishes loading asynchronously.
+pub type ObjectLoadedCallback = Box<dyn FnOnce(Result<Object, SceneryError>)>;
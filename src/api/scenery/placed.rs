@@ -0,0 +1,202 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::api::graphics::{world_to_local, Heading, WorldPosition};
+use crate::api::instance::{self, DrawInfo, Instance};
+
+use super::{load_object_async, Object, Result};
+
+/// A state shared between a placed object and its loading callback.
+struct PlacedState {
+    /// The instance is declared before the object to be destroyed first.
+    instance: Option<Instance>,
+    object: Option<Object>,
+    data_refs: Vec<String>,
+    values: Vec<f32>,
+    position: WorldPosition,
+    heading: Heading,
+    despawned: bool,
+    failed: bool,
+}
+
+impl PlacedState {
+    fn apply(&self) -> instance::Result<()> {
+        match &self.instance {
+            Some(instance) => {
+                let draw_info = DrawInfo::default()
+                    .position(world_to_local(&self.position))
+                    .heading(self.heading.to_true().degrees());
+                instance::instance_set_position(instance, &draw_info, &self.values)
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+/// A scenery object placed at a world position. The object is loaded asynchronously
+/// and becomes visible once loading completes. Dropping a [`PlacedObject`] removes the object
+/// from the scenery, even if it is still loading.
+///
+/// The local coordinate system origin may shift during flight, call [`PlacedObject::update`]
+/// each frame (e.g. from a flight loop) to keep the object at its world position.
+pub struct PlacedObject {
+    state: Rc<RefCell<PlacedState>>,
+}
+
+impl PlacedObject {
+    /// Loads an object and places it at the given world position.
+    ///
+    /// # Arguments
+    /// * `path` - an object file path relative to the X-System folder.
+    /// * `position` - a world position. See [`WorldPosition`] for more details.
+    /// * `heading` - an object heading. See [`Heading`] for more details.
+    ///
+    /// # Returns
+    /// Returns [`PlacedObject`] on success. Otherwise returns [`SceneryError`](super::SceneryError).
+    pub fn spawn<T: Into<String>>(
+        path: T,
+        position: WorldPosition,
+        heading: Heading,
+    ) -> Result<Self> {
+        Self::spawn_with_data_refs::<T, &str>(path, position, heading, &[])
+    }
+
+    /// Loads an object which animations are driven by the given datarefs
+    /// and places it at the given world position.
+    ///
+    /// # Arguments
+    /// * `path` - an object file path relative to the X-System folder.
+    /// * `position` - a world position. See [`WorldPosition`] for more details.
+    /// * `heading` - an object heading. See [`Heading`] for more details.
+    /// * `data_refs` - names of the datarefs used by the object animations.
+    ///
+    /// # Returns
+    /// Returns [`PlacedObject`] on success. Otherwise returns [`SceneryError`](super::SceneryError).
+    pub fn spawn_with_data_refs<T: Into<String>, D: AsRef<str>>(
+        path: T,
+        position: WorldPosition,
+        heading: Heading,
+        data_refs: &[D],
+    ) -> Result<Self> {
+        let state = Rc::new(RefCell::new(PlacedState {
+            instance: None,
+            object: None,
+            data_refs: data_refs.iter().map(|name| name.as_ref().into()).collect(),
+            values: vec![0.0; data_refs.len()],
+            position,
+            heading,
+            despawned: false,
+            failed: false,
+        }));
+
+        let callback_state = state.clone();
+        load_object_async(path, move |object| {
+            let mut state = callback_state.borrow_mut();
+            if state.despawned {
+                return;
+            }
+
+            let object = match object {
+                Ok(object) => object,
+                Err(err) => {
+                    crate::warn!("failed to load placed object: {}", err);
+                    state.failed = true;
+                    return;
+                }
+            };
+
+            match instance::create_instance(&object, &state.data_refs) {
+                Ok(instance) => {
+                    state.instance = Some(instance);
+                    state.object = Some(object);
+                    if let Err(err) = state.apply() {
+                        crate::warn!("failed to position placed object: {}", err);
+                    }
+                }
+                Err(err) => {
+                    crate::warn!("failed to instantiate placed object: {}", err);
+                    state.failed = true;
+                }
+            }
+        })?;
+
+        Ok(Self { state })
+    }
+
+    /// Checks wether the object is loaded and visible.
+    ///
+    /// # Returns
+    /// Returns `true` if the object is loaded. Otherwise returns `false`.
+    pub fn is_loaded(&self) -> bool {
+        self.state.borrow().instance.is_some()
+    }
+
+    /// Checks wether the object failed to load.
+    ///
+    /// # Returns
+    /// Returns `true` if loading failed. Otherwise returns `false`.
+    pub fn is_failed(&self) -> bool {
+        self.state.borrow().failed
+    }
+
+    /// Returns the current world position of the object.
+    pub fn position(&self) -> WorldPosition {
+        self.state.borrow().position
+    }
+
+    /// Returns the current heading of the object.
+    pub fn heading(&self) -> Heading {
+        self.state.borrow().heading
+    }
+
+    /// Moves the object to a new world position.
+    ///
+    /// # Arguments
+    /// * `position` - a world position. See [`WorldPosition`] for more details.
+    /// * `heading` - an object heading. See [`Heading`] for more details.
+    ///
+    /// # Returns
+    /// Returns empty result on success. Otherwise returns [`InstanceError`](crate::api::instance::InstanceError).
+    pub fn set_position(&self, position: WorldPosition, heading: Heading) -> instance::Result<()> {
+        let mut state = self.state.borrow_mut();
+        state.position = position;
+        state.heading = heading;
+        state.apply()
+    }
+
+    /// Sets the values of the datarefs the object was spawned with.
+    ///
+    /// # Arguments
+    /// * `values` - dataref values in the order the datarefs were passed on spawn.
+    ///
+    /// # Returns
+    /// Returns empty result on success. Otherwise returns [`InstanceError`](crate::api::instance::InstanceError).
+    pub fn set_dataref_values(&self, values: &[f32]) -> instance::Result<()> {
+        let mut state = self.state.borrow_mut();
+        if values.len() != state.data_refs.len() {
+            return Err(instance::InstanceError::DataRefValuesMismatch {
+                expected: state.data_refs.len(),
+                actual: values.len(),
+            });
+        }
+
+        state.values.copy_from_slice(values);
+        state.apply()
+    }
+
+    /// Re-converts the world position into local coordinates and updates the object.
+    ///
+    /// # Returns
+    /// Returns empty result on success. Otherwise returns [`InstanceError`](crate::api::instance::InstanceError).
+    pub fn update(&self) -> instance::Result<()> {
+        self.state.borrow().apply()
+    }
+}
+
+impl Drop for PlacedObject {
+    fn drop(&mut self) {
+        let mut state = self.state.borrow_mut();
+        state.despawned = true;
+        state.instance = None;
+        state.object = None;
+    }
+}
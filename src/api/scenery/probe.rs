@@ -0,0 +1,74 @@
+use std::ops::Deref;
+
+use super::{destroy_probe, SceneryError};
+
+/// A terrain probe reference. The probe is destroyed when dropped.
+pub struct Probe(xplm_sys::XPLMProbeRef);
+
+impl Deref for Probe {
+    type Target = xplm_sys::XPLMProbeRef;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl TryFrom<xplm_sys::XPLMProbeRef> for Probe {
+    type Error = SceneryError;
+
+    fn try_from(value: xplm_sys::XPLMProbeRef) -> std::result::Result<Self, Self::Error> {
+        if value.is_null() {
+            Err(Self::Error::InvalidProbe)
+        } else {
+            Ok(Probe(value))
+        }
+    }
+}
+
+impl Drop for Probe {
+    fn drop(&mut self) {
+        destroy_probe(self);
+    }
+}
+
+/// A terrain probe result in local OpenGL coordinates.
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct ProbeInfo {
+    /// The location X coordinate of the hit point.
+    pub x: f32,
+    /// The location Y coordinate of the hit point.
+    pub y: f32,
+    /// The location Z coordinate of the hit point.
+    pub z: f32,
+    /// The X component of the terrain normal vector.
+    pub normal_x: f32,
+    /// The Y component of the terrain normal vector.
+    pub normal_y: f32,
+    /// The Z component of the terrain normal vector.
+    pub normal_z: f32,
+    /// The X component of the terrain velocity in meters per second.
+    pub velocity_x: f32,
+    /// The Y component of the terrain velocity in meters per second.
+    pub velocity_y: f32,
+    /// The Z component of the terrain velocity in meters per second.
+    pub velocity_z: f32,
+    /// `true` if the terrain is water.
+    pub is_wet: bool,
+}
+
+impl From<xplm_sys::XPLMProbeInfo_t> for ProbeInfo {
+    fn from(value: xplm_sys::XPLMProbeInfo_t) -> Self {
+        Self {
+            x: value.locationX,
+            y: value.locationY,
+            z: value.locationZ,
+            normal_x: value.normalX,
+            normal_y: value.normalY,
+            normal_z: value.normalZ,
+            velocity_x: value.velocityX,
+            velocity_y: value.velocityY,
+            velocity_z: value.velocityZ,
+            is_wet: value.is_wet == 1,
+        }
+    }
+}
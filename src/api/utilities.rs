@@ -1,20 +1,45 @@
 pub mod app;
+pub mod balance;
+pub mod button;
+pub mod capabilities;
 pub mod command;
+pub mod directory;
 pub mod error;
 pub mod file;
 pub mod key;
 pub mod lang;
+pub mod os_path;
+pub mod situation;
 
 use std::ops::Deref;
-use std::{ffi, ops::DerefMut, path, str, sync::OnceLock};
-
-pub use self::app::{HostApplicationId, Versions};
+use std::ptr::NonNull;
+use std::{
+    ffi,
+    ops::DerefMut,
+    path, str,
+    sync::{Arc, Mutex, PoisonError, TryLockError},
+};
+
+pub use self::app::{HostApplicationId, Versions, XPlaneVersion, XplmVersion};
+pub use self::balance::audit_command_balance;
+pub use self::button::{ButtonMode, CommandButton};
+pub use self::capabilities::SdkCapabilities;
 pub use self::command::Command;
-pub use self::command::{CommandExecutionTime, CommandHandler, CommandHandlerRecord, CommandLink};
+pub use self::command::{
+    BlockingHandler, CommandCreation, CommandExecutionTime, CommandGuard, CommandHandler,
+    CommandHandlerRecord, CommandLink, CommandOutcome,
+};
+pub use self::directory::DirectoryContents;
 pub use self::error::UtilitiesError;
 pub use self::file::DataFileType;
 pub use self::key::VirtualKey;
 pub use self::lang::Language;
+pub use self::situation::{Replay, Situation};
+pub use super::naming::validate_command_name;
+
+use super::backend::backend;
+use super::plugin;
+use crate::package::Platform;
 
 pub type Result<T> = std::result::Result<T, UtilitiesError>;
 
@@ -24,13 +49,9 @@ pub type Result<T> = std::result::Result<T, UtilitiesError>;
 /// # Returns
 /// Returns system path on success. Otherwise returns [`UtilitiesError`].
 pub fn get_system_path() -> Result<path::PathBuf> {
-    unsafe {
-        let mut buf = [0; 4096];
-        xplm_sys::XPLMGetSystemPath(buf.as_mut_ptr());
-        ffi::CStr::from_ptr(buf.as_ptr()).to_owned().into_string()
-    }
-    .map(|path| path::PathBuf::from(&path))
-    .map_err(UtilitiesError::InvalidPrefsPath)
+    let mut buf = [0; 4096];
+    unsafe { xplm_sys::XPLMGetSystemPath(buf.as_mut_ptr()) };
+    Ok(os_path::path_from_c_buf(&buf))
 }
 
 /// Returns a full path to a file that is within X-Plane’s preferences directory.
@@ -38,13 +59,69 @@ pub fn get_system_path() -> Result<path::PathBuf> {
 /// # Returns
 /// Returns preferences file path on success. Otherwise returns [`UtilitiesError`].
 pub fn get_prefs_path() -> Result<path::PathBuf> {
-    unsafe {
-        let mut buf = [0; 4096];
-        xplm_sys::XPLMGetPrefsPath(buf.as_mut_ptr());
-        ffi::CStr::from_ptr(buf.as_ptr()).to_owned().into_string()
+    let mut buf = [0; 4096];
+    unsafe { xplm_sys::XPLMGetPrefsPath(buf.as_mut_ptr()) };
+    Ok(os_path::path_from_c_buf(&buf))
+}
+
+/// Returns the full path to the plugin `.xpl` file of the calling plugin.
+///
+/// # Returns
+/// Returns plugin file path on success. Otherwise returns [`UtilitiesError`].
+pub fn get_plugin_path() -> Result<path::PathBuf> {
+    let id = plugin::get_my_id()?;
+    let info = plugin::get_plugin_info(&id)?;
+    Ok(path::PathBuf::from(info.file_path))
+}
+
+/// Returns the root folder of the calling plugin. For fat plugins this is the folder
+/// which contains platform specific folders, e.g. `Resources/plugins/<name>`.
+/// This is the place where plugin resources are usually shipped.
+///
+/// # Returns
+/// Returns plugin root folder on success. Otherwise returns [`UtilitiesError`].
+pub fn get_plugin_resources_dir() -> Result<path::PathBuf> {
+    let plugin_path = get_plugin_path()?;
+    let plugin_dir = plugin_path
+        .parent()
+        .ok_or_else(|| UtilitiesError::InvalidPath(plugin_path.clone()))?;
+    let is_platform_dir = plugin_dir
+        .file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| {
+            Platform::all()
+                .iter()
+                .any(|platform| platform.folder_name() == name)
+        });
+
+    match plugin_dir.parent() {
+        Some(root) if is_platform_dir => Ok(root.to_path_buf()),
+        _ => Ok(plugin_dir.to_path_buf()),
     }
-    .map(|path| path::PathBuf::from(&path))
-    .map_err(UtilitiesError::InvalidPrefsPath)
+}
+
+/// Returns the full path to the X-Plane `Output` folder.
+///
+/// # Returns
+/// Returns output folder path on success. Otherwise returns [`UtilitiesError`].
+pub fn get_output_dir() -> Result<path::PathBuf> {
+    get_system_path().map(|path| path.join("Output"))
+}
+
+/// Returns the full path to the folder of the user's aircraft.
+///
+/// # Returns
+/// Returns aircraft folder path on success. Otherwise returns [`UtilitiesError`].
+pub fn get_aircraft_dir() -> Result<path::PathBuf> {
+    let mut file_name = [0; 256];
+    let mut path = [0; 512];
+    backend().get_nth_aircraft_model(0, &mut file_name, &mut path);
+    let aircraft_path = os_path::path_from_c_buf(&path);
+
+    aircraft_path
+        .parent()
+        .map(path::Path::to_path_buf)
+        .ok_or(UtilitiesError::InvalidPath(aircraft_path))
 }
 
 /// Returns a char that is the directory separator for the current platform.
@@ -61,6 +138,48 @@ pub fn get_directory_separator() -> Result<char> {
         .ok_or(UtilitiesError::EmptyDirectorySeparator)
 }
 
+/// Splits a full path into the directory path and the file name using
+/// X-Plane's path conventions.
+///
+/// # Arguments
+/// * `file_path` - a full file path.
+///
+/// # Returns
+/// Returns the directory path and the file name on success. Otherwise returns [`UtilitiesError`].
+pub fn extract_file_and_path<P: AsRef<path::Path>>(
+    file_path: P,
+) -> Result<(path::PathBuf, path::PathBuf)> {
+    let file_path_bytes = os_path::path_to_bytes(file_path.as_ref()).ok_or(
+        UtilitiesError::InvalidPath(file_path.as_ref().to_path_buf()),
+    )?;
+    let file_path_c = ffi::CString::new(file_path_bytes.into_owned())
+        .map_err(UtilitiesError::InvalidDataFilePath)?;
+    let mut buf = file_path_c.into_bytes_with_nul();
+    unsafe {
+        let file_name = xplm_sys::XPLMExtractFileAndPath(buf.as_mut_ptr() as *mut _);
+        let file_name = os_path::path_from_c_str(ffi::CStr::from_ptr(file_name));
+        let dir = os_path::path_from_c_str(ffi::CStr::from_ptr(buf.as_ptr() as *const _));
+        Ok((dir, file_name))
+    }
+}
+
+/// Returns an iterator over the files and directories in the given directory.
+/// The file names are fetched in batches as the iterator advances.
+///
+/// # Arguments
+/// * `dir` - a full directory path.
+///
+/// # Returns
+/// Returns [`DirectoryContents`] iterator of full paths on success. Otherwise returns [`UtilitiesError`].
+pub fn get_directory_contents<P: AsRef<path::Path>>(dir: P) -> Result<DirectoryContents> {
+    let dir = dir.as_ref().to_path_buf();
+    let dir_bytes =
+        os_path::path_to_bytes(&dir).ok_or_else(|| UtilitiesError::InvalidPath(dir.clone()))?;
+    let dir_c =
+        ffi::CString::new(dir_bytes.into_owned()).map_err(UtilitiesError::InvalidDataFilePath)?;
+    Ok(DirectoryContents::new(dir, dir_c))
+}
+
 /// Loads a data file of a given type.
 ///
 /// # Arguments
@@ -72,12 +191,10 @@ pub fn get_directory_separator() -> Result<char> {
 /// * [`UtilitiesError::LoadDataFile`] if data file can't be loaded.
 /// * [`UtilitiesError::InvalidDataFilePath`] if file_path contains invalid characters.
 pub fn load_data_file<P: AsRef<path::Path>>(file_type: DataFileType, file_path: P) -> Result<()> {
-    let file_path_str = file_path
-        .as_ref()
-        .to_str()
-        .ok_or(UtilitiesError::LoadDataFile)?;
-    let file_path_c =
-        ffi::CString::new(file_path_str).map_err(UtilitiesError::InvalidDataFilePath)?;
+    let file_path_bytes =
+        os_path::path_to_bytes(file_path.as_ref()).ok_or(UtilitiesError::LoadDataFile)?;
+    let file_path_c = ffi::CString::new(file_path_bytes.into_owned())
+        .map_err(UtilitiesError::InvalidDataFilePath)?;
     let is_loaded = unsafe {
         xplm_sys::XPLMLoadDataFile(
             file_type as xplm_sys::XPLMDataFileType,
@@ -122,12 +239,10 @@ pub fn clear_replay() -> Result<()> {
 /// * [`UtilitiesError::SaveDataFile`] if data file can't be loaded.
 /// * [`UtilitiesError::InvalidDataFilePath`] if file_path contains invalid characters.
 pub fn save_data_file<P: AsRef<path::Path>>(file_type: DataFileType, file_path: P) -> Result<()> {
-    let file_path_str = file_path
-        .as_ref()
-        .to_str()
-        .ok_or(UtilitiesError::SaveDataFile)?;
-    let file_path_c =
-        ffi::CString::new(file_path_str).map_err(UtilitiesError::InvalidDataFilePath)?;
+    let file_path_bytes =
+        os_path::path_to_bytes(file_path.as_ref()).ok_or(UtilitiesError::SaveDataFile)?;
+    let file_path_c = ffi::CString::new(file_path_bytes.into_owned())
+        .map_err(UtilitiesError::InvalidDataFilePath)?;
     let is_saved = unsafe {
         xplm_sys::XPLMSaveDataFile(
             file_type as xplm_sys::XPLMDataFileType,
@@ -154,11 +269,80 @@ pub fn get_versions() -> Result<Versions> {
     unsafe { xplm_sys::XPLMGetVersions(&mut xplane_version, &mut xplm_version, &mut host_id) };
     Ok(Versions {
         app_id: HostApplicationId::try_from(host_id)?,
-        xplane: xplane_version,
-        xplm: xplm_version,
+        xplane: XPlaneVersion(xplane_version),
+        xplm: XplmVersion(xplm_version),
     })
 }
 
+/// Detects SDK features available in the running X-Plane.
+///
+/// # Returns
+/// Returns [`SdkCapabilities`] on success. Otherwise returns [`UtilitiesError`].
+pub fn get_sdk_capabilities() -> Result<SdkCapabilities> {
+    get_versions().map(|versions| SdkCapabilities::from_versions(&versions))
+}
+
+/// Checks that the running XPLM version is at least the required one.
+/// Wrappers over newer SDK functions call it before touching symbols
+/// which may be missing in older X-Plane versions.
+///
+/// # Arguments
+/// * `required` - a minimum required XPLM version. See [`XplmVersion`] for more details.
+///
+/// # Returns
+/// Returns empty result if the version is supported. Otherwise returns
+/// [`UtilitiesError::UnsupportedSdkVersion`].
+pub fn require_xplm_version(required: XplmVersion) -> Result<()> {
+    let actual = get_versions()?.xplm;
+    if actual >= required {
+        Ok(())
+    } else {
+        Err(UtilitiesError::UnsupportedSdkVersion { required, actual })
+    }
+}
+
+/// Looks up an XPLM entry point by name. Use it to call SDK functions which
+/// may be missing in older X-Plane versions and degrade gracefully.
+///
+/// # Arguments
+/// * `name` - a symbol name, e.g. `XPLMGetDataRefsByIndex`.
+///
+/// # Returns
+/// Returns a symbol pointer on success. Otherwise returns:
+/// - [`None`] in case the symbol does not exist.
+/// - [`UtilitiesError`] in case of malformed symbol name.
+pub fn find_symbol<T: Into<String>>(name: T) -> Result<Option<NonNull<::std::os::raw::c_void>>> {
+    let name_c = ffi::CString::new(name.into()).map_err(UtilitiesError::InvalidSymbolName)?;
+    let symbol = unsafe { xplm_sys::XPLMFindSymbol(name_c.as_ptr()) };
+    Ok(NonNull::new(symbol))
+}
+
+/// Casts a symbol returned from [`find_symbol`] into a function pointer.
+///
+/// ```ignore
+/// type CountDataRefs = unsafe extern "C" fn() -> c_int;
+/// if let Some(symbol) = find_symbol("XPLMCountDataRefs")? {
+///     let count_data_refs: CountDataRefs = unsafe { cast_fn(symbol) };
+/// }
+/// ```
+///
+/// # Arguments
+/// * `symbol` - a symbol pointer.
+///
+/// # Returns
+/// Returns the symbol as a function pointer of type `F`.
+///
+/// # Safety
+/// `F` must be a function pointer type matching the symbol signature and calling convention.
+pub unsafe fn cast_fn<F: Copy>(symbol: NonNull<::std::os::raw::c_void>) -> F {
+    assert_eq!(
+        std::mem::size_of::<F>(),
+        std::mem::size_of::<*mut ::std::os::raw::c_void>(),
+        "function pointer type expected"
+    );
+    std::mem::transmute_copy(&symbol.as_ptr())
+}
+
 /// Returns the [`Language`] the sim is running in.
 ///
 /// # Returns
@@ -168,7 +352,9 @@ pub fn get_language() -> Result<Language> {
     Language::try_from(code)
 }
 
-static ERROR_CALLBACK: OnceLock<fn(&str)> = OnceLock::new();
+type ErrorCallback = Arc<Mutex<dyn FnMut(&str) + Send>>;
+
+static ERROR_CALLBACK: Mutex<Option<ErrorCallback>> = Mutex::new(None);
 
 /// Installs an error-reporting callback for your plugin. Normally the plugin
 /// system performs minimum diagnostics to maximize performance.
@@ -185,46 +371,81 @@ static ERROR_CALLBACK: OnceLock<fn(&str)> = OnceLock::new();
 /// shipping plugins. Since the only useful response to an error is to change code, error
 /// callbacks are not useful “in the field”.
 ///
+/// Installing a new callback replaces the previously installed one.
+///
 /// # Arguments
-/// * `callback` - a function which accepts `&str` messages.
-pub fn set_error_callback(callback: fn(&str)) {
+/// * `callback` - a closure which accepts `&str` messages.
+pub fn set_error_callback<F: FnMut(&str) + Send + 'static>(callback: F) {
     unsafe extern "C" fn error_callback(message: *const ::std::os::raw::c_char) {
         let message_c = ffi::CStr::from_ptr(message);
         match message_c.to_str() {
             Ok(message_str) => {
-                if let Some(handler) = ERROR_CALLBACK.get() {
-                    handler(message_str)
+                // The callback is called without holding the global lock, so it may log,
+                // install another callback or panic without blocking later errors.
+                let handler = ERROR_CALLBACK
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .clone();
+                if let Some(handler) = handler {
+                    match handler.try_lock() {
+                        Ok(mut handler) => handler(message_str),
+                        Err(TryLockError::Poisoned(handler)) => (handler.into_inner())(message_str),
+                        // An error raised by the callback itself is not reported to it again.
+                        Err(TryLockError::WouldBlock) => {}
+                    }
                 }
             }
             Err(err) => crate::error!("Error handler called with an invalid message. {}", err),
         }
     }
 
-    ERROR_CALLBACK.get_or_init(|| callback);
+    *ERROR_CALLBACK
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner) = Some(Arc::new(Mutex::new(callback)));
+
     unsafe { xplm_sys::XPLMSetErrorCallback(Some(error_callback)) };
 }
 
+/// Uninstalls the error callback installed with [`set_error_callback`].
+pub fn clear_error_callback() {
+    unsafe { xplm_sys::XPLMSetErrorCallback(None) };
+    *ERROR_CALLBACK
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner) = None;
+}
+
 /// Outputs a string to the `Log.txt` file. The file is immediately flushed so the data is not lost.
 /// This does cause a performance penalty.
 ///
+/// Interior nul characters are replaced with [`char::REPLACEMENT_CHARACTER`].
+///
 /// # Arguments
 /// * `message` - a message that will be written to the log file.
 pub fn debug_string<T: Into<String>>(message: T) {
-    if let Ok(message_c) = ffi::CString::new(message.into()) {
-        unsafe { xplm_sys::XPLMDebugString(message_c.as_ptr()) };
-    }
+    let message_c = c_string_lossy(message.into());
+    backend().debug_string(&message_c);
 }
 
 /// Displays the string in a translucent overlay over the current display and also speaks the string
 /// if text-to-speech is enabled. The string is spoken asynchronously, this function returns immediately.
 /// This function may not speak or print depending on user preferences.
 ///
+/// Interior nul characters are replaced with [`char::REPLACEMENT_CHARACTER`].
+///
 /// # Arguments
 /// * `message` - a message that will be spoken.
 pub fn speak_string<T: Into<String>>(message: T) {
-    if let Ok(message_c) = ffi::CString::new(message.into()) {
-        unsafe { xplm_sys::XPLMSpeakString(message_c.as_ptr()) };
-    }
+    let message_c = c_string_lossy(message.into());
+    backend().speak_string(&message_c);
+}
+
+fn c_string_lossy(message: String) -> ffi::CString {
+    let message = if message.contains('\0') {
+        message.replace('\0', "\u{FFFD}")
+    } else {
+        message
+    };
+    ffi::CString::new(message).unwrap_or_default()
 }
 
 /// Returns a human-readable string describing the character.
@@ -248,6 +469,7 @@ pub fn get_virtual_key_description(key: VirtualKey) -> Result<Option<String>> {
 }
 
 /// Reloads the current set of scenery.
+/// Use [`crate::api::scenery::SceneryLoader`] to get notified when loading finishes.
 pub fn reload_scenery() {
     unsafe { xplm_sys::XPLMReloadScenery() };
 }
@@ -263,7 +485,7 @@ pub fn reload_scenery() {
 /// - [`UtilitiesError`] in case of malformed command name.
 pub fn find_command<T: Into<String>>(name: T) -> Result<Option<Command>> {
     let name_c = ffi::CString::new(name.into()).map_err(UtilitiesError::InvalidCommandName)?;
-    let command = unsafe { xplm_sys::XPLMFindCommand(name_c.as_ptr()) };
+    let command = backend().find_command(&name_c);
     if command.is_null() {
         Ok(None)
     } else {
@@ -276,7 +498,8 @@ pub fn find_command<T: Into<String>>(name: T) -> Result<Option<Command>> {
 /// # Arguments
 /// * `command` - the [`Command`] to begin execution of.
 pub fn command_begin(command: &Command) {
-    unsafe { xplm_sys::XPLMCommandBegin(*command.deref()) };
+    balance::record_begin(*command.deref());
+    backend().command_begin(*command.deref());
 }
 
 /// Ends the execution of a given command that was previously started.
@@ -284,7 +507,8 @@ pub fn command_begin(command: &Command) {
 /// # Arguments
 /// * `command` - the [`Command`] to end execution of.
 pub fn command_end(command: &Command) {
-    unsafe { xplm_sys::XPLMCommandEnd(*command.deref()) };
+    balance::record_end(*command.deref());
+    backend().command_end(*command.deref());
 }
 
 /// Executes a given command momentarily, that is, the command begins and ends immediately.
@@ -292,7 +516,7 @@ pub fn command_end(command: &Command) {
 /// # Arguments
 /// * `command` - the [`Command`] to execute.
 pub fn command_once(command: &Command) {
-    unsafe { xplm_sys::XPLMCommandOnce(*command.deref()) };
+    backend().command_once(*command.deref());
 }
 
 /// Creates a new command for a given name and description.
@@ -310,13 +534,50 @@ where
     N: Into<String>,
     D: Into<String>,
 {
-    let name_c = ffi::CString::new(name.into()).map_err(UtilitiesError::InvalidCommandName)?;
+    let name = name.into();
+    validate_command_name(&name)?;
+    let name_c = ffi::CString::new(name).map_err(UtilitiesError::InvalidCommandName)?;
     let description_c =
         ffi::CString::new(description.into()).map_err(UtilitiesError::InvalidCommandDescription)?;
-    let command = unsafe { xplm_sys::XPLMCreateCommand(name_c.as_ptr(), description_c.as_ptr()) };
+    let command = backend().create_command(&name_c, &description_c);
     Command::try_from(command)
 }
 
+/// Creates a new command or finds an existing one with the same name.
+/// Unlike [`create_command`] it tells which one happened, so a collision with a command
+/// of another plugin or a typo in a name of a sim command can be detected.
+///
+/// # Arguments
+/// * `name` - a command name.
+/// * `description` - a command description, ignored if the command exists.
+///
+/// # Returns
+/// Returns [`CommandCreation::Created`] if the command is new or [`CommandCreation::Existing`]
+/// if it has been found. Otherwise returns [`UtilitiesError`].
+pub fn create_or_find_command<N, D>(name: N, description: D) -> Result<CommandCreation>
+where
+    N: Into<String>,
+    D: Into<String>,
+{
+    let name = name.into();
+    match find_command(name.as_str())? {
+        Some(command) => Ok(CommandCreation::Existing(command)),
+        None => create_command(name, description).map(CommandCreation::Created),
+    }
+}
+
+/// Checks wether a command with a given name exists.
+///
+/// # Arguments
+/// * `name` - a command name.
+///
+/// # Returns
+/// Returns `true` if the command exists. Otherwise returns `false`,
+/// a malformed name is treated as a missing command.
+pub fn command_exists<T: Into<String>>(name: T) -> bool {
+    matches!(find_command(name), Ok(Some(_)))
+}
+
 /// Registers a callback to be called when a command is executed.
 ///
 /// # Arguments
@@ -359,20 +620,45 @@ unsafe extern "C" fn command_handler(
     phase: xplm_sys::XPLMCommandPhase,
     refcon: *mut ::std::os::raw::c_void,
 ) -> ::std::os::raw::c_int {
-    const CONTINUE_EXECUTION: ::std::os::raw::c_int = 1;
-    const TERMINATE_EXECUTION: ::std::os::raw::c_int = 1;
+    #[cfg(feature = "metrics")]
+    let _scope = crate::metrics::Scope::enter("command");
     let link = refcon as *mut CommandLink;
-    if (*link).links_with(command) {
+    let outcome = if (*link).links_with(command) {
         match phase as ::std::os::raw::c_uint {
             xplm_sys::xplm_CommandBegin => (*link).command_begin(),
             xplm_sys::xplm_CommandContinue => (*link).command_continue(),
             xplm_sys::xplm_CommandEnd => (*link).command_end(),
-            _ => {}
-        };
-        TERMINATE_EXECUTION
+            _ => CommandOutcome::PassThrough,
+        }
     } else {
-        CONTINUE_EXECUTION
-    }
+        CommandOutcome::PassThrough
+    };
+
+    outcome.into()
+}
+
+/// Registers a handler which runs before X-Plane and can consume the command
+/// by returning [`CommandOutcome::Consume`], replacing the sim behavior.
+///
+/// # Arguments
+/// * `command` - the command to intercept.
+/// * `handler` - the handler which handles command execution. See [`CommandHandler`].
+///
+/// # Returns
+/// Returns a [`CommandHandlerRecord`]. Dropping this record restores the default behavior.
+pub fn intercept_command<H: CommandHandler>(command: &Command, handler: H) -> CommandHandlerRecord {
+    register_command_handler(command, CommandExecutionTime::BeforeXPlane, handler)
+}
+
+/// Blocks a command so neither X-Plane nor handlers registered after X-Plane see it.
+///
+/// # Arguments
+/// * `command` - the command to block.
+///
+/// # Returns
+/// Returns a [`CommandHandlerRecord`]. Dropping this record unblocks the command.
+pub fn block_command(command: &Command) -> CommandHandlerRecord {
+    intercept_command(command, BlockingHandler)
 }
 
 /// Removes a command callback registered with [`register_command_handler`] API call.
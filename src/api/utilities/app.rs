@@ -24,12 +24,59 @@ impl TryFrom<xplm_sys::XPLMHostApplicationID> for HostApplicationId {
     }
 }
 
+/// X-Plane version, e.g. `12060` for X-Plane 12.06.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct XPlaneVersion(pub i32);
+
+impl XPlaneVersion {
+    /// X-Plane 11.00.
+    pub const V11: Self = Self(11000);
+    /// X-Plane 11.50.
+    pub const V11_50: Self = Self(11500);
+    /// X-Plane 12.00.
+    pub const V12: Self = Self(12000);
+
+    /// Returns the major X-Plane version, e.g. `12`.
+    pub fn major(&self) -> i32 {
+        self.0 / 1000
+    }
+}
+
+impl std::fmt::Display for XPlaneVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{:02}", self.0 / 1000, (self.0 % 1000) / 10)
+    }
+}
+
+/// XPLM SDK version, e.g. `400` for SDK 4.0.0.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct XplmVersion(pub i32);
+
+impl XplmVersion {
+    /// SDK 3.0.0, X-Plane 11.10.
+    pub const V300: Self = Self(300);
+    /// SDK 3.0.1, X-Plane 11.20.
+    pub const V301: Self = Self(301);
+    /// SDK 3.0.3, X-Plane 11.50.
+    pub const V303: Self = Self(303);
+    /// SDK 4.0.0, X-Plane 12.00.
+    pub const V400: Self = Self(400);
+    /// SDK 4.1.0, X-Plane 12.1.
+    pub const V410: Self = Self(410);
+}
+
+impl std::fmt::Display for XplmVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.0 / 100, (self.0 / 10) % 10, self.0 % 10)
+    }
+}
+
 /// X-Plane and XPLM versions.
 pub struct Versions {
     /// Host ID of the app running the plugin.
     pub app_id: HostApplicationId,
     /// X-Plane version.
-    pub xplane: i32,
+    pub xplane: XPlaneVersion,
     /// XPLM version.
-    pub xplm: i32,
+    pub xplm: XplmVersion,
 }
@@ -0,0 +1,69 @@
+//! Tracks command begin and end calls in debug builds.
+//!
+//! A command which begins but never ends leaves sim controls stuck, e.g. a held brake.
+//! In release builds the tracking compiles to nothing.
+
+#[cfg(debug_assertions)]
+use std::cell::RefCell;
+#[cfg(debug_assertions)]
+use std::collections::HashMap;
+
+#[cfg(debug_assertions)]
+thread_local! {
+    static COMMAND_BALANCE: RefCell<HashMap<usize, i64>> = RefCell::new(HashMap::new());
+}
+
+#[cfg(debug_assertions)]
+fn update(command: xplm_sys::XPLMCommandRef, delta: i64) {
+    COMMAND_BALANCE.with(|balance| {
+        let mut balance = balance.borrow_mut();
+        let key = command as usize;
+        let value = balance.entry(key).or_default();
+        *value += delta;
+        if *value == 0 {
+            balance.remove(&key);
+        }
+    });
+}
+
+/// Records a command begin.
+pub(super) fn record_begin(_command: xplm_sys::XPLMCommandRef) {
+    #[cfg(debug_assertions)]
+    update(_command, 1);
+}
+
+/// Records a command end.
+pub(super) fn record_end(_command: xplm_sys::XPLMCommandRef) {
+    #[cfg(debug_assertions)]
+    update(_command, -1);
+}
+
+/// Logs a warning for every command which began more or less times than it ended.
+/// Called automatically on plugin disable by [`crate::register_plugin`].
+/// Does nothing in release builds.
+///
+/// # Returns
+/// Returns the number of unbalanced commands.
+pub fn audit_command_balance() -> usize {
+    #[cfg(debug_assertions)]
+    {
+        COMMAND_BALANCE.with(|balance| {
+            let balance = balance.borrow();
+            for (command, value) in balance.iter() {
+                if *value > 0 {
+                    crate::warn!("command {:#x} began {} times without end", command, value);
+                } else {
+                    crate::warn!(
+                        "command {:#x} ended {} times without begin",
+                        command,
+                        -value
+                    );
+                }
+            }
+            balance.len()
+        })
+    }
+
+    #[cfg(not(debug_assertions))]
+    0
+}
@@ -0,0 +1,132 @@
+use std::time::{Duration, Instant};
+
+use super::{command_begin, command_end, command_once, Command};
+
+/// A way a [`CommandButton`] translates its state into command executions.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ButtonMode {
+    /// Executes the command once when the button is pressed.
+    Pulse,
+    /// Begins the command when the button is pressed and ends it when the button is released.
+    Hold,
+    /// Executes the command once when the button is pressed and then repeatedly
+    /// while the button is held down.
+    Repeat {
+        /// A delay before the first repetition.
+        delay: Duration,
+        /// An interval between repetitions.
+        interval: Duration,
+    },
+}
+
+/// Translates physical button states into balanced command executions.
+///
+/// The button never begins a command twice and always ends a begun command,
+/// including when it is dropped while pressed.
+pub struct CommandButton {
+    command: Command,
+    mode: ButtonMode,
+    pressed: bool,
+    began: bool,
+    next_repeat: Option<Instant>,
+}
+
+impl CommandButton {
+    /// Creates a new released button.
+    ///
+    /// # Arguments
+    /// * `command` - a command to execute.
+    /// * `mode` - a button mode. See [`ButtonMode`] for more details.
+    ///
+    /// # Returns
+    /// Returns a new [`CommandButton`] instance.
+    pub fn new(command: Command, mode: ButtonMode) -> Self {
+        Self {
+            command,
+            mode,
+            pressed: false,
+            began: false,
+            next_repeat: None,
+        }
+    }
+
+    /// Checks wether the button is pressed.
+    ///
+    /// # Returns
+    /// Returns `true` if the button is pressed. Otherwise returns `false`.
+    pub fn is_pressed(&self) -> bool {
+        self.pressed
+    }
+
+    /// Updates the button state. Repeated states are ignored.
+    ///
+    /// # Arguments
+    /// * `pressed` - `true` if the button is pressed, `false` if released.
+    pub fn set_pressed(&mut self, pressed: bool) {
+        if pressed {
+            self.press();
+        } else {
+            self.release();
+        }
+    }
+
+    /// Presses the button. Does nothing if the button is already pressed.
+    pub fn press(&mut self) {
+        if self.pressed {
+            return;
+        }
+
+        self.pressed = true;
+        match self.mode {
+            ButtonMode::Pulse => command_once(&self.command),
+            ButtonMode::Hold => {
+                if !self.began {
+                    command_begin(&self.command);
+                    self.began = true;
+                }
+            }
+            ButtonMode::Repeat { delay, .. } => {
+                command_once(&self.command);
+                self.next_repeat = Some(Instant::now() + delay);
+            }
+        }
+    }
+
+    /// Releases the button. Does nothing if the button is already released.
+    pub fn release(&mut self) {
+        if !self.pressed {
+            return;
+        }
+
+        self.pressed = false;
+        self.next_repeat = None;
+        self.end();
+    }
+
+    /// Executes repetitions which are due. Should be called periodically,
+    /// e.g. from a flight loop, when the button uses [`ButtonMode::Repeat`].
+    pub fn update(&mut self) {
+        if let (ButtonMode::Repeat { interval, .. }, Some(next_repeat)) =
+            (self.mode, self.next_repeat)
+        {
+            let now = Instant::now();
+            if now >= next_repeat {
+                command_once(&self.command);
+                self.next_repeat = Some(now + interval);
+            }
+        }
+    }
+
+    fn end(&mut self) {
+        if self.began {
+            command_end(&self.command);
+            self.began = false;
+        }
+    }
+}
+
+impl Drop for CommandButton {
+    fn drop(&mut self) {
+        self.end();
+    }
+}
@@ -0,0 +1,48 @@
+use super::{Versions, XplmVersion};
+
+/// SDK features available in the running X-Plane, computed from the XPLM version.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct SdkCapabilities {
+    /// Modern windows created with `XPLMCreateWindowEx`, XPLM 3.00 and later.
+    pub modern_windows: bool,
+    /// Object instancing API, XPLM 3.00 and later.
+    pub instancing: bool,
+    /// VR windows positioning, XPLM 3.01 and later.
+    pub vr: bool,
+    /// TCAS override and traffic data refs, XPLM 3.03 and later.
+    pub tcas_override: bool,
+    /// FMOD sound API, XPLM 4.00 and later.
+    pub sound: bool,
+    /// Avionics draw callbacks and screens, XPLM 4.00 and later.
+    pub avionics: bool,
+    /// Data refs enumeration and introspection, XPLM 4.00 and later.
+    pub data_ref_introspection: bool,
+    /// Weather reading API, XPLM 4.00 and later.
+    pub weather: bool,
+    /// Weather injection API, XPLM 4.10 and later.
+    pub weather_injection: bool,
+}
+
+impl SdkCapabilities {
+    /// Computes capabilities from X-Plane versions.
+    ///
+    /// # Arguments
+    /// * `versions` - X-Plane and XPLM versions. See [`Versions`] for more details.
+    ///
+    /// # Returns
+    /// Returns [`SdkCapabilities`] supported by the XPLM version.
+    pub fn from_versions(versions: &Versions) -> Self {
+        let xplm = versions.xplm;
+        Self {
+            modern_windows: xplm >= XplmVersion::V300,
+            instancing: xplm >= XplmVersion::V300,
+            vr: xplm >= XplmVersion::V301,
+            tcas_override: xplm >= XplmVersion::V303,
+            sound: xplm >= XplmVersion::V400,
+            avionics: xplm >= XplmVersion::V400,
+            data_ref_introspection: xplm >= XplmVersion::V400,
+            weather: xplm >= XplmVersion::V400,
+            weather_injection: xplm >= XplmVersion::V410,
+        }
+    }
+}
@@ -1,9 +1,12 @@
 use std::ops::Deref;
 
-use super::{unregister_command_handler, UtilitiesError};
+use crate::plugin::main_thread::MainThreadOnly;
 
-/// An opaque identifier for an X-Plane command
-pub struct Command(xplm_sys::XPLMCommandRef);
+use super::{command_begin, command_end, unregister_command_handler, UtilitiesError};
+
+/// An opaque identifier for an X-Plane command.
+/// Must only be used on the main thread, see [`crate::plugin::main_thread`].
+pub struct Command(xplm_sys::XPLMCommandRef, MainThreadOnly);
 
 impl TryFrom<xplm_sys::XPLMCommandRef> for Command {
     type Error = UtilitiesError;
@@ -12,7 +15,7 @@ impl TryFrom<xplm_sys::XPLMCommandRef> for Command {
         if value.is_null() {
             Err(Self::Error::InvalidCommand)
         } else {
-            Ok(Command(value))
+            Ok(Command(value, MainThreadOnly::default()))
         }
     }
 }
@@ -25,14 +28,130 @@ impl Deref for Command {
     }
 }
 
+impl Command {
+    /// Starts the execution of the command and keeps it running until the guard is dropped.
+    ///
+    /// # Returns
+    /// Returns a [`CommandGuard`] which ends the command when dropped.
+    pub fn begin(&self) -> CommandGuard {
+        command_begin(self);
+        CommandGuard(Command(self.0, MainThreadOnly::default()))
+    }
+}
+
+/// A running command started with [`Command::begin`]. Dropping the guard ends the command,
+/// so the command can't be left running by an early return.
+pub struct CommandGuard(Command);
+
+impl CommandGuard {
+    /// Returns the running command.
+    pub fn command(&self) -> &Command {
+        &self.0
+    }
+}
+
+impl Drop for CommandGuard {
+    fn drop(&mut self) {
+        command_end(&self.0);
+    }
+}
+
+/// A command returned from [`super::create_or_find_command`].
+pub enum CommandCreation {
+    /// The command did not exist and has been created by the calling plugin.
+    Created(Command),
+    /// The command already existed, e.g. created by X-Plane or another plugin.
+    Existing(Command),
+}
+
+impl CommandCreation {
+    /// Checks wether the command has been created by the call.
+    ///
+    /// # Returns
+    /// Returns `true` if the command is new. Otherwise returns `false`.
+    pub fn is_created(&self) -> bool {
+        matches!(self, Self::Created(_))
+    }
+
+    /// Returns the command regardless of wether it has been created or found.
+    pub fn command(&self) -> &Command {
+        match self {
+            Self::Created(command) | Self::Existing(command) => command,
+        }
+    }
+
+    /// Turns the result into the command.
+    pub fn into_command(self) -> Command {
+        match self {
+            Self::Created(command) | Self::Existing(command) => command,
+        }
+    }
+}
+
+/// An outcome of a command handler telling X-Plane wether to continue command processing.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CommandOutcome {
+    /// Let X-Plane and other handlers process the command. Returned to X-Plane as `1`.
+    PassThrough,
+    /// Stop the command processing, X-Plane and other handlers won't see the command.
+    /// Returned to X-Plane as `0`.
+    Consume,
+}
+
+impl CommandOutcome {
+    /// Checks wether the command processing stops after the handler.
+    ///
+    /// # Returns
+    /// Returns `true` if the command is consumed. Otherwise returns `false`.
+    pub fn is_consumed(&self) -> bool {
+        matches!(self, Self::Consume)
+    }
+}
+
+impl From<::std::os::raw::c_int> for CommandOutcome {
+    fn from(value: ::std::os::raw::c_int) -> Self {
+        if value == 0 {
+            Self::Consume
+        } else {
+            Self::PassThrough
+        }
+    }
+}
+
+impl From<CommandOutcome> for ::std::os::raw::c_int {
+    fn from(value: CommandOutcome) -> Self {
+        match value {
+            CommandOutcome::PassThrough => 1,
+            CommandOutcome::Consume => 0,
+        }
+    }
+}
+
 /// Command handler.
 pub trait CommandHandler: 'static {
     /// Called when the command begins (corresponds to a button being pressed down)
-    fn command_begin(&mut self);
+    fn command_begin(&mut self) -> CommandOutcome;
     /// Called frequently while the command button is held down
-    fn command_continue(&mut self);
+    fn command_continue(&mut self) -> CommandOutcome;
     /// Called when the command ends (corresponds to a button being released)
-    fn command_end(&mut self);
+    fn command_end(&mut self) -> CommandOutcome;
+}
+
+/// A command handler which consumes every phase of a command.
+pub struct BlockingHandler;
+
+impl CommandHandler for BlockingHandler {
+    fn command_begin(&mut self) -> CommandOutcome {
+        CommandOutcome::Consume
+    }
+
+    fn command_continue(&mut self) -> CommandOutcome {
+        CommandOutcome::Consume
+    }
+
+    fn command_end(&mut self) -> CommandOutcome {
+        CommandOutcome::Consume
+    }
 }
 
 /// A link to [`CommandHandler`] for a given command.
@@ -58,16 +177,16 @@ impl CommandLink {
 }
 
 impl CommandHandler for CommandLink {
-    fn command_begin(&mut self) {
-        self.handler.command_begin();
+    fn command_begin(&mut self) -> CommandOutcome {
+        self.handler.command_begin()
     }
 
-    fn command_continue(&mut self) {
-        self.handler.command_continue();
+    fn command_continue(&mut self) -> CommandOutcome {
+        self.handler.command_continue()
     }
 
-    fn command_end(&mut self) {
-        self.handler.command_end();
+    fn command_end(&mut self) -> CommandOutcome {
+        self.handler.command_end()
     }
 }
 
@@ -0,0 +1,72 @@
+use std::collections::VecDeque;
+use std::{ffi, path};
+
+use super::os_path;
+use crate::api::backend::backend;
+
+/// A size of the buffer which receives file names in a single call.
+const NAMES_BUF_LEN: usize = 16 * 1024;
+/// A maximum number of file names returned in a single call.
+const INDICES_LEN: usize = 256;
+
+/// An iterator over the contents of a directory created by
+/// [`super::get_directory_contents`]. Fetches the file names in batches
+/// and continues from the last returned file until the directory is exhausted.
+pub struct DirectoryContents {
+    dir: path::PathBuf,
+    dir_c: ffi::CString,
+    offset: ::std::os::raw::c_int,
+    finished: bool,
+    pending: VecDeque<path::PathBuf>,
+}
+
+impl DirectoryContents {
+    pub(super) fn new(dir: path::PathBuf, dir_c: ffi::CString) -> Self {
+        Self {
+            dir,
+            dir_c,
+            offset: 0,
+            finished: false,
+            pending: VecDeque::new(),
+        }
+    }
+
+    fn fetch(&mut self) {
+        let mut names = vec![0 as ::std::os::raw::c_char; NAMES_BUF_LEN];
+        let mut indices = [std::ptr::null_mut(); INDICES_LEN];
+        let mut total = 0;
+        let mut returned = 0;
+        let is_complete = backend().get_directory_contents(
+            &self.dir_c,
+            self.offset,
+            &mut names,
+            &mut indices,
+            &mut total,
+            &mut returned,
+        );
+
+        let returned = returned.clamp(0, INDICES_LEN as _);
+        for name in indices.iter().take(returned as usize) {
+            if !name.is_null() {
+                let name = unsafe { ffi::CStr::from_ptr(*name) };
+                self.pending
+                    .push_back(self.dir.join(os_path::path_from_c_str(name)));
+            }
+        }
+
+        self.offset += returned;
+        self.finished = is_complete || returned == 0 || self.offset >= total;
+    }
+}
+
+impl Iterator for DirectoryContents {
+    type Item = path::PathBuf;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pending.is_empty() && !self.finished {
+            self.fetch();
+        }
+
+        self.pending.pop_front()
+    }
+}
@@ -1,4 +1,9 @@
-use std::{ffi, str};
+use std::{ffi, io, path, str};
+
+use crate::api::naming::NameError;
+use crate::api::plugin::PluginError;
+
+use super::XplmVersion;
 
 /// An error returned from utilities API calls.
 #[derive(thiserror::Error, Debug)]
@@ -6,9 +11,9 @@ pub enum UtilitiesError {
     /// Invalid system path string returned from X-Plane.
     #[error("invalid system path {0}")]
     InvalidSystemPath(ffi::IntoStringError),
-    /// Invalid preferences path string returned from X-Plane.
-    #[error("invalid preferences path {0}")]
-    InvalidPrefsPath(ffi::IntoStringError),
+    /// Invalid plugin or aircraft path.
+    #[error("invalid path {}", .0.display())]
+    InvalidPath(path::PathBuf),
     /// Invalid directory separator returned from X-Plane.
     #[error("invalid directory separator {0}")]
     InvalidDirectorySeparator(str::Utf8Error),
@@ -21,6 +26,12 @@ pub enum UtilitiesError {
     /// Unable to load data file.
     #[error("unable to load data file")]
     LoadDataFile,
+    /// Data file extension does not match the data file type.
+    #[error("invalid data file extension {}", .0.display())]
+    InvalidDataFileExtension(path::PathBuf),
+    /// Unable to list data files.
+    #[error("unable to list data files {0}")]
+    ListDataFiles(io::Error),
     /// Unable to load data file.
     #[error("unable to clear replay")]
     ClearReplay,
@@ -45,7 +56,34 @@ pub enum UtilitiesError {
     /// Invalid command name string passed to X-Plane.
     #[error("invalid command name {0}")]
     InvalidCommandName(ffi::NulError),
+    /// Command name does not follow the SDK naming conventions.
+    #[error("invalid command name {0}")]
+    InvalidName(NameError),
     /// Invalid command description string passed to X-Plane.
     #[error("invalid command description {0}")]
     InvalidCommandDescription(ffi::NulError),
+    /// Invalid symbol name string passed to X-Plane.
+    #[error("invalid symbol name {0}")]
+    InvalidSymbolName(ffi::NulError),
+    /// The running X-Plane does not support a required SDK version.
+    #[error("unsupported SDK version {actual}, required {required}")]
+    UnsupportedSdkVersion {
+        required: XplmVersion,
+        actual: XplmVersion,
+    },
+    /// Plugin error.
+    #[error("plugin error {0}")]
+    Plugin(PluginError),
+}
+
+impl From<NameError> for UtilitiesError {
+    fn from(value: NameError) -> Self {
+        Self::InvalidName(value)
+    }
+}
+
+impl From<PluginError> for UtilitiesError {
+    fn from(value: PluginError) -> Self {
+        Self::Plugin(value)
+    }
 }
@@ -1,8 +1,27 @@
 /// Types of data files you can load or unload using the SDK.
 #[repr(u32)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum DataFileType {
     /// A situation (.sit) file, which starts off a flight in a given configuration.
     Situation = xplm_sys::xplm_DataFile_Situation,
-    /// A situation movie (.smo) file, which replays a past flight.
+    /// A situation movie (.rep) file, which replays a past flight.
     ReplayMovie = xplm_sys::xplm_DataFile_ReplayMovie,
 }
+
+impl DataFileType {
+    /// Returns the file extension of the data file type.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Situation => "sit",
+            Self::ReplayMovie => "rep",
+        }
+    }
+
+    /// Returns the folder relative to the X-System folder where X-Plane keeps the data files.
+    pub fn default_dir(&self) -> &'static str {
+        match self {
+            Self::Situation => "Output/situations",
+            Self::ReplayMovie => "Output/replays",
+        }
+    }
+}
@@ -2,7 +2,7 @@ use super::UtilitiesError;
 
 /// A cross-platform virtual key codes for every distinct keyboard press on the computer.
 #[repr(u32)]
-#[derive(Debug)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum VirtualKey {
     Back = xplm_sys::XPLM_VK_BACK,
     Tab = xplm_sys::XPLM_VK_TAB,
@@ -121,7 +121,7 @@ impl TryFrom<::std::os::raw::c_char> for VirtualKey {
     type Error = UtilitiesError;
 
     fn try_from(value: ::std::os::raw::c_char) -> std::result::Result<Self, Self::Error> {
-        match value as _ {
+        match value as u8 as _ {
             xplm_sys::XPLM_VK_BACK => Ok(Self::Back),
             xplm_sys::XPLM_VK_TAB => Ok(Self::Tab),
             xplm_sys::XPLM_VK_CLEAR => Ok(Self::Clear),
@@ -0,0 +1,126 @@
+//! Conversions between X-Plane path strings and OS paths.
+//!
+//! X-Plane passes paths as UTF-8, but older setups and localized Windows installations
+//! may produce strings in the active code page. The conversions below never fail on
+//! non UTF-8 input so plugins keep working for users with non-ASCII install paths.
+
+use std::borrow::Cow;
+use std::{ffi, path};
+
+/// Converts a nul-terminated C string into a path.
+///
+/// # Arguments
+/// * `value` - a C string returned from X-Plane.
+///
+/// # Returns
+/// Returns an OS path.
+pub fn path_from_c_str(value: &ffi::CStr) -> path::PathBuf {
+    path_from_bytes(value.to_bytes())
+}
+
+/// Converts a nul-terminated buffer filled by X-Plane into a path.
+/// If the buffer has no nul terminator the whole buffer is used.
+///
+/// # Arguments
+/// * `buf` - a buffer filled by X-Plane.
+///
+/// # Returns
+/// Returns an OS path.
+pub fn path_from_c_buf(buf: &[::std::os::raw::c_char]) -> path::PathBuf {
+    let bytes: Vec<u8> = buf
+        .iter()
+        .take_while(|byte| **byte != 0)
+        .map(|byte| *byte as u8)
+        .collect();
+    path_from_bytes(&bytes)
+}
+
+/// Converts a path into bytes which can be passed to X-Plane.
+///
+/// # Arguments
+/// * `value` - an OS path.
+///
+/// # Returns
+/// Returns path bytes or [`None`] if the path can't be represented in X-Plane's encoding.
+pub fn path_to_bytes(value: &path::Path) -> Option<Cow<'_, [u8]>> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+        Some(Cow::Borrowed(value.as_os_str().as_bytes()))
+    }
+
+    #[cfg(not(unix))]
+    {
+        value.to_str().map(|value| Cow::Borrowed(value.as_bytes()))
+    }
+}
+
+#[cfg(unix)]
+fn path_from_bytes(bytes: &[u8]) -> path::PathBuf {
+    use std::os::unix::ffi::OsStrExt;
+    path::PathBuf::from(ffi::OsStr::from_bytes(bytes))
+}
+
+#[cfg(windows)]
+fn path_from_bytes(bytes: &[u8]) -> path::PathBuf {
+    match std::str::from_utf8(bytes) {
+        Ok(value) => path::PathBuf::from(value),
+        Err(_) => path::PathBuf::from(decode_active_code_page(bytes)),
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+fn path_from_bytes(bytes: &[u8]) -> path::PathBuf {
+    path::PathBuf::from(String::from_utf8_lossy(bytes).into_owned())
+}
+
+#[cfg(windows)]
+fn decode_active_code_page(bytes: &[u8]) -> ffi::OsString {
+    use std::os::windows::ffi::OsStringExt;
+
+    const CP_ACP: u32 = 0;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn MultiByteToWideChar(
+            code_page: u32,
+            flags: u32,
+            multi_byte: *const u8,
+            multi_byte_len: i32,
+            wide_char: *mut u16,
+            wide_char_len: i32,
+        ) -> i32;
+    }
+
+    if bytes.is_empty() {
+        return ffi::OsString::new();
+    }
+
+    let len = unsafe {
+        MultiByteToWideChar(
+            CP_ACP,
+            0,
+            bytes.as_ptr(),
+            bytes.len() as i32,
+            std::ptr::null_mut(),
+            0,
+        )
+    };
+    if len <= 0 {
+        return ffi::OsString::from(String::from_utf8_lossy(bytes).into_owned());
+    }
+
+    let mut wide = vec![0u16; len as usize];
+    let written = unsafe {
+        MultiByteToWideChar(
+            CP_ACP,
+            0,
+            bytes.as_ptr(),
+            bytes.len() as i32,
+            wide.as_mut_ptr(),
+            len,
+        )
+    };
+    wide.truncate(written.max(0) as usize);
+    ffi::OsString::from_wide(&wide)
+}
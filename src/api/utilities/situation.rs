@@ -0,0 +1,154 @@
+use std::{fs, path};
+
+use super::{
+    get_system_path, load_data_file, save_data_file, DataFileType, Result, UtilitiesError,
+};
+
+/// A situation (.sit) file relative to the X-System folder.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Situation(path::PathBuf);
+
+impl Situation {
+    /// Creates a new situation file reference.
+    ///
+    /// # Arguments
+    /// * `path` - a file path relative to the X-System folder.
+    ///
+    /// # Returns
+    /// Returns [`Situation`] on success. Otherwise returns
+    /// [`UtilitiesError::InvalidDataFileExtension`] if the file is not a situation file.
+    pub fn new<P: Into<path::PathBuf>>(path: P) -> Result<Self> {
+        validate_extension(path.into(), DataFileType::Situation).map(Self)
+    }
+
+    /// Lists all situation files in the X-Plane situations folder.
+    ///
+    /// # Returns
+    /// Returns a list of situations on success. Otherwise returns [`UtilitiesError`].
+    pub fn list() -> Result<Vec<Self>> {
+        list_data_files(DataFileType::Situation).map(|paths| paths.into_iter().map(Self).collect())
+    }
+
+    /// Returns the file path relative to the X-System folder.
+    pub fn path(&self) -> &path::Path {
+        &self.0
+    }
+
+    /// Loads the situation.
+    ///
+    /// # Returns
+    /// Returns `Ok` in case of success. Otherwise returns [`UtilitiesError`].
+    pub fn load(&self) -> Result<()> {
+        load_data_file(DataFileType::Situation, &self.0)
+    }
+
+    /// Saves the current situation in the X-Plane situations folder.
+    ///
+    /// # Arguments
+    /// * `name` - a file name without extension.
+    ///
+    /// # Returns
+    /// Returns the saved [`Situation`] on success. Otherwise returns [`UtilitiesError`].
+    pub fn save_as<T: AsRef<str>>(name: T) -> Result<Self> {
+        save_as(DataFileType::Situation, name.as_ref()).map(Self)
+    }
+}
+
+/// A replay (.rep) file relative to the X-System folder.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Replay(path::PathBuf);
+
+impl Replay {
+    /// Creates a new replay file reference.
+    ///
+    /// # Arguments
+    /// * `path` - a file path relative to the X-System folder.
+    ///
+    /// # Returns
+    /// Returns [`Replay`] on success. Otherwise returns
+    /// [`UtilitiesError::InvalidDataFileExtension`] if the file is not a replay file.
+    pub fn new<P: Into<path::PathBuf>>(path: P) -> Result<Self> {
+        validate_extension(path.into(), DataFileType::ReplayMovie).map(Self)
+    }
+
+    /// Lists all replay files in the X-Plane replays folder.
+    ///
+    /// # Returns
+    /// Returns a list of replays on success. Otherwise returns [`UtilitiesError`].
+    pub fn list() -> Result<Vec<Self>> {
+        list_data_files(DataFileType::ReplayMovie)
+            .map(|paths| paths.into_iter().map(Self).collect())
+    }
+
+    /// Returns the file path relative to the X-System folder.
+    pub fn path(&self) -> &path::Path {
+        &self.0
+    }
+
+    /// Loads the replay.
+    ///
+    /// # Returns
+    /// Returns `Ok` in case of success. Otherwise returns [`UtilitiesError`].
+    pub fn load(&self) -> Result<()> {
+        load_data_file(DataFileType::ReplayMovie, &self.0)
+    }
+
+    /// Saves the current replay in the X-Plane replays folder.
+    ///
+    /// # Arguments
+    /// * `name` - a file name without extension.
+    ///
+    /// # Returns
+    /// Returns the saved [`Replay`] on success. Otherwise returns [`UtilitiesError`].
+    pub fn save_as<T: AsRef<str>>(name: T) -> Result<Self> {
+        save_as(DataFileType::ReplayMovie, name.as_ref()).map(Self)
+    }
+}
+
+fn validate_extension(path: path::PathBuf, file_type: DataFileType) -> Result<path::PathBuf> {
+    if has_extension(&path, file_type) {
+        Ok(path)
+    } else {
+        Err(UtilitiesError::InvalidDataFileExtension(path))
+    }
+}
+
+fn has_extension(path: &path::Path, file_type: DataFileType) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case(file_type.extension()))
+}
+
+fn save_as(file_type: DataFileType, name: &str) -> Result<path::PathBuf> {
+    let path = path::Path::new(file_type.default_dir())
+        .join(name)
+        .with_extension(file_type.extension());
+    save_data_file(file_type, &path)?;
+    Ok(path)
+}
+
+fn list_data_files(file_type: DataFileType) -> Result<Vec<path::PathBuf>> {
+    let system_path = get_system_path()?;
+    let mut pending = vec![system_path.join(file_type.default_dir())];
+    let mut files = Vec::new();
+
+    while let Some(dir) = pending.pop() {
+        if !dir.is_dir() {
+            continue;
+        }
+
+        for entry in fs::read_dir(&dir).map_err(UtilitiesError::ListDataFiles)? {
+            let path = entry.map_err(UtilitiesError::ListDataFiles)?.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else if has_extension(&path, file_type) {
+                if let Ok(relative) = path.strip_prefix(&system_path) {
+                    files.push(relative.to_path_buf());
+                }
+            }
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
@@ -0,0 +1,4 @@
+//! Development tools which help to test plugins without running X-Plane.
+//! Available with the `devtools` feature enabled.
+
+pub mod mock;
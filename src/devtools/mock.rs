@@ -0,0 +1,3 @@
+pub mod input;
+
+pub use self::input::{InputEvent, InputRecorder, InputRecording};
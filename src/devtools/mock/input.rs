@@ -0,0 +1,199 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::api::display::{Coord, EventState, KeyFlags, MouseStatus, WheelAxis};
+use crate::api::display::{WindowHandler, WindowId};
+use crate::api::utilities::VirtualKey;
+
+/// An input event delivered to a window.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum InputEvent {
+    /// A mouse click event.
+    MouseClick {
+        /// Coordinates at which mouse event occured.
+        coord: Coord,
+        /// The mouse status.
+        status: MouseStatus,
+    },
+    /// A key event.
+    Key {
+        /// The key character which has been pressed or released.
+        key: char,
+        /// The virtual key which has been pressed or released.
+        virtual_key: VirtualKey,
+        /// The key flags bitmap.
+        flags: KeyFlags,
+    },
+    /// A cursor move event.
+    Cursor {
+        /// Coordinates at which cursor event occured.
+        coord: Coord,
+    },
+    /// A mouse wheel event.
+    MouseWheel {
+        /// Coordinates at which mouse event occured.
+        coord: Coord,
+        /// The direction of wheel axis.
+        wheel_axis: WheelAxis,
+        /// Number of clicks wheel performed after the last event.
+        clicks: i32,
+    },
+}
+
+/// A sequence of recorded input events.
+#[derive(Debug, Default, Clone)]
+pub struct InputRecording {
+    events: Vec<InputEvent>,
+}
+
+impl InputRecording {
+    /// Creates a new recording from a list of events.
+    ///
+    /// # Arguments
+    /// * `events` - a list of input events.
+    ///
+    /// # Returns
+    /// Returns a new recording.
+    pub fn new(events: Vec<InputEvent>) -> Self {
+        Self { events }
+    }
+
+    /// Appends an event to the recording.
+    ///
+    /// # Arguments
+    /// * `event` - an event to append.
+    pub fn push(&mut self, event: InputEvent) {
+        self.events.push(event);
+    }
+
+    /// Returns recorded events.
+    ///
+    /// # Returns
+    /// Returns a slice of recorded events.
+    pub fn events(&self) -> &[InputEvent] {
+        &self.events
+    }
+
+    /// Removes all recorded events.
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+
+    /// Delivers recorded events to a window handler in the order they were recorded.
+    ///
+    /// # Arguments
+    /// * `id` - a window identifier passed to the handler.
+    /// * `handler` - a window handler to deliver events to.
+    ///
+    /// # Returns
+    /// Returns an event state for every replayed event. Events which don't
+    /// return an event state produce [`None`].
+    pub fn replay<H: WindowHandler + ?Sized>(
+        &self,
+        id: &WindowId,
+        handler: &mut H,
+    ) -> Vec<Option<EventState>> {
+        self.events
+            .iter()
+            .map(|event| match *event {
+                InputEvent::MouseClick { coord, status } => {
+                    Some(handler.mouse_click(id, coord, status))
+                }
+                InputEvent::Key {
+                    key,
+                    virtual_key,
+                    flags,
+                } => {
+                    handler.handle_key(id, key, virtual_key, flags);
+                    None
+                }
+                InputEvent::Cursor { coord } => {
+                    handler.handle_cursor(id, coord);
+                    None
+                }
+                InputEvent::MouseWheel {
+                    coord,
+                    wheel_axis,
+                    clicks,
+                } => Some(handler.handle_mouse_wheel(id, coord, wheel_axis, clicks)),
+            })
+            .collect()
+    }
+}
+
+/// A window handler wrapper which records every input event before
+/// forwarding it to the wrapped handler.
+pub struct InputRecorder<H: WindowHandler> {
+    handler: H,
+    recording: Rc<RefCell<InputRecording>>,
+}
+
+impl<H: WindowHandler> InputRecorder<H> {
+    /// Creates a new recorder.
+    ///
+    /// # Arguments
+    /// * `handler` - a window handler to forward events to.
+    ///
+    /// # Returns
+    /// Returns a new recorder instance.
+    pub fn new(handler: H) -> Self {
+        Self {
+            handler,
+            recording: Rc::default(),
+        }
+    }
+
+    /// Returns a shared recording which stays accessible after the recorder
+    /// is moved into a window.
+    ///
+    /// # Returns
+    /// Returns a shared recording.
+    pub fn recording(&self) -> Rc<RefCell<InputRecording>> {
+        self.recording.clone()
+    }
+
+    fn record(&self, event: InputEvent) {
+        self.recording.borrow_mut().push(event);
+    }
+}
+
+impl<H: WindowHandler> WindowHandler for InputRecorder<H> {
+    fn draw(&mut self, id: &WindowId) {
+        self.handler.draw(id);
+    }
+
+    fn mouse_click(&mut self, id: &WindowId, coord: Coord, status: MouseStatus) -> EventState {
+        self.record(InputEvent::MouseClick { coord, status });
+        self.handler.mouse_click(id, coord, status)
+    }
+
+    fn handle_key(&mut self, id: &WindowId, key: char, virtual_key: VirtualKey, flags: KeyFlags) {
+        self.record(InputEvent::Key {
+            key,
+            virtual_key,
+            flags,
+        });
+        self.handler.handle_key(id, key, virtual_key, flags);
+    }
+
+    fn handle_cursor(&mut self, id: &WindowId, coord: Coord) {
+        self.record(InputEvent::Cursor { coord });
+        self.handler.handle_cursor(id, coord);
+    }
+
+    fn handle_mouse_wheel(
+        &mut self,
+        id: &WindowId,
+        coord: Coord,
+        wheel_axis: WheelAxis,
+        clicks: i32,
+    ) -> EventState {
+        self.record(InputEvent::MouseWheel {
+            coord,
+            wheel_axis,
+            clicks,
+        });
+        self.handler
+            .handle_mouse_wheel(id, coord, wheel_axis, clicks)
+    }
+}
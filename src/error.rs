@@ -0,0 +1,170 @@
+use crate::api::data_access::DataAccessError;
+use crate::api::display::DisplayError;
+use crate::api::graphics::GraphicsError;
+use crate::api::instance::InstanceError;
+use crate::api::menus::MenusError;
+use crate::api::navigation::NavigationError;
+use crate::api::plugin::PluginError;
+use crate::api::scenery::SceneryError;
+use crate::api::utilities::UtilitiesError;
+#[cfg(feature = "net")]
+use crate::net::NetError;
+#[cfg(feature = "prefs")]
+use crate::prefs::PrefsError;
+#[cfg(feature = "scenery-data")]
+use crate::scenery_data::SceneryDataError;
+use crate::sim::SimError;
+#[cfg(feature = "telemetry")]
+use crate::telemetry::TelemetryError;
+use crate::ui::UiError;
+
+/// A crate level error every API error converts into.
+/// Use it to handle errors from different API modules with a single `?`.
+#[derive(thiserror::Error, Debug)]
+pub enum XplmError {
+    /// Data access error.
+    #[error("data access error {0}")]
+    DataAccess(#[source] DataAccessError),
+    /// Display error.
+    #[error("display error {0}")]
+    Display(#[source] DisplayError),
+    /// Graphics error.
+    #[error("graphics error {0}")]
+    Graphics(#[source] GraphicsError),
+    /// Instance error.
+    #[error("instance error {0}")]
+    Instance(#[source] InstanceError),
+    /// Menus error.
+    #[error("menus error {0}")]
+    Menus(#[source] MenusError),
+    /// Navigation error.
+    #[error("navigation error {0}")]
+    Navigation(#[source] NavigationError),
+    /// Plugin error.
+    #[error("plugin error {0}")]
+    Plugin(#[source] PluginError),
+    /// Preferences error.
+    #[cfg(feature = "prefs")]
+    #[error("preferences error {0}")]
+    Prefs(#[source] PrefsError),
+    /// Scenery error.
+    #[error("scenery error {0}")]
+    Scenery(#[source] SceneryError),
+    /// Utilities error.
+    #[error("utilities error {0}")]
+    Utilities(#[source] UtilitiesError),
+    /// Sim façade error.
+    #[error("sim error {0}")]
+    Sim(#[source] SimError),
+    /// Scenery data error.
+    #[cfg(feature = "scenery-data")]
+    #[error("scenery data error {0}")]
+    SceneryData(#[source] SceneryDataError),
+    /// User interface error.
+    #[error("ui error {0}")]
+    Ui(#[source] UiError),
+    /// Networking error.
+    #[cfg(feature = "net")]
+    #[error("net error {0}")]
+    Net(#[source] NetError),
+    /// Telemetry export error.
+    #[cfg(feature = "telemetry")]
+    #[error("telemetry error {0}")]
+    Telemetry(#[source] TelemetryError),
+}
+
+impl From<DataAccessError> for XplmError {
+    fn from(value: DataAccessError) -> Self {
+        Self::DataAccess(value)
+    }
+}
+
+impl From<DisplayError> for XplmError {
+    fn from(value: DisplayError) -> Self {
+        Self::Display(value)
+    }
+}
+
+impl From<GraphicsError> for XplmError {
+    fn from(value: GraphicsError) -> Self {
+        Self::Graphics(value)
+    }
+}
+
+impl From<InstanceError> for XplmError {
+    fn from(value: InstanceError) -> Self {
+        Self::Instance(value)
+    }
+}
+
+impl From<MenusError> for XplmError {
+    fn from(value: MenusError) -> Self {
+        Self::Menus(value)
+    }
+}
+
+impl From<NavigationError> for XplmError {
+    fn from(value: NavigationError) -> Self {
+        Self::Navigation(value)
+    }
+}
+
+impl From<PluginError> for XplmError {
+    fn from(value: PluginError) -> Self {
+        Self::Plugin(value)
+    }
+}
+
+#[cfg(feature = "prefs")]
+impl From<PrefsError> for XplmError {
+    fn from(value: PrefsError) -> Self {
+        Self::Prefs(value)
+    }
+}
+
+impl From<SceneryError> for XplmError {
+    fn from(value: SceneryError) -> Self {
+        Self::Scenery(value)
+    }
+}
+
+impl From<UtilitiesError> for XplmError {
+    fn from(value: UtilitiesError) -> Self {
+        Self::Utilities(value)
+    }
+}
+
+impl From<SimError> for XplmError {
+    fn from(value: SimError) -> Self {
+        Self::Sim(value)
+    }
+}
+
+#[cfg(feature = "scenery-data")]
+impl From<SceneryDataError> for XplmError {
+    fn from(value: SceneryDataError) -> Self {
+        Self::SceneryData(value)
+    }
+}
+
+impl From<UiError> for XplmError {
+    fn from(value: UiError) -> Self {
+        Self::Ui(value)
+    }
+}
+
+#[cfg(feature = "net")]
+impl From<NetError> for XplmError {
+    fn from(value: NetError) -> Self {
+        Self::Net(value)
+    }
+}
+
+#[cfg(feature = "telemetry")]
+impl From<TelemetryError> for XplmError {
+    fn from(value: TelemetryError) -> Self {
+        Self::Telemetry(value)
+    }
+}
+
+pub type Result<T> = std::result::Result<T, XplmError>;
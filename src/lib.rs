@@ -1,3 +1,26 @@
 pub mod api;
+#[cfg(feature = "devtools")]
+pub mod devtools;
+pub mod error;
 pub mod log;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+#[cfg(feature = "net")]
+pub mod net;
+pub mod package;
 pub mod plugin;
+#[cfg(feature = "prefs")]
+pub mod prefs;
+pub mod scaffold;
+#[cfg(feature = "scenery-data")]
+pub mod scenery_data;
+pub mod sim;
+#[cfg(feature = "telemetry")]
+pub mod telemetry;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod ui;
+
+pub use crate::error::{Result, XplmError};
+#[cfg(feature = "macros")]
+pub use xplm_macros::command_handler;
@@ -1,3 +1,11 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// The interval during which a repeated message is not spoken again.
+pub const SPEAK_REPEAT_INTERVAL: Duration = Duration::from_secs(5);
+
+static LAST_SPOKEN: Mutex<Option<(String, Instant)>> = Mutex::new(None);
+
 pub enum Level {
     Info,
     Warn,
@@ -35,3 +43,39 @@ macro_rules! info {
     // info!("a {} event", "log")
     ($($arg:tt)+) => ($crate::log!($crate::log::Level::Info, $($arg)+))
 }
+
+#[macro_export]
+macro_rules! debug {
+    // debug!("a {} message", "raw")
+    ($($arg:tt)+) => ($crate::api::utilities::debug_string(format!("{}\n", format_args!($($arg)+))))
+}
+
+#[macro_export]
+macro_rules! speak {
+    // speak!("gear {}", "down")
+    ($($arg:tt)+) => ($crate::log::speak(format!($($arg)+)))
+}
+
+/// Speaks a message unless the same message was spoken within [`SPEAK_REPEAT_INTERVAL`].
+/// Use [`crate::speak!`] macro for formatted messages.
+///
+/// # Arguments
+/// * `message` - a message that will be spoken.
+///
+/// # Returns
+/// Returns `true` if the message was passed to X-Plane and `false` if it was throttled.
+pub fn speak<T: Into<String>>(message: T) -> bool {
+    let message = message.into();
+    let now = Instant::now();
+    if let Ok(mut last) = LAST_SPOKEN.lock() {
+        if let Some((last_message, spoken_at)) = last.as_ref() {
+            if *last_message == message && now.duration_since(*spoken_at) < SPEAK_REPEAT_INTERVAL {
+                return false;
+            }
+        }
+        *last = Some((message.clone(), now));
+    }
+
+    crate::api::utilities::speak_string(message);
+    true
+}
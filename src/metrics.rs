@@ -0,0 +1,199 @@
+//! Execution time metrics of the callbacks X-Plane calls into the plugin.
+//! Available with the `metrics` feature enabled.
+//!
+//! Every callback trampoline of the crate is measured under its own name, e.g. `window_draw`
+//! or `command`. Wrap parts of a handler in [`scope`] to attribute time to specific handlers.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+thread_local! {
+    static METRICS: RefCell<HashMap<&'static str, CallbackMetrics>> = RefCell::new(HashMap::new());
+}
+
+/// Execution statistics of a single callback.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct CallbackMetrics {
+    /// The number of invocations.
+    pub calls: u64,
+    /// The total execution time.
+    pub total: Duration,
+    /// The longest execution time.
+    pub max: Duration,
+}
+
+impl CallbackMetrics {
+    /// Returns the average execution time.
+    pub fn average(&self) -> Duration {
+        if self.calls == 0 {
+            Duration::ZERO
+        } else {
+            let nanos = self.total.as_nanos() / self.calls as u128;
+            Duration::from_nanos(nanos.try_into().unwrap_or(u64::MAX))
+        }
+    }
+
+    fn record(&mut self, elapsed: Duration) {
+        self.calls += 1;
+        self.total += elapsed;
+        self.max = self.max.max(elapsed);
+    }
+}
+
+/// A copy of the collected metrics.
+#[derive(Debug, Default, Clone)]
+pub struct MetricsSnapshot {
+    entries: Vec<(&'static str, CallbackMetrics)>,
+}
+
+impl MetricsSnapshot {
+    /// Returns metrics of a callback.
+    ///
+    /// # Arguments
+    /// * `name` - a callback or scope name.
+    ///
+    /// # Returns
+    /// Returns [`CallbackMetrics`] or [`None`] if the callback was never called.
+    pub fn get(&self, name: &str) -> Option<&CallbackMetrics> {
+        self.entries
+            .iter()
+            .find(|(entry, _)| *entry == name)
+            .map(|(_, metrics)| metrics)
+    }
+
+    /// Returns an iterator over the collected metrics, sorted by total execution time descending.
+    pub fn iter(&self) -> impl Iterator<Item = &(&'static str, CallbackMetrics)> {
+        self.entries.iter()
+    }
+
+    /// Checks whether no metrics were collected.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Measures the execution time of a scope and records it on drop.
+pub struct Scope {
+    name: &'static str,
+    started: Instant,
+}
+
+impl Scope {
+    /// Starts measuring a scope.
+    ///
+    /// # Arguments
+    /// * `name` - a scope name the time is attributed to.
+    ///
+    /// # Returns
+    /// Returns a [`Scope`] which records the elapsed time when dropped.
+    pub fn enter(name: &'static str) -> Self {
+        Self {
+            name,
+            started: Instant::now(),
+        }
+    }
+}
+
+impl Drop for Scope {
+    fn drop(&mut self) {
+        let elapsed = self.started.elapsed();
+        METRICS.with(|metrics| {
+            if let Ok(mut metrics) = metrics.try_borrow_mut() {
+                metrics.entry(self.name).or_default().record(elapsed);
+            }
+        });
+    }
+}
+
+/// Measures the execution time of a closure.
+///
+/// # Arguments
+/// * `name` - a scope name the time is attributed to.
+/// * `f` - a closure to measure.
+///
+/// # Returns
+/// Returns the closure result.
+pub fn scope<R, F: FnOnce() -> R>(name: &'static str, f: F) -> R {
+    let _scope = Scope::enter(name);
+    f()
+}
+
+/// Returns a copy of the metrics collected since the plugin start or the last [`reset`].
+pub fn snapshot() -> MetricsSnapshot {
+    let mut entries: Vec<_> = METRICS.with(|metrics| {
+        metrics
+            .borrow()
+            .iter()
+            .map(|(name, metrics)| (*name, *metrics))
+            .collect()
+    });
+    entries.sort_by(|(_, a), (_, b)| b.total.cmp(&a.total));
+    MetricsSnapshot { entries }
+}
+
+/// Clears the collected metrics.
+pub fn reset() {
+    METRICS.with(|metrics| metrics.borrow_mut().clear());
+}
+
+/// Writes the collected metrics to the `Log.txt` file.
+pub fn log_summary() {
+    let snapshot = snapshot();
+    for (name, metrics) in snapshot.iter() {
+        crate::info!(
+            "{}: {} calls, total {:?}, average {:?}, max {:?}",
+            name,
+            metrics.calls,
+            metrics.total,
+            metrics.average(),
+            metrics.max
+        );
+    }
+}
+
+/// Writes metrics summaries to the `Log.txt` file periodically.
+pub struct PeriodicSummary {
+    interval: Duration,
+    next: Instant,
+    reset: bool,
+}
+
+impl PeriodicSummary {
+    /// Creates a new periodic summary.
+    ///
+    /// # Arguments
+    /// * `interval` - an interval between summaries.
+    ///
+    /// # Returns
+    /// Returns a new [`PeriodicSummary`] instance.
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            next: Instant::now() + interval,
+            reset: false,
+        }
+    }
+
+    /// Clears the metrics after each summary, so every summary covers a single interval.
+    ///
+    /// # Returns
+    /// Returns a modified periodic summary.
+    pub fn reset_after_summary(mut self) -> Self {
+        self.reset = true;
+        self
+    }
+
+    /// Writes a summary if the interval elapsed.
+    /// Should be called periodically, e.g. from a flight loop or a draw callback.
+    pub fn poll(&mut self) {
+        let now = Instant::now();
+        if now >= self.next {
+            self.next = now + self.interval;
+            log_summary();
+            if self.reset {
+                reset();
+            }
+        }
+    }
+}
@@ -0,0 +1,64 @@
+//! Networking helpers which exchange sim data with external applications and devices.
+//! Available with the `net` feature enabled, each transport has its own feature.
+//!
+//! Sockets are served by worker threads, the data refs are only touched on the main thread.
+
+pub mod allowlist;
+pub mod error;
+#[cfg(feature = "http")]
+pub mod http;
+#[cfg(feature = "net-ingress")]
+pub mod ingress;
+#[cfg(feature = "serial")]
+pub mod serial;
+#[cfg(feature = "udp-out")]
+pub mod udp_out;
+#[cfg(feature = "websocket")]
+pub mod websocket;
+
+pub use self::allowlist::Allowlist;
+pub use self::error::NetError;
+
+pub type Result<T> = std::result::Result<T, NetError>;
+
+/// Reads an int, float or double data ref.
+#[cfg(any(feature = "serial", feature = "websocket"))]
+fn read_number(data_ref: &crate::api::data_access::DataRef) -> Option<f64> {
+    use crate::api::data_access::{
+        get_data_d, get_data_f, get_data_i, get_data_ref_types, DataType,
+    };
+
+    let types = get_data_ref_types(data_ref);
+    if types.contains(DataType::Double) {
+        Some(get_data_d(data_ref))
+    } else if types.contains(DataType::Float) {
+        Some(get_data_f(data_ref) as f64)
+    } else if types.contains(DataType::Int) {
+        Some(get_data_i(data_ref) as f64)
+    } else {
+        None
+    }
+}
+
+/// Writes an int, float or double data ref.
+///
+/// Returns `false` if the data ref has none of these types.
+#[cfg(any(feature = "net-ingress", feature = "serial", feature = "websocket"))]
+fn write_number(data_ref: &crate::api::data_access::DataRef, value: f64) -> bool {
+    use crate::api::data_access::{
+        get_data_ref_types, set_data_d, set_data_f, set_data_i, DataType,
+    };
+
+    let types = get_data_ref_types(data_ref);
+    if types.contains(DataType::Double) {
+        set_data_d(data_ref, value);
+    } else if types.contains(DataType::Float) {
+        set_data_f(data_ref, value as f32);
+    } else if types.contains(DataType::Int) {
+        set_data_i(data_ref, value.round() as _);
+    } else {
+        return false;
+    }
+
+    true
+}
@@ -0,0 +1,74 @@
+/// Data refs and commands remote clients are allowed to access.
+///
+/// A pattern matches a name exactly, or a name prefix when it ends with `*`,
+/// e.g. `sim/cockpit2/switches/*`. An empty allowlist rejects everything.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Allowlist {
+    data_refs: Vec<String>,
+    commands: Vec<String>,
+}
+
+impl Allowlist {
+    /// Creates an empty allowlist.
+    ///
+    /// # Returns
+    /// Returns a new [`Allowlist`] instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allows writing data refs.
+    ///
+    /// # Arguments
+    /// * `pattern` - a data ref name or prefix pattern.
+    ///
+    /// # Returns
+    /// Returns a modified allowlist.
+    pub fn data_ref<P: Into<String>>(mut self, pattern: P) -> Self {
+        self.data_refs.push(pattern.into());
+        self
+    }
+
+    /// Allows running commands.
+    ///
+    /// # Arguments
+    /// * `pattern` - a command name or prefix pattern.
+    ///
+    /// # Returns
+    /// Returns a modified allowlist.
+    pub fn command<P: Into<String>>(mut self, pattern: P) -> Self {
+        self.commands.push(pattern.into());
+        self
+    }
+
+    /// Checks wether a data ref can be written.
+    ///
+    /// # Arguments
+    /// * `name` - a data ref name.
+    ///
+    /// # Returns
+    /// Returns `true` if the data ref is allowed. Otherwise returns `false`.
+    pub fn allows_data_ref(&self, name: &str) -> bool {
+        matches_any(&self.data_refs, name)
+    }
+
+    /// Checks wether a command can be run.
+    ///
+    /// # Arguments
+    /// * `name` - a command name.
+    ///
+    /// # Returns
+    /// Returns `true` if the command is allowed. Otherwise returns `false`.
+    pub fn allows_command(&self, name: &str) -> bool {
+        matches_any(&self.commands, name)
+    }
+}
+
+fn matches_any(patterns: &[String], name: &str) -> bool {
+    patterns
+        .iter()
+        .any(|pattern| match pattern.strip_suffix('*') {
+            Some(prefix) => name.starts_with(prefix),
+            None => pattern == name,
+        })
+}
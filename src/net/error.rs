@@ -0,0 +1,43 @@
+use std::io;
+
+use crate::api::data_access::DataAccessError;
+
+/// An error returned from networking helpers.
+#[derive(thiserror::Error, Debug)]
+pub enum NetError {
+    /// Data access error.
+    #[error("data access error {0}")]
+    DataAccess(DataAccessError),
+    /// Socket can't be created or configured.
+    #[error("socket error {0}")]
+    Socket(io::Error),
+    /// Worker thread can't be started.
+    #[error("failed to start network worker {0}")]
+    Spawn(io::Error),
+    /// Worker thread has stopped.
+    #[error("network worker has stopped")]
+    WorkerStopped,
+    /// Data output group has too many values.
+    #[error("data group {index} has {len} values, at most 8 are allowed")]
+    InvalidGroup { index: i32, len: usize },
+    /// Received command can't be parsed or applied.
+    #[error("invalid command {0}")]
+    InvalidCommand(String),
+    /// Received command is not in the allowlist.
+    #[error("command not allowed {0}")]
+    NotAllowed(String),
+    /// HTTP request failed before a response was received.
+    #[cfg(feature = "http")]
+    #[error("http error {0}")]
+    Http(Box<ureq::Error>),
+    /// Serial port can't be opened.
+    #[cfg(feature = "serial")]
+    #[error("serial port error {0}")]
+    Serial(serialport::Error),
+}
+
+impl From<DataAccessError> for NetError {
+    fn from(value: DataAccessError) -> Self {
+        Self::DataAccess(value)
+    }
+}
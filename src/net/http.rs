@@ -0,0 +1,225 @@
+//! Background HTTP requests, available with the `http` feature enabled.
+//!
+//! Requests are executed by a worker thread and the responses are delivered through a
+//! [`MainThreadQueue`], so the callbacks run on the main thread when the plugin calls
+//! [`MainThreadQueue::run_pending`] and may use the SDK.
+
+use std::io::Read;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use crate::plugin::main_thread::{MainThreadQueue, MainThreadSender};
+
+use super::{NetError, Result};
+
+/// Default request timeout.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+const SIMBRIEF_FETCHER_URL: &str = "https://www.simbrief.com/api/xml.fetcher.php";
+
+type Callback = Box<dyn FnOnce(Result<HttpResponse>) + Send>;
+
+/// A received HTTP response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpResponse {
+    /// The HTTP status code.
+    pub status: u16,
+    /// The response body.
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    /// Checks wether the status code is in the 2xx range.
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// Returns the body as text, invalid UTF-8 sequences are replaced.
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+}
+
+/// A SimBrief account used to look up the latest operational flight plan.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SimBriefUser {
+    /// A numeric pilot ID.
+    PilotId(String),
+    /// A SimBrief user name.
+    UserName(String),
+}
+
+/// A SimBrief flight plan format.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum OfpFormat {
+    /// XML document.
+    #[default]
+    Xml,
+    /// JSON document.
+    Json,
+}
+
+struct Request {
+    url: String,
+    callback: Callback,
+}
+
+/// Executes HTTP GET requests on a worker thread.
+///
+/// The worker thread is stopped when the client is dropped, after the request in
+/// progress completes or times out. Queued requests which haven't started yet are
+/// discarded and their callbacks are never called.
+pub struct HttpClient {
+    requests: Option<Sender<Request>>,
+    stop: Arc<AtomicBool>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl HttpClient {
+    /// Starts a worker thread with [`DEFAULT_TIMEOUT`].
+    ///
+    /// # Arguments
+    /// * `queue` - a queue the responses are delivered through.
+    ///
+    /// # Returns
+    /// Returns [`HttpClient`] on success. Otherwise returns [`NetError`].
+    pub fn new(queue: &MainThreadQueue) -> Result<Self> {
+        Self::with_timeout(queue, DEFAULT_TIMEOUT)
+    }
+
+    /// Starts a worker thread.
+    ///
+    /// # Arguments
+    /// * `queue` - a queue the responses are delivered through.
+    /// * `timeout` - a timeout of a single request.
+    ///
+    /// # Returns
+    /// Returns [`HttpClient`] on success. Otherwise returns [`NetError`].
+    pub fn with_timeout(queue: &MainThreadQueue, timeout: Duration) -> Result<Self> {
+        let (requests, receiver) = mpsc::channel::<Request>();
+        let sender = queue.sender();
+        let agent = ureq::AgentBuilder::new().timeout(timeout).build();
+        let stop = Arc::new(AtomicBool::new(false));
+        let worker_stop = stop.clone();
+        let worker = std::thread::Builder::new()
+            .name("xplm-http".into())
+            .spawn(move || {
+                for request in receiver {
+                    if worker_stop.load(Ordering::Relaxed) {
+                        break;
+                    }
+
+                    let response = get(&agent, &request.url);
+                    deliver(&sender, request.callback, response);
+                }
+            })
+            .map_err(NetError::Spawn)?;
+
+        Ok(Self {
+            requests: Some(requests),
+            stop,
+            worker: Some(worker),
+        })
+    }
+
+    /// Schedules an HTTP GET request.
+    ///
+    /// # Arguments
+    /// * `url` - a request URL.
+    /// * `callback` - a closure which receives the response on the main thread.
+    ///
+    /// # Returns
+    /// Returns empty result on success. Otherwise returns [`NetError::WorkerStopped`].
+    pub fn get<U, F>(&self, url: U, callback: F) -> Result<()>
+    where
+        U: Into<String>,
+        F: FnOnce(Result<HttpResponse>) + Send + 'static,
+    {
+        let request = Request {
+            url: url.into(),
+            callback: Box::new(callback),
+        };
+
+        self.requests
+            .as_ref()
+            .and_then(|requests| requests.send(request).ok())
+            .ok_or(NetError::WorkerStopped)
+    }
+
+    /// Fetches the latest SimBrief operational flight plan of a user.
+    ///
+    /// # Arguments
+    /// * `user` - a SimBrief account. See [`SimBriefUser`] for more details.
+    /// * `format` - a document format. See [`OfpFormat`] for more details.
+    /// * `callback` - a closure which receives the response on the main thread.
+    ///
+    /// # Returns
+    /// Returns empty result on success. Otherwise returns [`NetError::WorkerStopped`].
+    pub fn fetch_simbrief_ofp<F>(
+        &self,
+        user: &SimBriefUser,
+        format: OfpFormat,
+        callback: F,
+    ) -> Result<()>
+    where
+        F: FnOnce(Result<HttpResponse>) + Send + 'static,
+    {
+        let query = match user {
+            SimBriefUser::PilotId(id) => format!("userid={}", encode(id)),
+            SimBriefUser::UserName(name) => format!("username={}", encode(name)),
+        };
+
+        let url = match format {
+            OfpFormat::Xml => format!("{}?{}", SIMBRIEF_FETCHER_URL, query),
+            OfpFormat::Json => format!("{}?{}&json=1", SIMBRIEF_FETCHER_URL, query),
+        };
+
+        self.get(url, callback)
+    }
+}
+
+impl Drop for HttpClient {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        self.requests.take();
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+fn get(agent: &ureq::Agent, url: &str) -> Result<HttpResponse> {
+    let response = match agent.get(url).call() {
+        Ok(response) => response,
+        Err(ureq::Error::Status(_, response)) => response,
+        Err(err) => return Err(NetError::Http(Box::new(err))),
+    };
+
+    let status = response.status();
+    let mut body = Vec::new();
+    response
+        .into_reader()
+        .read_to_end(&mut body)
+        .map_err(NetError::Socket)?;
+    Ok(HttpResponse { status, body })
+}
+
+fn deliver(sender: &MainThreadSender, callback: Callback, response: Result<HttpResponse>) {
+    sender.run(move || callback(response));
+}
+
+/// Percent-encodes a query parameter value.
+fn encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
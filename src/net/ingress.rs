@@ -0,0 +1,345 @@
+//! Network command ingress, available with the `net-ingress` feature enabled.
+//!
+//! Clients send one command per line, or one command per datagram over UDP:
+//!
+//! * `set <data ref> <value>` writes an int, float or double data ref.
+//! * `run <command>` runs a command once.
+//!
+//! Commands are checked against an [`Allowlist`] on the listener thread and applied on
+//! the main thread when the plugin calls [`MainThreadQueue::run_pending`]. TCP clients
+//! receive `ok` or `err <reason>` for every line.
+
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, UdpSocket};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use crate::api::data_access::find_data_ref;
+use crate::api::utilities::{command_once, find_command};
+use crate::plugin::main_thread::{MainThreadQueue, MainThreadSender};
+
+use super::{write_number, Allowlist, NetError, Result};
+
+/// How often listener threads check wether the server is stopped.
+const STOP_POLL_INTERVAL: Duration = Duration::from_millis(200);
+/// Maximum UDP datagram size.
+const MAX_DATAGRAM: usize = 1024;
+
+/// A command received from a client.
+#[derive(Debug, Clone, PartialEq)]
+pub enum IngressCommand {
+    /// Writes a data ref.
+    SetDataRef {
+        /// A data ref name.
+        name: String,
+        /// A value, converted to the data ref type.
+        value: f64,
+    },
+    /// Runs a command once.
+    RunCommand {
+        /// A command name.
+        name: String,
+    },
+}
+
+impl FromStr for IngressCommand {
+    type Err = NetError;
+
+    fn from_str(line: &str) -> Result<Self> {
+        let mut fields = line.split_whitespace();
+        let command = match (fields.next(), fields.next(), fields.next()) {
+            (Some("set"), Some(name), Some(value)) => Self::SetDataRef {
+                name: name.to_string(),
+                value: value
+                    .parse()
+                    .map_err(|_| NetError::InvalidCommand(line.to_string()))?,
+            },
+            (Some("run"), Some(name), None) => Self::RunCommand {
+                name: name.to_string(),
+            },
+            _ => return Err(NetError::InvalidCommand(line.to_string())),
+        };
+
+        match fields.next() {
+            Some(_) => Err(NetError::InvalidCommand(line.to_string())),
+            None => Ok(command),
+        }
+    }
+}
+
+impl IngressCommand {
+    /// Applies the command. Must be called from the main thread.
+    ///
+    /// # Returns
+    /// Returns empty result on success. Otherwise returns [`NetError`].
+    pub fn apply(&self) -> Result<()> {
+        match self {
+            Self::SetDataRef { name, value } => {
+                write_number(&find_data_ref(name.as_str())?, *value)
+                    .then_some(())
+                    .ok_or_else(|| NetError::InvalidCommand(name.clone()))
+            }
+            Self::RunCommand { name } => match find_command(name.as_str()) {
+                Ok(Some(command)) => {
+                    command_once(&command);
+                    Ok(())
+                }
+                _ => Err(NetError::InvalidCommand(name.clone())),
+            },
+        }
+    }
+
+    /// Checks wether the command is allowed.
+    ///
+    /// # Arguments
+    /// * `allowlist` - allowed data refs and commands.
+    ///
+    /// # Returns
+    /// Returns `true` if the command is allowed. Otherwise returns `false`.
+    pub fn is_allowed(&self, allowlist: &Allowlist) -> bool {
+        match self {
+            Self::SetDataRef { name, .. } => allowlist.allows_data_ref(name),
+            Self::RunCommand { name } => allowlist.allows_command(name),
+        }
+    }
+}
+
+/// Network command ingress configuration.
+pub struct IngressServer {
+    allowlist: Allowlist,
+    udp: Option<SocketAddr>,
+    tcp: Option<SocketAddr>,
+}
+
+impl IngressServer {
+    /// Creates a server configuration without listeners.
+    ///
+    /// # Arguments
+    /// * `allowlist` - allowed data refs and commands. See [`Allowlist`] for more details.
+    ///
+    /// # Returns
+    /// Returns a new [`IngressServer`] instance.
+    pub fn new(allowlist: Allowlist) -> Self {
+        Self {
+            allowlist,
+            udp: None,
+            tcp: None,
+        }
+    }
+
+    /// Listens for UDP datagrams.
+    ///
+    /// # Arguments
+    /// * `addr` - a local address, e.g. `127.0.0.1:49010`.
+    ///
+    /// # Returns
+    /// Returns a modified configuration.
+    pub fn udp(mut self, addr: SocketAddr) -> Self {
+        self.udp = Some(addr);
+        self
+    }
+
+    /// Listens for TCP connections.
+    ///
+    /// # Arguments
+    /// * `addr` - a local address, e.g. `127.0.0.1:49010`.
+    ///
+    /// # Returns
+    /// Returns a modified configuration.
+    pub fn tcp(mut self, addr: SocketAddr) -> Self {
+        self.tcp = Some(addr);
+        self
+    }
+
+    /// Binds the sockets and starts the listener threads.
+    ///
+    /// # Arguments
+    /// * `queue` - a queue the received commands are applied from.
+    ///
+    /// # Returns
+    /// Returns [`IngressHandle`] on success. Otherwise returns [`NetError`].
+    pub fn start(self, queue: &MainThreadQueue) -> Result<IngressHandle> {
+        let mut handle = IngressHandle {
+            stop: Arc::new(AtomicBool::new(false)),
+            local_addrs: Vec::new(),
+            workers: Vec::new(),
+        };
+        let context = Arc::new(Context {
+            allowlist: self.allowlist,
+            sender: queue.sender(),
+            stop: handle.stop.clone(),
+        });
+
+        if let Some(addr) = self.udp {
+            let socket = UdpSocket::bind(addr).map_err(NetError::Socket)?;
+            socket
+                .set_read_timeout(Some(STOP_POLL_INTERVAL))
+                .map_err(NetError::Socket)?;
+            handle
+                .local_addrs
+                .push(socket.local_addr().map_err(NetError::Socket)?);
+            let context = context.clone();
+            handle.spawn("xplm-ingress-udp", move || serve_udp(socket, &context))?;
+        }
+
+        if let Some(addr) = self.tcp {
+            let listener = TcpListener::bind(addr).map_err(NetError::Socket)?;
+            listener.set_nonblocking(true).map_err(NetError::Socket)?;
+            handle
+                .local_addrs
+                .push(listener.local_addr().map_err(NetError::Socket)?);
+            handle.spawn("xplm-ingress-tcp", move || serve_tcp(listener, context))?;
+        }
+
+        Ok(handle)
+    }
+}
+
+/// Running listeners. The listeners are stopped when the handle is dropped.
+pub struct IngressHandle {
+    stop: Arc<AtomicBool>,
+    local_addrs: Vec<SocketAddr>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl IngressHandle {
+    /// Returns the addresses the listeners are bound to.
+    pub fn local_addrs(&self) -> &[SocketAddr] {
+        &self.local_addrs
+    }
+
+    fn spawn<F: FnOnce() + Send + 'static>(&mut self, name: &str, f: F) -> Result<()> {
+        let worker = std::thread::Builder::new()
+            .name(name.into())
+            .spawn(f)
+            .map_err(NetError::Spawn)?;
+        self.workers.push(worker);
+        Ok(())
+    }
+}
+
+impl Drop for IngressHandle {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+/// State shared by the listener threads.
+struct Context {
+    allowlist: Allowlist,
+    sender: MainThreadSender,
+    stop: Arc<AtomicBool>,
+}
+
+impl Context {
+    fn is_stopped(&self) -> bool {
+        self.stop.load(Ordering::Relaxed)
+    }
+
+    fn submit(&self, line: &str) -> Result<()> {
+        let command = line.parse::<IngressCommand>()?;
+        if !command.is_allowed(&self.allowlist) {
+            return Err(NetError::NotAllowed(line.to_string()));
+        }
+
+        let scheduled = self.sender.run(move || {
+            if let Err(err) = command.apply() {
+                crate::warn!("{}", err);
+            }
+        });
+
+        if scheduled {
+            Ok(())
+        } else {
+            Err(NetError::WorkerStopped)
+        }
+    }
+}
+
+fn serve_udp(socket: UdpSocket, context: &Context) {
+    let mut buf = [0; MAX_DATAGRAM];
+    while !context.is_stopped() {
+        match socket.recv(&mut buf) {
+            Ok(len) => {
+                for line in String::from_utf8_lossy(&buf[..len]).lines() {
+                    if !line.trim().is_empty() {
+                        let _ = context.submit(line);
+                    }
+                }
+            }
+            Err(err) if is_timeout(&err) => {}
+            Err(_) => break,
+        }
+    }
+}
+
+fn serve_tcp(listener: TcpListener, context: Arc<Context>) {
+    let mut clients = Vec::new();
+    while !context.is_stopped() {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                let context = context.clone();
+                let client = std::thread::Builder::new()
+                    .name("xplm-ingress-client".into())
+                    .spawn(move || serve_client(stream, &context));
+                if let Ok(client) = client {
+                    clients.push(client);
+                }
+            }
+            Err(err) if err.kind() == ErrorKind::WouldBlock => {
+                std::thread::sleep(STOP_POLL_INTERVAL)
+            }
+            Err(_) => break,
+        }
+
+        clients.retain(|client: &JoinHandle<()>| !client.is_finished());
+    }
+
+    for client in clients {
+        let _ = client.join();
+    }
+}
+
+fn serve_client(stream: TcpStream, context: &Context) {
+    if stream.set_nonblocking(false).is_err()
+        || stream.set_read_timeout(Some(STOP_POLL_INTERVAL)).is_err()
+    {
+        return;
+    }
+
+    let Ok(mut writer) = stream.try_clone() else {
+        return;
+    };
+
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    while !context.is_stopped() {
+        match reader.read_line(&mut line) {
+            Ok(0) => break,
+            Ok(_) => {
+                if !line.trim().is_empty() {
+                    let reply = match context.submit(line.trim()) {
+                        Ok(()) => "ok".to_string(),
+                        Err(err) => format!("err {}", err),
+                    };
+                    if writeln!(writer, "{}", reply).is_err() {
+                        break;
+                    }
+                }
+                line.clear();
+            }
+            Err(err) if is_timeout(&err) => {}
+            Err(_) => break,
+        }
+    }
+}
+
+fn is_timeout(err: &std::io::Error) -> bool {
+    matches!(err.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut)
+}
@@ -0,0 +1,358 @@
+//! Serial port bridge for home cockpit hardware, available with the `serial` feature enabled.
+//!
+//! Devices exchange newline terminated ASCII lines with the plugin. Incoming lines are
+//! mapped to commands and data ref writes, outgoing lines are produced from data ref
+//! changes, e.g.
+//!
+//! ```ignore
+//! let bridge = SerialBridge::new("/dev/ttyUSB0", 115_200)
+//!     .on_line("GEAR_DN", SerialAction::command("sim/flight_controls/landing_gear_down"))
+//!     .on_value("HDG:", "sim/cockpit/autopilot/heading_mag")
+//!     .output("sim/cockpit2/controls/gear_handle_down", "GEAR:{}")
+//!     .start()?;
+//! ```
+//!
+//! The port is served by a background thread, mappings are applied on the main thread
+//! by [`SerialSession::poll`].
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use crate::api::data_access::{find_data_ref, DataRef};
+use crate::api::utilities::{command_begin, command_end, command_once, find_command, Command};
+
+use super::{read_number, write_number, NetError, Result};
+
+/// How often the port thread checks for outgoing lines and wether the bridge is stopped.
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// An action triggered by an incoming line.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SerialAction {
+    /// Runs a command once.
+    Command(String),
+    /// Starts holding a command, e.g. when a button is pressed.
+    CommandBegin(String),
+    /// Stops holding a command, e.g. when a button is released.
+    CommandEnd(String),
+    /// Writes a fixed value to an int, float or double data ref.
+    SetDataRef(String, f64),
+}
+
+impl SerialAction {
+    /// Creates an action which runs a command once.
+    ///
+    /// # Arguments
+    /// * `name` - a command name.
+    ///
+    /// # Returns
+    /// Returns [`SerialAction::Command`].
+    pub fn command<N: Into<String>>(name: N) -> Self {
+        Self::Command(name.into())
+    }
+
+    /// Creates an action which writes a fixed value.
+    ///
+    /// # Arguments
+    /// * `name` - a data ref name.
+    /// * `value` - a value to write.
+    ///
+    /// # Returns
+    /// Returns [`SerialAction::SetDataRef`].
+    pub fn set_data_ref<N: Into<String>>(name: N, value: f64) -> Self {
+        Self::SetDataRef(name.into(), value)
+    }
+}
+
+/// Serial bridge configuration.
+pub struct SerialBridge {
+    path: String,
+    baud_rate: u32,
+    lines: Vec<(String, SerialAction)>,
+    values: Vec<(String, String)>,
+    outputs: Vec<(String, String)>,
+}
+
+impl SerialBridge {
+    /// Creates a bridge configuration without mappings.
+    ///
+    /// # Arguments
+    /// * `path` - a serial device, e.g. `/dev/ttyUSB0` or `COM3`.
+    /// * `baud_rate` - a baud rate, e.g. `115200`.
+    ///
+    /// # Returns
+    /// Returns a new [`SerialBridge`] instance.
+    pub fn new<P: Into<String>>(path: P, baud_rate: u32) -> Self {
+        Self {
+            path: path.into(),
+            baud_rate,
+            lines: Vec::new(),
+            values: Vec::new(),
+            outputs: Vec::new(),
+        }
+    }
+
+    /// Maps an incoming line to an action.
+    ///
+    /// # Arguments
+    /// * `line` - an exact line, without the line terminator.
+    /// * `action` - an action to trigger. See [`SerialAction`] for more details.
+    ///
+    /// # Returns
+    /// Returns a modified configuration.
+    pub fn on_line<L: Into<String>>(mut self, line: L, action: SerialAction) -> Self {
+        self.lines.push((line.into(), action));
+        self
+    }
+
+    /// Maps incoming lines which start with a prefix followed by a number to data ref writes.
+    ///
+    /// # Arguments
+    /// * `prefix` - a line prefix, e.g. `HDG:`.
+    /// * `data_ref` - an int, float or double data ref name.
+    ///
+    /// # Returns
+    /// Returns a modified configuration.
+    pub fn on_value<P: Into<String>, N: Into<String>>(mut self, prefix: P, data_ref: N) -> Self {
+        self.values.push((prefix.into(), data_ref.into()));
+        self
+    }
+
+    /// Sends a line every time a data ref value changes.
+    ///
+    /// # Arguments
+    /// * `data_ref` - an int, float or double data ref name.
+    /// * `template` - a line template where `{}` is replaced with the value, e.g. `GEAR:{}`.
+    ///
+    /// # Returns
+    /// Returns a modified configuration.
+    pub fn output<N: Into<String>, T: Into<String>>(mut self, data_ref: N, template: T) -> Self {
+        self.outputs.push((data_ref.into(), template.into()));
+        self
+    }
+
+    /// Resolves the mappings, opens the port and starts the port thread.
+    ///
+    /// # Returns
+    /// Returns [`SerialSession`] on success. Otherwise returns [`NetError`].
+    pub fn start(self) -> Result<SerialSession> {
+        let mut lines = HashMap::with_capacity(self.lines.len());
+        for (line, action) in self.lines {
+            lines.insert(line, ResolvedAction::resolve(action)?);
+        }
+
+        let mut values = Vec::with_capacity(self.values.len());
+        for (prefix, name) in self.values {
+            values.push((prefix, find_data_ref(name)?));
+        }
+
+        let mut outputs = Vec::with_capacity(self.outputs.len());
+        for (name, template) in self.outputs {
+            outputs.push(Output {
+                data_ref: find_data_ref(name)?,
+                template,
+                last: None,
+            });
+        }
+
+        let port = serialport::new(self.path, self.baud_rate)
+            .timeout(POLL_INTERVAL)
+            .open()
+            .map_err(NetError::Serial)?;
+        let stop = Arc::new(AtomicBool::new(false));
+        let (incoming_sender, incoming) = mpsc::channel();
+        let (outgoing, outgoing_receiver) = mpsc::channel();
+        let port_stop = stop.clone();
+        let worker = std::thread::Builder::new()
+            .name("xplm-serial".into())
+            .spawn(move || serve(port, incoming_sender, outgoing_receiver, port_stop))
+            .map_err(NetError::Spawn)?;
+
+        Ok(SerialSession {
+            lines,
+            values,
+            outputs,
+            incoming,
+            outgoing,
+            stop,
+            worker: Some(worker),
+        })
+    }
+}
+
+/// An action with resolved handles.
+enum ResolvedAction {
+    Command(Command),
+    CommandBegin(Command),
+    CommandEnd(Command),
+    SetDataRef(DataRef, f64),
+}
+
+impl ResolvedAction {
+    fn resolve(action: SerialAction) -> Result<Self> {
+        let command = |name: String| match find_command(name.as_str()) {
+            Ok(Some(command)) => Ok(command),
+            _ => Err(NetError::InvalidCommand(name)),
+        };
+
+        Ok(match action {
+            SerialAction::Command(name) => Self::Command(command(name)?),
+            SerialAction::CommandBegin(name) => Self::CommandBegin(command(name)?),
+            SerialAction::CommandEnd(name) => Self::CommandEnd(command(name)?),
+            SerialAction::SetDataRef(name, value) => Self::SetDataRef(find_data_ref(name)?, value),
+        })
+    }
+
+    fn apply(&self) {
+        match self {
+            Self::Command(command) => command_once(command),
+            Self::CommandBegin(command) => command_begin(command),
+            Self::CommandEnd(command) => command_end(command),
+            Self::SetDataRef(data_ref, value) => {
+                write_number(data_ref, *value);
+            }
+        }
+    }
+}
+
+/// A data ref reported to the device.
+struct Output {
+    data_ref: DataRef,
+    template: String,
+    last: Option<f64>,
+}
+
+/// A running serial bridge. The port is closed when the session is dropped.
+pub struct SerialSession {
+    lines: HashMap<String, ResolvedAction>,
+    values: Vec<(String, DataRef)>,
+    outputs: Vec<Output>,
+    incoming: Receiver<String>,
+    outgoing: Sender<String>,
+    stop: Arc<AtomicBool>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl SerialSession {
+    /// Applies incoming lines and sends changed data ref values.
+    /// Should be called every frame, e.g. from a flight loop or a draw callback.
+    ///
+    /// # Returns
+    /// Returns empty result on success. Otherwise returns [`NetError::WorkerStopped`]
+    /// if the port has been closed.
+    pub fn poll(&mut self) -> Result<()> {
+        loop {
+            match self.incoming.try_recv() {
+                Ok(line) => self.handle_line(&line),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => return Err(NetError::WorkerStopped),
+            }
+        }
+
+        for output in self.outputs.iter_mut() {
+            let Some(value) = read_number(&output.data_ref) else {
+                continue;
+            };
+
+            if output.last != Some(value) {
+                output.last = Some(value);
+                let line = output.template.replace("{}", &format_value(value));
+                self.outgoing
+                    .send(line)
+                    .map_err(|_| NetError::WorkerStopped)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sends a line to the device.
+    ///
+    /// # Arguments
+    /// * `line` - a line without the line terminator.
+    ///
+    /// # Returns
+    /// Returns empty result on success. Otherwise returns [`NetError::WorkerStopped`].
+    pub fn send<L: Into<String>>(&self, line: L) -> Result<()> {
+        self.outgoing
+            .send(line.into())
+            .map_err(|_| NetError::WorkerStopped)
+    }
+
+    fn handle_line(&self, line: &str) {
+        if let Some(action) = self.lines.get(line) {
+            return action.apply();
+        }
+
+        for (prefix, data_ref) in &self.values {
+            if let Some(value) = line.strip_prefix(prefix.as_str()) {
+                match value.trim().parse() {
+                    Ok(value) => {
+                        write_number(data_ref, value);
+                    }
+                    Err(_) => crate::warn!("invalid serial value {}", line),
+                }
+                return;
+            }
+        }
+
+        crate::warn!("unmapped serial line {}", line);
+    }
+}
+
+impl Drop for SerialSession {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+/// Formats integral values without a fractional part.
+fn format_value(value: f64) -> String {
+    if value.fract() == 0.0 && value.abs() < i64::MAX as f64 {
+        (value as i64).to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+fn serve(
+    port: Box<dyn serialport::SerialPort>,
+    incoming: Sender<String>,
+    outgoing: Receiver<String>,
+    stop: Arc<AtomicBool>,
+) {
+    let Ok(mut writer) = port.try_clone() else {
+        return;
+    };
+
+    let mut reader = BufReader::new(port);
+    let mut line = String::new();
+    while !stop.load(Ordering::Relaxed) {
+        while let Ok(text) = outgoing.try_recv() {
+            if writer.write_all(format!("{}\n", text).as_bytes()).is_err() {
+                return;
+            }
+        }
+
+        match reader.read_line(&mut line) {
+            Ok(0) => return,
+            Ok(_) => {
+                let text = line.trim();
+                if !text.is_empty() && incoming.send(text.to_string()).is_err() {
+                    return;
+                }
+                line.clear();
+            }
+            Err(err) if matches!(err.kind(), ErrorKind::TimedOut | ErrorKind::WouldBlock) => {}
+            Err(_) => return,
+        }
+    }
+}
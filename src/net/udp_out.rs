@@ -0,0 +1,359 @@
+//! UDP output of data ref values, available with the `udp-out` feature enabled.
+//!
+//! The [`UdpFormat::Data`] format matches the X-Plane "Data Output" packets: a `DATA`
+//! header followed by one 36 byte record per group, each record being a little endian
+//! `i32` group index and eight little endian `f32` values. Unused values are sent as
+//! [`UNUSED_VALUE`].
+//!
+//! The [`UdpFormat::Json`] format sends one JSON object per packet, e.g.
+//! `{"time":12.5,"groups":{"3":{"ias":120.0,"tas":125.3}}}`.
+
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, UdpSocket};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, SyncSender, TrySendError};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use crate::api::data_access::{
+    find_data_ref, get_data_d, get_data_f, get_data_i, get_data_ref_types, DataRef, DataType,
+};
+use crate::api::processing::get_elapsed_time;
+
+use super::{NetError, Result};
+
+/// The number of values in a single data group.
+pub const GROUP_VALUES: usize = 8;
+/// The value X-Plane sends for unused slots of a data group.
+pub const UNUSED_VALUE: f32 = -999.0;
+/// Default send rate in packets per second.
+pub const DEFAULT_RATE_HZ: f32 = 20.0;
+/// The lowest send rate, lower positive rates are raised to it.
+pub const MIN_RATE_HZ: f32 = 0.1;
+
+const DATA_HEADER: &[u8; 5] = b"DATA\0";
+const RECORD_LEN: usize = 4 + GROUP_VALUES * 4;
+const QUEUE_CAPACITY: usize = 4;
+const ERROR_LOG_INTERVAL: Duration = Duration::from_secs(5);
+
+/// A packet format.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum UdpFormat {
+    /// X-Plane native `DATA` packets.
+    #[default]
+    Data,
+    /// JSON objects keyed by group index and value name.
+    Json,
+}
+
+/// UDP output configuration.
+pub struct UdpOutput {
+    target: SocketAddr,
+    format: UdpFormat,
+    rate_hz: f32,
+    groups: Vec<(i32, Vec<(String, String)>)>,
+}
+
+impl UdpOutput {
+    /// Creates an output configuration which sends [`UdpFormat::Data`] packets
+    /// at [`DEFAULT_RATE_HZ`].
+    ///
+    /// # Arguments
+    /// * `target` - a receiver address, e.g. `127.0.0.1:49003`.
+    ///
+    /// # Returns
+    /// Returns a new [`UdpOutput`] instance.
+    pub fn new(target: SocketAddr) -> Self {
+        Self {
+            target,
+            format: UdpFormat::default(),
+            rate_hz: DEFAULT_RATE_HZ,
+            groups: Vec::new(),
+        }
+    }
+
+    /// Sets the packet format.
+    ///
+    /// # Arguments
+    /// * `format` - a packet format. See [`UdpFormat`] for more details.
+    ///
+    /// # Returns
+    /// Returns a modified configuration.
+    pub fn format(mut self, format: UdpFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Sets the send rate.
+    ///
+    /// # Arguments
+    /// * `rate_hz` - packets per second, at least [`MIN_RATE_HZ`]. Zero, a negative
+    ///   or an invalid rate sends a packet on every poll.
+    ///
+    /// # Returns
+    /// Returns a modified configuration.
+    pub fn rate_hz(mut self, rate_hz: f32) -> Self {
+        self.rate_hz = if rate_hz > 0.0 {
+            rate_hz.max(MIN_RATE_HZ)
+        } else {
+            0.0
+        };
+        self
+    }
+
+    /// Adds a data group.
+    ///
+    /// # Arguments
+    /// * `index` - a group index sent in the record, e.g. the X-Plane data output row.
+    /// * `values` - up to [`GROUP_VALUES`] pairs of a value name and an int, float or
+    ///   double data ref name.
+    ///
+    /// # Returns
+    /// Returns a modified configuration.
+    pub fn group<I, K, N>(mut self, index: i32, values: I) -> Self
+    where
+        I: IntoIterator<Item = (K, N)>,
+        K: Into<String>,
+        N: Into<String>,
+    {
+        let values = values
+            .into_iter()
+            .map(|(name, data_ref)| (name.into(), data_ref.into()))
+            .collect();
+        self.groups.push((index, values));
+        self
+    }
+
+    /// Looks up the data refs, opens a socket and starts the sender thread.
+    ///
+    /// # Returns
+    /// Returns [`UdpOutputSession`] on success. Otherwise returns [`NetError`].
+    pub fn start(self) -> Result<UdpOutputSession> {
+        let mut groups = Vec::with_capacity(self.groups.len());
+        let mut names = Vec::with_capacity(self.groups.len());
+        for (index, values) in self.groups {
+            if values.len() > GROUP_VALUES {
+                return Err(NetError::InvalidGroup {
+                    index,
+                    len: values.len(),
+                });
+            }
+
+            let mut channels = Vec::with_capacity(values.len());
+            let mut group_names = Vec::with_capacity(values.len());
+            for (name, data_ref) in values {
+                channels.push(Channel::new(find_data_ref(data_ref)?));
+                group_names.push(name);
+            }
+
+            groups.push((index, channels));
+            names.push((index, group_names));
+        }
+
+        let local: SocketAddr = match self.target {
+            SocketAddr::V4(_) => (Ipv4Addr::UNSPECIFIED, 0).into(),
+            SocketAddr::V6(_) => (Ipv6Addr::UNSPECIFIED, 0).into(),
+        };
+        let socket = UdpSocket::bind(local).map_err(NetError::Socket)?;
+        socket.connect(self.target).map_err(NetError::Socket)?;
+        let (sender, receiver) = mpsc::sync_channel(QUEUE_CAPACITY);
+        let errors = Arc::new(AtomicU64::new(0));
+        let packet_sender = PacketSender {
+            socket,
+            format: self.format,
+            names,
+            errors: errors.clone(),
+        };
+        let worker = std::thread::Builder::new()
+            .name("xplm-udp-out".into())
+            .spawn(move || packet_sender.run(receiver))
+            .map_err(NetError::Spawn)?;
+        let period = if self.rate_hz > 0.0 {
+            Duration::from_secs_f32(1.0 / self.rate_hz)
+        } else {
+            Duration::ZERO
+        };
+
+        Ok(UdpOutputSession {
+            groups,
+            period,
+            sent_at: None,
+            sender: Some(sender),
+            worker: Some(worker),
+            errors,
+            reported_errors: 0,
+            reported_at: None,
+        })
+    }
+}
+
+/// A sampled data ref.
+struct Channel {
+    data_ref: DataRef,
+    data_type: DataType,
+}
+
+impl Channel {
+    fn new(data_ref: DataRef) -> Self {
+        let types = get_data_ref_types(&data_ref);
+        let data_type = [DataType::Float, DataType::Double, DataType::Int]
+            .into_iter()
+            .find(|data_type| types.contains(*data_type))
+            .unwrap_or(DataType::Float);
+        Self {
+            data_ref,
+            data_type,
+        }
+    }
+
+    fn read(&self) -> f32 {
+        match self.data_type {
+            DataType::Double => get_data_d(&self.data_ref) as f32,
+            DataType::Int => get_data_i(&self.data_ref) as f32,
+            _ => get_data_f(&self.data_ref),
+        }
+    }
+}
+
+/// Values sampled on the main thread.
+struct Frame {
+    time: f32,
+    groups: Vec<(i32, [f32; GROUP_VALUES])>,
+}
+
+/// An active UDP output. The sender thread is stopped when the session is dropped.
+pub struct UdpOutputSession {
+    groups: Vec<(i32, Vec<Channel>)>,
+    period: Duration,
+    sent_at: Option<Instant>,
+    sender: Option<SyncSender<Frame>>,
+    worker: Option<JoinHandle<()>>,
+    errors: Arc<AtomicU64>,
+    reported_errors: u64,
+    reported_at: Option<Instant>,
+}
+
+impl UdpOutputSession {
+    /// Samples the data refs and queues a packet when the configured rate allows.
+    /// Should be called every frame, e.g. from a flight loop or a draw callback.
+    ///
+    /// # Returns
+    /// Returns `true` if a packet was queued. Otherwise returns `false` or [`NetError`]
+    /// if the sender thread has stopped.
+    pub fn poll(&mut self) -> Result<bool> {
+        if self
+            .sent_at
+            .is_some_and(|sent_at| sent_at.elapsed() < self.period)
+        {
+            return Ok(false);
+        }
+
+        self.sent_at = Some(Instant::now());
+        self.report_errors();
+        let frame = Frame {
+            time: get_elapsed_time(),
+            groups: self
+                .groups
+                .iter()
+                .map(|(index, channels)| {
+                    let mut values = [UNUSED_VALUE; GROUP_VALUES];
+                    for (value, channel) in values.iter_mut().zip(channels) {
+                        *value = channel.read();
+                    }
+                    (*index, values)
+                })
+                .collect(),
+        };
+
+        let sender = self.sender.as_ref().ok_or(NetError::WorkerStopped)?;
+        match sender.try_send(frame) {
+            Ok(()) => Ok(true),
+            Err(TrySendError::Full(_)) => Ok(false),
+            Err(TrySendError::Disconnected(_)) => Err(NetError::WorkerStopped),
+        }
+    }
+
+    /// Returns the number of packets which failed to send.
+    pub fn send_errors(&self) -> u64 {
+        self.errors.load(Ordering::Relaxed)
+    }
+
+    fn report_errors(&mut self) {
+        let errors = self.send_errors();
+        if errors == self.reported_errors
+            || self
+                .reported_at
+                .is_some_and(|at| at.elapsed() < ERROR_LOG_INTERVAL)
+        {
+            return;
+        }
+
+        crate::warn!(
+            "udp output failed to send {} packets",
+            errors - self.reported_errors
+        );
+        self.reported_errors = errors;
+        self.reported_at = Some(Instant::now());
+    }
+}
+
+impl Drop for UdpOutputSession {
+    fn drop(&mut self) {
+        self.sender.take();
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+struct PacketSender {
+    socket: UdpSocket,
+    format: UdpFormat,
+    names: Vec<(i32, Vec<String>)>,
+    errors: Arc<AtomicU64>,
+}
+
+impl PacketSender {
+    fn run(self, receiver: Receiver<Frame>) {
+        for frame in receiver {
+            let packet = match self.format {
+                UdpFormat::Data => encode_data(&frame),
+                UdpFormat::Json => self.encode_json(&frame),
+            };
+
+            if self.socket.send(&packet).is_err() {
+                self.errors.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    fn encode_json(&self, frame: &Frame) -> Vec<u8> {
+        let mut groups = serde_json::Map::with_capacity(frame.groups.len());
+        for ((index, values), (_, names)) in frame.groups.iter().zip(&self.names) {
+            let group = names
+                .iter()
+                .zip(values)
+                .map(|(name, value)| (name.clone(), serde_json::Value::from(*value)))
+                .collect();
+            groups.insert(index.to_string(), serde_json::Value::Object(group));
+        }
+
+        let mut packet = serde_json::Map::with_capacity(2);
+        packet.insert("time".into(), serde_json::Value::from(frame.time));
+        packet.insert("groups".into(), serde_json::Value::Object(groups));
+        serde_json::Value::Object(packet).to_string().into_bytes()
+    }
+}
+
+fn encode_data(frame: &Frame) -> Vec<u8> {
+    let mut packet = Vec::with_capacity(DATA_HEADER.len() + frame.groups.len() * RECORD_LEN);
+    packet.extend_from_slice(DATA_HEADER);
+    for (index, values) in &frame.groups {
+        packet.extend_from_slice(&index.to_le_bytes());
+        for value in values {
+            packet.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+
+    packet
+}
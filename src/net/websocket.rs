@@ -0,0 +1,432 @@
+//! WebSocket remote control, available with the `websocket` feature enabled.
+//!
+//! Clients exchange JSON text messages with the server:
+//!
+//! * `{"op":"subscribe","data_refs":["sim/cockpit2/gauges/indicators/airspeed_kts_pilot"]}`
+//!   sends the current values and then every change at the configured rate.
+//! * `{"op":"unsubscribe","data_refs":["..."]}` stops the updates.
+//! * `{"op":"set","data_ref":"...","value":1.0}` writes an int, float or double data ref.
+//! * `{"op":"command","command":"sim/lights/landing_lights_toggle"}` runs a command once.
+//!
+//! The server replies with `{"op":"update","values":{"<data ref>":<value>}}` and
+//! `{"op":"error","message":"..."}`. Writes and commands are checked against an [`Allowlist`].
+//!
+//! Sockets are served by background threads, requests are applied and subscriptions are
+//! sampled on the main thread by [`WebSocketSession::poll`].
+
+use std::collections::HashMap;
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use serde_json::{json, Value};
+use tungstenite::{HandshakeError, Message, WebSocket};
+
+use crate::api::data_access::{find_data_ref, DataRef};
+use crate::api::utilities::{command_once, find_command};
+
+use super::{read_number, write_number, Allowlist, NetError, Result};
+
+/// Default update rate in messages per second.
+pub const DEFAULT_RATE_HZ: f32 = 10.0;
+
+/// The lowest update rate, lower positive rates are raised to it.
+pub const MIN_RATE_HZ: f32 = 0.1;
+
+/// How often socket threads check for outgoing messages and wether the server is stopped.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// How long a client may take to complete the WebSocket handshake.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A connected client identifier.
+pub type ClientId = u64;
+
+/// A network event passed to the main thread.
+enum Event {
+    Connected(ClientId, Sender<String>),
+    Received(ClientId, String),
+    Disconnected(ClientId),
+}
+
+/// WebSocket server configuration.
+pub struct WebSocketServer {
+    addr: SocketAddr,
+    allowlist: Allowlist,
+    rate_hz: f32,
+}
+
+impl WebSocketServer {
+    /// Creates a server configuration which sends updates at [`DEFAULT_RATE_HZ`].
+    ///
+    /// # Arguments
+    /// * `addr` - a local address, e.g. `127.0.0.1:8080`.
+    /// * `allowlist` - data refs clients may write and commands clients may run.
+    ///
+    /// # Returns
+    /// Returns a new [`WebSocketServer`] instance.
+    pub fn new(addr: SocketAddr, allowlist: Allowlist) -> Self {
+        Self {
+            addr,
+            allowlist,
+            rate_hz: DEFAULT_RATE_HZ,
+        }
+    }
+
+    /// Sets the subscription update rate.
+    ///
+    /// # Arguments
+    /// * `rate_hz` - updates per second, at least [`MIN_RATE_HZ`]. Zero, a negative
+    ///   or an invalid rate sends updates on every poll.
+    ///
+    /// # Returns
+    /// Returns a modified configuration.
+    pub fn rate_hz(mut self, rate_hz: f32) -> Self {
+        self.rate_hz = if rate_hz > 0.0 {
+            rate_hz.max(MIN_RATE_HZ)
+        } else {
+            0.0
+        };
+        self
+    }
+
+    /// Binds the socket and starts the listener thread.
+    ///
+    /// # Returns
+    /// Returns [`WebSocketSession`] on success. Otherwise returns [`NetError`].
+    pub fn start(self) -> Result<WebSocketSession> {
+        let listener = TcpListener::bind(self.addr).map_err(NetError::Socket)?;
+        listener.set_nonblocking(true).map_err(NetError::Socket)?;
+        let local_addr = listener.local_addr().map_err(NetError::Socket)?;
+        let stop = Arc::new(AtomicBool::new(false));
+        let (events, receiver) = mpsc::channel();
+        let listener_stop = stop.clone();
+        let worker = std::thread::Builder::new()
+            .name("xplm-websocket".into())
+            .spawn(move || serve(listener, events, listener_stop))
+            .map_err(NetError::Spawn)?;
+        let period = if self.rate_hz > 0.0 {
+            Duration::from_secs_f32(1.0 / self.rate_hz)
+        } else {
+            Duration::ZERO
+        };
+
+        Ok(WebSocketSession {
+            allowlist: self.allowlist,
+            local_addr,
+            period,
+            sent_at: None,
+            events: receiver,
+            clients: HashMap::new(),
+            data_refs: HashMap::new(),
+            stop,
+            worker: Some(worker),
+        })
+    }
+}
+
+/// Per client subscription bookkeeping.
+struct Client {
+    outbox: Sender<String>,
+    subscriptions: HashMap<String, Option<f64>>,
+}
+
+impl Client {
+    fn send(&self, message: Value) {
+        let _ = self.outbox.send(message.to_string());
+    }
+
+    fn send_error<E: ToString>(&self, err: E) {
+        self.send(json!({"op": "error", "message": err.to_string()}));
+    }
+}
+
+/// A running WebSocket server. The server is stopped when the session is dropped.
+pub struct WebSocketSession {
+    allowlist: Allowlist,
+    local_addr: SocketAddr,
+    period: Duration,
+    sent_at: Option<Instant>,
+    events: Receiver<Event>,
+    clients: HashMap<ClientId, Client>,
+    data_refs: HashMap<String, DataRef>,
+    stop: Arc<AtomicBool>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl WebSocketSession {
+    /// Returns the address the server is bound to.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Returns the number of connected clients.
+    pub fn client_count(&self) -> usize {
+        self.clients.len()
+    }
+
+    /// Applies client requests and sends subscription updates when the configured rate allows.
+    /// Should be called every frame, e.g. from a flight loop or a draw callback.
+    pub fn poll(&mut self) {
+        loop {
+            match self.events.try_recv() {
+                Ok(Event::Connected(id, outbox)) => {
+                    let client = Client {
+                        outbox,
+                        subscriptions: HashMap::new(),
+                    };
+                    self.clients.insert(id, client);
+                }
+                Ok(Event::Received(id, text)) => self.handle_request(id, &text),
+                Ok(Event::Disconnected(id)) => {
+                    self.clients.remove(&id);
+                }
+                Err(TryRecvError::Empty | TryRecvError::Disconnected) => break,
+            }
+        }
+
+        if self
+            .sent_at
+            .is_some_and(|sent_at| sent_at.elapsed() < self.period)
+        {
+            return;
+        }
+
+        self.sent_at = Some(Instant::now());
+        self.send_updates();
+    }
+
+    fn handle_request(&mut self, id: ClientId, text: &str) {
+        let Some(client) = self.clients.get_mut(&id) else {
+            return;
+        };
+
+        let request = match serde_json::from_str::<Value>(text) {
+            Ok(request) => request,
+            Err(err) => return client.send_error(err),
+        };
+
+        match request["op"].as_str() {
+            Some("subscribe") => {
+                for name in string_list(&request["data_refs"]) {
+                    client.subscriptions.entry(name).or_insert(None);
+                }
+            }
+            Some("unsubscribe") => {
+                for name in string_list(&request["data_refs"]) {
+                    client.subscriptions.remove(&name);
+                }
+            }
+            Some("set") => {
+                let (Some(name), Some(value)) =
+                    (request["data_ref"].as_str(), request["value"].as_f64())
+                else {
+                    return client.send_error(NetError::InvalidCommand(text.to_string()));
+                };
+
+                if !self.allowlist.allows_data_ref(name) {
+                    return client.send_error(NetError::NotAllowed(name.to_string()));
+                }
+
+                let written = match resolve(&mut self.data_refs, name) {
+                    Ok(data_ref) => write_number(data_ref, value),
+                    Err(err) => return client.send_error(err),
+                };
+                if !written {
+                    client.send_error(NetError::InvalidCommand(name.to_string()));
+                }
+            }
+            Some("command") => {
+                let Some(name) = request["command"].as_str() else {
+                    return client.send_error(NetError::InvalidCommand(text.to_string()));
+                };
+
+                if !self.allowlist.allows_command(name) {
+                    return client.send_error(NetError::NotAllowed(name.to_string()));
+                }
+
+                match find_command(name) {
+                    Ok(Some(command)) => command_once(&command),
+                    _ => client.send_error(NetError::InvalidCommand(name.to_string())),
+                }
+            }
+            _ => client.send_error(NetError::InvalidCommand(text.to_string())),
+        }
+    }
+
+    fn send_updates(&mut self) {
+        let mut values = HashMap::new();
+        for client in self.clients.values_mut() {
+            let mut changed = serde_json::Map::new();
+            let mut missing = Vec::new();
+            for (name, last) in client.subscriptions.iter_mut() {
+                let value = *values.entry(name.clone()).or_insert_with(|| {
+                    resolve(&mut self.data_refs, name)
+                        .ok()
+                        .and_then(read_number)
+                });
+                match value {
+                    Some(value) if *last != Some(value) => {
+                        *last = Some(value);
+                        changed.insert(name.clone(), Value::from(value));
+                    }
+                    Some(_) => {}
+                    None => missing.push(name.clone()),
+                }
+            }
+
+            for name in missing {
+                client.subscriptions.remove(&name);
+                client.send_error(format!("data ref {} can't be read", name));
+            }
+
+            if !changed.is_empty() {
+                client.send(json!({"op": "update", "values": changed}));
+            }
+        }
+    }
+}
+
+impl Drop for WebSocketSession {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+fn resolve<'a>(data_refs: &'a mut HashMap<String, DataRef>, name: &str) -> Result<&'a DataRef> {
+    if !data_refs.contains_key(name) {
+        data_refs.insert(name.to_string(), find_data_ref(name)?);
+    }
+
+    Ok(&data_refs[name])
+}
+
+fn string_list(value: &Value) -> Vec<String> {
+    value
+        .as_array()
+        .map(|names| {
+            names
+                .iter()
+                .filter_map(Value::as_str)
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn serve(listener: TcpListener, events: Sender<Event>, stop: Arc<AtomicBool>) {
+    let next_id = Arc::new(AtomicU64::new(1));
+    let mut clients: Vec<JoinHandle<()>> = Vec::new();
+    while !stop.load(Ordering::Relaxed) {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                let id = next_id.fetch_add(1, Ordering::Relaxed);
+                let events = events.clone();
+                let stop = stop.clone();
+                let client = std::thread::Builder::new()
+                    .name("xplm-websocket-client".into())
+                    .spawn(move || serve_client(id, stream, events, stop));
+                if let Ok(client) = client {
+                    clients.push(client);
+                }
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => {
+                std::thread::sleep(POLL_INTERVAL)
+            }
+            Err(_) => break,
+        }
+
+        clients.retain(|client| !client.is_finished());
+    }
+
+    for client in clients {
+        let _ = client.join();
+    }
+}
+
+fn serve_client(id: ClientId, stream: TcpStream, events: Sender<Event>, stop: Arc<AtomicBool>) {
+    let Some(mut socket) = handshake(stream, &stop) else {
+        return;
+    };
+
+    let stream = socket.get_ref();
+    if stream.set_nonblocking(false).is_err()
+        || stream.set_read_timeout(Some(POLL_INTERVAL)).is_err()
+    {
+        return;
+    }
+
+    let (outbox, outgoing) = mpsc::channel();
+    if events.send(Event::Connected(id, outbox)).is_err() {
+        return;
+    }
+
+    while !stop.load(Ordering::Relaxed) && exchange(id, &mut socket, &outgoing, &events) {}
+    let _ = socket.close(None);
+    let _ = socket.flush();
+    let _ = events.send(Event::Disconnected(id));
+}
+
+/// Performs the server handshake on a non-blocking stream, so a client which connects
+/// but never completes the handshake can't keep the thread alive after the server stops.
+///
+/// Returns [`None`] if the handshake fails, times out or the server is stopped.
+fn handshake(stream: TcpStream, stop: &AtomicBool) -> Option<WebSocket<TcpStream>> {
+    if stream.set_nonblocking(true).is_err() {
+        return None;
+    }
+
+    let deadline = Instant::now() + HANDSHAKE_TIMEOUT;
+    let mut result = tungstenite::accept(stream);
+    loop {
+        match result {
+            Ok(socket) => return Some(socket),
+            Err(HandshakeError::Interrupted(handshake)) => {
+                if stop.load(Ordering::Relaxed) || Instant::now() >= deadline {
+                    return None;
+                }
+
+                std::thread::sleep(POLL_INTERVAL);
+                result = handshake.handshake();
+            }
+            Err(HandshakeError::Failure(_)) => return None,
+        }
+    }
+}
+
+/// Sends queued messages and receives a single message.
+///
+/// Returns `false` when the connection is closed.
+fn exchange(
+    id: ClientId,
+    socket: &mut WebSocket<TcpStream>,
+    outgoing: &Receiver<String>,
+    events: &Sender<Event>,
+) -> bool {
+    while let Ok(text) = outgoing.try_recv() {
+        if socket.send(Message::Text(text)).is_err() {
+            return false;
+        }
+    }
+
+    match socket.read() {
+        Ok(Message::Text(text)) => events.send(Event::Received(id, text)).is_ok(),
+        Ok(Message::Close(_)) => false,
+        Ok(_) => true,
+        Err(tungstenite::Error::Io(err))
+            if matches!(
+                err.kind(),
+                std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+            ) =>
+        {
+            true
+        }
+        Err(_) => false,
+    }
+}
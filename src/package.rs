@@ -0,0 +1,180 @@
+//! Fat plugin layout helpers.
+//!
+//! X-Plane loads plugins from a folder which contains a sub-folder per platform:
+//! ```text
+//! <name>/
+//!     mac_x64/<name>.xpl
+//!     win_x64/<name>.xpl
+//!     lin_x64/<name>.xpl
+//! ```
+//! The helpers below can be used from a build script or an xtask to package the built
+//! `cdylib` and at runtime to verify the plugin is installed correctly.
+
+use std::{fs, io, path};
+
+use crate::api::plugin::{get_my_id, get_plugin_info};
+
+/// A file extension of X-Plane plugins.
+pub const PLUGIN_EXTENSION: &str = "xpl";
+
+/// A platform supported by X-Plane.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Platform {
+    /// Apple macOS.
+    Mac,
+    /// Microsoft Windows.
+    Windows,
+    /// Linux.
+    Linux,
+}
+
+impl Platform {
+    /// Returns all supported platforms.
+    pub fn all() -> [Platform; 3] {
+        [Self::Mac, Self::Windows, Self::Linux]
+    }
+
+    /// Returns the platform the crate is compiled for.
+    ///
+    /// # Returns
+    /// Returns a [`Platform`] or [`None`] if the platform is not supported by X-Plane.
+    pub fn current() -> Option<Self> {
+        if cfg!(target_os = "macos") {
+            Some(Self::Mac)
+        } else if cfg!(target_os = "windows") {
+            Some(Self::Windows)
+        } else if cfg!(target_os = "linux") {
+            Some(Self::Linux)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the platform folder name inside a fat plugin folder.
+    pub fn folder_name(&self) -> &'static str {
+        match self {
+            Self::Mac => "mac_x64",
+            Self::Windows => "win_x64",
+            Self::Linux => "lin_x64",
+        }
+    }
+
+    /// Returns the file name of a `cdylib` produced by cargo for this platform.
+    ///
+    /// # Arguments
+    /// * `crate_name` - the library crate name.
+    pub fn cdylib_file_name(&self, crate_name: &str) -> String {
+        let crate_name = crate_name.replace('-', "_");
+        match self {
+            Self::Mac => format!("lib{}.dylib", crate_name),
+            Self::Windows => format!("{}.dll", crate_name),
+            Self::Linux => format!("lib{}.so", crate_name),
+        }
+    }
+}
+
+/// A fat plugin folder layout.
+pub struct FatPluginLayout {
+    root: path::PathBuf,
+    name: String,
+}
+
+impl FatPluginLayout {
+    /// Creates a new layout description.
+    ///
+    /// # Arguments
+    /// * `root` - a folder which will contain the plugin folder, e.g. `Resources/plugins`.
+    /// * `name` - the plugin name used for the plugin folder and `.xpl` files.
+    ///
+    /// # Returns
+    /// Returns a new layout instance.
+    pub fn new<R: Into<path::PathBuf>, N: Into<String>>(root: R, name: N) -> Self {
+        Self {
+            root: root.into(),
+            name: name.into(),
+        }
+    }
+
+    /// Returns the plugin folder path.
+    pub fn plugin_dir(&self) -> path::PathBuf {
+        self.root.join(&self.name)
+    }
+
+    /// Returns the platform folder path.
+    ///
+    /// # Arguments
+    /// * `platform` - a target platform.
+    pub fn platform_dir(&self, platform: Platform) -> path::PathBuf {
+        self.plugin_dir().join(platform.folder_name())
+    }
+
+    /// Returns the `.xpl` file path for a platform.
+    ///
+    /// # Arguments
+    /// * `platform` - a target platform.
+    pub fn xpl_path(&self, platform: Platform) -> path::PathBuf {
+        self.platform_dir(platform)
+            .join(&self.name)
+            .with_extension(PLUGIN_EXTENSION)
+    }
+
+    /// Copies a built `cdylib` into the layout creating all required folders.
+    ///
+    /// # Arguments
+    /// * `platform` - a platform the library is built for.
+    /// * `library` - a path to the built library.
+    ///
+    /// # Returns
+    /// Returns the path of the installed `.xpl` file on success. Otherwise returns [`io::Error`].
+    pub fn install<P: AsRef<path::Path>>(
+        &self,
+        platform: Platform,
+        library: P,
+    ) -> io::Result<path::PathBuf> {
+        fs::create_dir_all(self.platform_dir(platform))?;
+        let xpl_path = self.xpl_path(platform);
+        fs::copy(library, &xpl_path)?;
+        Ok(xpl_path)
+    }
+}
+
+/// Verifies the running plugin is installed using the fat plugin layout
+/// and writes a warning to the log otherwise.
+///
+/// # Returns
+/// Returns `true` if the plugin is installed correctly. Otherwise returns `false`.
+pub fn assert_correct_install() -> bool {
+    let file_path = match get_my_id().and_then(|id| get_plugin_info(&id)) {
+        Ok(info) => path::PathBuf::from(info.file_path),
+        Err(err) => {
+            crate::warn!("unable to verify plugin installation: {}", err);
+            return false;
+        }
+    };
+
+    let platform_dir = file_path.parent();
+    let platform_name = platform_dir
+        .and_then(|dir| dir.file_name())
+        .and_then(|name| name.to_str());
+    let plugin_name = platform_dir
+        .and_then(|dir| dir.parent())
+        .and_then(|dir| dir.file_name())
+        .and_then(|name| name.to_str());
+    let expected_platform = Platform::current().map(|platform| platform.folder_name());
+
+    let is_correct = platform_name.is_some()
+        && platform_name == expected_platform
+        && file_path.extension().and_then(|ext| ext.to_str()) == Some(PLUGIN_EXTENSION)
+        && file_path.file_stem().and_then(|stem| stem.to_str()) == plugin_name;
+
+    if !is_correct {
+        crate::warn!(
+            "plugin {} is not installed as <name>/{}/<name>.{}",
+            file_path.display(),
+            expected_platform.unwrap_or("<platform>"),
+            PLUGIN_EXTENSION
+        );
+    }
+
+    is_correct
+}
@@ -1,34 +1,122 @@
+pub mod abi;
+pub mod filter;
+pub mod instance;
+pub mod main_thread;
+pub mod namespace;
+#[cfg(feature = "dev-reload")]
+pub mod reload;
+pub mod retry;
+
+pub use self::filter::MessageFilter;
+pub use self::instance::PluginCell;
+pub use self::main_thread::{MainThreadQueue, MainThreadSender};
+pub use self::namespace::Namespace;
+#[cfg(feature = "dev-reload")]
+pub use self::reload::{reload_command_name, DevReload, PluginWatcher};
+pub use self::retry::{retry_on_load, RetryOnLoad};
+
+use crate::api::plugin::{Message, PluginId};
+
 pub trait XPlugin: Sized {
     type Error: std::error::Error;
 
+    /// When `true` messages received before [`XPlugin::enable`] completes are queued
+    /// and delivered right after the plugin is enabled. See [`MessageFilter`].
+    const QUEUE_MESSAGES_UNTIL_ENABLED: bool = false;
+
     fn start() -> Result<Self, Self::Error>;
     fn stop(&mut self);
     fn enable(&mut self) -> Result<(), Self::Error>;
     fn disable(&mut self);
+
+    /// Called when a message is sent to the plugin by X-Plane or another plugin.
+    ///
+    /// # Arguments
+    /// * `from` - the sender plugin identifier.
+    /// * `message` - the received message. See [`Message`] for more details.
+    fn receive_message(&mut self, _from: PluginId, _message: Message) {}
 }
 
+/// Registers a plugin type implementing [`XPlugin`] and exports the X-Plane plugin entry points.
+///
+/// The metadata accepts any `&'static str` expression, e.g. `concat!` or `env!`.
+/// Optional `on_enable` and `on_disable` hooks receive `&mut` plugin instance and are called
+/// after a successful enable and before disable respectively.
+///
+/// The instance is kept in a [`PluginCell`] inside a hidden module, so no `static mut` is
+/// generated. It is dropped in `XPluginStop` and created again by the next `XPluginStart`.
+/// Set `fat_plugin = true` when the same binary is shipped in every platform folder of a
+/// fat plugin, the installation layout is then verified on start, see
+/// [`crate::package::assert_correct_install`].
+///
+/// ```ignore
+/// xplm::register_plugin!(
+///     instance = MyPlugin,
+///     name = concat!("My Plugin ", env!("CARGO_PKG_VERSION")),
+///     signature = "com.example.my_plugin",
+///     description = env!("CARGO_PKG_DESCRIPTION"),
+///     fat_plugin = true,
+///     on_enable = |plugin: &mut MyPlugin| xplm::info!("enabled"),
+/// );
+/// ```
 #[macro_export]
 macro_rules! register_plugin {
     (
         instance = $plugin_type: ty,
-        name = $name: literal,
-        signature = $signature: literal,
-        description = $description: literal,
+        name = $name: expr,
+        signature = $signature: expr,
+        description = $description: expr,
+        $(fat_plugin = $fat_plugin: expr,)?
+        $(on_enable = $on_enable: expr,)?
+        $(on_disable = $on_disable: expr,)?
     ) => {
-        use xplm::plugin::XPlugin;
-
-        static mut PLUGIN_INSTANCE: std::sync::OnceLock<$plugin_type> = std::sync::OnceLock::new();
-        const XP_RESULT_OK: ::std::os::raw::c_int = 1;
-        const XP_RESULT_ERR: ::std::os::raw::c_int = 0;
-
-        #[no_mangle]
-        #[allow(non_snake_case)]
-        pub unsafe extern "C" fn XPluginStart(
-            name: *mut ::std::os::raw::c_char,
-            signature: *mut ::std::os::raw::c_char,
-            description: *mut ::std::os::raw::c_char,
-        ) -> ::std::os::raw::c_int {
-            pub unsafe fn copy_to_c_buffer(src: &str, dest: *mut ::std::os::raw::c_char) {
+        #[doc(hidden)]
+        type __XplmPluginInstance = $plugin_type;
+
+        // The user expressions are evaluated here rather than inside the hidden module,
+        // so they resolve names from the scope the macro is invoked in.
+        #[doc(hidden)]
+        fn __xplm_plugin_name() -> &'static str {
+            $name
+        }
+
+        #[doc(hidden)]
+        fn __xplm_plugin_signature() -> &'static str {
+            $signature
+        }
+
+        #[doc(hidden)]
+        fn __xplm_plugin_description() -> &'static str {
+            $description
+        }
+
+        #[doc(hidden)]
+        fn __xplm_plugin_is_fat() -> bool {
+            false $(|| $fat_plugin)?
+        }
+
+        #[doc(hidden)]
+        #[allow(unused_variables)]
+        fn __xplm_plugin_on_enable(instance: &mut __XplmPluginInstance) {
+            $(($on_enable)(instance);)?
+        }
+
+        #[doc(hidden)]
+        #[allow(unused_variables)]
+        fn __xplm_plugin_on_disable(instance: &mut __XplmPluginInstance) {
+            $(($on_disable)(instance);)?
+        }
+
+        #[doc(hidden)]
+        mod __xplm_plugin {
+            use $crate::plugin::XPlugin;
+
+            static PLUGIN: $crate::plugin::PluginCell<super::__XplmPluginInstance> =
+                $crate::plugin::PluginCell::new();
+            const XP_RESULT_OK: ::std::os::raw::c_int = 1;
+            const XP_RESULT_ERR: ::std::os::raw::c_int = 0;
+
+            unsafe fn copy_to_c_buffer(src: &str, dest: *mut ::std::os::raw::c_char) {
                 let src_len = std::cmp::min(src.len(), 255);
                 let src_c = std::ffi::CString::new(&src[..src_len])
                     .unwrap_or_else(|_| std::ffi::CString::new("<invalid>").unwrap());
@@ -36,66 +124,91 @@ macro_rules! register_plugin {
                 std::ptr::copy_nonoverlapping(src_c.as_ptr(), dest, src_c_length);
             }
 
-            // Replace with get_or_try_init after stabilization https://github.com/rust-lang/rust/issues/109737
-            if PLUGIN_INSTANCE.get().is_none() {
-                match <$plugin_type>::start() {
-                    Ok(instance) => {
-                        copy_to_c_buffer($name, name);
-                        copy_to_c_buffer($signature, signature);
-                        copy_to_c_buffer($description, description);
-
-                        PLUGIN_INSTANCE
-                            .set(instance)
-                            .map_or(XP_RESULT_ERR, |_| XP_RESULT_OK)
-                    }
+            #[no_mangle]
+            #[allow(non_snake_case)]
+            pub unsafe extern "C" fn XPluginStart(
+                name: *mut ::std::os::raw::c_char,
+                signature: *mut ::std::os::raw::c_char,
+                description: *mut ::std::os::raw::c_char,
+            ) -> ::std::os::raw::c_int {
+                // The metadata is copied on every start, X-Plane passes new buffers after a reload.
+                copy_to_c_buffer(super::__xplm_plugin_name(), name);
+                copy_to_c_buffer(super::__xplm_plugin_signature(), signature);
+                copy_to_c_buffer(super::__xplm_plugin_description(), description);
+                if super::__xplm_plugin_is_fat() {
+                    $crate::package::assert_correct_install();
+                }
+
+                match PLUGIN.start(<super::__XplmPluginInstance>::start) {
+                    Ok(true) => XP_RESULT_OK,
+                    Ok(false) => XP_RESULT_ERR,
                     Err(err) => {
-                        xplm::error!("{}", err);
+                        $crate::error!("{}", err);
                         XP_RESULT_ERR
                     }
                 }
-            } else {
-                XP_RESULT_OK
             }
-        }
 
-        #[no_mangle]
-        #[allow(non_snake_case)]
-        pub unsafe extern "C" fn XPluginStop() {
-            if let Some(instance) = PLUGIN_INSTANCE.get_mut() {
-                instance.stop();
+            #[no_mangle]
+            #[allow(non_snake_case)]
+            pub unsafe extern "C" fn XPluginStop() {
+                PLUGIN.stop(|instance| instance.stop());
+                $crate::api::scenery::release_shared_probe();
+                $crate::api::display::animation::stop_window_animations();
             }
-        }
 
-        #[no_mangle]
-        #[allow(non_snake_case)]
-        pub unsafe extern "C" fn XPluginEnable() -> ::std::os::raw::c_int {
-            if let Some(instance) = PLUGIN_INSTANCE.get_mut() {
-                if let Err(err) = instance.enable() {
-                    xplm::error!("{}", err);
-                    XP_RESULT_ERR
-                } else {
-                    XP_RESULT_OK
-                }
-            } else {
-                XP_RESULT_ERR
+            #[no_mangle]
+            #[allow(non_snake_case)]
+            pub unsafe extern "C" fn XPluginEnable() -> ::std::os::raw::c_int {
+                PLUGIN
+                    .with(|instance, filter| {
+                        if let Err(err) = instance.enable() {
+                            $crate::error!("{}", err);
+                            XP_RESULT_ERR
+                        } else {
+                            super::__xplm_plugin_on_enable(instance);
+                            for (from, message) in filter.enable() {
+                                instance.receive_message(from, message);
+                            }
+                            XP_RESULT_OK
+                        }
+                    })
+                    .unwrap_or(XP_RESULT_ERR)
             }
-        }
 
-        #[no_mangle]
-        #[allow(non_snake_case)]
-        pub unsafe extern "C" fn XPluginDisable() {
-            if let Some(instance) = PLUGIN_INSTANCE.get_mut() {
-                instance.disable();
+            #[no_mangle]
+            #[allow(non_snake_case)]
+            pub unsafe extern "C" fn XPluginDisable() {
+                PLUGIN.with(|instance, filter| {
+                    filter.disable();
+                    super::__xplm_plugin_on_disable(instance);
+                    instance.disable();
+                    $crate::api::utilities::audit_command_balance();
+                });
             }
-        }
 
-        #[no_mangle]
-        #[allow(non_snake_case)]
-        pub unsafe extern "C" fn XPluginReceiveMessage(
-            _from: ::std::os::raw::c_int,
-            _message: ::std::os::raw::c_int,
-            _param: *mut ::std::os::raw::c_void,
-        ) {
+            #[no_mangle]
+            #[allow(non_snake_case)]
+            pub unsafe extern "C" fn XPluginReceiveMessage(
+                from: ::std::os::raw::c_int,
+                message: ::std::os::raw::c_int,
+                param: *mut ::std::os::raw::c_void,
+            ) {
+                let Ok(from) = $crate::api::plugin::PluginId::try_from(from) else {
+                    return;
+                };
+
+                PLUGIN.with(|instance, filter| {
+                    let message = $crate::api::plugin::Message::new(message, param);
+                    if <super::__XplmPluginInstance>::QUEUE_MESSAGES_UNTIL_ENABLED {
+                        if let Some((from, message)) = filter.filter(from, message) {
+                            instance.receive_message(from, message);
+                        }
+                    } else {
+                        instance.receive_message(from, message);
+                    }
+                });
+            }
         }
     };
 }
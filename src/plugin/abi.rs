@@ -0,0 +1,151 @@
+//! A convention to expose typed APIs between Rust plugins built as separate `cdylib`s.
+//!
+//! A provider exports a `#[repr(C)]` table of `extern "C"` functions under a name and
+//! a version. A consumer sends the [`API_REQUEST_MESSAGE`] message to the provider with an
+//! [`ApiRequest`] parameter describing the accepted versions range. The provider answers
+//! synchronously from its message handler by filling the negotiated version and the table
+//! pointer. The table must be `'static` and must never change its layout within a version.
+
+use std::ffi;
+
+use crate::api::plugin::{self, Message, PluginError, PluginId};
+
+/// A message identifier used for the API handshake.
+pub const API_REQUEST_MESSAGE: ::std::os::raw::c_int = 0x7870_4101;
+
+/// A magic number which protects against foreign messages with the same identifier.
+const API_REQUEST_MAGIC: u32 = 0x5853_4142;
+
+/// An API handshake request shared between plugins.
+#[repr(C)]
+pub struct ApiRequest {
+    magic: u32,
+    name: *const ::std::os::raw::c_char,
+    min_version: u32,
+    max_version: u32,
+    version: u32,
+    table: *const ::std::os::raw::c_void,
+}
+
+/// An API exported by a provider plugin.
+pub struct ExportedApi {
+    name: &'static ffi::CStr,
+    version: u32,
+    table: *const ::std::os::raw::c_void,
+}
+
+impl ExportedApi {
+    /// Creates a new exported API description.
+    ///
+    /// # Arguments
+    /// * `name` - a unique API name.
+    /// * `version` - an API version.
+    /// * `table` - a `#[repr(C)]` table of `extern "C"` functions.
+    ///
+    /// # Returns
+    /// Returns a new exported API description.
+    pub fn new<V>(name: &'static ffi::CStr, version: u32, table: &'static V) -> Self {
+        Self {
+            name,
+            version,
+            table: table as *const V as *const _,
+        }
+    }
+}
+
+/// A typed API table received from a provider plugin.
+pub struct ApiHandle<V: 'static> {
+    /// A negotiated API version.
+    pub version: u32,
+    /// An API functions table.
+    pub table: &'static V,
+}
+
+/// Handles an API handshake request. Should be called from [`super::XPlugin::receive_message`]
+/// of the provider plugin.
+///
+/// When the same API is exported in multiple versions the highest version
+/// within the requested range is picked.
+///
+/// # Arguments
+/// * `message` - a received message.
+/// * `exports` - APIs exported by the plugin.
+///
+/// # Returns
+/// Returns `true` if the message was an API request and it was answered. Otherwise returns `false`.
+///
+/// # Safety
+/// A non-null parameter of a message with the [`API_REQUEST_MESSAGE`] identifier must point
+/// to a valid, writable [`ApiRequest`] whose name is a nul-terminated string, e.g. as sent by
+/// [`request_api`]. The magic number only rejects foreign messages which happen to use the
+/// same identifier with a readable parameter, it can't make an arbitrary pointer safe.
+pub unsafe fn handle_api_request(message: &Message, exports: &[ExportedApi]) -> bool {
+    let param = match message {
+        Message::Other { id, param } if *id == API_REQUEST_MESSAGE && !param.is_null() => *param,
+        _ => return false,
+    };
+
+    let request = &mut *(param as *mut ApiRequest);
+    if request.magic != API_REQUEST_MAGIC || request.name.is_null() {
+        return false;
+    }
+
+    let name = ffi::CStr::from_ptr(request.name);
+    let export = exports
+        .iter()
+        .filter(|export| export.name == name)
+        .filter(|export| (request.min_version..=request.max_version).contains(&export.version))
+        .max_by_key(|export| export.version);
+
+    match export {
+        Some(export) => {
+            request.version = export.version;
+            request.table = export.table;
+            true
+        }
+        None => false,
+    }
+}
+
+/// Requests an API table from a provider plugin.
+///
+/// # Arguments
+/// * `provider` - the provider plugin identifier.
+/// * `name` - a unique API name.
+/// * `min_version` - a minimum accepted API version.
+/// * `max_version` - a maximum accepted API version.
+///
+/// # Returns
+/// Returns [`ApiHandle`] if the provider exports a compatible version, [`None`]
+/// if it doesn't. Otherwise returns [`PluginError`].
+///
+/// # Safety
+/// The caller must guarantee that `V` matches the table layout exported by the provider
+/// for every version in the requested range.
+pub unsafe fn request_api<V: 'static, T: Into<String>>(
+    provider: &PluginId,
+    name: T,
+    min_version: u32,
+    max_version: u32,
+) -> Result<Option<ApiHandle<V>>, PluginError> {
+    let name_c = ffi::CString::new(name.into()).map_err(PluginError::InvalidApiName)?;
+    let mut request = ApiRequest {
+        magic: API_REQUEST_MAGIC,
+        name: name_c.as_ptr(),
+        min_version,
+        max_version,
+        version: 0,
+        table: std::ptr::null(),
+    };
+
+    plugin::send_message_to_plugin(provider, API_REQUEST_MESSAGE, &mut request);
+
+    if request.table.is_null() {
+        Ok(None)
+    } else {
+        Ok(Some(ApiHandle {
+            version: request.version,
+            table: &*(request.table as *const V),
+        }))
+    }
+}
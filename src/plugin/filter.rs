@@ -0,0 +1,69 @@
+use crate::api::plugin::{Message, PluginId};
+
+/// Queues messages received before the plugin is enabled and delivers them afterwards,
+/// so message handlers never touch state initialized in [`super::XPlugin::enable`].
+///
+/// Messages of unknown types carry raw pointers which are only valid during delivery,
+/// so they are dropped instead of being queued.
+pub struct MessageFilter {
+    is_enabled: bool,
+    queue: Vec<(PluginId, Message)>,
+}
+
+impl MessageFilter {
+    /// Creates a new filter for a disabled plugin.
+    pub const fn new() -> Self {
+        Self {
+            is_enabled: false,
+            queue: Vec::new(),
+        }
+    }
+
+    /// Checks wether messages are delivered immediately.
+    pub fn is_enabled(&self) -> bool {
+        self.is_enabled
+    }
+
+    /// Marks the plugin enabled.
+    ///
+    /// # Returns
+    /// Returns the queued messages which should be delivered now.
+    pub fn enable(&mut self) -> Vec<(PluginId, Message)> {
+        self.is_enabled = true;
+        std::mem::take(&mut self.queue)
+    }
+
+    /// Marks the plugin disabled. Messages received afterwards are queued again.
+    pub fn disable(&mut self) {
+        self.is_enabled = false;
+    }
+
+    /// Filters a received message.
+    ///
+    /// # Arguments
+    /// * `from` - the sender plugin identifier.
+    /// * `message` - the received message.
+    ///
+    /// # Returns
+    /// Returns the message if it should be delivered immediately. Otherwise returns [`None`].
+    pub fn filter(&mut self, from: PluginId, message: Message) -> Option<(PluginId, Message)> {
+        if self.is_enabled {
+            return Some((from, message));
+        }
+
+        match message {
+            Message::Other { id, .. } => {
+                crate::warn!("dropping message {} received before plugin is enabled", id);
+            }
+            _ => self.queue.push((from, message)),
+        }
+
+        None
+    }
+}
+
+impl Default for MessageFilter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,118 @@
+//! Storage of the plugin instance used by [`crate::register_plugin`].
+//!
+//! X-Plane calls every plugin entry point on the main thread, so the instance is owned
+//! by the first thread which touches the cell. Access from any other thread and
+//! re-entrant access, e.g. an entry point called while another one is running, is
+//! refused instead of creating aliasing references.
+
+use std::cell::UnsafeCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+use std::thread::{self, ThreadId};
+
+use super::MessageFilter;
+
+struct Slot<T> {
+    instance: Option<T>,
+    filter: MessageFilter,
+}
+
+/// A cell holding the plugin instance and its [`MessageFilter`], usable as a `static`.
+pub struct PluginCell<T> {
+    owner: OnceLock<ThreadId>,
+    is_borrowed: AtomicBool,
+    slot: UnsafeCell<Slot<T>>,
+}
+
+// The slot is only accessed from the owner thread and never re-entrantly, see `borrow`.
+unsafe impl<T> Sync for PluginCell<T> {}
+
+impl<T> PluginCell<T> {
+    /// Creates an empty cell.
+    pub const fn new() -> Self {
+        Self {
+            owner: OnceLock::new(),
+            is_borrowed: AtomicBool::new(false),
+            slot: UnsafeCell::new(Slot {
+                instance: None,
+                filter: MessageFilter::new(),
+            }),
+        }
+    }
+
+    /// Checks wether the plugin instance has been started.
+    pub fn is_started(&self) -> bool {
+        self.borrow(|slot| slot.instance.is_some()).unwrap_or(false)
+    }
+
+    /// Starts the plugin instance unless it is already running. The instance is created
+    /// again after [`PluginCell::stop`], e.g. when the plugin is reloaded.
+    ///
+    /// # Arguments
+    /// * `start` - a function which creates the instance.
+    ///
+    /// # Returns
+    /// Returns `Ok(true)` if the instance is running, `Ok(false)` if the cell is accessed
+    /// from a wrong thread or re-entrantly. Otherwise returns the start error.
+    pub fn start<E, F: FnOnce() -> Result<T, E>>(&self, start: F) -> Result<bool, E> {
+        self.borrow(|slot| {
+            if slot.instance.is_none() {
+                slot.instance = Some(start()?);
+                slot.filter = MessageFilter::new();
+            }
+
+            Ok(true)
+        })
+        .unwrap_or(Ok(false))
+    }
+
+    /// Calls a function with the running instance and the message filter.
+    ///
+    /// # Arguments
+    /// * `f` - a function to call.
+    ///
+    /// # Returns
+    /// Returns the function result or [`None`] if there is no running instance or the
+    /// cell is accessed from a wrong thread or re-entrantly.
+    pub fn with<R, F: FnOnce(&mut T, &mut MessageFilter) -> R>(&self, f: F) -> Option<R> {
+        self.borrow(|slot| {
+            let Slot { instance, filter } = slot;
+            instance.as_mut().map(|instance| f(instance, filter))
+        })
+        .flatten()
+    }
+
+    /// Stops the running instance and drops it.
+    ///
+    /// # Arguments
+    /// * `stop` - a function called with the instance before it is dropped.
+    pub fn stop<F: FnOnce(&mut T)>(&self, stop: F) {
+        let instance = self.borrow(|slot| slot.instance.take()).flatten();
+        if let Some(mut instance) = instance {
+            stop(&mut instance);
+        }
+    }
+
+    fn borrow<R, F: FnOnce(&mut Slot<T>) -> R>(&self, f: F) -> Option<R> {
+        let current = thread::current().id();
+        if *self.owner.get_or_init(|| current) != current {
+            crate::error!("plugin instance accessed from a thread other than the main thread");
+            return None;
+        }
+
+        if self.is_borrowed.swap(true, Ordering::Acquire) {
+            crate::error!("plugin instance accessed re-entrantly, the call is ignored");
+            return None;
+        }
+
+        let result = f(unsafe { &mut *self.slot.get() });
+        self.is_borrowed.store(false, Ordering::Release);
+        Some(result)
+    }
+}
+
+impl<T> Default for PluginCell<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,156 @@
+//! Main thread confinement of X-Plane handles.
+//!
+//! The X-Plane SDK may only be called from the main thread. Handles such as
+//! [`DataRef`], [`Command`] and [`WindowId`] carry a [`MainThreadOnly`] marker, which makes
+//! them neither `Send` nor `Sync`, so the compiler rejects moving them into another thread.
+//!
+//! Background threads convert a handle into its sendable counterpart, e.g. [`SendableDataRef`],
+//! and access it through a [`MainThreadSender`]. The work is executed when the plugin calls
+//! [`MainThreadQueue::run_pending`] on the main thread, e.g. from a draw callback.
+
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::sync::mpsc;
+
+use crate::api::data_access::DataRef;
+use crate::api::display::WindowId;
+use crate::api::utilities::Command;
+
+/// A zero-sized marker which makes a type neither `Send` nor `Sync`.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct MainThreadOnly(PhantomData<*const ()>);
+
+type Task = Box<dyn FnOnce() + Send>;
+
+/// A queue of work scheduled from other threads to run on the main thread.
+pub struct MainThreadQueue {
+    sender: mpsc::Sender<Task>,
+    receiver: mpsc::Receiver<Task>,
+    _marker: MainThreadOnly,
+}
+
+impl MainThreadQueue {
+    /// Creates a new queue. Should be created on the main thread.
+    ///
+    /// # Returns
+    /// Returns a new [`MainThreadQueue`] instance.
+    pub fn new() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            sender,
+            receiver,
+            _marker: MainThreadOnly::default(),
+        }
+    }
+
+    /// Returns a sender which can be moved to other threads.
+    pub fn sender(&self) -> MainThreadSender {
+        MainThreadSender(self.sender.clone())
+    }
+
+    /// Runs all scheduled work. Must be called from the main thread.
+    ///
+    /// # Returns
+    /// Returns the number of executed tasks.
+    pub fn run_pending(&self) -> usize {
+        let mut count = 0;
+        while let Ok(task) = self.receiver.try_recv() {
+            task();
+            count += 1;
+        }
+
+        count
+    }
+}
+
+impl Default for MainThreadQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A handle used by other threads to schedule work on the main thread.
+#[derive(Clone)]
+pub struct MainThreadSender(mpsc::Sender<Task>);
+
+impl MainThreadSender {
+    /// Schedules a closure to run on the main thread.
+    ///
+    /// # Arguments
+    /// * `task` - a closure to run.
+    ///
+    /// # Returns
+    /// Returns `true` if scheduled or `false` if the queue has been dropped.
+    pub fn run<F: FnOnce() + Send + 'static>(&self, task: F) -> bool {
+        self.0.send(Box::new(task)).is_ok()
+    }
+
+    /// Schedules a closure which accesses a main thread handle.
+    ///
+    /// # Arguments
+    /// * `handle` - a sendable handle, e.g. [`SendableDataRef`].
+    /// * `task` - a closure which receives the restored handle on the main thread.
+    ///
+    /// # Returns
+    /// Returns `true` if scheduled or `false` if the queue has been dropped.
+    pub fn with<S, F>(&self, handle: S, task: F) -> bool
+    where
+        S: Sendable,
+        F: FnOnce(&S::Target) + Send + 'static,
+    {
+        self.run(move || task(&handle.restore()))
+    }
+}
+
+/// A handle which can be sent to other threads but only accessed on the main thread
+/// through [`MainThreadSender::with`].
+pub trait Sendable: Send + 'static {
+    /// A main thread handle type.
+    type Target;
+
+    #[doc(hidden)]
+    fn restore(&self) -> Self::Target;
+}
+
+macro_rules! sendable_handle {
+    ($(#[$meta:meta])* $name:ident, $target:ty, $raw:ty) => {
+        $(#[$meta])*
+        #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+        pub struct $name(usize);
+
+        impl From<&$target> for $name {
+            fn from(value: &$target) -> Self {
+                Self(*value.deref() as usize)
+            }
+        }
+
+        impl Sendable for $name {
+            type Target = $target;
+
+            fn restore(&self) -> Self::Target {
+                <$target>::try_from(self.0 as $raw).expect("sendable handles are never null")
+            }
+        }
+    };
+}
+
+sendable_handle!(
+    /// A [`DataRef`] which can be sent to other threads.
+    SendableDataRef,
+    DataRef,
+    xplm_sys::XPLMDataRef
+);
+
+sendable_handle!(
+    /// A [`Command`] which can be sent to other threads.
+    SendableCommand,
+    Command,
+    xplm_sys::XPLMCommandRef
+);
+
+sendable_handle!(
+    /// A [`WindowId`] which can be sent to other threads.
+    SendableWindowId,
+    WindowId,
+    xplm_sys::XPLMWindowID
+);
@@ -0,0 +1,157 @@
+use std::any::Any;
+use std::cell::Cell;
+use std::rc::Rc;
+
+use crate::api::data_access::{register_data_ref, OwnedValue};
+use crate::api::plugin::{get_my_id, get_plugin_info};
+use crate::api::utilities::{
+    create_command, register_command_handler, Command, CommandExecutionTime, CommandHandler,
+    CommandHandlerRecord,
+};
+
+/// Publishes data refs and commands under a common prefix and keeps track of them.
+///
+/// Everything published through the namespace is unregistered at once by
+/// [`Namespace::clear`] or when the namespace is dropped, so it is usually created in
+/// [`crate::plugin::XPlugin::enable`] and cleared in [`crate::plugin::XPlugin::disable`].
+///
+/// ```ignore
+/// let mut namespace = Namespace::new()?; // com.acme.myplugin -> acme/myplugin
+/// let gear = namespace.publish("gear/handle", false, 0)?; // acme/myplugin/gear/handle
+/// namespace.command("gear/toggle", "Toggle gear", GearToggle(gear))?;
+/// ```
+pub struct Namespace {
+    prefix: String,
+    names: Vec<String>,
+    data_refs: Vec<Box<dyn Any>>,
+    commands: Vec<CommandHandlerRecord>,
+}
+
+impl Namespace {
+    /// Creates a namespace prefixed with the calling plugin signature.
+    /// Dots are turned into slashes and a leading top level domain of a reverse
+    /// domain signature is dropped, e.g. `com.acme.myplugin` becomes `acme/myplugin`.
+    ///
+    /// # Returns
+    /// Returns a new [`Namespace`] on success. Otherwise returns [`crate::XplmError`].
+    pub fn new() -> crate::Result<Self> {
+        let info = get_plugin_info(&get_my_id()?)?;
+        Ok(Self::with_prefix(signature_prefix(&info.signature)))
+    }
+
+    /// Creates a namespace with a custom prefix.
+    ///
+    /// # Arguments
+    /// * `prefix` - a prefix, e.g. `acme/myplugin`. Leading and trailing slashes are ignored.
+    ///
+    /// # Returns
+    /// Returns a new [`Namespace`] instance.
+    pub fn with_prefix<P: Into<String>>(prefix: P) -> Self {
+        Self {
+            prefix: prefix.into().trim_matches('/').to_string(),
+            names: Vec::new(),
+            data_refs: Vec::new(),
+            commands: Vec::new(),
+        }
+    }
+
+    /// Returns the namespace prefix.
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Builds a full name inside the namespace.
+    ///
+    /// # Arguments
+    /// * `name` - a name relative to the namespace, e.g. `gear/handle`.
+    ///
+    /// # Returns
+    /// Returns the prefixed name, e.g. `acme/myplugin/gear/handle`.
+    pub fn name(&self, name: &str) -> String {
+        format!("{}/{}", self.prefix, name.trim_start_matches('/'))
+    }
+
+    /// Publishes a scalar data ref inside the namespace.
+    ///
+    /// # Arguments
+    /// * `name` - a data ref name relative to the namespace.
+    /// * `writable` - `true` if other plugins may write the value.
+    /// * `initial` - an initial value.
+    ///
+    /// # Returns
+    /// Returns the shared value which is read by other plugins on success.
+    /// Otherwise returns [`crate::XplmError`].
+    pub fn publish<T: OwnedValue>(
+        &mut self,
+        name: &str,
+        writable: bool,
+        initial: T,
+    ) -> crate::Result<Rc<Cell<T>>> {
+        let data_ref = register_data_ref(self.name(name), writable, initial)?;
+        let value = data_ref.value();
+        self.names.push(data_ref.name().to_string());
+        self.data_refs.push(Box::new(data_ref));
+        Ok(value)
+    }
+
+    /// Creates a command inside the namespace and registers its handler.
+    ///
+    /// # Arguments
+    /// * `name` - a command name relative to the namespace.
+    /// * `description` - a command description.
+    /// * `handler` - a command handler. See [`CommandHandler`] for more details.
+    ///
+    /// # Returns
+    /// Returns the created [`Command`] on success. Otherwise returns [`crate::XplmError`].
+    pub fn command<D, H>(
+        &mut self,
+        name: &str,
+        description: D,
+        handler: H,
+    ) -> crate::Result<Command>
+    where
+        D: Into<String>,
+        H: CommandHandler,
+    {
+        let name = self.name(name);
+        let command = create_command(name.as_str(), description)?;
+        let record =
+            register_command_handler(&command, CommandExecutionTime::BeforeXPlane, handler);
+        self.names.push(name);
+        self.commands.push(record);
+        Ok(command)
+    }
+
+    /// Returns the full names of everything published through the namespace.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.names.iter().map(String::as_str)
+    }
+
+    /// Unregisters all published data refs and command handlers.
+    /// X-Plane keeps the commands themselves, they do nothing until registered again.
+    pub fn clear(&mut self) {
+        self.commands.clear();
+        self.data_refs.clear();
+        self.names.clear();
+    }
+}
+
+/// Turns a plugin signature into a namespace prefix.
+fn signature_prefix(signature: &str) -> String {
+    let segments = signature
+        .split(['.', '/'])
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>();
+    let segments = match segments.as_slice() {
+        [tld, rest @ ..] if rest.len() >= 2 && is_top_level_domain(tld) => rest,
+        all => all,
+    };
+    segments.join("/").replace(' ', "_")
+}
+
+fn is_top_level_domain(segment: &str) -> bool {
+    matches!(
+        segment.to_ascii_lowercase().as_str(),
+        "com" | "org" | "net" | "io" | "dev" | "de" | "uk" | "eu"
+    )
+}
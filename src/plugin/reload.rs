@@ -0,0 +1,224 @@
+//! Hot reload support for plugin development.
+//! Available with the `dev-reload` feature enabled.
+
+use std::time::{Duration, Instant, SystemTime};
+use std::{fs, path};
+
+use crate::api::menus::{self, MenuId, MenuItemId};
+use crate::api::plugin::{get_my_id, get_plugin_info, reload_plugins};
+use crate::api::utilities::{
+    create_command, register_command_handler, CommandExecutionTime, CommandHandler,
+    CommandHandlerRecord, CommandOutcome,
+};
+
+/// The last part of the reload command name, it is prefixed with the plugin signature.
+pub const RELOAD_COMMAND_SUFFIX: &str = "reload_plugins";
+
+/// The default interval between plugin binary checks.
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+struct ReloadHandler;
+
+impl CommandHandler for ReloadHandler {
+    fn command_begin(&mut self) -> CommandOutcome {
+        crate::info!("reloading plugins");
+        reload_plugins();
+        CommandOutcome::Consume
+    }
+
+    fn command_continue(&mut self) -> CommandOutcome {
+        CommandOutcome::Consume
+    }
+
+    fn command_end(&mut self) -> CommandOutcome {
+        CommandOutcome::Consume
+    }
+}
+
+/// Returns the name of the calling plugin's reload command, which is the plugin signature
+/// followed by [`RELOAD_COMMAND_SUFFIX`], e.g. `com.example.plugin/reload_plugins`.
+/// Every plugin gets its own command, so a key bound to it reloads plugins once.
+///
+/// # Returns
+/// Returns the command name on success. Otherwise returns [`crate::XplmError`].
+pub fn reload_command_name() -> crate::Result<String> {
+    let info = get_plugin_info(&get_my_id()?)?;
+    Ok(format!("{}/{}", info.signature, RELOAD_COMMAND_SUFFIX))
+}
+
+/// Registers the [`reload_command_name`] command and a "Reload plugins" item in the plugins menu.
+/// Bind the command to a key to reload plugins with a single keystroke.
+///
+/// Optionally watches the plugin binary and reloads plugins once a newer build appears.
+/// The command handler and the menu item are removed when dropped. X-Plane can't delete
+/// commands, so the command itself stays registered and does nothing.
+pub struct DevReload {
+    command_name: String,
+    menu: MenuId,
+    item: MenuItemId,
+    watcher: Option<PluginWatcher>,
+    _record: CommandHandlerRecord,
+}
+
+impl DevReload {
+    /// Registers the reload command and menu item.
+    ///
+    /// # Returns
+    /// Returns a new [`DevReload`] instance on success. Otherwise returns [`crate::XplmError`].
+    pub fn new() -> crate::Result<Self> {
+        let command_name = reload_command_name()?;
+        let command = create_command(&command_name, "Reload all plugins")?;
+        let record =
+            register_command_handler(&command, CommandExecutionTime::BeforeXPlane, ReloadHandler);
+        let menu = menus::find_plugins_menu()?;
+        let item = menus::append_menu_item_with_command(&menu, "Reload plugins", &command)?;
+        Ok(Self {
+            command_name,
+            menu,
+            item,
+            watcher: None,
+            _record: record,
+        })
+    }
+
+    /// Enables automatic reload when the calling plugin binary changes on disk.
+    ///
+    /// # Returns
+    /// Returns a modified [`DevReload`] on success. Otherwise returns [`crate::XplmError`].
+    pub fn watch_binary(mut self) -> crate::Result<Self> {
+        self.watcher = Some(PluginWatcher::new()?);
+        Ok(self)
+    }
+
+    /// Enables automatic reload with a custom watcher.
+    ///
+    /// # Arguments
+    /// * `watcher` - a plugin binary watcher.
+    ///
+    /// # Returns
+    /// Returns a modified [`DevReload`].
+    pub fn watcher(mut self, watcher: PluginWatcher) -> Self {
+        self.watcher = Some(watcher);
+        self
+    }
+
+    /// Returns the name of the registered reload command.
+    pub fn command_name(&self) -> &str {
+        &self.command_name
+    }
+
+    /// Checks the watched binary and reloads plugins if a newer build is found.
+    /// Should be called periodically, e.g. from a flight loop or a draw callback.
+    pub fn poll(&mut self) {
+        if let Some(watcher) = self.watcher.as_mut() {
+            watcher.poll();
+        }
+    }
+}
+
+impl Drop for DevReload {
+    fn drop(&mut self) {
+        menus::remove_menu_item(&self.menu, &self.item);
+    }
+}
+
+/// Watches a plugin binary and reports when a newer build is written.
+///
+/// A change is reported only when the modification time stays the same for two
+/// consecutive checks, so a binary which is still being written is not loaded.
+pub struct PluginWatcher {
+    path: path::PathBuf,
+    interval: Duration,
+    next_check: Instant,
+    loaded: Option<SystemTime>,
+    candidate: Option<SystemTime>,
+}
+
+impl PluginWatcher {
+    /// Creates a watcher for the calling plugin binary.
+    ///
+    /// # Returns
+    /// Returns a new [`PluginWatcher`] on success. Otherwise returns [`crate::XplmError`].
+    pub fn new() -> crate::Result<Self> {
+        let info = get_plugin_info(&get_my_id()?)?;
+        Ok(Self::with_path(info.file_path))
+    }
+
+    /// Creates a watcher for an arbitrary file.
+    ///
+    /// # Arguments
+    /// * `path` - a file to watch.
+    ///
+    /// # Returns
+    /// Returns a new [`PluginWatcher`] instance.
+    pub fn with_path<P: Into<path::PathBuf>>(path: P) -> Self {
+        let path = path.into();
+        let loaded = modified_time(&path);
+        Self {
+            path,
+            interval: DEFAULT_POLL_INTERVAL,
+            next_check: Instant::now(),
+            loaded,
+            candidate: None,
+        }
+    }
+
+    /// Sets the interval between file checks.
+    ///
+    /// # Arguments
+    /// * `value` - an interval to set.
+    ///
+    /// # Returns
+    /// Returns a modified watcher with new interval.
+    pub fn interval(mut self, value: Duration) -> Self {
+        self.interval = value;
+        self
+    }
+
+    /// Returns the watched file path.
+    pub fn path(&self) -> &path::Path {
+        &self.path
+    }
+
+    /// Checks whether a newer file was written since the watcher was created.
+    /// The file is checked not more often than the configured interval.
+    ///
+    /// # Returns
+    /// Returns `true` if the file changed and finished writing.
+    pub fn has_changed(&mut self) -> bool {
+        let now = Instant::now();
+        if now < self.next_check {
+            return false;
+        }
+
+        self.next_check = now + self.interval;
+        let modified = modified_time(&self.path);
+        if modified.is_none() || modified == self.loaded {
+            self.candidate = None;
+            return false;
+        }
+
+        if modified == self.candidate {
+            self.loaded = modified;
+            self.candidate = None;
+            true
+        } else {
+            self.candidate = modified;
+            false
+        }
+    }
+
+    /// Reloads all plugins if the watched file changed.
+    pub fn poll(&mut self) {
+        if self.has_changed() {
+            crate::info!("{} changed, reloading plugins", self.path.display());
+            reload_plugins();
+        }
+    }
+}
+
+fn modified_time(path: &path::Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
@@ -0,0 +1,109 @@
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+
+use crate::api::plugin::Message;
+
+/// The delay before the first retry of a failed initialization.
+const INITIAL_BACKOFF: Duration = Duration::from_millis(250);
+
+/// Defers an initialization closure until X-Plane finished loading the scenery.
+///
+/// Some SDK calls fail during sim loading, e.g. return null identifiers.
+/// The closure runs when the first [`Message::AirportLoaded`] or [`Message::SceneryLoaded`]
+/// message is received or when the timeout elapses, whatever comes first.
+/// A failed initialization is retried with an exponential backoff.
+///
+/// # Arguments
+/// * `timeout` - the maximum time to wait for the loading messages.
+/// * `init` - the initialization closure.
+///
+/// # Returns
+/// Returns a [`RetryOnLoad`] which should be fed with plugin messages and polled.
+pub fn retry_on_load<T, E, F>(timeout: Duration, init: F) -> RetryOnLoad<T, F>
+where
+    E: Display,
+    F: FnMut() -> Result<T, E>,
+{
+    RetryOnLoad {
+        init,
+        value: None,
+        loaded: false,
+        deadline: Instant::now() + timeout,
+        backoff: INITIAL_BACKOFF,
+        next_attempt: None,
+    }
+}
+
+/// A deferred initialization created by [`retry_on_load`].
+pub struct RetryOnLoad<T, F> {
+    init: F,
+    value: Option<T>,
+    loaded: bool,
+    deadline: Instant,
+    backoff: Duration,
+    next_attempt: Option<Instant>,
+}
+
+impl<T, E, F> RetryOnLoad<T, F>
+where
+    E: Display,
+    F: FnMut() -> Result<T, E>,
+{
+    /// Handles a plugin message. Should be called from [`super::XPlugin::receive_message`].
+    ///
+    /// # Arguments
+    /// * `message` - the received message.
+    ///
+    /// # Returns
+    /// Returns the initialized value if available.
+    pub fn handle_message(&mut self, message: &Message) -> Option<&mut T> {
+        if matches!(message, Message::AirportLoaded | Message::SceneryLoaded) {
+            self.loaded = true;
+        }
+
+        self.poll()
+    }
+
+    /// Runs the initialization if the sim is loaded or the timeout elapsed
+    /// and the value is not initialized yet.
+    ///
+    /// # Returns
+    /// Returns the initialized value if available.
+    pub fn poll(&mut self) -> Option<&mut T> {
+        if self.value.is_none() && self.is_ready() {
+            match (self.init)() {
+                Ok(value) => self.value = Some(value),
+                Err(err) => {
+                    crate::warn!("deferred initialization failed, retrying: {}", err);
+                    self.next_attempt = Some(Instant::now() + self.backoff);
+                    self.backoff *= 2;
+                }
+            }
+        }
+
+        self.value.as_mut()
+    }
+
+    /// Returns the initialized value without running the initialization.
+    ///
+    /// # Returns
+    /// Returns the initialized value if available.
+    pub fn get(&self) -> Option<&T> {
+        self.value.as_ref()
+    }
+
+    /// Checks wether the initialization completed.
+    ///
+    /// # Returns
+    /// Returns `true` if the value is initialized. Otherwise returns `false`.
+    pub fn is_initialized(&self) -> bool {
+        self.value.is_some()
+    }
+
+    fn is_ready(&self) -> bool {
+        let now = Instant::now();
+        let waited = self.loaded || now >= self.deadline;
+        let backed_off = self.next_attempt.is_none_or(|next| now >= next);
+        waited && backed_off
+    }
+}
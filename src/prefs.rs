@@ -0,0 +1,17 @@
+//! JSON preferences stored in the X-Plane preferences folder.
+//! Available with the `prefs` feature enabled.
+//!
+//! Versioned preferences are wrapped into an envelope with a schema version. When a
+//! stored version is older than the current one, migrations registered in [`Migrations`]
+//! upgrade the stored JSON step by step before it is deserialized, and the original file
+//! is kept as a backup.
+
+pub mod error;
+pub mod migration;
+pub mod preferences;
+
+pub use self::error::PrefsError;
+pub use self::migration::Migrations;
+pub use self::preferences::Preferences;
+
+pub type Result<T> = std::result::Result<T, PrefsError>;
@@ -0,0 +1,38 @@
+use std::{io, path};
+
+use crate::api::utilities::UtilitiesError;
+
+/// An error returned from preferences API calls.
+#[derive(thiserror::Error, Debug)]
+pub enum PrefsError {
+    /// Utilities error, e.g. the preferences folder can't be resolved.
+    #[error("utilities error {0}")]
+    Utilities(UtilitiesError),
+    /// Preferences file can't be read or written.
+    #[error("preferences io error {}: {1}", .0.display())]
+    Io(path::PathBuf, io::Error),
+    /// Preferences can't be serialized or deserialized.
+    #[error("invalid preferences {0}")]
+    Invalid(serde_json::Error),
+    /// Stored preferences are newer than the plugin supports, e.g. after a downgrade.
+    #[error("stored preferences version {stored} is newer than {current}")]
+    UnsupportedVersion { stored: u32, current: u32 },
+    /// There is no migration from a stored version.
+    #[error("no preferences migration from version {0}")]
+    MissingMigration(u32),
+    /// A migration has failed.
+    #[error("preferences migration from version {version} failed: {message}")]
+    Migration { version: u32, message: String },
+}
+
+impl From<UtilitiesError> for PrefsError {
+    fn from(value: UtilitiesError) -> Self {
+        Self::Utilities(value)
+    }
+}
+
+impl From<serde_json::Error> for PrefsError {
+    fn from(value: serde_json::Error) -> Self {
+        Self::Invalid(value)
+    }
+}
@@ -0,0 +1,91 @@
+use serde_json::Value;
+
+use super::{PrefsError, Result};
+
+type Step = Box<dyn Fn(Value) -> std::result::Result<Value, String>>;
+
+/// A chain of migrations which upgrade stored preferences to the current schema version.
+///
+/// ```ignore
+/// let migrations = Migrations::new(2)
+///     .step(0, |mut value| {
+///         value["volume"] = value["volume_percent"].take();
+///         Ok(value)
+///     })
+///     .step(1, |mut value| {
+///         value["theme"] = "dark".into();
+///         Ok(value)
+///     });
+/// ```
+pub struct Migrations {
+    current: u32,
+    steps: Vec<(u32, Step)>,
+}
+
+impl Migrations {
+    /// Creates an empty chain.
+    ///
+    /// # Arguments
+    /// * `current` - the current schema version, preferences are saved with it.
+    ///
+    /// # Returns
+    /// Returns a new [`Migrations`] instance.
+    pub fn new(current: u32) -> Self {
+        Self {
+            current,
+            steps: Vec::new(),
+        }
+    }
+
+    /// Adds a migration from a version to the next one. A step registered for the
+    /// same version replaces the previous one.
+    ///
+    /// # Arguments
+    /// * `from` - a version the step upgrades from, preferences saved without a version are `0`.
+    /// * `migration` - a closure which receives the stored JSON and returns the upgraded JSON
+    ///   or an error message.
+    ///
+    /// # Returns
+    /// Returns a modified chain.
+    pub fn step<F>(mut self, from: u32, migration: F) -> Self
+    where
+        F: Fn(Value) -> std::result::Result<Value, String> + 'static,
+    {
+        self.steps.retain(|(version, _)| *version != from);
+        self.steps.push((from, Box::new(migration)));
+        self
+    }
+
+    /// Returns the current schema version.
+    pub fn current(&self) -> u32 {
+        self.current
+    }
+
+    /// Upgrades stored JSON to the current version.
+    ///
+    /// # Arguments
+    /// * `stored` - the stored version.
+    /// * `value` - the stored JSON.
+    ///
+    /// # Returns
+    /// Returns the upgraded JSON on success. Otherwise returns [`PrefsError`].
+    pub fn migrate(&self, stored: u32, mut value: Value) -> Result<Value> {
+        if stored > self.current {
+            return Err(PrefsError::UnsupportedVersion {
+                stored,
+                current: self.current,
+            });
+        }
+
+        for version in stored..self.current {
+            let (_, step) = self
+                .steps
+                .iter()
+                .find(|(from, _)| *from == version)
+                .ok_or(PrefsError::MissingMigration(version))?;
+            value = step(value).map_err(|message| PrefsError::Migration { version, message })?;
+        }
+
+        Ok(value)
+    }
+}
@@ -0,0 +1,173 @@
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+
+use crate::api::utilities::get_prefs_path;
+
+use super::{Migrations, PrefsError, Result};
+
+const VERSION_KEY: &str = "version";
+const DATA_KEY: &str = "data";
+
+/// A JSON preferences file.
+///
+/// ```ignore
+/// let prefs = Preferences::new("my_plugin.json")?;
+/// let migrations = Migrations::new(1).step(0, |value| Ok(upgrade(value)));
+/// let settings: Settings = prefs.load_versioned(&migrations)?.unwrap_or_default();
+/// prefs.save_versioned(&migrations, &settings)?;
+/// ```
+pub struct Preferences {
+    path: PathBuf,
+}
+
+impl Preferences {
+    /// Creates preferences stored in the X-Plane preferences folder.
+    ///
+    /// # Arguments
+    /// * `file_name` - a file name unique to the plugin, e.g. `my_plugin.json`.
+    ///
+    /// # Returns
+    /// Returns [`Preferences`] on success. Otherwise returns [`PrefsError`].
+    pub fn new<F: AsRef<Path>>(file_name: F) -> Result<Self> {
+        let prefs_path = get_prefs_path()?;
+        let dir = prefs_path.parent().unwrap_or(Path::new(""));
+        Ok(Self::with_path(dir.join(file_name)))
+    }
+
+    /// Creates preferences stored in an arbitrary file.
+    ///
+    /// # Arguments
+    /// * `path` - a preferences file path.
+    ///
+    /// # Returns
+    /// Returns a new [`Preferences`] instance.
+    pub fn with_path<P: Into<PathBuf>>(path: P) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Returns the preferences file path.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Loads unversioned preferences.
+    ///
+    /// # Returns
+    /// Returns the stored value or [`None`] if the file does not exist.
+    /// Otherwise returns [`PrefsError`].
+    pub fn load<T: DeserializeOwned>(&self) -> Result<Option<T>> {
+        match self.read()? {
+            Some(value) => Ok(Some(serde_json::from_value(value)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Saves unversioned preferences.
+    ///
+    /// # Arguments
+    /// * `value` - a value to save.
+    ///
+    /// # Returns
+    /// Returns empty result on success. Otherwise returns [`PrefsError`].
+    pub fn save<T: Serialize>(&self, value: &T) -> Result<()> {
+        self.write(&serde_json::to_value(value)?)
+    }
+
+    /// Loads versioned preferences and migrates them to the current version.
+    /// Preferences saved with [`Preferences::save`] are treated as version `0`.
+    ///
+    /// When a migration runs, the original file is copied next to it with a
+    /// `.v<version>.bak` suffix and the migrated preferences are saved, so the
+    /// migration runs only once. Nothing is written if a migration fails.
+    ///
+    /// # Arguments
+    /// * `migrations` - migrations to the current version. See [`Migrations`] for more details.
+    ///
+    /// # Returns
+    /// Returns the stored value or [`None`] if the file does not exist.
+    /// Otherwise returns [`PrefsError`].
+    pub fn load_versioned<T: DeserializeOwned>(
+        &self,
+        migrations: &Migrations,
+    ) -> Result<Option<T>> {
+        let Some(value) = self.read()? else {
+            return Ok(None);
+        };
+
+        let (stored, data) = unwrap_envelope(value);
+        if stored == migrations.current() {
+            return Ok(Some(serde_json::from_value(data)?));
+        }
+
+        let data = migrations.migrate(stored, data)?;
+        let result = T::deserialize(&data)?;
+        fs::copy(&self.path, self.backup_path(stored))
+            .map_err(|err| PrefsError::Io(self.path.clone(), err))?;
+        self.write(&wrap_envelope(migrations.current(), data))?;
+        Ok(Some(result))
+    }
+
+    /// Saves versioned preferences with the current version.
+    ///
+    /// # Arguments
+    /// * `migrations` - migrations which define the current version.
+    /// * `value` - a value to save.
+    ///
+    /// # Returns
+    /// Returns empty result on success. Otherwise returns [`PrefsError`].
+    pub fn save_versioned<T: Serialize>(&self, migrations: &Migrations, value: &T) -> Result<()> {
+        let data = serde_json::to_value(value)?;
+        self.write(&wrap_envelope(migrations.current(), data))
+    }
+
+    fn read(&self) -> Result<Option<Value>> {
+        match fs::read(&self.path) {
+            Ok(data) => Ok(Some(serde_json::from_slice(&data)?)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(PrefsError::Io(self.path.clone(), err)),
+        }
+    }
+
+    /// Writes to a temporary file first, so an interrupted write leaves the old file intact.
+    fn write(&self, value: &Value) -> Result<()> {
+        let data = serde_json::to_vec_pretty(value)?;
+        let mut temp = self.path.clone().into_os_string();
+        temp.push(".tmp");
+        let temp = PathBuf::from(temp);
+        fs::write(&temp, data).map_err(|err| PrefsError::Io(temp.clone(), err))?;
+        fs::rename(&temp, &self.path).map_err(|err| PrefsError::Io(self.path.clone(), err))
+    }
+
+    fn backup_path(&self, version: u32) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(format!(".v{}.bak", version));
+        PathBuf::from(path)
+    }
+}
+
+fn wrap_envelope(version: u32, data: Value) -> Value {
+    let mut envelope = serde_json::Map::new();
+    envelope.insert(VERSION_KEY.to_string(), Value::from(version));
+    envelope.insert(DATA_KEY.to_string(), data);
+    Value::Object(envelope)
+}
+
+fn unwrap_envelope(value: Value) -> (u32, Value) {
+    let version = match &value {
+        Value::Object(object) if object.len() == 2 && object.contains_key(DATA_KEY) => object
+            .get(VERSION_KEY)
+            .and_then(Value::as_u64)
+            .and_then(|version| u32::try_from(version).ok()),
+        _ => None,
+    };
+
+    match (version, value) {
+        (Some(version), Value::Object(mut object)) => {
+            (version, object.remove(DATA_KEY).unwrap_or(Value::Null))
+        }
+        (_, value) => (0, value),
+    }
+}
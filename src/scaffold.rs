@@ -0,0 +1,236 @@
+//! Plugin project scaffolding for companion tooling, e.g. an `xtask` which bootstraps
+//! new plugins consistently with the crate APIs.
+//!
+//! ```ignore
+//! let meta = PluginMeta::new("my_plugin", "My Plugin", "com.example.my_plugin")
+//!     .description("Does something useful");
+//! xplm::scaffold::write_plugin_skeleton("plugins/my_plugin", &meta)?;
+//! ```
+
+use std::{fs, io, path};
+
+use crate::package::{Platform, PLUGIN_EXTENSION};
+
+/// Default dependency specification of the `xplm` crate in generated manifests.
+pub const DEFAULT_XPLM_DEPENDENCY: &str = r#"{ git = "https://github.com/artemkorobko/xplm" }"#;
+
+/// Metadata of a generated plugin.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginMeta {
+    crate_name: String,
+    name: String,
+    signature: String,
+    description: String,
+    xplm_dependency: String,
+}
+
+impl PluginMeta {
+    /// Creates plugin metadata.
+    ///
+    /// # Arguments
+    /// * `crate_name` - a cargo package name, also used for the plugin folder, e.g. `my_plugin`.
+    /// * `name` - a human readable plugin name shown in the plugin admin.
+    /// * `signature` - a unique plugin signature, e.g. `com.example.my_plugin`.
+    ///
+    /// # Returns
+    /// Returns a new [`PluginMeta`] instance.
+    pub fn new<C, N, S>(crate_name: C, name: N, signature: S) -> Self
+    where
+        C: Into<String>,
+        N: Into<String>,
+        S: Into<String>,
+    {
+        let name = name.into();
+        Self {
+            crate_name: crate_name.into(),
+            description: name.clone(),
+            name,
+            signature: signature.into(),
+            xplm_dependency: DEFAULT_XPLM_DEPENDENCY.to_string(),
+        }
+    }
+
+    /// Sets the plugin description, the name is used by default.
+    ///
+    /// # Arguments
+    /// * `description` - a plugin description.
+    ///
+    /// # Returns
+    /// Returns modified metadata.
+    pub fn description<D: Into<String>>(mut self, description: D) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    /// Sets the `xplm` dependency specification, e.g. `"0.1"` or `{ path = "../xplm" }`.
+    ///
+    /// # Arguments
+    /// * `dependency` - a dependency specification, see [`DEFAULT_XPLM_DEPENDENCY`].
+    ///
+    /// # Returns
+    /// Returns modified metadata.
+    pub fn xplm_dependency<D: Into<String>>(mut self, dependency: D) -> Self {
+        self.xplm_dependency = dependency.into();
+        self
+    }
+
+    /// Returns the cargo package name.
+    pub fn crate_name(&self) -> &str {
+        &self.crate_name
+    }
+
+    /// Returns the name of the plugin type in the generated source.
+    fn type_name(&self) -> String {
+        self.crate_name
+            .split(['-', '_'])
+            .filter(|part| !part.is_empty())
+            .map(|part| {
+                let mut chars = part.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                    .unwrap_or_default()
+            })
+            .collect()
+    }
+}
+
+/// Writes a plugin project skeleton: a `Cargo.toml` building a `cdylib` and a minimal
+/// `src/lib.rs` registering an [`crate::plugin::XPlugin`] implementation.
+/// Existing files are never overwritten.
+///
+/// # Arguments
+/// * `path` - a project folder, created if missing.
+/// * `meta` - plugin metadata. See [`PluginMeta`] for more details.
+///
+/// # Returns
+/// Returns the paths of the written files on success. Otherwise returns [`io::Error`],
+/// [`io::ErrorKind::AlreadyExists`] if a file already exists.
+pub fn write_plugin_skeleton<P: AsRef<path::Path>>(
+    path: P,
+    meta: &PluginMeta,
+) -> io::Result<Vec<path::PathBuf>> {
+    let root = path.as_ref();
+    let files = [
+        (root.join("Cargo.toml"), cargo_manifest(meta)),
+        (root.join("src").join("lib.rs"), plugin_source(meta)),
+    ];
+
+    if let Some((existing, _)) = files.iter().find(|(path, _)| path.exists()) {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", existing.display()),
+        ));
+    }
+
+    files
+        .into_iter()
+        .map(|(path, contents)| {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            fs::write(&path, contents)?;
+            Ok(path)
+        })
+        .collect()
+}
+
+/// Generates the `Cargo.toml` contents of a plugin.
+///
+/// # Arguments
+/// * `meta` - plugin metadata.
+///
+/// # Returns
+/// Returns the manifest contents.
+pub fn cargo_manifest(meta: &PluginMeta) -> String {
+    let layout = Platform::all()
+        .iter()
+        .map(|platform| {
+            format!(
+                "#   {} -> {}/{}/{}.{}\n",
+                platform.cdylib_file_name(&meta.crate_name),
+                meta.crate_name,
+                platform.folder_name(),
+                meta.crate_name,
+                PLUGIN_EXTENSION
+            )
+        })
+        .collect::<String>();
+
+    format!(
+        r#"[package]
+name = "{crate_name}"
+version = "0.1.0"
+edition = "2021"
+description = "{description}"
+
+# X-Plane loads a plugin from a shared library renamed to `.xpl` and placed into
+# a folder per platform, see `xplm::package::FatPluginLayout`:
+{layout}# The legacy layout with `win.xpl`, `mac.xpl` and `lin.xpl` directly in the plugin
+# folder is still loaded, but can't hold 64-bit builds for every platform side by side.
+
+[lib]
+crate-type = ["cdylib"]
+
+[dependencies]
+xplm = {dependency}
+
+[profile.release]
+lto = true
+panic = "abort"
+"#,
+        crate_name = meta.crate_name,
+        description = escape(&meta.description),
+        layout = layout,
+        dependency = meta.xplm_dependency,
+    )
+}
+
+/// Generates a minimal plugin source.
+///
+/// # Arguments
+/// * `meta` - plugin metadata.
+///
+/// # Returns
+/// Returns the `src/lib.rs` contents.
+pub fn plugin_source(meta: &PluginMeta) -> String {
+    format!(
+        r#"use xplm::plugin::XPlugin;
+
+pub struct {type_name};
+
+impl XPlugin for {type_name} {{
+    type Error = xplm::XplmError;
+
+    fn start() -> Result<Self, Self::Error> {{
+        Ok(Self)
+    }}
+
+    fn stop(&mut self) {{}}
+
+    fn enable(&mut self) -> Result<(), Self::Error> {{
+        xplm::info!("enabled");
+        Ok(())
+    }}
+
+    fn disable(&mut self) {{}}
+}}
+
+xplm::register_plugin!(
+    instance = {type_name},
+    name = "{name}",
+    signature = "{signature}",
+    description = "{description}",
+);
+"#,
+        type_name = meta.type_name(),
+        name = escape(&meta.name),
+        signature = escape(&meta.signature),
+        description = escape(&meta.description),
+    )
+}
+
+/// Escapes a value placed into a double quoted string.
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
@@ -0,0 +1,31 @@
+//! Parsers for the X-Plane data files the SDK doesn't expose, e.g. runway geometry
+//! and gate positions. Available with the `scenery-data` feature enabled.
+
+pub mod apt;
+pub mod error;
+pub mod index;
+pub mod nav;
+
+pub use self::apt::{parse_apt_dat, read_apt_dat, AptAirport, AptRunway, Gate, RunwayEnd};
+pub use self::error::SceneryDataError;
+pub use self::index::SceneryDataIndex;
+pub use self::nav::{parse_earth_nav_dat, read_earth_nav_dat, NavDataType, NavRecord};
+
+pub type Result<T> = std::result::Result<T, SceneryDataError>;
+
+/// Splits a data file line into the row code and the remaining fields.
+fn split_row(line: &str) -> Option<(u32, std::str::SplitWhitespace<'_>)> {
+    let mut fields = line.split_whitespace();
+    let code = fields.next()?.parse().ok()?;
+    Some((code, fields))
+}
+
+/// Joins the remaining fields of a row, used for names which may contain spaces.
+fn rest<'a, I: Iterator<Item = &'a str>>(fields: I) -> String {
+    fields.collect::<Vec<_>>().join(" ")
+}
+
+/// Parses the next field of a row.
+fn field<T: std::str::FromStr>(fields: &mut std::str::SplitWhitespace<'_>) -> Option<T> {
+    fields.next()?.parse().ok()
+}
@@ -0,0 +1,203 @@
+use std::io::BufRead;
+use std::{fs, io, path};
+
+use crate::api::graphics::WorldPosition;
+
+use super::{field, rest, split_row, Result, SceneryDataError};
+
+const ROW_AIRPORT: u32 = 1;
+const ROW_SEAPLANE_BASE: u32 = 16;
+const ROW_HELIPORT: u32 = 17;
+const ROW_LAND_RUNWAY: u32 = 100;
+const ROW_START_LOCATION: u32 = 1300;
+const ROW_END: u32 = 99;
+
+/// An airport parsed from an `apt.dat` file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AptAirport {
+    /// An airport ICAO code or identifier.
+    pub icao: String,
+    /// An airport name.
+    pub name: String,
+    /// The field elevation in feet MSL.
+    pub elevation: f32,
+    /// Land runways.
+    pub runways: Vec<AptRunway>,
+    /// Gates, ramp and tie-down start locations.
+    pub gates: Vec<Gate>,
+}
+
+/// A land runway.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AptRunway {
+    /// The runway width in meters.
+    pub width: f32,
+    /// The surface type code, e.g. 1 for asphalt, 2 for concrete.
+    pub surface: u32,
+    /// Both runway ends.
+    pub ends: [RunwayEnd; 2],
+}
+
+impl AptRunway {
+    /// Returns the runway end with the given name, e.g. `"04R"`.
+    ///
+    /// # Arguments
+    /// * `name` - a runway end name.
+    ///
+    /// # Returns
+    /// Returns [`RunwayEnd`] or [`None`] if the runway has no such end.
+    pub fn end(&self, name: &str) -> Option<&RunwayEnd> {
+        self.ends
+            .iter()
+            .find(|end| end.name.eq_ignore_ascii_case(name))
+    }
+}
+
+/// A runway end.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunwayEnd {
+    /// A runway end name, e.g. `"04R"`.
+    pub name: String,
+    /// The runway end position on the centerline. The altitude is not set.
+    pub position: WorldPosition,
+    /// The displaced threshold length in meters.
+    pub displaced_threshold: f32,
+    /// The blast pad length in meters.
+    pub blast_pad: f32,
+}
+
+/// A start location, e.g. a gate or a tie-down.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gate {
+    /// A start location name.
+    pub name: String,
+    /// The start location position. The altitude is not set.
+    pub position: WorldPosition,
+    /// The heading in degrees true.
+    pub heading: f32,
+    /// The location type, e.g. `gate`, `hangar`, `misc` or `tie-down`.
+    pub kind: String,
+}
+
+/// Parses an `apt.dat` file.
+///
+/// # Arguments
+/// * `path` - a file path.
+///
+/// # Returns
+/// Returns parsed airports on success. Otherwise returns [`SceneryDataError`].
+pub fn parse_apt_dat<P: AsRef<path::Path>>(path: P) -> Result<Vec<AptAirport>> {
+    let path = path.as_ref();
+    let file = fs::File::open(path).map_err(|err| SceneryDataError::Read(path.into(), err))?;
+    read_apt_dat(io::BufReader::new(file)).map_err(|err| SceneryDataError::Read(path.into(), err))
+}
+
+/// Parses airports from a reader over `apt.dat` contents.
+/// Rows this parser doesn't know about are skipped.
+///
+/// # Arguments
+/// * `reader` - a reader over `apt.dat` contents.
+///
+/// # Returns
+/// Returns parsed airports on success. Otherwise returns an IO error.
+pub fn read_apt_dat<R: BufRead>(mut reader: R) -> io::Result<Vec<AptAirport>> {
+    let mut airports = Vec::new();
+    let mut current: Option<AptAirport> = None;
+    let mut buf = Vec::new();
+    while reader.read_until(b'\n', &mut buf)? > 0 {
+        let line = String::from_utf8_lossy(&buf).into_owned();
+        if let Some((code, mut fields)) = split_row(&line) {
+            match code {
+                ROW_AIRPORT | ROW_SEAPLANE_BASE | ROW_HELIPORT => {
+                    airports.extend(current.take());
+                    current = parse_airport(&mut fields);
+                }
+                ROW_LAND_RUNWAY => {
+                    if let (Some(airport), Some(runway)) =
+                        (current.as_mut(), parse_runway(&mut fields))
+                    {
+                        airport.runways.push(runway);
+                    }
+                }
+                ROW_START_LOCATION => {
+                    if let (Some(airport), Some(gate)) = (current.as_mut(), parse_gate(fields)) {
+                        airport.gates.push(gate);
+                    }
+                }
+                ROW_END => break,
+                _ => {}
+            }
+        }
+        buf.clear();
+    }
+
+    airports.extend(current);
+    Ok(airports)
+}
+
+fn parse_airport(fields: &mut std::str::SplitWhitespace<'_>) -> Option<AptAirport> {
+    let elevation = field(fields)?;
+    fields.next()?;
+    fields.next()?;
+    let icao = fields.next()?.to_string();
+    Some(AptAirport {
+        icao,
+        name: rest(fields),
+        elevation,
+        runways: Vec::new(),
+        gates: Vec::new(),
+    })
+}
+
+fn parse_runway(fields: &mut std::str::SplitWhitespace<'_>) -> Option<AptRunway> {
+    let width = field(fields)?;
+    let surface = field(fields)?;
+    // Shoulder, smoothness, centerline lights, edge lights and distance signs.
+    for _ in 0..5 {
+        fields.next()?;
+    }
+
+    Some(AptRunway {
+        width,
+        surface,
+        ends: [parse_runway_end(fields)?, parse_runway_end(fields)?],
+    })
+}
+
+fn parse_runway_end(fields: &mut std::str::SplitWhitespace<'_>) -> Option<RunwayEnd> {
+    let name = fields.next()?.to_string();
+    let latitude = field(fields)?;
+    let longitude = field(fields)?;
+    let displaced_threshold = field(fields)?;
+    let blast_pad = field(fields)?;
+    // Markings, approach lights, touchdown zone lights and REIL.
+    for _ in 0..4 {
+        fields.next()?;
+    }
+
+    Some(RunwayEnd {
+        name,
+        position: WorldPosition::default()
+            .latitude(latitude)
+            .longitude(longitude),
+        displaced_threshold,
+        blast_pad,
+    })
+}
+
+fn parse_gate(mut fields: std::str::SplitWhitespace<'_>) -> Option<Gate> {
+    let latitude = field(&mut fields)?;
+    let longitude = field(&mut fields)?;
+    let heading = field(&mut fields)?;
+    let kind = fields.next()?.to_string();
+    // Aircraft types.
+    fields.next()?;
+    Some(Gate {
+        name: rest(fields),
+        position: WorldPosition::default()
+            .latitude(latitude)
+            .longitude(longitude),
+        heading,
+        kind,
+    })
+}
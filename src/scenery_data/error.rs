@@ -0,0 +1,23 @@
+use std::{io, path};
+
+use crate::api::utilities::UtilitiesError;
+
+/// An error returned from scenery data parsers.
+#[derive(thiserror::Error, Debug)]
+pub enum SceneryDataError {
+    /// Utilities error.
+    #[error("utilities error {0}")]
+    Utilities(UtilitiesError),
+    /// Data file can't be read.
+    #[error("failed to read {}: {1}", .0.display())]
+    Read(path::PathBuf, io::Error),
+    /// Data file is not found in any of the known locations.
+    #[error("data file {0} not found")]
+    NotFound(&'static str),
+}
+
+impl From<UtilitiesError> for SceneryDataError {
+    fn from(value: UtilitiesError) -> Self {
+        Self::Utilities(value)
+    }
+}
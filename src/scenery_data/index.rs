@@ -0,0 +1,138 @@
+use std::cell::OnceCell;
+use std::collections::HashMap;
+use std::path;
+
+use crate::api::utilities::get_system_path;
+
+use super::apt::parse_apt_dat;
+use super::nav::parse_earth_nav_dat;
+use super::{AptAirport, NavRecord, Result, SceneryDataError};
+
+/// Known `apt.dat` locations relative to the X-Plane folder, in lookup order.
+const APT_DAT_PATHS: [&str; 3] = [
+    "Global Scenery/Global Airports/Earth nav data/apt.dat",
+    "Custom Scenery/Global Airports/Earth nav data/apt.dat",
+    "Resources/default scenery/default apt dat/Earth nav data/apt.dat",
+];
+
+/// Known `earth_nav.dat` locations relative to the X-Plane folder, in lookup order.
+/// User navigation data updates take precedence over the default data.
+const EARTH_NAV_DAT_PATHS: [&str; 2] = [
+    "Custom Data/earth_nav.dat",
+    "Resources/default data/earth_nav.dat",
+];
+
+/// A queryable in-memory index of the global airport and navaid data.
+///
+/// The data files are large, so each file is parsed on first query and kept in memory
+/// afterwards. Create the index once and keep it around.
+pub struct SceneryDataIndex {
+    root: path::PathBuf,
+    airports: OnceCell<HashMap<String, AptAirport>>,
+    navaids: OnceCell<Vec<NavRecord>>,
+}
+
+impl SceneryDataIndex {
+    /// Creates an index over the data files of the running X-Plane installation.
+    ///
+    /// # Returns
+    /// Returns a new [`SceneryDataIndex`] on success. Otherwise returns [`SceneryDataError`].
+    pub fn new() -> Result<Self> {
+        Ok(Self::with_root(get_system_path()?))
+    }
+
+    /// Creates an index over the data files of an X-Plane installation.
+    ///
+    /// # Arguments
+    /// * `root` - the X-Plane folder.
+    ///
+    /// # Returns
+    /// Returns a new [`SceneryDataIndex`] instance.
+    pub fn with_root<P: Into<path::PathBuf>>(root: P) -> Self {
+        Self {
+            root: root.into(),
+            airports: OnceCell::new(),
+            navaids: OnceCell::new(),
+        }
+    }
+
+    /// Looks up an airport by its ICAO code.
+    ///
+    /// # Arguments
+    /// * `icao` - an airport ICAO code.
+    ///
+    /// # Returns
+    /// Returns [`AptAirport`] or [`None`] if not found. Otherwise returns [`SceneryDataError`].
+    pub fn airport(&self, icao: &str) -> Result<Option<&AptAirport>> {
+        Ok(self.airports()?.get(&icao.to_ascii_uppercase()))
+    }
+
+    /// Returns all airports keyed by the upper-case ICAO code.
+    ///
+    /// # Returns
+    /// Returns airports on success. Otherwise returns [`SceneryDataError`].
+    pub fn airports(&self) -> Result<&HashMap<String, AptAirport>> {
+        if let Some(airports) = self.airports.get() {
+            return Ok(airports);
+        }
+
+        let path = self.locate(&APT_DAT_PATHS, "apt.dat")?;
+        let airports = parse_apt_dat(path)?
+            .into_iter()
+            .map(|airport| (airport.icao.to_ascii_uppercase(), airport))
+            .collect();
+        Ok(self.airports.get_or_init(|| airports))
+    }
+
+    /// Returns all navaids.
+    ///
+    /// # Returns
+    /// Returns navaids on success. Otherwise returns [`SceneryDataError`].
+    pub fn navaids(&self) -> Result<&[NavRecord]> {
+        if let Some(navaids) = self.navaids.get() {
+            return Ok(navaids);
+        }
+
+        let path = self.locate(&EARTH_NAV_DAT_PATHS, "earth_nav.dat")?;
+        let navaids = parse_earth_nav_dat(path)?;
+        Ok(self.navaids.get_or_init(|| navaids))
+    }
+
+    /// Returns navaids with the given identifier.
+    ///
+    /// # Arguments
+    /// * `id` - a navaid identifier, e.g. `"BOS"`.
+    ///
+    /// # Returns
+    /// Returns matching navaids on success. Otherwise returns [`SceneryDataError`].
+    pub fn navaids_by_id(&self, id: &str) -> Result<Vec<&NavRecord>> {
+        Ok(self
+            .navaids()?
+            .iter()
+            .filter(|navaid| navaid.id.eq_ignore_ascii_case(id))
+            .collect())
+    }
+
+    /// Returns navaids which belong to an airport, e.g. its ILS components.
+    ///
+    /// # Arguments
+    /// * `icao` - an airport ICAO code.
+    ///
+    /// # Returns
+    /// Returns matching navaids on success. Otherwise returns [`SceneryDataError`].
+    pub fn navaids_for_airport(&self, icao: &str) -> Result<Vec<&NavRecord>> {
+        Ok(self
+            .navaids()?
+            .iter()
+            .filter(|navaid| navaid.terminal.eq_ignore_ascii_case(icao))
+            .collect())
+    }
+
+    fn locate(&self, candidates: &[&str], name: &'static str) -> Result<path::PathBuf> {
+        candidates
+            .iter()
+            .map(|candidate| self.root.join(candidate))
+            .find(|path| path.is_file())
+            .ok_or(SceneryDataError::NotFound(name))
+    }
+}
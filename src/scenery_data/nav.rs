@@ -0,0 +1,137 @@
+use std::io::BufRead;
+use std::{fs, io, path};
+
+use crate::api::graphics::WorldPosition;
+
+use super::{field, rest, split_row, Result, SceneryDataError};
+
+const ROW_END: u32 = 99;
+
+/// A navaid type of an `earth_nav.dat` record.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum NavDataType {
+    /// A non-directional beacon.
+    Ndb,
+    /// A VHF omnidirectional range.
+    Vor,
+    /// A localizer which is a part of an ILS.
+    IlsLocalizer,
+    /// A stand-alone localizer.
+    Localizer,
+    /// An ILS glide slope.
+    GlideSlope,
+    /// An outer marker.
+    OuterMarker,
+    /// A middle marker.
+    MiddleMarker,
+    /// An inner marker.
+    InnerMarker,
+    /// A distance measuring equipment.
+    Dme,
+    /// Any other record, e.g. FPAP or GLS.
+    Other(u32),
+}
+
+impl From<u32> for NavDataType {
+    fn from(value: u32) -> Self {
+        match value {
+            2 => Self::Ndb,
+            3 => Self::Vor,
+            4 => Self::IlsLocalizer,
+            5 => Self::Localizer,
+            6 => Self::GlideSlope,
+            7 => Self::OuterMarker,
+            8 => Self::MiddleMarker,
+            9 => Self::InnerMarker,
+            12 | 13 => Self::Dme,
+            _ => Self::Other(value),
+        }
+    }
+}
+
+/// A navaid parsed from an `earth_nav.dat` file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NavRecord {
+    /// A navaid type.
+    pub nav_type: NavDataType,
+    /// The navaid position. The altitude is in feet MSL.
+    pub position: WorldPosition,
+    /// The frequency. NDB frequencies are in kHz, other frequencies are in 10 kHz units.
+    pub frequency: i32,
+    /// The reception range in nautical miles.
+    pub range: f32,
+    /// A type specific value, e.g. the VOR slaved variation or the localizer bearing.
+    pub parameter: f32,
+    /// A navaid identifier.
+    pub id: String,
+    /// The airport ICAO code the navaid belongs to or `ENRT` for enroute navaids.
+    pub terminal: String,
+    /// The ICAO region code.
+    pub region: String,
+    /// A navaid name.
+    pub name: String,
+}
+
+/// Parses an `earth_nav.dat` file.
+///
+/// # Arguments
+/// * `path` - a file path.
+///
+/// # Returns
+/// Returns parsed navaids on success. Otherwise returns [`SceneryDataError`].
+pub fn parse_earth_nav_dat<P: AsRef<path::Path>>(path: P) -> Result<Vec<NavRecord>> {
+    let path = path.as_ref();
+    let file = fs::File::open(path).map_err(|err| SceneryDataError::Read(path.into(), err))?;
+    read_earth_nav_dat(io::BufReader::new(file))
+        .map_err(|err| SceneryDataError::Read(path.into(), err))
+}
+
+/// Parses navaids from a reader over `earth_nav.dat` contents.
+/// Rows which can't be parsed are skipped.
+///
+/// # Arguments
+/// * `reader` - a reader over `earth_nav.dat` contents.
+///
+/// # Returns
+/// Returns parsed navaids on success. Otherwise returns an IO error.
+pub fn read_earth_nav_dat<R: BufRead>(mut reader: R) -> io::Result<Vec<NavRecord>> {
+    let mut records = Vec::new();
+    let mut buf = Vec::new();
+    while reader.read_until(b'\n', &mut buf)? > 0 {
+        let line = String::from_utf8_lossy(&buf).into_owned();
+        match split_row(&line) {
+            Some((ROW_END, _)) => break,
+            Some((code, mut fields)) => records.extend(parse_record(code, &mut fields)),
+            None => {}
+        }
+        buf.clear();
+    }
+
+    Ok(records)
+}
+
+fn parse_record(code: u32, fields: &mut std::str::SplitWhitespace<'_>) -> Option<NavRecord> {
+    let latitude = field(fields)?;
+    let longitude = field(fields)?;
+    let elevation = field(fields)?;
+    let frequency = field(fields)?;
+    let range = field(fields)?;
+    let parameter = field(fields)?;
+    let id = fields.next()?.to_string();
+    let terminal = fields.next()?.to_string();
+    let region = fields.next()?.to_string();
+    Some(NavRecord {
+        nav_type: NavDataType::from(code),
+        position: WorldPosition::default()
+            .latitude(latitude)
+            .longitude(longitude)
+            .altitude(elevation),
+        frequency,
+        range,
+        parameter,
+        id,
+        terminal,
+        region,
+        name: rest(fields),
+    })
+}
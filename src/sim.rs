@@ -0,0 +1,45 @@
+//! High-level façades over the standard X-Plane datarefs.
+
+pub mod aircraft;
+pub mod cdu;
+pub mod error;
+pub mod failures;
+pub mod ground;
+pub mod joystick;
+pub mod lighting;
+pub mod placement;
+pub mod radios;
+pub mod surfaces;
+pub mod tcas;
+pub mod timing;
+pub mod traffic;
+#[cfg(feature = "prefs")]
+pub mod views;
+pub mod weather;
+
+pub use self::aircraft::{
+    current_aircraft_path, current_icao, current_livery, list_liveries, reload_current_aircraft,
+    set_livery, Aircraft, AircraftInfo, AircraftWatcher,
+};
+pub use self::cdu::CduScreen;
+pub use self::error::SimError;
+pub use self::failures::{
+    fail, get_failure_state, repair, repair_all, set_failure_state, Failure, FailureScheduler,
+    FailureState,
+};
+pub use self::ground::{GroundServices, PushbackDirection, PushbackPhase, PushbackScript};
+pub use self::joystick::{AxisAssignment, Joystick, ENGINE_AXES};
+pub use self::lighting::{InstrumentBus, Lighting, PanelLight};
+pub use self::placement::{place_user_at_airport, place_user_at_location, PlacementRequest};
+pub use self::radios::{Frequency, Radio, Radios, TransponderMode};
+pub use self::surfaces::{ControlSurface, Surfaces};
+pub use self::tcas::{TrafficInjector, TrafficProvider};
+pub use self::timing::{FrameBudget, FrameBudgetGuard, FrameTiming};
+pub use self::traffic::{Traffic, TrafficTarget};
+#[cfg(feature = "prefs")]
+pub use self::views::{create_recall_command, ViewPreset, ViewPresets};
+pub use self::weather::{
+    CloudLayer, CloudType, WeatherController, WeatherPreset, WindLayer, CLOUD_LAYERS, WIND_LAYERS,
+};
+
+pub type Result<T> = std::result::Result<T, SimError>;
@@ -0,0 +1,409 @@
+use std::cell::OnceCell;
+use std::{ffi, path};
+
+use crate::api::backend::backend;
+use crate::api::data_access::{DataRefArray, DataRefString, DataRefValue};
+use crate::api::graphics::{world_to_local, Heading, WorldPosition};
+use crate::api::plugin::Message;
+use crate::api::utilities::{self, os_path};
+
+use super::{Result, SimError};
+
+/// The name of the folder next to the `.acf` file which contains aircraft liveries.
+const LIVERIES_DIR: &str = "liveries";
+
+/// The attitude quaternion X-Plane derives the Euler angles from every frame.
+const QUATERNION: &str = "sim/flightmodel/position/q";
+
+/// The user's aircraft position and attitude.
+///
+/// Data refs are looked up on first use and cached afterwards.
+/// The attitude is written through the attitude quaternion, X-Plane overwrites
+/// direct writes of the Euler angles on the next frame.
+pub struct Aircraft {
+    latitude: DataRefValue<f64>,
+    longitude: DataRefValue<f64>,
+    elevation: DataRefValue<f64>,
+    local_x: DataRefValue<f64>,
+    local_y: DataRefValue<f64>,
+    local_z: DataRefValue<f64>,
+    pitch: DataRefValue<f32>,
+    roll: DataRefValue<f32>,
+    heading: DataRefValue<f32>,
+    quaternion: OnceCell<DataRefArray<f32>>,
+    ground_speed: DataRefValue<f32>,
+    indicated_airspeed: DataRefValue<f32>,
+    on_ground: DataRefValue<bool>,
+}
+
+impl Aircraft {
+    /// Creates a new user's aircraft façade.
+    ///
+    /// # Returns
+    /// Returns a new [`Aircraft`] instance.
+    pub fn new() -> Self {
+        Self {
+            latitude: DataRefValue::new("sim/flightmodel/position/latitude"),
+            longitude: DataRefValue::new("sim/flightmodel/position/longitude"),
+            elevation: DataRefValue::new("sim/flightmodel/position/elevation"),
+            local_x: DataRefValue::new("sim/flightmodel/position/local_x"),
+            local_y: DataRefValue::new("sim/flightmodel/position/local_y"),
+            local_z: DataRefValue::new("sim/flightmodel/position/local_z"),
+            pitch: DataRefValue::new("sim/flightmodel/position/theta"),
+            roll: DataRefValue::new("sim/flightmodel/position/phi"),
+            heading: DataRefValue::new("sim/flightmodel/position/psi"),
+            quaternion: OnceCell::new(),
+            ground_speed: DataRefValue::new("sim/flightmodel/position/groundspeed"),
+            indicated_airspeed: DataRefValue::new("sim/flightmodel/position/indicated_airspeed"),
+            on_ground: DataRefValue::new("sim/flightmodel/failures/onground_any"),
+        }
+    }
+
+    /// Reads the aircraft world position.
+    ///
+    /// # Returns
+    /// Returns the latitude, longitude and elevation in meters MSL.
+    pub fn position(&self) -> Result<WorldPosition> {
+        Ok(WorldPosition::default()
+            .latitude(self.latitude.get()?)
+            .longitude(self.longitude.get()?)
+            .altitude(self.elevation.get()?))
+    }
+
+    /// Moves the aircraft to a new world position.
+    ///
+    /// # Arguments
+    /// * `position` - a new world position. The altitude is in meters MSL.
+    pub fn set_position(&self, position: &WorldPosition) -> Result<()> {
+        let local = world_to_local(position);
+        self.local_x.set(local.x)?;
+        self.local_y.set(local.y)?;
+        self.local_z.set(local.z)?;
+        Ok(())
+    }
+
+    /// Reads the aircraft latitude in degrees.
+    pub fn latitude(&self) -> Result<f64> {
+        Ok(self.latitude.get()?)
+    }
+
+    /// Reads the aircraft longitude in degrees.
+    pub fn longitude(&self) -> Result<f64> {
+        Ok(self.longitude.get()?)
+    }
+
+    /// Reads the aircraft elevation in meters MSL.
+    pub fn elevation(&self) -> Result<f64> {
+        Ok(self.elevation.get()?)
+    }
+
+    /// Reads the aircraft pitch in degrees.
+    pub fn pitch(&self) -> Result<f32> {
+        Ok(self.pitch.get()?)
+    }
+
+    /// Sets the aircraft pitch in degrees, keeping the roll and the heading.
+    pub fn set_pitch(&self, value: f32) -> Result<()> {
+        self.set_attitude(value, self.roll.get()?, self.heading.get()?)
+    }
+
+    /// Reads the aircraft roll in degrees.
+    pub fn roll(&self) -> Result<f32> {
+        Ok(self.roll.get()?)
+    }
+
+    /// Sets the aircraft roll in degrees, keeping the pitch and the heading.
+    pub fn set_roll(&self, value: f32) -> Result<()> {
+        self.set_attitude(self.pitch.get()?, value, self.heading.get()?)
+    }
+
+    /// Reads the aircraft true heading.
+    pub fn heading(&self) -> Result<Heading> {
+        Ok(Heading::True(self.heading.get()?))
+    }
+
+    /// Sets the aircraft heading, keeping the pitch and the roll.
+    /// A magnetic heading is converted to a true heading.
+    pub fn set_heading(&self, value: Heading) -> Result<()> {
+        self.set_attitude(
+            self.pitch.get()?,
+            self.roll.get()?,
+            value.to_true().degrees(),
+        )
+    }
+
+    /// Sets the aircraft attitude. The attitude quaternion is written together with
+    /// the Euler angles, so both stay consistent until the next flight model frame.
+    ///
+    /// # Arguments
+    /// * `pitch` - a pitch in degrees.
+    /// * `roll` - a roll in degrees.
+    /// * `heading` - a true heading in degrees.
+    ///
+    /// # Returns
+    /// Returns empty result on success. Otherwise returns [`SimError`].
+    pub fn set_attitude(&self, pitch: f32, roll: f32, heading: f32) -> Result<()> {
+        self.quaternion()?
+            .write_at(0, &attitude_quaternion(pitch, roll, heading))?;
+        self.pitch.set(pitch)?;
+        self.roll.set(roll)?;
+        self.heading.set(heading)?;
+        Ok(())
+    }
+
+    fn quaternion(&self) -> Result<&DataRefArray<f32>> {
+        if let Some(quaternion) = self.quaternion.get() {
+            return Ok(quaternion);
+        }
+
+        let quaternion = DataRefArray::find(QUATERNION)?;
+        Ok(self.quaternion.get_or_init(|| quaternion))
+    }
+
+    /// Reads the aircraft ground speed in meters per second.
+    pub fn ground_speed(&self) -> Result<f32> {
+        Ok(self.ground_speed.get()?)
+    }
+
+    /// Reads the aircraft indicated airspeed in knots.
+    pub fn indicated_airspeed(&self) -> Result<f32> {
+        Ok(self.indicated_airspeed.get()?)
+    }
+
+    /// Checks wether any of the aircraft wheels touches the ground.
+    pub fn is_on_ground(&self) -> Result<bool> {
+        Ok(self.on_ground.get()?)
+    }
+}
+
+impl Default for Aircraft {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Converts Euler angles in degrees to the X-Plane attitude quaternion, as described
+/// in the SDK "Moving the plane" technical note.
+fn attitude_quaternion(pitch: f32, roll: f32, heading: f32) -> [f32; 4] {
+    let (sin_theta, cos_theta) = (pitch.to_radians() / 2.0).sin_cos();
+    let (sin_phi, cos_phi) = (roll.to_radians() / 2.0).sin_cos();
+    let (sin_psi, cos_psi) = (heading.to_radians() / 2.0).sin_cos();
+    [
+        cos_psi * cos_theta * cos_phi + sin_psi * sin_theta * sin_phi,
+        cos_psi * cos_theta * sin_phi - sin_psi * sin_theta * cos_phi,
+        cos_psi * sin_theta * cos_phi + sin_psi * cos_theta * sin_phi,
+        -cos_psi * sin_theta * sin_phi + sin_psi * cos_theta * cos_phi,
+    ]
+}
+
+/// Returns the full path to the user's aircraft `.acf` file.
+///
+/// # Returns
+/// Returns aircraft file path on success. Otherwise returns [`SimError`].
+pub fn current_aircraft_path() -> Result<path::PathBuf> {
+    let mut file_name = [0; 256];
+    let mut path = [0; 512];
+    backend().get_nth_aircraft_model(0, &mut file_name, &mut path);
+    let aircraft_path = os_path::path_from_c_buf(&path);
+    if aircraft_path.as_os_str().is_empty() {
+        Err(SimError::InvalidAircraftPath(aircraft_path))
+    } else {
+        Ok(aircraft_path)
+    }
+}
+
+/// Returns the ICAO type designator of the user's aircraft, e.g. `B738`.
+///
+/// # Returns
+/// Returns the designator, empty if the aircraft author did not set one.
+/// Otherwise returns [`SimError`].
+pub fn current_icao() -> Result<String> {
+    let icao = DataRefString::find("sim/aircraft/view/acf_ICAO")?.read_lossy();
+    Ok(icao.trim().to_string())
+}
+
+/// Identifies the user's aircraft.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AircraftInfo {
+    /// The ICAO type designator, see [`current_icao`].
+    pub icao: String,
+    /// The `.acf` file path, see [`current_aircraft_path`].
+    pub path: path::PathBuf,
+}
+
+impl AircraftInfo {
+    /// Identifies the user's aircraft.
+    ///
+    /// # Returns
+    /// Returns [`AircraftInfo`] on success. Otherwise returns [`SimError`].
+    pub fn current() -> Result<Self> {
+        Ok(Self {
+            icao: current_icao()?,
+            path: current_aircraft_path()?,
+        })
+    }
+}
+
+type AircraftChangedCallback = Box<dyn FnMut(&AircraftInfo)>;
+
+/// Notifies callbacks when the user loads a different aircraft, so plugins can load
+/// per-aircraft configuration in a single place.
+///
+/// Reloading the same aircraft doesn't fire the callbacks.
+pub struct AircraftWatcher {
+    current: Option<AircraftInfo>,
+    callbacks: Vec<AircraftChangedCallback>,
+}
+
+impl AircraftWatcher {
+    /// Creates a new watcher and identifies the aircraft loaded at the moment.
+    ///
+    /// # Returns
+    /// Returns a new [`AircraftWatcher`] instance.
+    pub fn new() -> Self {
+        Self {
+            current: AircraftInfo::current().ok(),
+            callbacks: Vec::new(),
+        }
+    }
+
+    /// Returns the aircraft identified by the last check.
+    pub fn current(&self) -> Option<&AircraftInfo> {
+        self.current.as_ref()
+    }
+
+    /// Registers a callback which is called every time the user's aircraft changes.
+    ///
+    /// # Arguments
+    /// * `callback` - a callback which receives the new aircraft.
+    pub fn on_aircraft_changed<F: FnMut(&AircraftInfo) + 'static>(&mut self, callback: F) {
+        self.callbacks.push(Box::new(callback));
+    }
+
+    /// Handles a plugin message. Should be called from [`crate::plugin::XPlugin::receive_message`].
+    ///
+    /// # Arguments
+    /// * `message` - the received message.
+    pub fn handle_message(&mut self, message: &Message) {
+        if matches!(message, Message::PlaneLoaded(0)) {
+            self.check();
+        }
+    }
+
+    /// Identifies the user's aircraft and calls the callbacks if it has changed.
+    ///
+    /// # Returns
+    /// Returns `true` if the aircraft has changed.
+    pub fn check(&mut self) -> bool {
+        let info = match AircraftInfo::current() {
+            Ok(info) => info,
+            Err(err) => {
+                crate::warn!("{}", err);
+                return false;
+            }
+        };
+
+        if self.current.as_ref() == Some(&info) {
+            return false;
+        }
+
+        for callback in self.callbacks.iter_mut() {
+            callback(&info);
+        }
+
+        self.current = Some(info);
+        true
+    }
+}
+
+impl Default for AircraftWatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Reloads the user's aircraft from disk, resetting its systems the same way
+/// as loading it from the aircraft menu does.
+///
+/// # Returns
+/// Returns empty result on success. Otherwise returns [`SimError`].
+pub fn reload_current_aircraft() -> Result<()> {
+    let aircraft_path = current_aircraft_path()?;
+    let path_c = path_to_c_string(&aircraft_path)?;
+    backend().set_users_aircraft(&path_c);
+    Ok(())
+}
+
+/// Lists the liveries available for the user's aircraft.
+/// The liveries are sorted by folder name ignoring case, which is the order X-Plane numbers
+/// `sim/aircraft/view/acf_livery_index` in, so the livery at position `n` has index `n + 1`.
+/// Folder names which only differ in case are ordered byte-wise.
+///
+/// # Returns
+/// Returns livery folder paths on success. Otherwise returns [`SimError`].
+pub fn list_liveries() -> Result<Vec<path::PathBuf>> {
+    let liveries_dir = utilities::get_aircraft_dir()?.join(LIVERIES_DIR);
+    if !liveries_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut liveries: Vec<path::PathBuf> = utilities::get_directory_contents(&liveries_dir)?
+        .filter(|livery| livery.is_dir())
+        .collect();
+    liveries.sort_by_cached_key(|livery| {
+        let name = livery.file_name().unwrap_or_default().to_os_string();
+        (name.to_string_lossy().to_lowercase(), name)
+    });
+    Ok(liveries)
+}
+
+/// Returns the folder of the livery currently applied to the user's aircraft.
+///
+/// # Returns
+/// Returns livery folder path or [`None`] if the default livery is used.
+/// Otherwise returns [`SimError`].
+pub fn current_livery() -> Result<Option<path::PathBuf>> {
+    let livery = DataRefString::find("sim/aircraft/view/acf_livery_path")?.read_lossy();
+    if livery.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(path::PathBuf::from(livery)))
+    }
+}
+
+/// Applies a livery to the user's aircraft.
+/// The livery is selected by its position in [`list_liveries`], index 0 is the default livery.
+///
+/// # Arguments
+/// * `livery` - a livery folder returned from [`list_liveries`] or [`None`] to restore
+///   the default livery. A folder name relative to the `liveries` folder is accepted as well.
+///
+/// # Returns
+/// Returns empty result on success. Otherwise returns [`SimError`].
+pub fn set_livery<P: AsRef<path::Path>>(livery: Option<P>) -> Result<()> {
+    let index = match livery {
+        Some(livery) => {
+            let livery = livery.as_ref();
+            let liveries = list_liveries()?;
+            let position = liveries
+                .iter()
+                .position(|candidate| {
+                    candidate == livery || candidate.file_name() == Some(livery.as_os_str())
+                })
+                .ok_or_else(|| SimError::LiveryNotFound(livery.to_path_buf()))?;
+            // Index 0 is reserved for the default livery.
+            position as i32 + 1
+        }
+        None => 0,
+    };
+
+    DataRefValue::new("sim/aircraft/view/acf_livery_index").set(index)?;
+    Ok(())
+}
+
+fn path_to_c_string(value: &path::Path) -> Result<ffi::CString> {
+    let bytes = os_path::path_to_bytes(value)
+        .ok_or_else(|| SimError::InvalidAircraftPath(value.to_path_buf()))?;
+    ffi::CString::new(bytes.into_owned())
+        .map_err(|_| SimError::InvalidAircraftPath(value.to_path_buf()))
+}
@@ -0,0 +1,85 @@
+use crate::api::data_access::DataRefString;
+
+use super::Result;
+
+type ChangeCallback = Box<dyn FnMut(&[String])>;
+
+/// Reads CDU or FMC screen text published by an aircraft as one byte data ref per line.
+///
+/// ```ignore
+/// let mut cdu = CduScreen::new((1..=14).map(|line| format!("laminar/B738/fmc1/Line{:02}_L", line)))?
+///     .on_change(|lines| send_to_remote_cdu(lines));
+/// cdu.poll();
+/// ```
+pub struct CduScreen {
+    lines: Vec<DataRefString>,
+    text: Vec<String>,
+    on_change: Option<ChangeCallback>,
+}
+
+impl CduScreen {
+    /// Looks up the screen line data refs.
+    ///
+    /// # Arguments
+    /// * `lines` - byte data ref names, from the top to the bottom line.
+    ///
+    /// # Returns
+    /// Returns [`CduScreen`] on success. Otherwise returns [`super::SimError`].
+    pub fn new<I, N>(lines: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = N>,
+        N: Into<String>,
+    {
+        let lines = lines
+            .into_iter()
+            .map(DataRefString::find)
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        let text = vec![String::new(); lines.len()];
+        Ok(Self {
+            lines,
+            text,
+            on_change: None,
+        })
+    }
+
+    /// Sets a callback which receives the screen lines every time the text changes.
+    ///
+    /// # Arguments
+    /// * `callback` - a change callback.
+    ///
+    /// # Returns
+    /// Returns a modified reader.
+    pub fn on_change<F: FnMut(&[String]) + 'static>(mut self, callback: F) -> Self {
+        self.on_change = Some(Box::new(callback));
+        self
+    }
+
+    /// Returns the screen lines read by the last refresh.
+    pub fn lines(&self) -> &[String] {
+        &self.text
+    }
+
+    /// Reads the screen lines and fires the change callback if the text has changed.
+    /// Should be called periodically, e.g. from a flight loop or a draw callback.
+    ///
+    /// # Returns
+    /// Returns `true` if the text has changed.
+    pub fn poll(&mut self) -> bool {
+        let mut changed = false;
+        for (line, text) in self.lines.iter().zip(self.text.iter_mut()) {
+            let value = line.read_lossy();
+            if *text != value {
+                *text = value;
+                changed = true;
+            }
+        }
+
+        if changed {
+            if let Some(callback) = self.on_change.as_mut() {
+                callback(&self.text);
+            }
+        }
+
+        changed
+    }
+}
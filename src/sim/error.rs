@@ -0,0 +1,78 @@
+use std::path::PathBuf;
+
+use crate::api::data_access::DataAccessError;
+use crate::api::utilities::UtilitiesError;
+#[cfg(feature = "prefs")]
+use crate::prefs::PrefsError;
+
+use super::{AxisAssignment, PlacementRequest};
+
+/// An error returned from simulator façades.
+#[derive(thiserror::Error, Debug)]
+pub enum SimError {
+    /// Data access error.
+    #[error("data access error {0}")]
+    DataAccess(DataAccessError),
+    /// Utilities error.
+    #[error("utilities error {0}")]
+    Utilities(UtilitiesError),
+    /// Command does not exist.
+    #[error("command {0} not found")]
+    CommandNotFound(String),
+    /// Invalid transponder code.
+    #[error("invalid transponder code {0}")]
+    InvalidTransponderCode(u16),
+    /// AI planes are controlled by another plugin.
+    #[error("ai planes are not available")]
+    PlanesNotAvailable,
+    /// Aircraft path is empty or can't be passed to X-Plane.
+    #[error("invalid aircraft path {}", .0.display())]
+    InvalidAircraftPath(PathBuf),
+    /// Livery does not exist in the aircraft `liveries` folder.
+    #[error("livery {} not found", .0.display())]
+    LiveryNotFound(PathBuf),
+    /// Placement request is out of range.
+    #[error("invalid placement request {0:?}")]
+    InvalidPlacement(PlacementRequest),
+    /// Airport code can't be passed to X-Plane.
+    #[error("invalid airport code {0}")]
+    InvalidAirportCode(String),
+    /// Weather preset is out of range.
+    #[error("invalid weather {0}")]
+    InvalidWeather(&'static str),
+    /// Failure data ref does not exist in the running X-Plane.
+    #[error("failure {0} not found")]
+    FailureNotFound(String),
+    /// Failure data ref contains an unknown failure mode.
+    #[error("unknown failure state {0}")]
+    UnknownFailureState(::std::os::raw::c_int),
+    /// Axis assignment has no X-Plane equivalent.
+    #[error("invalid axis assignment {0:?}")]
+    InvalidAxisAssignment(AxisAssignment),
+    /// View preset does not exist.
+    #[error("view preset {0} not found")]
+    ViewPresetNotFound(String),
+    /// View presets can't be loaded or saved.
+    #[cfg(feature = "prefs")]
+    #[error("preferences error {0}")]
+    Prefs(PrefsError),
+}
+
+impl From<DataAccessError> for SimError {
+    fn from(value: DataAccessError) -> Self {
+        Self::DataAccess(value)
+    }
+}
+
+impl From<UtilitiesError> for SimError {
+    fn from(value: UtilitiesError) -> Self {
+        Self::Utilities(value)
+    }
+}
+
+#[cfg(feature = "prefs")]
+impl From<PrefsError> for SimError {
+    fn from(value: PrefsError) -> Self {
+        Self::Prefs(value)
+    }
+}
@@ -0,0 +1,437 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::api::data_access::{exists, find_data_ref_opt, get_data_i, set_data_i, DataRef};
+use crate::api::processing::{
+    create_flight_loop, schedule_flight_loop, FlightLoopPhase, FlightLoopRecord,
+};
+use crate::api::utilities::{command_once, find_command};
+
+use super::{Result, SimError};
+
+/// The prefix of every failure data ref.
+const FAILURE_PREFIX: &str = "sim/operation/failures/";
+/// Maximum number of engines supported by X-Plane.
+const ENGINES: usize = 8;
+/// Maximum number of landing gears supported by X-Plane.
+const GEARS: usize = 5;
+/// Maximum number of electrical generators and batteries supported by X-Plane.
+const ELECTRICAL_SOURCES: usize = 8;
+/// The command which repairs every failed system.
+const FIX_ALL_COMMAND: &str = "sim/operation/fix_all_systems";
+
+/// How often [`FailureScheduler`] rolls the dice in seconds.
+const POLL_INTERVAL: f32 = 1.0;
+
+/// A failure mode of a system.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FailureState {
+    /// The system always works.
+    AlwaysWorking,
+    /// The system fails randomly based on the mean time between failures set in X-Plane.
+    MeanTimeBetweenFailures,
+    /// The system fails at the exact time set in X-Plane.
+    ExactTime,
+    /// The system fails at the exact indicated airspeed set in X-Plane.
+    ExactSpeed,
+    /// The system fails at the exact altitude above ground set in X-Plane.
+    ExactAltitude,
+    /// The system fails when the failure key or joystick button is pressed.
+    OnInput,
+    /// The system has failed.
+    Inoperative,
+}
+
+impl TryFrom<::std::os::raw::c_int> for FailureState {
+    type Error = SimError;
+
+    fn try_from(value: ::std::os::raw::c_int) -> Result<Self> {
+        match value {
+            0 => Ok(Self::AlwaysWorking),
+            1 => Ok(Self::MeanTimeBetweenFailures),
+            2 => Ok(Self::ExactTime),
+            3 => Ok(Self::ExactSpeed),
+            4 => Ok(Self::ExactAltitude),
+            5 => Ok(Self::OnInput),
+            6 => Ok(Self::Inoperative),
+            _ => Err(SimError::UnknownFailureState(value)),
+        }
+    }
+}
+
+impl From<FailureState> for ::std::os::raw::c_int {
+    fn from(value: FailureState) -> Self {
+        match value {
+            FailureState::AlwaysWorking => 0,
+            FailureState::MeanTimeBetweenFailures => 1,
+            FailureState::ExactTime => 2,
+            FailureState::ExactSpeed => 3,
+            FailureState::ExactAltitude => 4,
+            FailureState::OnInput => 5,
+            FailureState::Inoperative => 6,
+        }
+    }
+}
+
+/// A system which can fail.
+///
+/// Indexed failures take a zero based index, e.g. `Failure::Engine(0)` is the first engine.
+/// Use [`Failure::Custom`] for the failures not listed here.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Failure {
+    /// Engine failure.
+    Engine(usize),
+    /// Engine fire.
+    EngineFire(usize),
+    /// Electrical generator failure.
+    Generator(usize),
+    /// Battery failure.
+    Battery(usize),
+    /// Landing gear collapse.
+    GearCollapse(usize),
+    /// Tire blowout.
+    Tire(usize),
+    /// Pilot pitot tube blockage.
+    Pitot,
+    /// Pilot static port blockage.
+    StaticPort,
+    /// Vacuum system failure.
+    Vacuum,
+    /// Hydraulic pump failure.
+    HydraulicPump,
+    /// Autopilot servos failure.
+    Autopilot,
+    /// GPS failure.
+    Gps,
+    /// Bird strike.
+    BirdStrike,
+    /// Wind shear or microburst.
+    WindShear,
+    /// Smoke in the cockpit.
+    CockpitSmoke,
+    /// Any other failure by its data ref name without the `sim/operation/failures/` prefix,
+    /// e.g. `rel_hydpmp2`.
+    Custom(String),
+}
+
+impl Failure {
+    /// Returns the failure data ref name.
+    pub fn data_ref_name(&self) -> String {
+        let name = match self {
+            Self::Engine(index) => format!("rel_engfai{}", index),
+            Self::EngineFire(index) => format!("rel_engfir{}", index),
+            Self::Generator(index) => format!("rel_genera{}", index),
+            Self::Battery(index) => format!("rel_batter{}", index),
+            Self::GearCollapse(index) => format!("rel_lagear{}", index + 1),
+            Self::Tire(index) => format!("rel_tire{}", index + 1),
+            Self::Pitot => "rel_pitot".to_string(),
+            Self::StaticPort => "rel_static".to_string(),
+            Self::Vacuum => "rel_vacuum".to_string(),
+            Self::HydraulicPump => "rel_hydpmp".to_string(),
+            Self::Autopilot => "rel_otto".to_string(),
+            Self::Gps => "rel_gps".to_string(),
+            Self::BirdStrike => "rel_bird_strike".to_string(),
+            Self::WindShear => "rel_wind_shear".to_string(),
+            Self::CockpitSmoke => "rel_smoke_cpit".to_string(),
+            Self::Custom(name) => name.clone(),
+        };
+
+        format!("{}{}", FAILURE_PREFIX, name)
+    }
+
+    /// Lists the named failures known to the crate, including every engine,
+    /// gear and electrical source index.
+    ///
+    /// # Returns
+    /// Returns all [`Failure`] values except [`Failure::Custom`].
+    pub fn all() -> Vec<Failure> {
+        let mut failures = Vec::new();
+        failures.extend((0..ENGINES).map(Self::Engine));
+        failures.extend((0..ENGINES).map(Self::EngineFire));
+        failures.extend((0..ELECTRICAL_SOURCES).map(Self::Generator));
+        failures.extend((0..ELECTRICAL_SOURCES).map(Self::Battery));
+        failures.extend((0..GEARS).map(Self::GearCollapse));
+        failures.extend((0..GEARS).map(Self::Tire));
+        failures.extend([
+            Self::Pitot,
+            Self::StaticPort,
+            Self::Vacuum,
+            Self::HydraulicPump,
+            Self::Autopilot,
+            Self::Gps,
+            Self::BirdStrike,
+            Self::WindShear,
+            Self::CockpitSmoke,
+        ]);
+        failures
+    }
+
+    /// Lists the named failures the running X-Plane provides data refs for.
+    ///
+    /// # Returns
+    /// Returns the subset of [`Failure::all`] which exists.
+    pub fn available() -> Vec<Failure> {
+        Self::all()
+            .into_iter()
+            .filter(|failure| exists(failure.data_ref_name()))
+            .collect()
+    }
+}
+
+/// Reads a failure mode.
+///
+/// # Arguments
+/// * `failure` - a system. See [`Failure`] for more details.
+///
+/// # Returns
+/// Returns [`FailureState`] on success. Otherwise returns [`SimError`].
+pub fn get_failure_state(failure: &Failure) -> Result<FailureState> {
+    let data_ref = find_failure(failure)?;
+    FailureState::try_from(get_data_i(&data_ref))
+}
+
+/// Sets a failure mode.
+///
+/// # Arguments
+/// * `failure` - a system. See [`Failure`] for more details.
+/// * `state` - a failure mode. See [`FailureState`] for more details.
+///
+/// # Returns
+/// Returns empty result on success. Otherwise returns [`SimError`].
+pub fn set_failure_state(failure: &Failure, state: FailureState) -> Result<()> {
+    let data_ref = find_failure(failure)?;
+    set_data_i(&data_ref, state.into());
+    Ok(())
+}
+
+/// Fails a system immediately.
+///
+/// # Arguments
+/// * `failure` - a system. See [`Failure`] for more details.
+///
+/// # Returns
+/// Returns empty result on success. Otherwise returns [`SimError`].
+pub fn fail(failure: &Failure) -> Result<()> {
+    set_failure_state(failure, FailureState::Inoperative)
+}
+
+/// Repairs a system and keeps it working.
+///
+/// # Arguments
+/// * `failure` - a system. See [`Failure`] for more details.
+///
+/// # Returns
+/// Returns empty result on success. Otherwise returns [`SimError`].
+pub fn repair(failure: &Failure) -> Result<()> {
+    set_failure_state(failure, FailureState::AlwaysWorking)
+}
+
+/// Repairs every failed system.
+///
+/// # Returns
+/// Returns empty result on success. Otherwise returns [`SimError`].
+pub fn repair_all() -> Result<()> {
+    let command =
+        find_command(FIX_ALL_COMMAND)?.ok_or(SimError::CommandNotFound(FIX_ALL_COMMAND.into()))?;
+    command_once(&command);
+    Ok(())
+}
+
+fn find_failure(failure: &Failure) -> Result<DataRef> {
+    let name = failure.data_ref_name();
+    find_data_ref_opt(name.as_str()).ok_or(SimError::FailureNotFound(name))
+}
+
+/// A failure armed by [`FailureScheduler`].
+struct Armed {
+    failure: Failure,
+    mtbf: Duration,
+}
+
+/// A handler called for every failure triggered by [`FailureScheduler`].
+type FailureHandler = Box<dyn FnMut(&Failure)>;
+
+/// The armed failures shared with the flight loop of [`FailureScheduler`].
+struct Schedule {
+    armed: Vec<Armed>,
+    last_poll: Option<Instant>,
+    seed: u64,
+}
+
+impl Schedule {
+    /// Rolls the dice for every armed failure since the previous call.
+    /// A failure which triggers is disarmed once it has been applied, a failure which
+    /// can't be applied stays armed and is retried by the next call.
+    ///
+    /// # Returns
+    /// Returns the failures triggered by this call.
+    fn poll(&mut self) -> Vec<Failure> {
+        let now = Instant::now();
+        let elapsed = match self.last_poll.replace(now) {
+            Some(last_poll) => now.duration_since(last_poll).as_secs_f64(),
+            None => return Vec::new(),
+        };
+
+        let mut triggered = Vec::new();
+        let mut index = 0;
+        while index < self.armed.len() {
+            let mtbf = self.armed[index].mtbf.as_secs_f64();
+            let probability = if mtbf > 0.0 {
+                1.0 - (-elapsed / mtbf).exp()
+            } else {
+                1.0
+            };
+
+            if self.next_random() < probability {
+                match fail(&self.armed[index].failure) {
+                    Ok(()) => {
+                        triggered.push(self.armed.remove(index).failure);
+                        continue;
+                    }
+                    Err(err) => crate::error!("{}", err),
+                }
+            }
+
+            index += 1;
+        }
+
+        triggered
+    }
+
+    /// Returns a uniformly distributed number in range 0.0..1.0 (xorshift64*).
+    fn next_random(&mut self) -> f64 {
+        self.seed ^= self.seed >> 12;
+        self.seed ^= self.seed << 25;
+        self.seed ^= self.seed >> 27;
+        let value = self.seed.wrapping_mul(0x2545_f491_4f6c_dd1d);
+        (value >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Fails systems randomly based on their mean time between failures.
+///
+/// Unlike [`FailureState::MeanTimeBetweenFailures`], the failure probability is computed by
+/// the plugin, so every system can have its own mean time between failures. The dice are
+/// rolled every second by a flight loop owned by the scheduler, which runs while at least
+/// one failure is armed and is destroyed with the scheduler.
+pub struct FailureScheduler {
+    schedule: Rc<RefCell<Schedule>>,
+    on_failure: Rc<RefCell<Option<FailureHandler>>>,
+    flight_loop: FlightLoopRecord,
+}
+
+impl FailureScheduler {
+    /// Creates a scheduler without armed failures.
+    ///
+    /// # Returns
+    /// Returns a new [`FailureScheduler`] instance.
+    pub fn new() -> Self {
+        let seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|time| time.as_nanos() as u64)
+            .unwrap_or_default();
+        Self::with_seed(seed)
+    }
+
+    /// Creates a scheduler with a fixed random seed, e.g. to repeat a training scenario.
+    ///
+    /// # Arguments
+    /// * `seed` - a random seed.
+    ///
+    /// # Returns
+    /// Returns a new [`FailureScheduler`] instance.
+    pub fn with_seed(seed: u64) -> Self {
+        let schedule = Rc::new(RefCell::new(Schedule {
+            armed: Vec::new(),
+            last_poll: None,
+            seed: seed | 1,
+        }));
+        let on_failure = Rc::new(RefCell::new(None::<FailureHandler>));
+        let flight_loop = {
+            let schedule = schedule.clone();
+            let on_failure = on_failure.clone();
+            create_flight_loop(FlightLoopPhase::BeforeFlightModel, move |_| {
+                let triggered = schedule.borrow_mut().poll();
+                if let Some(handler) = on_failure.borrow_mut().as_mut() {
+                    triggered.iter().for_each(|failure| handler(failure));
+                }
+
+                let mut schedule = schedule.borrow_mut();
+                if schedule.armed.is_empty() {
+                    schedule.last_poll = None;
+                    0.0
+                } else {
+                    POLL_INTERVAL
+                }
+            })
+        };
+
+        Self {
+            schedule,
+            on_failure,
+            flight_loop,
+        }
+    }
+
+    /// Arms a failure.
+    ///
+    /// # Arguments
+    /// * `failure` - a system. See [`Failure`] for more details.
+    /// * `mtbf` - a mean time between failures.
+    ///
+    /// # Returns
+    /// Returns a modified scheduler.
+    pub fn arm(self, failure: Failure, mtbf: Duration) -> Self {
+        let mut schedule = self.schedule.borrow_mut();
+        if schedule.armed.is_empty() {
+            schedule.last_poll = Some(Instant::now());
+            schedule_flight_loop(&self.flight_loop, POLL_INTERVAL, true);
+        }
+
+        schedule.armed.push(Armed { failure, mtbf });
+        drop(schedule);
+        self
+    }
+
+    /// Sets a handler which is called for every triggered failure, e.g. to notify the user.
+    ///
+    /// # Arguments
+    /// * `handler` - a failure handler.
+    ///
+    /// # Returns
+    /// Returns a modified scheduler.
+    pub fn on_failure<F: FnMut(&Failure) + 'static>(self, handler: F) -> Self {
+        *self.on_failure.borrow_mut() = Some(Box::new(handler));
+        self
+    }
+
+    /// Disarms a failure.
+    ///
+    /// # Arguments
+    /// * `failure` - a system. See [`Failure`] for more details.
+    pub fn disarm(&mut self, failure: &Failure) {
+        let mut schedule = self.schedule.borrow_mut();
+        schedule.armed.retain(|armed| armed.failure != *failure);
+        if schedule.armed.is_empty() {
+            schedule.last_poll = None;
+            schedule_flight_loop(&self.flight_loop, 0.0, true);
+        }
+    }
+
+    /// Returns the number of armed failures.
+    pub fn len(&self) -> usize {
+        self.schedule.borrow().armed.len()
+    }
+
+    /// Checks wether no failure is armed.
+    pub fn is_empty(&self) -> bool {
+        self.schedule.borrow().armed.is_empty()
+    }
+}
+
+impl Default for FailureScheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
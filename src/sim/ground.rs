@@ -0,0 +1,338 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use crate::api::data_access::DataRefValue;
+use crate::api::processing::{
+    create_flight_loop, schedule_flight_loop, FlightLoopPhase, FlightLoopRecord,
+};
+use crate::api::utilities::{command_once, find_command};
+
+use super::{Result, SimError};
+
+/// Starts pushing the aircraft back straight.
+pub const PUSHBACK_STRAIGHT_COMMAND: &str = "sim/ground_ops/pushback_straight";
+/// Pushes the aircraft back with the tail moving left.
+pub const PUSHBACK_LEFT_COMMAND: &str = "sim/ground_ops/pushback_left";
+/// Pushes the aircraft back with the tail moving right.
+pub const PUSHBACK_RIGHT_COMMAND: &str = "sim/ground_ops/pushback_right";
+/// Stops the pushback and disconnects the tug.
+pub const PUSHBACK_STOP_COMMAND: &str = "sim/ground_ops/pushback_stop";
+/// Connects the ground power unit.
+pub const GPU_ON_COMMAND: &str = "sim/electrical/GPU_on";
+/// Disconnects the ground power unit.
+pub const GPU_OFF_COMMAND: &str = "sim/electrical/GPU_off";
+
+/// Default heading tolerance of a scripted pushback in degrees.
+pub const DEFAULT_HEADING_TOLERANCE: f32 = 3.0;
+
+/// A pushback steering direction.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PushbackDirection {
+    /// Push straight back.
+    Straight,
+    /// Move the tail left, the nose turns right.
+    TailLeft,
+    /// Move the tail right, the nose turns left.
+    TailRight,
+}
+
+impl PushbackDirection {
+    fn command(&self) -> &'static str {
+        match self {
+            Self::Straight => PUSHBACK_STRAIGHT_COMMAND,
+            Self::TailLeft => PUSHBACK_LEFT_COMMAND,
+            Self::TailRight => PUSHBACK_RIGHT_COMMAND,
+        }
+    }
+}
+
+/// X-Plane 12 ground services: pushback tug, ground power unit and wheel chocks.
+pub struct GroundServices {
+    gpu_on: DataRefValue<bool>,
+    chocks: DataRefValue<bool>,
+    heading: DataRefValue<f32>,
+    ground_speed: DataRefValue<f32>,
+}
+
+impl GroundServices {
+    /// Creates a ground services façade. Data refs are looked up on first use.
+    ///
+    /// # Returns
+    /// Returns a new [`GroundServices`] instance.
+    pub fn new() -> Self {
+        Self {
+            gpu_on: DataRefValue::new("sim/cockpit/electrical/gpu_on"),
+            chocks: DataRefValue::new("sim/flightmodel2/gear/wheel_chocks_on"),
+            heading: DataRefValue::new("sim/flightmodel/position/psi"),
+            ground_speed: DataRefValue::new("sim/flightmodel/position/groundspeed"),
+        }
+    }
+
+    /// Calls the tug and pushes the aircraft back.
+    ///
+    /// # Arguments
+    /// * `direction` - a steering direction. See [`PushbackDirection`] for more details.
+    ///
+    /// # Returns
+    /// Returns empty result on success. Otherwise returns [`SimError`].
+    pub fn pushback(&self, direction: PushbackDirection) -> Result<()> {
+        run(direction.command())
+    }
+
+    /// Stops the pushback.
+    ///
+    /// # Returns
+    /// Returns empty result on success. Otherwise returns [`SimError`].
+    pub fn stop_pushback(&self) -> Result<()> {
+        run(PUSHBACK_STOP_COMMAND)
+    }
+
+    /// Checks wether the ground power unit is connected.
+    ///
+    /// # Returns
+    /// Returns `true` if connected on success. Otherwise returns [`SimError`].
+    pub fn is_gpu_connected(&self) -> Result<bool> {
+        Ok(self.gpu_on.get()?)
+    }
+
+    /// Connects or disconnects the ground power unit.
+    ///
+    /// # Arguments
+    /// * `connected` - `true` to connect the ground power unit.
+    ///
+    /// # Returns
+    /// Returns empty result on success. Otherwise returns [`SimError`].
+    pub fn set_gpu_connected(&self, connected: bool) -> Result<()> {
+        run(if connected {
+            GPU_ON_COMMAND
+        } else {
+            GPU_OFF_COMMAND
+        })
+    }
+
+    /// Checks wether the wheel chocks are in place.
+    ///
+    /// # Returns
+    /// Returns `true` if the chocks are in place on success. Otherwise returns [`SimError`].
+    pub fn are_chocks_set(&self) -> Result<bool> {
+        Ok(self.chocks.get()?)
+    }
+
+    /// Places or removes the wheel chocks.
+    ///
+    /// # Arguments
+    /// * `set` - `true` to place the chocks.
+    ///
+    /// # Returns
+    /// Returns empty result on success. Otherwise returns [`SimError`].
+    pub fn set_chocks(&self, set: bool) -> Result<()> {
+        Ok(self.chocks.set(set)?)
+    }
+
+    fn heading(&self) -> Result<f32> {
+        Ok(self.heading.get()?)
+    }
+
+    fn ground_speed(&self) -> Result<f32> {
+        Ok(self.ground_speed.get()?)
+    }
+}
+
+impl Default for GroundServices {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A phase of a scripted pushback.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum PushbackPhase {
+    /// The script has not been started.
+    Idle,
+    /// The aircraft is pushed straight back.
+    Straight,
+    /// The aircraft is turned to the target heading.
+    Turning,
+    /// The tug has been stopped and the aircraft is slowing down.
+    Stopping,
+    /// The aircraft has stopped.
+    Done,
+}
+
+/// The state of a scripted pushback shared with its flight loop.
+struct Script {
+    services: GroundServices,
+    target_heading: Option<f32>,
+    straight: Duration,
+    tolerance: f32,
+    phase: PushbackPhase,
+    direction: Option<PushbackDirection>,
+    phase_started: Instant,
+}
+
+impl Script {
+    fn start(&mut self) -> Result<()> {
+        self.direction = None;
+        self.steer(PushbackDirection::Straight)?;
+        self.enter(PushbackPhase::Straight);
+        Ok(())
+    }
+
+    fn abort(&mut self) -> Result<()> {
+        self.services.stop_pushback()?;
+        self.enter(PushbackPhase::Stopping);
+        Ok(())
+    }
+
+    fn advance(&mut self) -> Result<()> {
+        match self.phase {
+            PushbackPhase::Straight if self.phase_started.elapsed() >= self.straight => {
+                if self.target_heading.is_some() {
+                    self.enter(PushbackPhase::Turning);
+                } else {
+                    self.abort()?;
+                }
+            }
+            PushbackPhase::Turning => {
+                let target = self.target_heading.unwrap_or_default();
+                let error = (target - self.services.heading()? + 540.0).rem_euclid(360.0) - 180.0;
+                if error.abs() <= self.tolerance {
+                    self.abort()?;
+                } else if error > 0.0 {
+                    self.steer(PushbackDirection::TailLeft)?;
+                } else {
+                    self.steer(PushbackDirection::TailRight)?;
+                }
+            }
+            PushbackPhase::Stopping if self.services.ground_speed()?.abs() < 0.1 => {
+                self.enter(PushbackPhase::Done);
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn steer(&mut self, direction: PushbackDirection) -> Result<()> {
+        if self.direction != Some(direction) {
+            self.services.pushback(direction)?;
+            self.direction = Some(direction);
+        }
+
+        Ok(())
+    }
+
+    fn enter(&mut self, phase: PushbackPhase) {
+        self.phase = phase;
+        self.phase_started = Instant::now();
+    }
+}
+
+/// A scripted pushback: push straight back, turn to a heading and stop.
+///
+/// The script is advanced every frame by a flight loop owned by the script,
+/// which is destroyed with it.
+pub struct PushbackScript {
+    script: Rc<RefCell<Script>>,
+    flight_loop: FlightLoopRecord,
+}
+
+impl PushbackScript {
+    /// Creates a script which pushes straight back for a duration and stops.
+    ///
+    /// # Arguments
+    /// * `straight` - how long to push straight back.
+    ///
+    /// # Returns
+    /// Returns a new [`PushbackScript`] instance.
+    pub fn new(straight: Duration) -> Self {
+        let script = Rc::new(RefCell::new(Script {
+            services: GroundServices::new(),
+            target_heading: None,
+            straight,
+            tolerance: DEFAULT_HEADING_TOLERANCE,
+            phase: PushbackPhase::Idle,
+            direction: None,
+            phase_started: Instant::now(),
+        }));
+        let flight_loop = {
+            let script = script.clone();
+            create_flight_loop(FlightLoopPhase::BeforeFlightModel, move |_| {
+                let mut script = script.borrow_mut();
+                if let Err(err) = script.advance() {
+                    crate::error!("{}", err);
+                    if let Err(err) = script.abort() {
+                        crate::error!("{}", err);
+                        script.enter(PushbackPhase::Done);
+                    }
+                }
+
+                match script.phase {
+                    PushbackPhase::Idle | PushbackPhase::Done => 0.0,
+                    _ => -1.0,
+                }
+            })
+        };
+
+        Self {
+            script,
+            flight_loop,
+        }
+    }
+
+    /// Turns the aircraft to a heading after the straight segment.
+    ///
+    /// # Arguments
+    /// * `heading` - a target true heading in degrees.
+    ///
+    /// # Returns
+    /// Returns a modified script.
+    pub fn turn_to(self, heading: f32) -> Self {
+        self.script.borrow_mut().target_heading = Some(heading.rem_euclid(360.0));
+        self
+    }
+
+    /// Sets how close to the target heading the turn ends.
+    ///
+    /// # Arguments
+    /// * `degrees` - a heading tolerance, see [`DEFAULT_HEADING_TOLERANCE`].
+    ///
+    /// # Returns
+    /// Returns a modified script.
+    pub fn tolerance(self, degrees: f32) -> Self {
+        self.script.borrow_mut().tolerance = degrees.abs();
+        self
+    }
+
+    /// Returns the current phase.
+    pub fn phase(&self) -> PushbackPhase {
+        self.script.borrow().phase
+    }
+
+    /// Starts the pushback, the script keeps running until the aircraft has stopped.
+    ///
+    /// # Returns
+    /// Returns empty result on success. Otherwise returns [`SimError`].
+    pub fn start(&mut self) -> Result<()> {
+        self.script.borrow_mut().start()?;
+        schedule_flight_loop(&self.flight_loop, -1.0, true);
+        Ok(())
+    }
+
+    /// Stops the tug immediately.
+    ///
+    /// # Returns
+    /// Returns empty result on success. Otherwise returns [`SimError`].
+    pub fn abort(&mut self) -> Result<()> {
+        self.script.borrow_mut().abort()?;
+        schedule_flight_loop(&self.flight_loop, -1.0, true);
+        Ok(())
+    }
+}
+
+fn run(name: &str) -> Result<()> {
+    let command = find_command(name)?.ok_or_else(|| SimError::CommandNotFound(name.into()))?;
+    command_once(&command);
+    Ok(())
+}
@@ -0,0 +1,259 @@
+use crate::api::data_access::DataRef;
+use crate::api::data_access::{find_data_ref, get_data_i, get_data_vf, get_data_vi, set_data_vi};
+
+use super::{Result, SimError};
+
+/// Number of engines which have their own axis assignments.
+pub const ENGINE_AXES: u8 = 4;
+/// Maximum number of joystick axes reported by X-Plane.
+const AXES: usize = 500;
+/// Maximum number of joystick buttons reported by X-Plane.
+const BUTTONS: usize = 3200;
+
+/// A function assigned to a joystick axis.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AxisAssignment {
+    None,
+    Pitch,
+    Roll,
+    Yaw,
+    Throttle,
+    Collective,
+    LeftToeBrake,
+    RightToeBrake,
+    Prop,
+    Mixture,
+    CarbHeat,
+    Flaps,
+    ThrustVector,
+    WingSweep,
+    Speedbrakes,
+    Displacement,
+    Reverse,
+    ElevatorTrim,
+    AileronTrim,
+    RudderTrim,
+    /// Throttle of a single engine, zero based and below [`ENGINE_AXES`].
+    EngineThrottle(u8),
+    /// Propeller of a single engine, zero based and below [`ENGINE_AXES`].
+    EngineProp(u8),
+    /// Mixture of a single engine, zero based and below [`ENGINE_AXES`].
+    EngineMixture(u8),
+    /// Reverser of a single engine, zero based and below [`ENGINE_AXES`].
+    EngineReverse(u8),
+    /// An assignment which is not known to this library.
+    Other(::std::os::raw::c_int),
+}
+
+impl From<::std::os::raw::c_int> for AxisAssignment {
+    fn from(value: ::std::os::raw::c_int) -> Self {
+        match value {
+            0 => Self::None,
+            1 => Self::Pitch,
+            2 => Self::Roll,
+            3 => Self::Yaw,
+            4 => Self::Throttle,
+            5 => Self::Collective,
+            6 => Self::LeftToeBrake,
+            7 => Self::RightToeBrake,
+            8 => Self::Prop,
+            9 => Self::Mixture,
+            10 => Self::CarbHeat,
+            11 => Self::Flaps,
+            12 => Self::ThrustVector,
+            13 => Self::WingSweep,
+            14 => Self::Speedbrakes,
+            15 => Self::Displacement,
+            16 => Self::Reverse,
+            17 => Self::ElevatorTrim,
+            18 => Self::AileronTrim,
+            19 => Self::RudderTrim,
+            20..=23 => Self::EngineThrottle((value - 20) as u8),
+            24..=27 => Self::EngineProp((value - 24) as u8),
+            28..=31 => Self::EngineMixture((value - 28) as u8),
+            32..=35 => Self::EngineReverse((value - 32) as u8),
+            _ => Self::Other(value),
+        }
+    }
+}
+
+impl TryFrom<AxisAssignment> for ::std::os::raw::c_int {
+    type Error = SimError;
+
+    fn try_from(value: AxisAssignment) -> std::result::Result<Self, Self::Error> {
+        let engine_axis = |base: Self, engine: u8| {
+            if engine < ENGINE_AXES {
+                Ok(base + engine as Self)
+            } else {
+                Err(SimError::InvalidAxisAssignment(value))
+            }
+        };
+
+        let value = match value {
+            AxisAssignment::None => 0,
+            AxisAssignment::Pitch => 1,
+            AxisAssignment::Roll => 2,
+            AxisAssignment::Yaw => 3,
+            AxisAssignment::Throttle => 4,
+            AxisAssignment::Collective => 5,
+            AxisAssignment::LeftToeBrake => 6,
+            AxisAssignment::RightToeBrake => 7,
+            AxisAssignment::Prop => 8,
+            AxisAssignment::Mixture => 9,
+            AxisAssignment::CarbHeat => 10,
+            AxisAssignment::Flaps => 11,
+            AxisAssignment::ThrustVector => 12,
+            AxisAssignment::WingSweep => 13,
+            AxisAssignment::Speedbrakes => 14,
+            AxisAssignment::Displacement => 15,
+            AxisAssignment::Reverse => 16,
+            AxisAssignment::ElevatorTrim => 17,
+            AxisAssignment::AileronTrim => 18,
+            AxisAssignment::RudderTrim => 19,
+            AxisAssignment::EngineThrottle(engine) => engine_axis(20, engine)?,
+            AxisAssignment::EngineProp(engine) => engine_axis(24, engine)?,
+            AxisAssignment::EngineMixture(engine) => engine_axis(28, engine)?,
+            AxisAssignment::EngineReverse(engine) => engine_axis(32, engine)?,
+            AxisAssignment::Other(value) => value,
+        };
+        Ok(value)
+    }
+}
+
+/// Joystick axes and buttons state.
+pub struct Joystick {
+    has_joystick: DataRef,
+    axis_values: DataRef,
+    axis_assignments: DataRef,
+    axis_reverse: DataRef,
+    button_values: DataRef,
+}
+
+impl Joystick {
+    /// Looks up all data refs required to read joystick state.
+    ///
+    /// # Returns
+    /// Returns [`Joystick`] on success. Otherwise returns [`super::SimError`].
+    pub fn new() -> Result<Self> {
+        Ok(Self {
+            has_joystick: find_data_ref("sim/joystick/has_joystick")?,
+            axis_values: find_data_ref("sim/joystick/joystick_axis_values")?,
+            axis_assignments: find_data_ref("sim/joystick/joystick_axis_assignments")?,
+            axis_reverse: find_data_ref("sim/joystick/joystick_axis_reverse")?,
+            button_values: find_data_ref("sim/joystick/joystick_button_values")?,
+        })
+    }
+
+    /// Checks wether any joystick is connected.
+    pub fn has_joystick(&self) -> bool {
+        get_data_i(&self.has_joystick) != 0
+    }
+
+    /// Reads normalized values of all axes.
+    ///
+    /// # Returns
+    /// Returns axis values in range 0.0..=1.0 indexed by axis number.
+    pub fn axis_values(&self) -> Vec<f32> {
+        let mut values = vec![0.0; AXES];
+        let count = get_data_vf(&self.axis_values, 0, &mut values);
+        values.truncate(count);
+        values
+    }
+
+    /// Reads a normalized value of a single axis.
+    ///
+    /// # Arguments
+    /// * `index` - an axis number.
+    ///
+    /// # Returns
+    /// Returns axis value in range 0.0..=1.0 or [`None`] if the axis does not exist.
+    pub fn axis_value(&self, index: usize) -> Option<f32> {
+        self.axis_values().get(index).copied()
+    }
+
+    /// Reads assignments of all axes.
+    ///
+    /// # Returns
+    /// Returns axis assignments indexed by axis number.
+    pub fn axis_assignments(&self) -> Vec<AxisAssignment> {
+        self.read_ints(&self.axis_assignments, AXES)
+            .into_iter()
+            .map(AxisAssignment::from)
+            .collect()
+    }
+
+    /// Assigns a function to an axis.
+    ///
+    /// # Arguments
+    /// * `index` - an axis number.
+    /// * `assignment` - an axis function. See [`AxisAssignment`] for more details.
+    ///
+    /// # Returns
+    /// Returns empty result on success. Otherwise returns [`SimError::InvalidAxisAssignment`]
+    /// if the engine of a per-engine assignment is not below [`ENGINE_AXES`].
+    pub fn set_axis_assignment(&self, index: usize, assignment: AxisAssignment) -> Result<()> {
+        set_data_vi(&self.axis_assignments, index, &[assignment.try_into()?]);
+        Ok(())
+    }
+
+    /// Finds the first axis with the given assignment.
+    ///
+    /// # Arguments
+    /// * `assignment` - an axis function. See [`AxisAssignment`] for more details.
+    ///
+    /// # Returns
+    /// Returns an axis number or [`None`] if no axis has the given assignment.
+    pub fn find_axis(&self, assignment: AxisAssignment) -> Option<usize> {
+        self.axis_assignments()
+            .into_iter()
+            .position(|axis| axis == assignment)
+    }
+
+    /// Reads a normalized value of the first axis with the given assignment.
+    ///
+    /// # Arguments
+    /// * `assignment` - an axis function. See [`AxisAssignment`] for more details.
+    ///
+    /// # Returns
+    /// Returns axis value in range 0.0..=1.0 or [`None`] if no axis has the given assignment.
+    pub fn assigned_axis_value(&self, assignment: AxisAssignment) -> Option<f32> {
+        self.find_axis(assignment)
+            .and_then(|index| self.axis_value(index))
+    }
+
+    /// Checks wether an axis is reversed.
+    ///
+    /// # Arguments
+    /// * `index` - an axis number.
+    pub fn is_axis_reversed(&self, index: usize) -> bool {
+        self.read_ints(&self.axis_reverse, AXES)
+            .get(index)
+            .is_some_and(|value| *value != 0)
+    }
+
+    /// Reads states of all buttons.
+    ///
+    /// # Returns
+    /// Returns `true` for every pressed button indexed by button number.
+    pub fn button_values(&self) -> Vec<bool> {
+        self.read_ints(&self.button_values, BUTTONS)
+            .into_iter()
+            .map(|value| value != 0)
+            .collect()
+    }
+
+    /// Checks wether a button is pressed.
+    ///
+    /// # Arguments
+    /// * `index` - a button number.
+    pub fn is_button_pressed(&self, index: usize) -> bool {
+        self.button_values().get(index).copied().unwrap_or(false)
+    }
+
+    fn read_ints(&self, data_ref: &DataRef, max: usize) -> Vec<::std::os::raw::c_int> {
+        let mut values = vec![0; max];
+        let count = get_data_vi(data_ref, 0, &mut values);
+        values.truncate(count);
+        values
+    }
+}
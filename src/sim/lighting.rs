@@ -0,0 +1,178 @@
+use crate::api::data_access::{DataAccessError, DataRefArray};
+
+use super::Result;
+
+/// An instrument brightness rheostat.
+///
+/// X-Plane exposes 32 rheostats, aircraft authors assign them to instruments
+/// and lights in Plane Maker. The first two are conventionally used by the pilot
+/// and copilot instrument panels, the rest are aircraft specific.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum InstrumentBus {
+    /// Pilot side instruments, rheostat 0.
+    Pilot,
+    /// Copilot side instruments, rheostat 1.
+    Copilot,
+    /// Any other rheostat by its zero based index.
+    Rheostat(usize),
+}
+
+impl InstrumentBus {
+    /// Returns the rheostat index in the brightness data ref.
+    pub fn index(&self) -> usize {
+        match self {
+            Self::Pilot => 0,
+            Self::Copilot => 1,
+            Self::Rheostat(index) => *index,
+        }
+    }
+}
+
+/// A panel light.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PanelLight {
+    /// Overall panel flood light.
+    Flood,
+    /// The first panel spot light.
+    Spot1,
+    /// The second panel spot light.
+    Spot2,
+    /// The third panel spot light.
+    Spot3,
+}
+
+impl PanelLight {
+    /// Returns the light index in the brightness data ref.
+    pub fn index(&self) -> usize {
+        *self as usize
+    }
+}
+
+/// Instrument brightness rheostats and panel lighting.
+pub struct Lighting {
+    instrument_brightness: DataRefArray<f32>,
+    panel_brightness: DataRefArray<f32>,
+}
+
+impl Lighting {
+    /// Looks up all data refs required to control cockpit lighting.
+    ///
+    /// # Returns
+    /// Returns [`Lighting`] on success. Otherwise returns [`super::SimError`].
+    pub fn new() -> Result<Self> {
+        Ok(Self {
+            instrument_brightness: DataRefArray::find(
+                "sim/cockpit2/switches/instrument_brightness_ratio",
+            )?,
+            panel_brightness: DataRefArray::find("sim/cockpit2/switches/panel_brightness_ratio")?,
+        })
+    }
+
+    /// Reads an instrument rheostat position.
+    ///
+    /// # Arguments
+    /// * `bus` - an instrument rheostat. See [`InstrumentBus`] for more details.
+    ///
+    /// # Returns
+    /// Returns brightness in range 0.0..=1.0 on success. Otherwise returns [`super::SimError`].
+    pub fn instrument_brightness(&self, bus: InstrumentBus) -> Result<f32> {
+        read_ratio(&self.instrument_brightness, bus.index())
+    }
+
+    /// Sets an instrument rheostat position.
+    ///
+    /// # Arguments
+    /// * `bus` - an instrument rheostat. See [`InstrumentBus`] for more details.
+    /// * `ratio` - brightness, clamped to range 0.0..=1.0.
+    ///
+    /// # Returns
+    /// Returns empty result on success. Otherwise returns [`super::SimError`].
+    pub fn set_instrument_brightness(&self, bus: InstrumentBus, ratio: f32) -> Result<()> {
+        write_ratio(&self.instrument_brightness, bus.index(), ratio)
+    }
+
+    /// Moves an instrument rheostat, e.g. by a hardware encoder detent.
+    ///
+    /// # Arguments
+    /// * `bus` - an instrument rheostat. See [`InstrumentBus`] for more details.
+    /// * `delta` - a brightness change, negative values dim the lights.
+    ///
+    /// # Returns
+    /// Returns the new brightness on success. Otherwise returns [`super::SimError`].
+    pub fn adjust_instrument_brightness(&self, bus: InstrumentBus, delta: f32) -> Result<f32> {
+        adjust_ratio(&self.instrument_brightness, bus.index(), delta)
+    }
+
+    /// Reads all instrument rheostat positions.
+    ///
+    /// # Returns
+    /// Returns brightness in range 0.0..=1.0 for every rheostat.
+    pub fn instrument_brightness_all(&self) -> Vec<f32> {
+        self.instrument_brightness.iter().map(normalize).collect()
+    }
+
+    /// Reads a panel light brightness.
+    ///
+    /// # Arguments
+    /// * `light` - a panel light. See [`PanelLight`] for more details.
+    ///
+    /// # Returns
+    /// Returns brightness in range 0.0..=1.0 on success. Otherwise returns [`super::SimError`].
+    pub fn panel_brightness(&self, light: PanelLight) -> Result<f32> {
+        read_ratio(&self.panel_brightness, light.index())
+    }
+
+    /// Sets a panel light brightness.
+    ///
+    /// # Arguments
+    /// * `light` - a panel light. See [`PanelLight`] for more details.
+    /// * `ratio` - brightness, clamped to range 0.0..=1.0.
+    ///
+    /// # Returns
+    /// Returns empty result on success. Otherwise returns [`super::SimError`].
+    pub fn set_panel_brightness(&self, light: PanelLight, ratio: f32) -> Result<()> {
+        write_ratio(&self.panel_brightness, light.index(), ratio)
+    }
+
+    /// Moves a panel light rheostat, e.g. by a hardware encoder detent.
+    ///
+    /// # Arguments
+    /// * `light` - a panel light. See [`PanelLight`] for more details.
+    /// * `delta` - a brightness change, negative values dim the light.
+    ///
+    /// # Returns
+    /// Returns the new brightness on success. Otherwise returns [`super::SimError`].
+    pub fn adjust_panel_brightness(&self, light: PanelLight, delta: f32) -> Result<f32> {
+        adjust_ratio(&self.panel_brightness, light.index(), delta)
+    }
+}
+
+fn read_ratio(array: &DataRefArray<f32>, index: usize) -> Result<f32> {
+    let mut value = [0.0];
+    if array.read_at(index, &mut value)? == 0 {
+        let len = array.len();
+        return Err(DataAccessError::OffsetOutOfBounds {
+            offset: index,
+            count: 1,
+            len,
+        }
+        .into());
+    }
+
+    Ok(normalize(value[0]))
+}
+
+fn write_ratio(array: &DataRefArray<f32>, index: usize, ratio: f32) -> Result<()> {
+    array.write_at(index, &[normalize(ratio)])?;
+    Ok(())
+}
+
+fn adjust_ratio(array: &DataRefArray<f32>, index: usize, delta: f32) -> Result<f32> {
+    let ratio = normalize(read_ratio(array, index)? + delta);
+    write_ratio(array, index, ratio)?;
+    Ok(ratio)
+}
+
+fn normalize(value: f32) -> f32 {
+    value.clamp(0.0, 1.0)
+}
@@ -0,0 +1,122 @@
+use std::ffi;
+
+use crate::api::backend::backend;
+use crate::api::graphics::{Heading, WorldPosition};
+use crate::api::utilities::{require_xplm_version, XplmVersion};
+
+use super::{Result, SimError};
+
+const METERS_PER_SECOND_IN_KNOT: f32 = 0.514_444;
+
+/// A request to place the user's aircraft at an arbitrary location.
+///
+/// The aircraft is placed with its engines running and the given speed,
+/// which makes it possible to start a flight in the air.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PlacementRequest {
+    /// The aircraft position. The altitude is in meters MSL.
+    pub position: WorldPosition,
+    /// The aircraft heading.
+    pub heading: Heading,
+    /// The aircraft speed in meters per second.
+    pub speed: f32,
+}
+
+impl PlacementRequest {
+    /// Creates a new placement request with a north heading and zero speed.
+    ///
+    /// # Arguments
+    /// * `position` - a position to place the aircraft at. The altitude is in meters MSL.
+    ///
+    /// # Returns
+    /// Returns a new [`PlacementRequest`] instance.
+    pub fn new(position: WorldPosition) -> Self {
+        Self {
+            position,
+            heading: Heading::True(0.0),
+            speed: 0.0,
+        }
+    }
+
+    /// Sets the aircraft heading.
+    ///
+    /// # Arguments
+    /// * `value` - a heading to set. A magnetic heading is converted to a true heading
+    ///   when the request is executed.
+    ///
+    /// # Returns
+    /// Returns a modified placement request with new heading.
+    pub fn heading(mut self, value: Heading) -> Self {
+        self.heading = value;
+        self
+    }
+
+    /// Sets the aircraft speed.
+    ///
+    /// # Arguments
+    /// * `value` - a speed in meters per second to set.
+    ///
+    /// # Returns
+    /// Returns a modified placement request with new speed.
+    pub fn speed(mut self, value: f32) -> Self {
+        self.speed = value;
+        self
+    }
+
+    /// Sets the aircraft speed in knots.
+    ///
+    /// # Arguments
+    /// * `value` - a speed in knots to set.
+    ///
+    /// # Returns
+    /// Returns a modified placement request with new speed.
+    pub fn speed_knots(self, value: f32) -> Self {
+        self.speed(value * METERS_PER_SECOND_IN_KNOT)
+    }
+}
+
+/// Places the user's aircraft at the location described by the request.
+/// Requires X-Plane 11.50 or newer.
+///
+/// # Arguments
+/// * `request` - a placement request.
+///
+/// # Returns
+/// Returns empty result on success. Otherwise returns [`SimError`].
+pub fn place_user_at_location(request: &PlacementRequest) -> Result<()> {
+    let position = &request.position;
+    if !(-90.0..=90.0).contains(&position.latitude)
+        || !(-180.0..=180.0).contains(&position.longitude)
+        || !request.speed.is_finite()
+        || request.speed < 0.0
+    {
+        return Err(SimError::InvalidPlacement(*request));
+    }
+
+    require_xplm_version(XplmVersion::V303)?;
+    backend().place_user_at_location(
+        position.latitude,
+        position.longitude,
+        position.altitude as f32,
+        request.heading.to_true().degrees(),
+        request.speed,
+    );
+
+    Ok(())
+}
+
+/// Places the user's aircraft at an airport. The aircraft is placed on the
+/// default runway with its engines running.
+///
+/// # Arguments
+/// * `icao` - an airport ICAO code.
+///
+/// # Returns
+/// Returns empty result on success. Otherwise returns [`SimError`].
+pub fn place_user_at_airport<T: AsRef<str>>(icao: T) -> Result<()> {
+    let icao = icao.as_ref();
+    let icao_c =
+        ffi::CString::new(icao).map_err(|_| SimError::InvalidAirportCode(icao.to_string()))?;
+    backend().place_user_at_airport(&icao_c);
+    Ok(())
+}
@@ -0,0 +1,281 @@
+use crate::api::data_access::DataRefValue;
+use crate::api::utilities::{command_once, find_command};
+
+use super::{Result, SimError};
+
+/// A cockpit radio.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Radio {
+    /// The first communication radio.
+    Com1,
+    /// The second communication radio.
+    Com2,
+    /// The first navigation radio.
+    Nav1,
+    /// The second navigation radio.
+    Nav2,
+    /// The first automatic direction finder.
+    Adf1,
+    /// The second automatic direction finder.
+    Adf2,
+}
+
+impl Radio {
+    const ALL: [Radio; 6] = [
+        Self::Com1,
+        Self::Com2,
+        Self::Nav1,
+        Self::Nav2,
+        Self::Adf1,
+        Self::Adf2,
+    ];
+
+    fn index(&self) -> usize {
+        *self as usize
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Com1 => "com1",
+            Self::Com2 => "com2",
+            Self::Nav1 => "nav1",
+            Self::Nav2 => "nav2",
+            Self::Adf1 => "adf1",
+            Self::Adf2 => "adf2",
+        }
+    }
+
+    /// Returns a data ref suffix and a number of kHz in a data ref unit.
+    fn frequency_format(&self) -> (&'static str, u32) {
+        match self {
+            Self::Com1 | Self::Com2 => ("frequency_hz_833", 1),
+            Self::Nav1 | Self::Nav2 => ("frequency_hz", 10),
+            Self::Adf1 | Self::Adf2 => ("frequency_hz", 1),
+        }
+    }
+}
+
+/// A radio frequency stored in kHz.
+///
+/// COM frequencies use 8.33 kHz channel names, e.g. 118.005 MHz is stored as 118005 kHz,
+/// which is the same representation X-Plane uses in the `*_833` data refs.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Frequency(u32);
+
+impl Frequency {
+    /// Creates a frequency from kHz.
+    pub fn from_khz(value: u32) -> Self {
+        Self(value)
+    }
+
+    /// Creates a frequency from MHz rounding it to the nearest kHz.
+    pub fn from_mhz(value: f64) -> Self {
+        Self((value * 1000.0).round() as u32)
+    }
+
+    /// Returns the frequency in kHz.
+    pub fn khz(&self) -> u32 {
+        self.0
+    }
+
+    /// Returns the frequency in MHz.
+    pub fn mhz(&self) -> f64 {
+        self.0 as f64 / 1000.0
+    }
+
+    /// Checks wether the frequency is a valid 25 kHz spaced channel.
+    pub fn is_25_khz_channel(&self) -> bool {
+        self.0 % 25 == 0
+    }
+
+    /// Checks wether the frequency is a valid 8.33 kHz channel name.
+    /// Channel names end with .x00, .x05, .x10, .x15, .x25, .x30, .x35, .x40, .x50,
+    /// .x55, .x60, .x65, .x75, .x80, .x85 or .x90.
+    pub fn is_833_khz_channel(&self) -> bool {
+        let block = self.0 % 25;
+        self.0 % 5 == 0 && block != 20
+    }
+
+    /// Returns the frequency the 8.33 kHz channel name refers to, in Hz.
+    ///
+    /// # Returns
+    /// Returns the frequency in Hz or [`None`] if the frequency is not a valid channel name.
+    pub fn channel_to_hz(&self) -> Option<u64> {
+        if !self.is_833_khz_channel() {
+            return None;
+        }
+
+        let block_start = (self.0 - self.0 % 25) as u64 * 1000;
+        let offset_hz = match self.0 % 25 {
+            0 | 5 => 0,
+            10 => 8_333,
+            15 => 16_667,
+            _ => return None,
+        };
+        Some(block_start + offset_hz)
+    }
+}
+
+/// A transponder mode.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TransponderMode {
+    Off,
+    Standby,
+    On,
+    Alt,
+    Test,
+    Ground,
+    /// A mode which is not known to this library.
+    Other(::std::os::raw::c_int),
+}
+
+impl From<::std::os::raw::c_int> for TransponderMode {
+    fn from(value: ::std::os::raw::c_int) -> Self {
+        match value {
+            0 => Self::Off,
+            1 => Self::Standby,
+            2 => Self::On,
+            3 => Self::Alt,
+            4 => Self::Test,
+            5 => Self::Ground,
+            _ => Self::Other(value),
+        }
+    }
+}
+
+impl From<TransponderMode> for ::std::os::raw::c_int {
+    fn from(value: TransponderMode) -> Self {
+        match value {
+            TransponderMode::Off => 0,
+            TransponderMode::Standby => 1,
+            TransponderMode::On => 2,
+            TransponderMode::Alt => 3,
+            TransponderMode::Test => 4,
+            TransponderMode::Ground => 5,
+            TransponderMode::Other(value) => value,
+        }
+    }
+}
+
+/// Cockpit radios built on the standard data refs and commands.
+///
+/// Data refs are looked up on first use and cached afterwards.
+pub struct Radios {
+    active: Vec<DataRefValue<::std::os::raw::c_int>>,
+    standby: Vec<DataRefValue<::std::os::raw::c_int>>,
+    transponder_code: DataRefValue<::std::os::raw::c_int>,
+    transponder_mode: DataRefValue<::std::os::raw::c_int>,
+}
+
+impl Radios {
+    /// Creates a new radios façade.
+    ///
+    /// # Returns
+    /// Returns a new [`Radios`] instance.
+    pub fn new() -> Self {
+        let data_ref = |radio: &Radio, prefix: &str| {
+            let (suffix, _) = radio.frequency_format();
+            DataRefValue::new(format!(
+                "sim/cockpit2/radios/actuators/{}_{}{}",
+                radio.name(),
+                prefix,
+                suffix
+            ))
+        };
+
+        Self {
+            active: Radio::ALL.iter().map(|radio| data_ref(radio, "")).collect(),
+            standby: Radio::ALL
+                .iter()
+                .map(|radio| data_ref(radio, "standby_"))
+                .collect(),
+            transponder_code: DataRefValue::new("sim/cockpit2/radios/actuators/transponder_code"),
+            transponder_mode: DataRefValue::new("sim/cockpit2/radios/actuators/transponder_mode"),
+        }
+    }
+
+    /// Reads the active frequency of a radio.
+    pub fn active_frequency(&self, radio: Radio) -> Result<Frequency> {
+        read_frequency(&self.active[radio.index()], radio)
+    }
+
+    /// Sets the active frequency of a radio.
+    pub fn set_active_frequency(&self, radio: Radio, frequency: Frequency) -> Result<()> {
+        write_frequency(&self.active[radio.index()], radio, frequency)
+    }
+
+    /// Reads the standby frequency of a radio.
+    pub fn standby_frequency(&self, radio: Radio) -> Result<Frequency> {
+        read_frequency(&self.standby[radio.index()], radio)
+    }
+
+    /// Sets the standby frequency of a radio.
+    pub fn set_standby_frequency(&self, radio: Radio, frequency: Frequency) -> Result<()> {
+        write_frequency(&self.standby[radio.index()], radio, frequency)
+    }
+
+    /// Swaps the active and standby frequencies using the radio flip command
+    /// so the aircraft systems see a regular swap.
+    pub fn swap(&self, radio: Radio) -> Result<()> {
+        let name = format!("sim/radios/{}_standy_flip", radio.name());
+        let command = find_command(name.as_str())?.ok_or(SimError::CommandNotFound(name))?;
+        command_once(&command);
+        Ok(())
+    }
+
+    /// Reads the transponder code, e.g. 7000.
+    pub fn transponder_code(&self) -> Result<u16> {
+        Ok(self.transponder_code.get()? as u16)
+    }
+
+    /// Sets the transponder code.
+    ///
+    /// # Arguments
+    /// * `code` - a transponder code, every digit must be in range 0..=7.
+    ///
+    /// # Returns
+    /// Returns empty result on success. Otherwise returns [`SimError::InvalidTransponderCode`]
+    /// if the code contains invalid digits.
+    pub fn set_transponder_code(&self, code: u16) -> Result<()> {
+        let digits = [code / 1000, code / 100 % 10, code / 10 % 10, code % 10];
+        if code > 7777 || digits.iter().any(|digit| *digit > 7) {
+            return Err(SimError::InvalidTransponderCode(code));
+        }
+
+        Ok(self.transponder_code.set(code as _)?)
+    }
+
+    /// Reads the transponder mode.
+    pub fn transponder_mode(&self) -> Result<TransponderMode> {
+        Ok(TransponderMode::from(self.transponder_mode.get()?))
+    }
+
+    /// Sets the transponder mode.
+    pub fn set_transponder_mode(&self, mode: TransponderMode) -> Result<()> {
+        Ok(self.transponder_mode.set(mode.into())?)
+    }
+}
+
+impl Default for Radios {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn read_frequency(
+    data_ref: &DataRefValue<::std::os::raw::c_int>,
+    radio: Radio,
+) -> Result<Frequency> {
+    let (_, scale) = radio.frequency_format();
+    let value = data_ref.get()?.max(0) as u32;
+    Ok(Frequency::from_khz(value * scale))
+}
+
+fn write_frequency(
+    data_ref: &DataRefValue<::std::os::raw::c_int>,
+    radio: Radio,
+    frequency: Frequency,
+) -> Result<()> {
+    let (_, scale) = radio.frequency_format();
+    Ok(data_ref.set((frequency.khz() / scale) as _)?)
+}
@@ -0,0 +1,133 @@
+use crate::api::data_access::{find_data_ref, get_data_f, get_data_vf, DataRef};
+
+use super::Result;
+
+/// Maximum number of wing elements reported by the flight model.
+const WING_ELEMENTS: usize = 32;
+/// Maximum number of doors reported by the flight model.
+const DOORS: usize = 10;
+
+/// An aircraft control surface.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ControlSurface {
+    /// Ailerons.
+    Aileron,
+    /// Elevators.
+    Elevator,
+    /// Rudders.
+    Rudder,
+    /// Trailing edge flaps.
+    Flap,
+    /// Spoilers.
+    Spoiler,
+    /// Speed brakes.
+    Speedbrake,
+}
+
+/// Aircraft control surfaces, high lift devices and doors state.
+pub struct Surfaces {
+    aileron_deg: DataRef,
+    elevator_deg: DataRef,
+    rudder_deg: DataRef,
+    flap_deg: DataRef,
+    spoiler_deg: DataRef,
+    speedbrake_deg: DataRef,
+    flap_ratio: DataRef,
+    slat_ratio: DataRef,
+    speedbrake_ratio: DataRef,
+    door_ratio: DataRef,
+}
+
+impl Surfaces {
+    /// Looks up all data refs required to read surfaces state.
+    ///
+    /// # Returns
+    /// Returns [`Surfaces`] on success. Otherwise returns [`super::SimError`].
+    pub fn new() -> Result<Self> {
+        Ok(Self {
+            aileron_deg: find_data_ref("sim/flightmodel2/wing/aileron1_deg")?,
+            elevator_deg: find_data_ref("sim/flightmodel2/wing/elevator1_deg")?,
+            rudder_deg: find_data_ref("sim/flightmodel2/wing/rudder1_deg")?,
+            flap_deg: find_data_ref("sim/flightmodel2/wing/flap1_deg")?,
+            spoiler_deg: find_data_ref("sim/flightmodel2/wing/spoiler1_deg")?,
+            speedbrake_deg: find_data_ref("sim/flightmodel2/wing/speedbrake1_deg")?,
+            flap_ratio: find_data_ref("sim/flightmodel2/controls/flap1_deploy_ratio")?,
+            slat_ratio: find_data_ref("sim/flightmodel2/controls/slat1_deploy_ratio")?,
+            speedbrake_ratio: find_data_ref("sim/flightmodel2/controls/speedbrake_ratio")?,
+            door_ratio: find_data_ref("sim/flightmodel2/misc/door_open_ratio")?,
+        })
+    }
+
+    /// Reads surface deflections of every wing element.
+    ///
+    /// # Arguments
+    /// * `surface` - a control surface. See [`ControlSurface`] for more details.
+    ///
+    /// # Returns
+    /// Returns deflections in degrees, one value per wing element.
+    pub fn deflection_deg(&self, surface: ControlSurface) -> Vec<f32> {
+        let data_ref = match surface {
+            ControlSurface::Aileron => &self.aileron_deg,
+            ControlSurface::Elevator => &self.elevator_deg,
+            ControlSurface::Rudder => &self.rudder_deg,
+            ControlSurface::Flap => &self.flap_deg,
+            ControlSurface::Spoiler => &self.spoiler_deg,
+            ControlSurface::Speedbrake => &self.speedbrake_deg,
+        };
+
+        let mut values = vec![0.0; WING_ELEMENTS];
+        let count = get_data_vf(data_ref, 0, &mut values);
+        values.truncate(count);
+        values
+    }
+
+    /// Reads flaps deployment.
+    ///
+    /// # Returns
+    /// Returns flaps deployment in range 0.0..=1.0.
+    pub fn flap_ratio(&self) -> f32 {
+        normalize(get_data_f(&self.flap_ratio))
+    }
+
+    /// Reads slats deployment.
+    ///
+    /// # Returns
+    /// Returns slats deployment in range 0.0..=1.0.
+    pub fn slat_ratio(&self) -> f32 {
+        normalize(get_data_f(&self.slat_ratio))
+    }
+
+    /// Reads spoilers and speed brakes deployment.
+    ///
+    /// # Returns
+    /// Returns speed brakes deployment in range 0.0..=1.0.
+    pub fn speedbrake_ratio(&self) -> f32 {
+        normalize(get_data_f(&self.speedbrake_ratio))
+    }
+
+    /// Reads doors opening state.
+    ///
+    /// # Returns
+    /// Returns opening ratio in range 0.0..=1.0 for every door.
+    pub fn door_ratios(&self) -> Vec<f32> {
+        let mut values = vec![0.0; DOORS];
+        let count = get_data_vf(&self.door_ratio, 0, &mut values);
+        values.truncate(count);
+        values.into_iter().map(normalize).collect()
+    }
+
+    /// Reads a single door opening state.
+    ///
+    /// # Arguments
+    /// * `index` - a zero based door index.
+    ///
+    /// # Returns
+    /// Returns opening ratio in range 0.0..=1.0 or [`None`] if the door does not exist.
+    pub fn door_ratio(&self, index: usize) -> Option<f32> {
+        self.door_ratios().get(index).copied()
+    }
+}
+
+fn normalize(value: f32) -> f32 {
+    value.clamp(0.0, 1.0)
+}
@@ -0,0 +1,150 @@
+use crate::api::backend::backend;
+use crate::api::data_access::{find_data_ref, set_data_i, set_data_vf, set_data_vi, DataRef};
+use crate::api::graphics::world_to_local;
+use crate::api::plugin::Message;
+
+use super::traffic::{TrafficTarget, MAX_TARGETS};
+use super::{Result, SimError};
+
+/// A source of traffic targets published through the TCAS override.
+pub trait TrafficProvider: 'static {
+    /// Returns traffic targets to publish in the current frame.
+    /// At most 63 targets are published, target indices are ignored.
+    fn targets(&mut self) -> Vec<TrafficTarget>;
+
+    /// Called when another plugin asks to take over AI planes.
+    ///
+    /// # Returns
+    /// Returns `true` to release the planes. Otherwise returns `false`.
+    fn release_requested(&mut self) -> bool {
+        false
+    }
+}
+
+/// Publishes traffic through the TCAS override data refs available in X-Plane 11.50 and later.
+///
+/// The injector acquires AI planes on creation and releases them when dropped.
+pub struct TrafficInjector<P: TrafficProvider> {
+    provider: P,
+    is_active: bool,
+    override_tcas: DataRef,
+    count: DataRef,
+    mode_s_id: DataRef,
+    x: DataRef,
+    y: DataRef,
+    z: DataRef,
+    heading: DataRef,
+    vx: DataRef,
+    vy: DataRef,
+    vz: DataRef,
+}
+
+impl<P: TrafficProvider> TrafficInjector<P> {
+    /// Acquires AI planes and enables the TCAS override.
+    ///
+    /// # Arguments
+    /// * `provider` - a traffic provider. See [`TrafficProvider`] for more details.
+    ///
+    /// # Returns
+    /// Returns [`TrafficInjector`] on success. Otherwise returns [`SimError::PlanesNotAvailable`]
+    /// if another plugin controls AI planes or other [`SimError`].
+    pub fn claim(provider: P) -> Result<Self> {
+        let mut injector = Self {
+            provider,
+            is_active: false,
+            override_tcas: find_data_ref("sim/operation/override/override_TCAS")?,
+            count: find_data_ref("sim/cockpit2/tcas/indicators/tcas_num_acf")?,
+            mode_s_id: find_data_ref("sim/cockpit2/tcas/targets/modeS_id")?,
+            x: find_data_ref("sim/cockpit2/tcas/targets/position/x")?,
+            y: find_data_ref("sim/cockpit2/tcas/targets/position/y")?,
+            z: find_data_ref("sim/cockpit2/tcas/targets/position/z")?,
+            heading: find_data_ref("sim/cockpit2/tcas/targets/position/psi")?,
+            vx: find_data_ref("sim/cockpit2/tcas/targets/position/vx")?,
+            vy: find_data_ref("sim/cockpit2/tcas/targets/position/vy")?,
+            vz: find_data_ref("sim/cockpit2/tcas/targets/position/vz")?,
+        };
+
+        if !backend().acquire_planes() {
+            return Err(SimError::PlanesNotAvailable);
+        }
+
+        set_data_i(&injector.override_tcas, 1);
+        injector.is_active = true;
+        Ok(injector)
+    }
+
+    /// Checks wether the injector controls the TCAS targets.
+    pub fn is_active(&self) -> bool {
+        self.is_active
+    }
+
+    /// Publishes the provider targets. Should be called once per frame, e.g. from a flight loop.
+    pub fn update(&mut self) {
+        if !self.is_active {
+            return;
+        }
+
+        let targets = self.provider.targets();
+        let targets = &targets[..targets.len().min(MAX_TARGETS - 1)];
+        let mut mode_s_id = Vec::with_capacity(targets.len());
+        let mut x = Vec::with_capacity(targets.len());
+        let mut y = Vec::with_capacity(targets.len());
+        let mut z = Vec::with_capacity(targets.len());
+        let mut heading = Vec::with_capacity(targets.len());
+        let mut vx = Vec::with_capacity(targets.len());
+        let mut vy = Vec::with_capacity(targets.len());
+        let mut vz = Vec::with_capacity(targets.len());
+
+        for target in targets {
+            let local = world_to_local(&target.position);
+            let heading_rad = target.heading.to_true().degrees().to_radians();
+            mode_s_id.push(target.mode_s_id);
+            x.push(local.x as f32);
+            y.push(local.y as f32);
+            z.push(local.z as f32);
+            heading.push(target.heading.to_true().degrees());
+            vx.push(target.ground_speed * heading_rad.sin());
+            vy.push(target.vertical_speed);
+            vz.push(-target.ground_speed * heading_rad.cos());
+        }
+
+        set_data_vi(&self.mode_s_id, 1, &mode_s_id);
+        set_data_vf(&self.x, 1, &x);
+        set_data_vf(&self.y, 1, &y);
+        set_data_vf(&self.z, 1, &z);
+        set_data_vf(&self.heading, 1, &heading);
+        set_data_vf(&self.vx, 1, &vx);
+        set_data_vf(&self.vy, 1, &vy);
+        set_data_vf(&self.vz, 1, &vz);
+        set_data_i(&self.count, targets.len() as ::std::os::raw::c_int + 1);
+    }
+
+    /// Handles a plugin message. Should be called from [`crate::plugin::XPlugin::receive_message`].
+    /// Releases the planes if another plugin asks for them and the provider agrees.
+    ///
+    /// # Arguments
+    /// * `message` - the received message.
+    pub fn handle_message(&mut self, message: &Message) {
+        if matches!(message, Message::ReleasePlanes)
+            && self.is_active
+            && self.provider.release_requested()
+        {
+            self.release();
+        }
+    }
+
+    /// Disables the TCAS override and releases AI planes.
+    pub fn release(&mut self) {
+        if self.is_active {
+            set_data_i(&self.override_tcas, 0);
+            backend().release_planes();
+            self.is_active = false;
+        }
+    }
+}
+
+impl<P: TrafficProvider> Drop for TrafficInjector<P> {
+    fn drop(&mut self) {
+        self.release();
+    }
+}
@@ -0,0 +1,236 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use crate::api::data_access::DataRefValue;
+use crate::api::processing::{get_cycle_number, get_elapsed_time};
+
+use super::Result;
+
+/// Default number of frames kept for statistics.
+pub const DEFAULT_WINDOW: usize = 120;
+
+/// Collects frame timing statistics over a sliding window of frames.
+///
+/// Call [`FrameTiming::sample`] once per frame, e.g. from a flight loop or a draw callback.
+/// Repeated calls within the same sim cycle are ignored.
+pub struct FrameTiming {
+    frame_period: DataRefValue<f32>,
+    window: usize,
+    samples: VecDeque<f32>,
+    last_cycle: Option<::std::os::raw::c_int>,
+    started_at: f32,
+}
+
+impl FrameTiming {
+    /// Creates frame timing statistics with the default window size.
+    ///
+    /// # Returns
+    /// Returns a new [`FrameTiming`] instance.
+    pub fn new() -> Self {
+        Self::with_window(DEFAULT_WINDOW)
+    }
+
+    /// Creates frame timing statistics with a custom window size.
+    ///
+    /// # Arguments
+    /// * `window` - a number of frames to keep, at least one frame is kept.
+    ///
+    /// # Returns
+    /// Returns a new [`FrameTiming`] instance.
+    pub fn with_window(window: usize) -> Self {
+        let window = window.max(1);
+        Self {
+            frame_period: DataRefValue::new("sim/operation/misc/frame_rate_period"),
+            window,
+            samples: VecDeque::with_capacity(window),
+            last_cycle: None,
+            started_at: get_elapsed_time(),
+        }
+    }
+
+    /// Records the current frame period if a new sim cycle has started.
+    ///
+    /// # Returns
+    /// Returns empty result on success. Otherwise returns [`super::SimError`].
+    pub fn sample(&mut self) -> Result<()> {
+        let cycle = get_cycle_number();
+        if self.last_cycle == Some(cycle) {
+            return Ok(());
+        }
+
+        let period = self.frame_period.get()?;
+        self.last_cycle = Some(cycle);
+        if period > 0.0 {
+            if self.samples.len() == self.window {
+                self.samples.pop_front();
+            }
+            self.samples.push_back(period);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the number of recorded frames.
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Checks wether no frames were recorded yet.
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Removes all recorded frames.
+    pub fn reset(&mut self) {
+        self.samples.clear();
+        self.last_cycle = None;
+        self.started_at = get_elapsed_time();
+    }
+
+    /// Returns the wall time in seconds passed since the statistics were created or reset.
+    pub fn elapsed(&self) -> f32 {
+        get_elapsed_time() - self.started_at
+    }
+
+    /// Returns the average frame time over the window.
+    ///
+    /// # Returns
+    /// Returns average frame time in seconds or `None` if no frames were recorded.
+    pub fn average_frame_time(&self) -> Option<f32> {
+        if self.samples.is_empty() {
+            None
+        } else {
+            Some(self.samples.iter().sum::<f32>() / self.samples.len() as f32)
+        }
+    }
+
+    /// Returns the smoothed frame rate over the window.
+    ///
+    /// # Returns
+    /// Returns frames per second or `None` if no frames were recorded.
+    pub fn fps(&self) -> Option<f32> {
+        self.average_frame_time().map(|period| 1.0 / period)
+    }
+
+    /// Returns a frame time percentile over the window using the nearest-rank method.
+    ///
+    /// # Arguments
+    /// * `percentile` - a percentile in range 0.0..=100.0, e.g. 99.0 for the 1% slowest frames.
+    ///
+    /// # Returns
+    /// Returns frame time in seconds or `None` if no frames were recorded.
+    pub fn frame_time_percentile(&self, percentile: f32) -> Option<f32> {
+        if self.samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = self.samples.iter().copied().collect::<Vec<_>>();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        let rank = (percentile.clamp(0.0, 100.0) / 100.0 * sorted.len() as f32).ceil() as usize;
+        sorted.get(rank.saturating_sub(1)).copied()
+    }
+
+    /// Returns the longest frame time over the window.
+    ///
+    /// # Returns
+    /// Returns frame time in seconds or `None` if no frames were recorded.
+    pub fn max_frame_time(&self) -> Option<f32> {
+        self.samples.iter().copied().reduce(f32::max)
+    }
+}
+
+impl Default for FrameTiming {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A time budget for plugin callbacks which warns when a callback runs too long.
+///
+/// ```ignore
+/// let budget = FrameBudget::new("flight loop", Duration::from_millis(2));
+/// let _guard = budget.start();
+/// // callback work
+/// ```
+#[derive(Debug, Clone)]
+pub struct FrameBudget {
+    name: String,
+    budget: Duration,
+}
+
+impl FrameBudget {
+    /// Creates a new time budget.
+    ///
+    /// # Arguments
+    /// * `name` - a name reported in warnings.
+    /// * `budget` - a maximum allowed duration.
+    ///
+    /// # Returns
+    /// Returns a new [`FrameBudget`] instance.
+    pub fn new<T: Into<String>>(name: T, budget: Duration) -> Self {
+        Self {
+            name: name.into(),
+            budget,
+        }
+    }
+
+    /// Returns the budget name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the maximum allowed duration.
+    pub fn budget(&self) -> Duration {
+        self.budget
+    }
+
+    /// Starts measuring a callback.
+    ///
+    /// # Returns
+    /// Returns [`FrameBudgetGuard`] which checks the budget when dropped.
+    pub fn start(&self) -> FrameBudgetGuard<'_> {
+        FrameBudgetGuard {
+            budget: self,
+            started_at: Instant::now(),
+        }
+    }
+
+    /// Measures a closure against the budget.
+    ///
+    /// # Arguments
+    /// * `f` - a closure to measure.
+    ///
+    /// # Returns
+    /// Returns the closure result.
+    pub fn measure<T, F: FnOnce() -> T>(&self, f: F) -> T {
+        let _guard = self.start();
+        f()
+    }
+}
+
+/// A guard which warns via [`crate::warn!`] on drop if the budget was exceeded.
+pub struct FrameBudgetGuard<'a> {
+    budget: &'a FrameBudget,
+    started_at: Instant,
+}
+
+impl FrameBudgetGuard<'_> {
+    /// Returns the time passed since the guard was created.
+    pub fn elapsed(&self) -> Duration {
+        self.started_at.elapsed()
+    }
+}
+
+impl Drop for FrameBudgetGuard<'_> {
+    fn drop(&mut self) {
+        let elapsed = self.elapsed();
+        if elapsed > self.budget.budget {
+            crate::warn!(
+                "{} took {:.3} ms exceeding the budget of {:.3} ms",
+                self.budget.name,
+                elapsed.as_secs_f64() * 1000.0,
+                self.budget.budget.as_secs_f64() * 1000.0
+            );
+        }
+    }
+}
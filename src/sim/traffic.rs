@@ -0,0 +1,115 @@
+use std::cell::RefCell;
+
+use crate::api::data_access::{find_data_ref, get_data_i, get_data_vf, get_data_vi, DataRef};
+use crate::api::graphics::{Heading, WorldPosition};
+use crate::api::processing::get_cycle_number;
+
+use super::Result;
+
+/// Maximum number of TCAS targets including the user's aircraft.
+pub const MAX_TARGETS: usize = 64;
+
+/// A traffic target reported by X-Plane.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TrafficTarget {
+    /// A target index, zero is the user's aircraft.
+    pub index: usize,
+    /// A Mode S identifier.
+    pub mode_s_id: ::std::os::raw::c_int,
+    /// A world position, the altitude is in meters MSL.
+    pub position: WorldPosition,
+    /// A true heading.
+    pub heading: Heading,
+    /// A ground speed in meters per second.
+    pub ground_speed: f32,
+    /// A vertical speed in meters per second.
+    pub vertical_speed: f32,
+}
+
+/// Reads AI and multiplayer aircraft from the TCAS target data refs
+/// available in X-Plane 11.50 and later.
+///
+/// Targets are read at most once per sim cycle.
+pub struct Traffic {
+    count: DataRef,
+    mode_s_id: DataRef,
+    latitude: DataRef,
+    longitude: DataRef,
+    elevation: DataRef,
+    heading: DataRef,
+    vx: DataRef,
+    vy: DataRef,
+    vz: DataRef,
+    cached: RefCell<Option<(::std::os::raw::c_int, Vec<TrafficTarget>)>>,
+}
+
+impl Traffic {
+    /// Looks up all data refs required to read traffic.
+    ///
+    /// # Returns
+    /// Returns [`Traffic`] on success. Otherwise returns [`super::SimError`].
+    pub fn new() -> Result<Self> {
+        Ok(Self {
+            count: find_data_ref("sim/cockpit2/tcas/indicators/tcas_num_acf")?,
+            mode_s_id: find_data_ref("sim/cockpit2/tcas/targets/modeS_id")?,
+            latitude: find_data_ref("sim/cockpit2/tcas/targets/position/lat")?,
+            longitude: find_data_ref("sim/cockpit2/tcas/targets/position/lon")?,
+            elevation: find_data_ref("sim/cockpit2/tcas/targets/position/ele")?,
+            heading: find_data_ref("sim/cockpit2/tcas/targets/position/psi")?,
+            vx: find_data_ref("sim/cockpit2/tcas/targets/position/vx")?,
+            vy: find_data_ref("sim/cockpit2/tcas/targets/position/vy")?,
+            vz: find_data_ref("sim/cockpit2/tcas/targets/position/vz")?,
+            cached: RefCell::new(None),
+        })
+    }
+
+    /// Reads all traffic targets excluding the user's aircraft.
+    ///
+    /// # Returns
+    /// Returns a list of traffic targets.
+    pub fn targets(&self) -> Vec<TrafficTarget> {
+        let cycle = get_cycle_number();
+        if let Some((cached_cycle, targets)) = self.cached.borrow().as_ref() {
+            if *cached_cycle == cycle {
+                return targets.clone();
+            }
+        }
+
+        let targets = self.read_targets();
+        *self.cached.borrow_mut() = Some((cycle, targets.clone()));
+        targets
+    }
+
+    fn read_targets(&self) -> Vec<TrafficTarget> {
+        let count = (get_data_i(&self.count).max(0) as usize).min(MAX_TARGETS);
+        let read = |data_ref: &DataRef| {
+            let mut values = [0.0; MAX_TARGETS];
+            get_data_vf(data_ref, 0, &mut values);
+            values
+        };
+
+        let mut mode_s_id = [0; MAX_TARGETS];
+        get_data_vi(&self.mode_s_id, 0, &mut mode_s_id);
+        let latitude = read(&self.latitude);
+        let longitude = read(&self.longitude);
+        let elevation = read(&self.elevation);
+        let heading = read(&self.heading);
+        let vx = read(&self.vx);
+        let vy = read(&self.vy);
+        let vz = read(&self.vz);
+
+        (1..count)
+            .map(|index| TrafficTarget {
+                index,
+                mode_s_id: mode_s_id[index],
+                position: WorldPosition::default()
+                    .latitude(latitude[index] as f64)
+                    .longitude(longitude[index] as f64)
+                    .altitude(elevation[index] as f64),
+                heading: Heading::True(heading[index]),
+                ground_speed: vx[index].hypot(vz[index]),
+                vertical_speed: vy[index],
+            })
+            .collect()
+    }
+}
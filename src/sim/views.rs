@@ -0,0 +1,427 @@
+use std::cell::RefCell;
+use std::path::Path;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+
+use crate::api::data_access::DataRefValue;
+use crate::api::processing::{
+    create_flight_loop, schedule_flight_loop, FlightLoopPhase, FlightLoopRecord,
+};
+use crate::api::utilities::{
+    create_command, register_command_handler, CommandExecutionTime, CommandHandler,
+    CommandHandlerRecord, CommandOutcome,
+};
+use crate::prefs::Preferences;
+
+use super::{Result, SimError};
+
+/// A pilot head position and orientation in the 3D cockpit.
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct ViewPreset {
+    /// Head position along the aircraft X axis in meters.
+    pub x: f32,
+    /// Head position along the aircraft Y axis in meters.
+    pub y: f32,
+    /// Head position along the aircraft Z axis in meters.
+    pub z: f32,
+    /// Head heading relative to the aircraft in degrees.
+    pub heading: f32,
+    /// Head pitch in degrees.
+    pub pitch: f32,
+    /// Head roll in degrees.
+    pub roll: f32,
+    /// Horizontal field of view in degrees.
+    pub fov_deg: f32,
+}
+
+impl ViewPreset {
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        let heading_delta = (to.heading - self.heading + 540.0).rem_euclid(360.0) - 180.0;
+        Self {
+            x: lerp(self.x, to.x, t),
+            y: lerp(self.y, to.y, t),
+            z: lerp(self.z, to.z, t),
+            heading: (self.heading + heading_delta * t).rem_euclid(360.0),
+            pitch: lerp(self.pitch, to.pitch, t),
+            roll: lerp(self.roll, to.roll, t),
+            fov_deg: lerp(self.fov_deg, to.fov_deg, t),
+        }
+    }
+
+    fn to_value(&self, name: &str) -> Value {
+        serde_json::json!({
+            "name": name,
+            "x": self.x,
+            "y": self.y,
+            "z": self.z,
+            "heading": self.heading,
+            "pitch": self.pitch,
+            "roll": self.roll,
+            "fov_deg": self.fov_deg,
+        })
+    }
+
+    fn from_value(value: &Value) -> Option<(String, Self)> {
+        let name = value.get("name")?.as_str()?;
+        let field = |key: &str| value.get(key).and_then(Value::as_f64).map(|v| v as f32);
+        let preset = Self {
+            x: field("x")?,
+            y: field("y")?,
+            z: field("z")?,
+            heading: field("heading")?,
+            pitch: field("pitch")?,
+            roll: field("roll")?,
+            fov_deg: field("fov_deg")?,
+        };
+        Some((name.to_string(), preset))
+    }
+}
+
+/// An active transition between two views.
+struct Blend {
+    from: ViewPreset,
+    to: ViewPreset,
+    started: Instant,
+    duration: Duration,
+}
+
+impl Blend {
+    /// Computes the current frame of the transition.
+    ///
+    /// # Returns
+    /// Returns the view to apply and `true` once the transition has finished.
+    fn step(&self) -> (ViewPreset, bool) {
+        let t = if self.duration.is_zero() {
+            1.0
+        } else {
+            (self.started.elapsed().as_secs_f32() / self.duration.as_secs_f32()).min(1.0)
+        };
+
+        let eased = t * t * (3.0 - 2.0 * t);
+        (self.from.lerp(&self.to, eased), t >= 1.0)
+    }
+}
+
+/// The 3D cockpit view data refs.
+struct ViewRefs {
+    head_x: DataRefValue<f32>,
+    head_y: DataRefValue<f32>,
+    head_z: DataRefValue<f32>,
+    head_heading: DataRefValue<f32>,
+    head_pitch: DataRefValue<f32>,
+    head_roll: DataRefValue<f32>,
+    fov: DataRefValue<f32>,
+}
+
+impl ViewRefs {
+    fn capture(&self) -> Result<ViewPreset> {
+        Ok(ViewPreset {
+            x: self.head_x.get()?,
+            y: self.head_y.get()?,
+            z: self.head_z.get()?,
+            heading: self.head_heading.get()?,
+            pitch: self.head_pitch.get()?,
+            roll: self.head_roll.get()?,
+            fov_deg: self.fov.get()?,
+        })
+    }
+
+    fn apply(&self, preset: &ViewPreset) -> Result<()> {
+        self.head_x.set(preset.x)?;
+        self.head_y.set(preset.y)?;
+        self.head_z.set(preset.z)?;
+        self.head_heading.set(preset.heading)?;
+        self.head_pitch.set(preset.pitch)?;
+        self.head_roll.set(preset.roll)?;
+        self.fov.set(preset.fov_deg)?;
+        Ok(())
+    }
+}
+
+/// Named 3D cockpit views persisted as JSON [`Preferences`] in the X-Plane preferences folder.
+/// Available with the `prefs` feature enabled.
+///
+/// Transitions are advanced every frame by a flight loop owned by the presets,
+/// which is destroyed with them.
+pub struct ViewPresets {
+    prefs: Preferences,
+    presets: Vec<(String, ViewPreset)>,
+    refs: Rc<ViewRefs>,
+    blend: Rc<RefCell<Option<Blend>>>,
+    flight_loop: FlightLoopRecord,
+}
+
+impl ViewPresets {
+    /// Loads presets from a file in the X-Plane preferences folder.
+    ///
+    /// # Arguments
+    /// * `file_name` - a file name unique to the plugin, e.g. `my_plugin_views.json`.
+    ///
+    /// # Returns
+    /// Returns [`ViewPresets`] on success. Otherwise returns [`SimError`].
+    pub fn new<F: AsRef<Path>>(file_name: F) -> Result<Self> {
+        Self::with_prefs(Preferences::new(file_name)?)
+    }
+
+    /// Loads presets from a file. A missing file yields no presets.
+    ///
+    /// # Arguments
+    /// * `path` - a presets file path.
+    ///
+    /// # Returns
+    /// Returns [`ViewPresets`] on success. Otherwise returns [`SimError`].
+    pub fn with_path<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::with_prefs(Preferences::with_path(path.as_ref()))
+    }
+
+    fn with_prefs(prefs: Preferences) -> Result<Self> {
+        let presets = prefs
+            .load::<Value>()?
+            .as_ref()
+            .and_then(Value::as_array)
+            .map(|presets| presets.iter().filter_map(ViewPreset::from_value).collect())
+            .unwrap_or_default();
+
+        let refs = Rc::new(ViewRefs {
+            head_x: DataRefValue::new("sim/graphics/view/pilots_head_x"),
+            head_y: DataRefValue::new("sim/graphics/view/pilots_head_y"),
+            head_z: DataRefValue::new("sim/graphics/view/pilots_head_z"),
+            head_heading: DataRefValue::new("sim/graphics/view/pilots_head_psi"),
+            head_pitch: DataRefValue::new("sim/graphics/view/pilots_head_the"),
+            head_roll: DataRefValue::new("sim/graphics/view/pilots_head_phi"),
+            fov: DataRefValue::new("sim/graphics/view/field_of_view_deg"),
+        });
+        let blend = Rc::new(RefCell::new(None::<Blend>));
+        let flight_loop = {
+            let refs = refs.clone();
+            let blend = blend.clone();
+            create_flight_loop(FlightLoopPhase::BeforeFlightModel, move |_| {
+                let mut blend = blend.borrow_mut();
+                let Some((preset, finished)) = blend.as_ref().map(Blend::step) else {
+                    return 0.0;
+                };
+
+                if let Err(err) = refs.apply(&preset) {
+                    crate::error!("{}", err);
+                    *blend = None;
+                    return 0.0;
+                }
+
+                if finished {
+                    *blend = None;
+                    0.0
+                } else {
+                    -1.0
+                }
+            })
+        };
+
+        Ok(Self {
+            prefs,
+            presets,
+            refs,
+            blend,
+            flight_loop,
+        })
+    }
+
+    /// Returns the preset names in the order they were saved.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.presets.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// Returns a preset.
+    ///
+    /// # Arguments
+    /// * `name` - a preset name.
+    ///
+    /// # Returns
+    /// Returns [`ViewPreset`] or [`None`] if the preset does not exist.
+    pub fn get(&self, name: &str) -> Option<&ViewPreset> {
+        self.presets
+            .iter()
+            .find(|(preset, _)| preset == name)
+            .map(|(_, preset)| preset)
+    }
+
+    /// Reads the current 3D cockpit view.
+    ///
+    /// # Returns
+    /// Returns [`ViewPreset`] on success. Otherwise returns [`SimError`].
+    pub fn capture(&self) -> Result<ViewPreset> {
+        self.refs.capture()
+    }
+
+    /// Captures the current view under a name, replacing an existing preset, and
+    /// writes the presets file.
+    ///
+    /// # Arguments
+    /// * `name` - a preset name.
+    ///
+    /// # Returns
+    /// Returns empty result on success. Otherwise returns [`SimError`].
+    pub fn save(&mut self, name: &str) -> Result<()> {
+        let name = name.to_string();
+        let preset = self.capture()?;
+        match self
+            .presets
+            .iter_mut()
+            .find(|(existing, _)| *existing == name)
+        {
+            Some((_, existing)) => *existing = preset,
+            None => self.presets.push((name, preset)),
+        }
+
+        self.store()
+    }
+
+    /// Removes a preset and writes the presets file.
+    ///
+    /// # Arguments
+    /// * `name` - a preset name.
+    ///
+    /// # Returns
+    /// Returns empty result on success. Otherwise returns [`SimError`].
+    pub fn remove(&mut self, name: &str) -> Result<()> {
+        self.presets.retain(|(existing, _)| existing != name);
+        self.store()
+    }
+
+    /// Moves the view to a preset at once, cancelling an active transition.
+    ///
+    /// # Arguments
+    /// * `name` - a preset name.
+    ///
+    /// # Returns
+    /// Returns empty result on success. Otherwise returns [`SimError`].
+    pub fn recall(&mut self, name: &str) -> Result<()> {
+        let preset = *self.find(name)?;
+        self.cancel_blend();
+        self.refs.apply(&preset)
+    }
+
+    /// Starts a smooth transition from the current view to a preset.
+    /// The transition is advanced every frame by the flight loop of the presets.
+    ///
+    /// # Arguments
+    /// * `name` - a preset name.
+    /// * `duration` - a transition duration.
+    ///
+    /// # Returns
+    /// Returns empty result on success. Otherwise returns [`SimError`].
+    pub fn blend_to(&mut self, name: &str, duration: Duration) -> Result<()> {
+        let to = *self.find(name)?;
+        let blend = Blend {
+            from: self.capture()?,
+            to,
+            started: Instant::now(),
+            duration,
+        };
+        let (preset, finished) = blend.step();
+        self.refs.apply(&preset)?;
+        *self.blend.borrow_mut() = (!finished).then_some(blend);
+        if !finished {
+            schedule_flight_loop(&self.flight_loop, -1.0, true);
+        }
+
+        Ok(())
+    }
+
+    /// Checks wether a transition is in progress.
+    pub fn is_blending(&self) -> bool {
+        self.blend.borrow().is_some()
+    }
+
+    /// Stops an active transition, the view stays as it is.
+    pub fn cancel_blend(&mut self) {
+        *self.blend.borrow_mut() = None;
+        schedule_flight_loop(&self.flight_loop, 0.0, true);
+    }
+
+    fn find(&self, name: &str) -> Result<&ViewPreset> {
+        self.get(name)
+            .ok_or_else(|| SimError::ViewPresetNotFound(name.to_string()))
+    }
+
+    fn store(&self) -> Result<()> {
+        let presets = self
+            .presets
+            .iter()
+            .map(|(name, preset)| preset.to_value(name))
+            .collect::<Vec<_>>();
+        Ok(self.prefs.save(&presets)?)
+    }
+}
+
+/// A command handler which recalls a view preset.
+struct RecallHandler {
+    presets: Rc<RefCell<ViewPresets>>,
+    preset: String,
+    duration: Duration,
+}
+
+impl CommandHandler for RecallHandler {
+    fn command_begin(&mut self) -> CommandOutcome {
+        if let Err(err) = self
+            .presets
+            .borrow_mut()
+            .blend_to(&self.preset, self.duration)
+        {
+            crate::error!("{}", err);
+        }
+
+        CommandOutcome::Consume
+    }
+
+    fn command_continue(&mut self) -> CommandOutcome {
+        CommandOutcome::Consume
+    }
+
+    fn command_end(&mut self) -> CommandOutcome {
+        CommandOutcome::Consume
+    }
+}
+
+/// Creates a command which recalls a view preset, e.g. to bind it to a joystick button.
+///
+/// # Arguments
+/// * `presets` - shared view presets.
+/// * `command` - a command name.
+/// * `description` - a command description.
+/// * `preset` - a preset name.
+/// * `duration` - a transition duration, zero moves the view at once.
+///
+/// # Returns
+/// Returns [`CommandHandlerRecord`] which keeps the command handler registered on success.
+/// Otherwise returns [`SimError`].
+pub fn create_recall_command<C, D, P>(
+    presets: Rc<RefCell<ViewPresets>>,
+    command: C,
+    description: D,
+    preset: P,
+    duration: Duration,
+) -> Result<CommandHandlerRecord>
+where
+    C: Into<String>,
+    D: Into<String>,
+    P: Into<String>,
+{
+    let command = create_command(command, description)?;
+    let handler = RecallHandler {
+        presets,
+        preset: preset.into(),
+        duration,
+    };
+
+    Ok(register_command_handler(
+        &command,
+        CommandExecutionTime::BeforeXPlane,
+        handler,
+    ))
+}
+
+fn lerp(from: f32, to: f32, t: f32) -> f32 {
+    from + (to - from) * t
+}
@@ -0,0 +1,638 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use crate::api::data_access::{DataRefArray, DataRefValue};
+use crate::api::processing::{
+    create_flight_loop, schedule_flight_loop, FlightLoopPhase, FlightLoopRecord,
+};
+
+use super::{Result, SimError};
+
+/// Number of cloud layers supported by X-Plane 12.
+pub const CLOUD_LAYERS: usize = 3;
+/// Number of wind layers supported by X-Plane 12.
+pub const WIND_LAYERS: usize = 13;
+/// Standard sea level pressure in hectopascals.
+pub const STANDARD_QNH_HPA: f32 = 1013.25;
+
+const HPA_PER_INHG: f32 = 33.863_89;
+const QNH_RANGE_HPA: std::ops::RangeInclusive<f32> = 870.0..=1085.0;
+
+/// A cloud type.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum CloudType {
+    /// Cirrus clouds.
+    Cirrus = 0,
+    /// Stratus clouds.
+    Stratus = 1,
+    /// Cumulus clouds.
+    #[default]
+    Cumulus = 2,
+    /// Cumulonimbus clouds.
+    Cumulonimbus = 3,
+}
+
+impl From<f32> for CloudType {
+    fn from(value: f32) -> Self {
+        match value.round() as i32 {
+            0 => Self::Cirrus,
+            1 => Self::Stratus,
+            3 => Self::Cumulonimbus,
+            _ => Self::Cumulus,
+        }
+    }
+}
+
+impl From<CloudType> for f32 {
+    fn from(value: CloudType) -> Self {
+        value as i32 as f32
+    }
+}
+
+/// A cloud layer.
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct CloudLayer {
+    /// Cloud base above mean sea level in meters.
+    pub base_msl_m: f32,
+    /// Cloud tops above mean sea level in meters.
+    pub tops_msl_m: f32,
+    /// Sky coverage in range 0.0..=1.0.
+    pub coverage: f32,
+    /// Cloud type.
+    pub cloud_type: CloudType,
+}
+
+impl CloudLayer {
+    /// Creates a cloud layer.
+    ///
+    /// # Arguments
+    /// * `cloud_type` - a cloud type.
+    /// * `base_msl_m` - cloud base above mean sea level in meters.
+    /// * `tops_msl_m` - cloud tops above mean sea level in meters.
+    /// * `coverage` - sky coverage in range 0.0..=1.0.
+    ///
+    /// # Returns
+    /// Returns a new [`CloudLayer`] instance.
+    pub fn new(cloud_type: CloudType, base_msl_m: f32, tops_msl_m: f32, coverage: f32) -> Self {
+        Self {
+            base_msl_m,
+            tops_msl_m,
+            coverage,
+            cloud_type,
+        }
+    }
+
+    fn validate(&self) -> Result<()> {
+        if !(0.0..=1.0).contains(&self.coverage) {
+            return Err(SimError::InvalidWeather("cloud coverage out of range"));
+        }
+
+        if self.base_msl_m < -500.0 || self.tops_msl_m < self.base_msl_m {
+            return Err(SimError::InvalidWeather("cloud tops below cloud base"));
+        }
+
+        Ok(())
+    }
+
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        Self {
+            base_msl_m: lerp(self.base_msl_m, to.base_msl_m, t),
+            tops_msl_m: lerp(self.tops_msl_m, to.tops_msl_m, t),
+            coverage: lerp(self.coverage, to.coverage, t),
+            cloud_type: if t < 0.5 {
+                self.cloud_type
+            } else {
+                to.cloud_type
+            },
+        }
+    }
+}
+
+/// A wind layer.
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct WindLayer {
+    /// Layer altitude above mean sea level in meters.
+    pub altitude_msl_m: f32,
+    /// Wind direction in true degrees the wind is blowing from.
+    pub direction_deg: f32,
+    /// Wind speed in meters per second.
+    pub speed_ms: f32,
+    /// Gust speed increase in meters per second.
+    pub shear_speed_ms: f32,
+    /// Turbulence in range 0.0..=10.0.
+    pub turbulence: f32,
+}
+
+impl WindLayer {
+    /// Creates a calm wind layer without gusts and turbulence.
+    ///
+    /// # Arguments
+    /// * `altitude_msl_m` - layer altitude above mean sea level in meters.
+    /// * `direction_deg` - wind direction in true degrees.
+    /// * `speed_ms` - wind speed in meters per second.
+    ///
+    /// # Returns
+    /// Returns a new [`WindLayer`] instance.
+    pub fn new(altitude_msl_m: f32, direction_deg: f32, speed_ms: f32) -> Self {
+        Self {
+            altitude_msl_m,
+            direction_deg,
+            speed_ms,
+            shear_speed_ms: 0.0,
+            turbulence: 0.0,
+        }
+    }
+
+    /// Sets gust speed increase.
+    ///
+    /// # Arguments
+    /// * `shear_speed_ms` - gust speed increase in meters per second.
+    ///
+    /// # Returns
+    /// Returns a modified wind layer.
+    pub fn gusts(mut self, shear_speed_ms: f32) -> Self {
+        self.shear_speed_ms = shear_speed_ms;
+        self
+    }
+
+    /// Sets turbulence.
+    ///
+    /// # Arguments
+    /// * `turbulence` - turbulence in range 0.0..=10.0.
+    ///
+    /// # Returns
+    /// Returns a modified wind layer.
+    pub fn turbulence(mut self, turbulence: f32) -> Self {
+        self.turbulence = turbulence;
+        self
+    }
+
+    fn validate(&self) -> Result<()> {
+        if !(0.0..360.0).contains(&self.direction_deg) {
+            return Err(SimError::InvalidWeather("wind direction out of range"));
+        }
+
+        if self.speed_ms < 0.0 || self.shear_speed_ms < 0.0 {
+            return Err(SimError::InvalidWeather("negative wind speed"));
+        }
+
+        if !(0.0..=10.0).contains(&self.turbulence) {
+            return Err(SimError::InvalidWeather("turbulence out of range"));
+        }
+
+        Ok(())
+    }
+
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        let delta = (to.direction_deg - self.direction_deg + 540.0) % 360.0 - 180.0;
+        Self {
+            altitude_msl_m: lerp(self.altitude_msl_m, to.altitude_msl_m, t),
+            direction_deg: (self.direction_deg + delta * t).rem_euclid(360.0),
+            speed_ms: lerp(self.speed_ms, to.speed_ms, t),
+            shear_speed_ms: lerp(self.shear_speed_ms, to.shear_speed_ms, t),
+            turbulence: lerp(self.turbulence, to.turbulence, t),
+        }
+    }
+}
+
+/// A set of weather conditions which can be applied to the simulator.
+///
+/// Layers which are not specified keep their current values, except cloud layers
+/// which are cleared.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeatherPreset {
+    clouds: Vec<CloudLayer>,
+    winds: Vec<WindLayer>,
+    visibility_sm: f32,
+    qnh_hpa: f32,
+}
+
+impl Default for WeatherPreset {
+    fn default() -> Self {
+        Self {
+            clouds: Vec::new(),
+            winds: Vec::new(),
+            visibility_sm: 10.0,
+            qnh_hpa: STANDARD_QNH_HPA,
+        }
+    }
+}
+
+impl WeatherPreset {
+    /// Creates a clear sky preset with 10 statute miles visibility and standard pressure.
+    ///
+    /// # Returns
+    /// Returns a new [`WeatherPreset`] instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a cloud layer, at most [`CLOUD_LAYERS`] layers are applied.
+    ///
+    /// # Arguments
+    /// * `layer` - a cloud layer.
+    ///
+    /// # Returns
+    /// Returns a modified preset.
+    pub fn cloud_layer(mut self, layer: CloudLayer) -> Self {
+        self.clouds.push(layer);
+        self
+    }
+
+    /// Adds a wind layer, at most [`WIND_LAYERS`] layers are applied.
+    ///
+    /// # Arguments
+    /// * `layer` - a wind layer.
+    ///
+    /// # Returns
+    /// Returns a modified preset.
+    pub fn wind_layer(mut self, layer: WindLayer) -> Self {
+        self.winds.push(layer);
+        self
+    }
+
+    /// Sets reported visibility.
+    ///
+    /// # Arguments
+    /// * `visibility_sm` - visibility in statute miles.
+    ///
+    /// # Returns
+    /// Returns a modified preset.
+    pub fn visibility_sm(mut self, visibility_sm: f32) -> Self {
+        self.visibility_sm = visibility_sm;
+        self
+    }
+
+    /// Sets sea level pressure.
+    ///
+    /// # Arguments
+    /// * `qnh_hpa` - pressure in hectopascals.
+    ///
+    /// # Returns
+    /// Returns a modified preset.
+    pub fn qnh_hpa(mut self, qnh_hpa: f32) -> Self {
+        self.qnh_hpa = qnh_hpa;
+        self
+    }
+
+    /// Sets sea level pressure.
+    ///
+    /// # Arguments
+    /// * `qnh_inhg` - pressure in inches of mercury.
+    ///
+    /// # Returns
+    /// Returns a modified preset.
+    pub fn qnh_inhg(self, qnh_inhg: f32) -> Self {
+        self.qnh_hpa(qnh_inhg * HPA_PER_INHG)
+    }
+
+    /// Returns cloud layers.
+    pub fn clouds(&self) -> &[CloudLayer] {
+        &self.clouds
+    }
+
+    /// Returns wind layers.
+    pub fn winds(&self) -> &[WindLayer] {
+        &self.winds
+    }
+
+    /// Returns visibility in statute miles.
+    pub fn visibility(&self) -> f32 {
+        self.visibility_sm
+    }
+
+    /// Returns sea level pressure in hectopascals.
+    pub fn qnh(&self) -> f32 {
+        self.qnh_hpa
+    }
+
+    /// Checks wether every value is within the range accepted by X-Plane.
+    ///
+    /// # Returns
+    /// Returns empty result on success. Otherwise returns [`SimError::InvalidWeather`].
+    pub fn validate(&self) -> Result<()> {
+        if self.clouds.len() > CLOUD_LAYERS {
+            return Err(SimError::InvalidWeather("too many cloud layers"));
+        }
+
+        if self.winds.len() > WIND_LAYERS {
+            return Err(SimError::InvalidWeather("too many wind layers"));
+        }
+
+        if self.visibility_sm < 0.0 {
+            return Err(SimError::InvalidWeather("negative visibility"));
+        }
+
+        if !QNH_RANGE_HPA.contains(&self.qnh_hpa) {
+            return Err(SimError::InvalidWeather("sea level pressure out of range"));
+        }
+
+        self.clouds.iter().try_for_each(CloudLayer::validate)?;
+        self.winds.iter().try_for_each(WindLayer::validate)
+    }
+
+    /// Fills missing layers so the preset describes every layer X-Plane has.
+    fn complete(&self, current: &Self) -> Self {
+        let clouds = (0..CLOUD_LAYERS)
+            .map(|index| match self.clouds.get(index) {
+                Some(layer) => *layer,
+                None => CloudLayer {
+                    coverage: 0.0,
+                    ..current.clouds.get(index).copied().unwrap_or_default()
+                },
+            })
+            .collect();
+        let winds = (0..WIND_LAYERS)
+            .map(|index| {
+                self.winds
+                    .get(index)
+                    .or_else(|| current.winds.get(index))
+                    .copied()
+                    .unwrap_or_default()
+            })
+            .collect();
+        Self {
+            clouds,
+            winds,
+            visibility_sm: self.visibility_sm,
+            qnh_hpa: self.qnh_hpa,
+        }
+    }
+
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        Self {
+            clouds: self
+                .clouds
+                .iter()
+                .zip(&to.clouds)
+                .map(|(from, to)| from.lerp(to, t))
+                .collect(),
+            winds: self
+                .winds
+                .iter()
+                .zip(&to.winds)
+                .map(|(from, to)| from.lerp(to, t))
+                .collect(),
+            visibility_sm: lerp(self.visibility_sm, to.visibility_sm, t),
+            qnh_hpa: lerp(self.qnh_hpa, to.qnh_hpa, t),
+        }
+    }
+}
+
+/// An active transition between two presets.
+struct Blend {
+    from: WeatherPreset,
+    to: WeatherPreset,
+    started: Instant,
+    duration: Duration,
+}
+
+impl Blend {
+    /// Computes the current frame of the transition.
+    ///
+    /// # Returns
+    /// Returns the preset to write and `true` once the transition has finished.
+    fn step(&self) -> (WeatherPreset, bool) {
+        let t = if self.duration.is_zero() {
+            1.0
+        } else {
+            (self.started.elapsed().as_secs_f32() / self.duration.as_secs_f32()).min(1.0)
+        };
+
+        (self.from.lerp(&self.to, t), t >= 1.0)
+    }
+}
+
+/// Regional weather data refs.
+struct WeatherRefs {
+    cloud_base: DataRefArray<f32>,
+    cloud_tops: DataRefArray<f32>,
+    cloud_coverage: DataRefArray<f32>,
+    cloud_type: DataRefArray<f32>,
+    wind_altitude: DataRefArray<f32>,
+    wind_direction: DataRefArray<f32>,
+    wind_speed: DataRefArray<f32>,
+    shear_speed: DataRefArray<f32>,
+    turbulence: DataRefArray<f32>,
+    visibility: DataRefValue<f32>,
+    sealevel_pressure: DataRefValue<f32>,
+    update_immediately: DataRefValue<::std::os::raw::c_int>,
+}
+
+impl WeatherRefs {
+    fn find() -> Result<Self> {
+        let refs = Self {
+            cloud_base: DataRefArray::find("sim/weather/region/cloud_base_msl_m")?,
+            cloud_tops: DataRefArray::find("sim/weather/region/cloud_tops_msl_m")?,
+            cloud_coverage: DataRefArray::find("sim/weather/region/cloud_coverage_percent")?,
+            cloud_type: DataRefArray::find("sim/weather/region/cloud_type")?,
+            wind_altitude: DataRefArray::find("sim/weather/region/wind_altitude_msl_m")?,
+            wind_direction: DataRefArray::find("sim/weather/region/wind_direction_degt")?,
+            wind_speed: DataRefArray::find("sim/weather/region/wind_speed_msc")?,
+            shear_speed: DataRefArray::find("sim/weather/region/shear_speed_msc")?,
+            turbulence: DataRefArray::find("sim/weather/region/turbulence")?,
+            visibility: DataRefValue::new("sim/weather/region/visibility_reported_sm"),
+            sealevel_pressure: DataRefValue::new("sim/weather/region/sealevel_pressure_pas"),
+            update_immediately: DataRefValue::new("sim/weather/region/update_immediately"),
+        };
+        refs.visibility.data_ref()?;
+        refs.sealevel_pressure.data_ref()?;
+        refs.update_immediately.data_ref()?;
+        Ok(refs)
+    }
+
+    fn current(&self) -> Result<WeatherPreset> {
+        let base = self.cloud_base.to_vec();
+        let tops = self.cloud_tops.to_vec();
+        let coverage = self.cloud_coverage.to_vec();
+        let cloud_type = self.cloud_type.to_vec();
+        let clouds = (0..CLOUD_LAYERS.min(base.len()))
+            .map(|index| CloudLayer {
+                base_msl_m: base[index],
+                tops_msl_m: value_at(&tops, index),
+                coverage: value_at(&coverage, index),
+                cloud_type: CloudType::from(value_at(&cloud_type, index)),
+            })
+            .collect();
+
+        let altitude = self.wind_altitude.to_vec();
+        let direction = self.wind_direction.to_vec();
+        let speed = self.wind_speed.to_vec();
+        let shear = self.shear_speed.to_vec();
+        let turbulence = self.turbulence.to_vec();
+        let winds = (0..WIND_LAYERS.min(altitude.len()))
+            .map(|index| WindLayer {
+                altitude_msl_m: altitude[index],
+                direction_deg: value_at(&direction, index),
+                speed_ms: value_at(&speed, index),
+                shear_speed_ms: value_at(&shear, index),
+                turbulence: value_at(&turbulence, index),
+            })
+            .collect();
+
+        Ok(WeatherPreset {
+            clouds,
+            winds,
+            visibility_sm: self.visibility.get()?,
+            qnh_hpa: self.sealevel_pressure.get()? / 100.0,
+        })
+    }
+
+    fn write(&self, preset: &WeatherPreset) -> Result<()> {
+        let clouds = &preset.clouds;
+        let winds = &preset.winds;
+        self.cloud_base
+            .write_at(0, &collect(clouds, |layer| layer.base_msl_m))?;
+        self.cloud_tops
+            .write_at(0, &collect(clouds, |layer| layer.tops_msl_m))?;
+        self.cloud_coverage
+            .write_at(0, &collect(clouds, |layer| layer.coverage))?;
+        self.cloud_type
+            .write_at(0, &collect(clouds, |layer| layer.cloud_type.into()))?;
+        self.wind_altitude
+            .write_at(0, &collect(winds, |layer| layer.altitude_msl_m))?;
+        self.wind_direction
+            .write_at(0, &collect(winds, |layer| layer.direction_deg))?;
+        self.wind_speed
+            .write_at(0, &collect(winds, |layer| layer.speed_ms))?;
+        self.shear_speed
+            .write_at(0, &collect(winds, |layer| layer.shear_speed_ms))?;
+        self.turbulence
+            .write_at(0, &collect(winds, |layer| layer.turbulence))?;
+        self.visibility.set(preset.visibility_sm)?;
+        self.sealevel_pressure.set(preset.qnh_hpa * 100.0)?;
+        Ok(())
+    }
+}
+
+/// Writes X-Plane 12 regional weather.
+///
+/// X-Plane overwrites the values when real weather is enabled, so the user should
+/// select manual weather before a preset is applied.
+pub struct WeatherController {
+    refs: Rc<WeatherRefs>,
+    blend: Rc<RefCell<Option<Blend>>>,
+    flight_loop: FlightLoopRecord,
+}
+
+impl WeatherController {
+    /// Looks up all data refs required to write weather and creates the flight loop
+    /// which advances transitions.
+    ///
+    /// # Returns
+    /// Returns [`WeatherController`] on success. Otherwise returns [`super::SimError`],
+    /// e.g. when running in X-Plane 11.
+    pub fn new() -> Result<Self> {
+        let refs = Rc::new(WeatherRefs::find()?);
+        let blend = Rc::new(RefCell::new(None::<Blend>));
+        let flight_loop = {
+            let refs = refs.clone();
+            let blend = blend.clone();
+            create_flight_loop(FlightLoopPhase::BeforeFlightModel, move |_| {
+                let mut blend = blend.borrow_mut();
+                let Some((preset, finished)) = blend.as_ref().map(Blend::step) else {
+                    return 0.0;
+                };
+
+                if let Err(err) = refs.write(&preset) {
+                    crate::error!("{}", err);
+                    *blend = None;
+                    return 0.0;
+                }
+
+                if finished {
+                    *blend = None;
+                    0.0
+                } else {
+                    -1.0
+                }
+            })
+        };
+
+        Ok(Self {
+            refs,
+            blend,
+            flight_loop,
+        })
+    }
+
+    /// Reads the current weather.
+    ///
+    /// # Returns
+    /// Returns a preset describing every cloud and wind layer on success.
+    /// Otherwise returns [`super::SimError`].
+    pub fn current(&self) -> Result<WeatherPreset> {
+        self.refs.current()
+    }
+
+    /// Applies a preset at once, cancelling an active transition.
+    ///
+    /// # Arguments
+    /// * `preset` - a weather preset. See [`WeatherPreset`] for more details.
+    ///
+    /// # Returns
+    /// Returns empty result on success. Otherwise returns [`super::SimError`].
+    pub fn apply(&mut self, preset: &WeatherPreset) -> Result<()> {
+        preset.validate()?;
+        self.cancel_blend();
+        let preset = preset.complete(&self.current()?);
+        self.refs.write(&preset)?;
+        self.refs.update_immediately.set(1)?;
+        Ok(())
+    }
+
+    /// Starts a smooth transition from the current weather to a preset.
+    /// The transition is advanced every frame by a flight loop owned by the controller,
+    /// so the controller has to be kept alive until the transition finishes.
+    ///
+    /// # Arguments
+    /// * `preset` - a weather preset. See [`WeatherPreset`] for more details.
+    /// * `duration` - a transition duration.
+    ///
+    /// # Returns
+    /// Returns empty result on success. Otherwise returns [`super::SimError`].
+    pub fn blend_to(&mut self, preset: &WeatherPreset, duration: Duration) -> Result<()> {
+        preset.validate()?;
+        let current = self.current()?;
+        let from = current.complete(&current);
+        let to = preset.complete(&current);
+        let blend = Blend {
+            from,
+            to,
+            started: Instant::now(),
+            duration,
+        };
+        let (preset, finished) = blend.step();
+        self.refs.write(&preset)?;
+        *self.blend.borrow_mut() = (!finished).then_some(blend);
+        if !finished {
+            schedule_flight_loop(&self.flight_loop, -1.0, true);
+        }
+
+        Ok(())
+    }
+
+    /// Checks wether a transition is in progress.
+    pub fn is_blending(&self) -> bool {
+        self.blend.borrow().is_some()
+    }
+
+    /// Stops an active transition, the weather stays as it is.
+    pub fn cancel_blend(&mut self) {
+        *self.blend.borrow_mut() = None;
+        schedule_flight_loop(&self.flight_loop, 0.0, true);
+    }
+}
+
+fn collect<T, F: Fn(&T) -> f32>(layers: &[T], f: F) -> Vec<f32> {
+    layers.iter().map(f).collect()
+}
+
+fn value_at(values: &[f32], index: usize) -> f32 {
+    values.get(index).copied().unwrap_or_default()
+}
+
+fn lerp(from: f32, to: f32, t: f32) -> f32 {
+    from + (to - from) * t
+}
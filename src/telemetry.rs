@@ -0,0 +1,16 @@
+//! Telemetry export to CSV or JSON Lines files.
+//! Available with the `telemetry` feature enabled.
+//!
+//! Samples are read on the main thread and handed to a worker thread over a bounded
+//! channel, so file IO never blocks the simulator. Samples which don't fit into the
+//! channel are dropped and reported through the log.
+
+pub mod error;
+pub mod recorder;
+pub mod writer;
+
+pub use self::error::TelemetryError;
+pub use self::recorder::{TelemetryRecorder, TelemetrySession};
+pub use self::writer::{Sample, TelemetryFormat, TelemetryWriter, WriterStats};
+
+pub type Result<T> = std::result::Result<T, TelemetryError>;
@@ -0,0 +1,32 @@
+use std::{io, path};
+
+use crate::api::data_access::DataAccessError;
+
+/// An error returned from telemetry export.
+#[derive(thiserror::Error, Debug)]
+pub enum TelemetryError {
+    /// Data access error.
+    #[error("data access error {0}")]
+    DataAccess(DataAccessError),
+    /// Telemetry file can't be created or written.
+    #[error("failed to write {}: {1}", .0.display())]
+    Write(path::PathBuf, io::Error),
+    /// Sample can't be serialized.
+    #[error("failed to serialize sample {0}")]
+    Serialize(serde_json::Error),
+    /// Worker thread can't be started.
+    #[error("failed to start telemetry writer {0}")]
+    Spawn(io::Error),
+    /// Worker thread has stopped.
+    #[error("telemetry writer has stopped")]
+    WriterStopped,
+    /// Sample has a different number of values than there are columns.
+    #[error("expected {expected} sample values, got {actual}")]
+    ColumnMismatch { expected: usize, actual: usize },
+}
+
+impl From<DataAccessError> for TelemetryError {
+    fn from(value: DataAccessError) -> Self {
+        Self::DataAccess(value)
+    }
+}
@@ -0,0 +1,188 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::api::data_access::{
+    find_data_ref, get_data_d, get_data_f, get_data_i, get_data_ref_types, DataRef, DataType,
+};
+use crate::api::processing::get_elapsed_time;
+
+use super::{Result, Sample, TelemetryFormat, TelemetryWriter, WriterStats};
+
+/// Default number of samples which can wait for the writer thread.
+pub const DEFAULT_CAPACITY: usize = 1024;
+/// Minimum interval between two backpressure warnings.
+const BACKPRESSURE_LOG_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Telemetry recording configuration.
+pub struct TelemetryRecorder {
+    path: PathBuf,
+    format: TelemetryFormat,
+    capacity: usize,
+    channels: Vec<(String, String)>,
+}
+
+impl TelemetryRecorder {
+    /// Creates a recorder configuration which writes CSV.
+    ///
+    /// # Arguments
+    /// * `path` - a telemetry file path.
+    ///
+    /// # Returns
+    /// Returns a new [`TelemetryRecorder`] instance.
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            format: TelemetryFormat::default(),
+            capacity: DEFAULT_CAPACITY,
+            channels: Vec::new(),
+        }
+    }
+
+    /// Sets the file format.
+    ///
+    /// # Arguments
+    /// * `format` - a file format. See [`TelemetryFormat`] for more details.
+    ///
+    /// # Returns
+    /// Returns a modified configuration.
+    pub fn format(mut self, format: TelemetryFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Sets the number of samples which can wait for the writer thread.
+    /// Samples are dropped when the writer falls further behind.
+    ///
+    /// # Arguments
+    /// * `capacity` - a queue capacity, see [`DEFAULT_CAPACITY`].
+    ///
+    /// # Returns
+    /// Returns a modified configuration.
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Adds a recorded value.
+    ///
+    /// # Arguments
+    /// * `column` - a column name.
+    /// * `data_ref` - a name of an int, float or double data ref.
+    ///
+    /// # Returns
+    /// Returns a modified configuration.
+    pub fn channel<C: Into<String>, N: Into<String>>(mut self, column: C, data_ref: N) -> Self {
+        self.channels.push((column.into(), data_ref.into()));
+        self
+    }
+
+    /// Looks up the data refs and starts the writer thread.
+    ///
+    /// # Returns
+    /// Returns [`TelemetrySession`] on success. Otherwise returns [`super::TelemetryError`].
+    pub fn start(self) -> Result<TelemetrySession> {
+        let mut columns = Vec::with_capacity(self.channels.len());
+        let mut channels = Vec::with_capacity(self.channels.len());
+        for (column, name) in self.channels {
+            channels.push(Channel::new(find_data_ref(name)?));
+            columns.push(column);
+        }
+
+        let writer = TelemetryWriter::spawn(&self.path, self.format, columns, self.capacity)?;
+        Ok(TelemetrySession {
+            channels,
+            writer,
+            reported_dropped: 0,
+            reported_at: None,
+        })
+    }
+}
+
+/// A recorded data ref.
+struct Channel {
+    data_ref: DataRef,
+    data_type: DataType,
+}
+
+impl Channel {
+    fn new(data_ref: DataRef) -> Self {
+        let types = get_data_ref_types(&data_ref);
+        let data_type = [DataType::Double, DataType::Float, DataType::Int]
+            .into_iter()
+            .find(|data_type| types.contains(*data_type))
+            .unwrap_or(DataType::Float);
+        Self {
+            data_ref,
+            data_type,
+        }
+    }
+
+    fn read(&self) -> f64 {
+        match self.data_type {
+            DataType::Double => get_data_d(&self.data_ref),
+            DataType::Int => get_data_i(&self.data_ref) as f64,
+            _ => get_data_f(&self.data_ref) as f64,
+        }
+    }
+}
+
+/// An active telemetry recording. The file is closed when the session is dropped.
+pub struct TelemetrySession {
+    channels: Vec<Channel>,
+    writer: TelemetryWriter,
+    reported_dropped: u64,
+    reported_at: Option<Instant>,
+}
+
+impl TelemetrySession {
+    /// Reads every channel and queues a sample for the writer thread.
+    /// Should be called at the desired sample rate, e.g. from a flight loop or a draw callback.
+    ///
+    /// # Returns
+    /// Returns `true` if the sample was queued or `false` if it was dropped.
+    /// Otherwise returns [`super::TelemetryError`].
+    pub fn sample(&mut self) -> Result<bool> {
+        let sample = Sample {
+            time: get_elapsed_time() as f64,
+            values: self.channels.iter().map(Channel::read).collect(),
+        };
+        let queued = self.writer.push(sample)?;
+        self.report_backpressure();
+        Ok(queued)
+    }
+
+    /// Returns the writer counters.
+    pub fn stats(&self) -> WriterStats {
+        self.writer.stats()
+    }
+
+    /// Writes the queued samples and closes the file.
+    ///
+    /// # Returns
+    /// Returns the final counters on success. Otherwise returns [`super::TelemetryError`].
+    pub fn finish(self) -> Result<WriterStats> {
+        self.writer.finish()
+    }
+
+    fn report_backpressure(&mut self) {
+        let dropped = self.writer.stats().dropped;
+        if dropped == self.reported_dropped {
+            return;
+        }
+
+        if self
+            .reported_at
+            .is_some_and(|at| at.elapsed() < BACKPRESSURE_LOG_INTERVAL)
+        {
+            return;
+        }
+
+        crate::warn!(
+            "telemetry writer is behind, {} samples dropped ({} in total)",
+            dropped - self.reported_dropped,
+            dropped
+        );
+        self.reported_dropped = dropped;
+        self.reported_at = Some(Instant::now());
+    }
+}
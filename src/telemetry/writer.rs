@@ -0,0 +1,238 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, SyncSender, TryRecvError, TrySendError};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+
+use super::{Result, TelemetryError};
+
+/// A telemetry file format.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum TelemetryFormat {
+    /// Comma separated values with a header row.
+    #[default]
+    Csv,
+    /// One JSON object per line.
+    JsonLines,
+}
+
+/// A single telemetry record.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sample {
+    /// Time the sample was taken at in seconds, e.g. the sim elapsed time.
+    pub time: f64,
+    /// Values in the order of the writer columns.
+    pub values: Vec<f64>,
+}
+
+/// Telemetry writer counters.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct WriterStats {
+    /// The number of samples written to the file.
+    pub written: u64,
+    /// The number of samples dropped because the writer fell behind.
+    pub dropped: u64,
+}
+
+#[derive(Default)]
+struct Counters {
+    written: AtomicU64,
+    dropped: AtomicU64,
+}
+
+/// Writes telemetry samples to a file on a worker thread.
+///
+/// The writer is stopped and the file is flushed when [`TelemetryWriter::finish`]
+/// is called or the writer is dropped.
+pub struct TelemetryWriter {
+    columns: usize,
+    sender: Option<SyncSender<Sample>>,
+    worker: Option<JoinHandle<Result<()>>>,
+    counters: Arc<Counters>,
+}
+
+impl TelemetryWriter {
+    /// Creates a telemetry file and starts the worker thread.
+    ///
+    /// # Arguments
+    /// * `path` - a telemetry file path, an existing file is truncated.
+    /// * `format` - a file format. See [`TelemetryFormat`] for more details.
+    /// * `columns` - column names.
+    /// * `capacity` - the number of samples which can wait for the worker.
+    ///
+    /// # Returns
+    /// Returns [`TelemetryWriter`] on success. Otherwise returns [`TelemetryError`].
+    pub fn spawn<P: AsRef<Path>>(
+        path: P,
+        format: TelemetryFormat,
+        columns: Vec<String>,
+        capacity: usize,
+    ) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let file = File::create(&path).map_err(|err| TelemetryError::Write(path.clone(), err))?;
+        let (sender, receiver) = mpsc::sync_channel(capacity.max(1));
+        let counters = Arc::new(Counters::default());
+        let worker = Worker {
+            path,
+            format,
+            columns: columns.clone(),
+            output: BufWriter::new(file),
+            counters: counters.clone(),
+        };
+        let worker = std::thread::Builder::new()
+            .name("xplm-telemetry".into())
+            .spawn(move || worker.run(receiver))
+            .map_err(TelemetryError::Spawn)?;
+        Ok(Self {
+            columns: columns.len(),
+            sender: Some(sender),
+            worker: Some(worker),
+            counters,
+        })
+    }
+
+    /// Hands a sample over to the worker thread without blocking.
+    ///
+    /// # Arguments
+    /// * `sample` - a sample with one value per column.
+    ///
+    /// # Returns
+    /// Returns `true` if the sample was queued or `false` if it was dropped because
+    /// the queue is full. Otherwise returns [`TelemetryError`].
+    pub fn push(&self, sample: Sample) -> Result<bool> {
+        if sample.values.len() != self.columns {
+            return Err(TelemetryError::ColumnMismatch {
+                expected: self.columns,
+                actual: sample.values.len(),
+            });
+        }
+
+        let sender = self.sender.as_ref().ok_or(TelemetryError::WriterStopped)?;
+        match sender.try_send(sample) {
+            Ok(()) => Ok(true),
+            Err(TrySendError::Full(_)) => {
+                self.counters.dropped.fetch_add(1, Ordering::Relaxed);
+                Ok(false)
+            }
+            Err(TrySendError::Disconnected(_)) => Err(TelemetryError::WriterStopped),
+        }
+    }
+
+    /// Returns the writer counters.
+    pub fn stats(&self) -> WriterStats {
+        WriterStats {
+            written: self.counters.written.load(Ordering::Relaxed),
+            dropped: self.counters.dropped.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Writes the queued samples, flushes the file and stops the worker thread.
+    ///
+    /// # Returns
+    /// Returns the final counters on success. Otherwise returns [`TelemetryError`]
+    /// the worker thread has stopped with.
+    pub fn finish(mut self) -> Result<WriterStats> {
+        self.stop()?;
+        Ok(self.stats())
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        self.sender.take();
+        match self.worker.take().map(JoinHandle::join) {
+            Some(Ok(result)) => result,
+            Some(Err(_)) => Err(TelemetryError::WriterStopped),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for TelemetryWriter {
+    fn drop(&mut self) {
+        if let Err(err) = self.stop() {
+            crate::error!("{}", err);
+        }
+    }
+}
+
+struct Worker {
+    path: PathBuf,
+    format: TelemetryFormat,
+    columns: Vec<String>,
+    output: BufWriter<File>,
+    counters: Arc<Counters>,
+}
+
+impl Worker {
+    fn run(mut self, receiver: Receiver<Sample>) -> Result<()> {
+        if self.format == TelemetryFormat::Csv {
+            let header = std::iter::once("time")
+                .chain(self.columns.iter().map(String::as_str))
+                .map(csv_field)
+                .collect::<Vec<_>>()
+                .join(",");
+            self.write_line(&header)?;
+        }
+
+        loop {
+            let sample = match receiver.try_recv() {
+                Ok(sample) => sample,
+                Err(TryRecvError::Empty) => {
+                    self.flush()?;
+                    match receiver.recv() {
+                        Ok(sample) => sample,
+                        Err(_) => break,
+                    }
+                }
+                Err(TryRecvError::Disconnected) => break,
+            };
+
+            let line = match self.format {
+                TelemetryFormat::Csv => self.csv_line(&sample),
+                TelemetryFormat::JsonLines => self.json_line(&sample)?,
+            };
+            self.write_line(&line)?;
+            self.counters.written.fetch_add(1, Ordering::Relaxed);
+        }
+
+        self.flush()
+    }
+
+    fn csv_line(&self, sample: &Sample) -> String {
+        std::iter::once(sample.time)
+            .chain(sample.values.iter().copied())
+            .map(|value| value.to_string())
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    fn json_line(&self, sample: &Sample) -> Result<String> {
+        let mut object = serde_json::Map::with_capacity(self.columns.len() + 1);
+        object.insert("time".into(), serde_json::Value::from(sample.time));
+        for (column, value) in self.columns.iter().zip(&sample.values) {
+            object.insert(column.clone(), serde_json::Value::from(*value));
+        }
+
+        serde_json::to_string(&object).map_err(TelemetryError::Serialize)
+    }
+
+    fn write_line(&mut self, line: &str) -> Result<()> {
+        writeln!(self.output, "{}", line)
+            .map_err(|err| TelemetryError::Write(self.path.clone(), err))
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.output
+            .flush()
+            .map_err(|err| TelemetryError::Write(self.path.clone(), err))
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,256 @@
+//! Self-tests which run inside X-Plane, e.g. to smoke-test data ref bindings against
+//! a real simulator build. Available with the `testing` feature enabled.
+//!
+//! Every test is exposed as a command under `<prefix>/selftest/`, together with
+//! `<prefix>/selftest/run_all`, so tests can be run from the keyboard settings or a
+//! joystick button. Results and timings are written to `Log.txt`.
+//!
+//! ```ignore
+//! let mut tests = SelfTests::new("my_plugin")?;
+//! tests.add("gear_handle", || {
+//!     let value = DataRefValue::<i32>::new("sim/cockpit2/controls/gear_handle_down")
+//!         .get()
+//!         .map_err(|err| err.to_string())?;
+//!     if value == 0 || value == 1 { Ok(()) } else { Err(format!("unexpected value {}", value)) }
+//! })?;
+//! ```
+
+use std::cell::RefCell;
+use std::panic::{self, AssertUnwindSafe};
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use crate::api::utilities::{
+    create_command, register_command_handler, CommandExecutionTime, CommandHandler,
+    CommandHandlerRecord, CommandOutcome,
+};
+
+/// A result of a single self-test, the error describes the failure.
+pub type TestResult = std::result::Result<(), String>;
+
+/// An outcome of a single self-test.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestOutcome {
+    /// The test name.
+    pub name: String,
+    /// The test result, a panic is reported as a failure.
+    pub result: TestResult,
+    /// The test execution time.
+    pub elapsed: Duration,
+}
+
+/// Outcomes of a test run.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TestReport {
+    /// Outcomes in the order the tests ran.
+    pub outcomes: Vec<TestOutcome>,
+    /// The total execution time.
+    pub elapsed: Duration,
+}
+
+impl TestReport {
+    /// Returns the number of passed tests.
+    pub fn passed(&self) -> usize {
+        self.outcomes
+            .iter()
+            .filter(|outcome| outcome.result.is_ok())
+            .count()
+    }
+
+    /// Returns the number of failed tests.
+    pub fn failed(&self) -> usize {
+        self.outcomes.len() - self.passed()
+    }
+
+    /// Checks wether all tests have passed.
+    pub fn is_success(&self) -> bool {
+        self.failed() == 0
+    }
+}
+
+struct SelfTest {
+    name: String,
+    test: Box<dyn FnMut() -> TestResult>,
+}
+
+impl SelfTest {
+    fn run(&mut self) -> TestOutcome {
+        let started = Instant::now();
+        let result = panic::catch_unwind(AssertUnwindSafe(&mut self.test))
+            .unwrap_or_else(|panic| Err(panic_message(panic)));
+        let outcome = TestOutcome {
+            name: self.name.clone(),
+            result,
+            elapsed: started.elapsed(),
+        };
+
+        match &outcome.result {
+            Ok(()) => crate::info!("self-test {} passed in {:?}", outcome.name, outcome.elapsed),
+            Err(err) => crate::error!(
+                "self-test {} failed in {:?}: {}",
+                outcome.name,
+                outcome.elapsed,
+                err
+            ),
+        }
+
+        outcome
+    }
+}
+
+type Tests = Rc<RefCell<Vec<SelfTest>>>;
+
+/// A set of named self-tests exposed as commands. The commands stop working when dropped.
+pub struct SelfTests {
+    prefix: String,
+    tests: Tests,
+    _records: Vec<CommandHandlerRecord>,
+}
+
+impl SelfTests {
+    /// Creates an empty set and the `<prefix>/selftest/run_all` command.
+    ///
+    /// # Arguments
+    /// * `prefix` - a command prefix unique to the plugin, e.g. `my_plugin`.
+    ///
+    /// # Returns
+    /// Returns a new [`SelfTests`] instance on success. Otherwise returns [`crate::XplmError`].
+    pub fn new<P: Into<String>>(prefix: P) -> crate::Result<Self> {
+        let mut tests = Self {
+            prefix: prefix.into().trim_matches('/').to_string(),
+            tests: Rc::default(),
+            _records: Vec::new(),
+        };
+        tests.register("run_all", "Run all self-tests", None)?;
+        Ok(tests)
+    }
+
+    /// Adds a self-test and the `<prefix>/selftest/<name>` command which runs it.
+    ///
+    /// # Arguments
+    /// * `name` - a unique test name, a valid command name segment.
+    /// * `test` - a test function, it returns an error message or panics on failure.
+    ///
+    /// # Returns
+    /// Returns empty result on success. Otherwise returns [`crate::XplmError`].
+    pub fn add<N, F>(&mut self, name: N, test: F) -> crate::Result<()>
+    where
+        N: Into<String>,
+        F: FnMut() -> TestResult + 'static,
+    {
+        let name = name.into();
+        let description = format!("Run self-test {}", name);
+        self.register(&name, &description, Some(name.clone()))?;
+        self.tests.borrow_mut().push(SelfTest {
+            name,
+            test: Box::new(test),
+        });
+        Ok(())
+    }
+
+    /// Returns the names of the registered tests.
+    pub fn names(&self) -> Vec<String> {
+        self.tests
+            .borrow()
+            .iter()
+            .map(|test| test.name.clone())
+            .collect()
+    }
+
+    /// Runs all tests and writes a summary to the log.
+    ///
+    /// # Returns
+    /// Returns a [`TestReport`].
+    pub fn run_all(&self) -> TestReport {
+        run_tests(&self.tests, None)
+    }
+
+    /// Runs a single test.
+    ///
+    /// # Arguments
+    /// * `name` - a test name.
+    ///
+    /// # Returns
+    /// Returns [`TestOutcome`] or [`None`] if there is no such test.
+    pub fn run(&self, name: &str) -> Option<TestOutcome> {
+        run_tests(&self.tests, Some(name)).outcomes.pop()
+    }
+
+    fn register(
+        &mut self,
+        name: &str,
+        description: &str,
+        test: Option<String>,
+    ) -> crate::Result<()> {
+        let command = create_command(format!("{}/selftest/{}", self.prefix, name), description)?;
+        let handler = RunTestsHandler {
+            tests: self.tests.clone(),
+            test,
+        };
+        self._records.push(register_command_handler(
+            &command,
+            CommandExecutionTime::BeforeXPlane,
+            handler,
+        ));
+        Ok(())
+    }
+}
+
+struct RunTestsHandler {
+    tests: Tests,
+    test: Option<String>,
+}
+
+impl CommandHandler for RunTestsHandler {
+    fn command_begin(&mut self) -> CommandOutcome {
+        run_tests(&self.tests, self.test.as_deref());
+        CommandOutcome::Consume
+    }
+
+    fn command_continue(&mut self) -> CommandOutcome {
+        CommandOutcome::Consume
+    }
+
+    fn command_end(&mut self) -> CommandOutcome {
+        CommandOutcome::Consume
+    }
+}
+
+fn run_tests(tests: &Tests, name: Option<&str>) -> TestReport {
+    let Ok(mut tests) = tests.try_borrow_mut() else {
+        crate::warn!("self-tests are already running");
+        return TestReport::default();
+    };
+
+    let started = Instant::now();
+    let outcomes = tests
+        .iter_mut()
+        .filter(|test| name.is_none_or(|name| test.name == name))
+        .map(SelfTest::run)
+        .collect();
+    let report = TestReport {
+        outcomes,
+        elapsed: started.elapsed(),
+    };
+
+    if name.is_none() {
+        crate::info!(
+            "self-tests finished in {:?}: {} passed, {} failed",
+            report.elapsed,
+            report.passed(),
+            report.failed()
+        );
+    }
+
+    report
+}
+
+fn panic_message(panic: Box<dyn std::any::Any + Send>) -> String {
+    match panic.downcast::<String>() {
+        Ok(message) => format!("panicked: {}", message),
+        Err(panic) => match panic.downcast::<&str>() {
+            Ok(message) => format!("panicked: {}", message),
+            Err(_) => "panicked".to_string(),
+        },
+    }
+}
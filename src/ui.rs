@@ -0,0 +1,21 @@
+//! Ready-made user interface components built on top of the display and graphics APIs.
+
+pub mod error;
+pub mod gauge_overlay;
+pub mod list_view;
+pub mod message_box;
+#[cfg(feature = "prefs")]
+pub mod shortcuts;
+pub mod theme;
+pub mod toast;
+
+pub use self::error::UiError;
+pub use self::gauge_overlay::{GaugeOverlay, GaugeOverlayHandle, OverlayCorner};
+pub use self::list_view::ListView;
+pub use self::message_box::{MessageBox, MessageBoxHandle};
+#[cfg(feature = "prefs")]
+pub use self::shortcuts::{Shortcut, ShortcutAction, Shortcuts};
+pub use self::theme::{Theme, ThemeWatcher};
+pub use self::toast::{ToastCorner, ToastManager};
+
+pub type Result<T> = std::result::Result<T, UiError>;
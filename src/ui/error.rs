@@ -0,0 +1,70 @@
+use crate::api::display::DisplayError;
+use crate::api::graphics::GraphicsError;
+use crate::api::utilities::UtilitiesError;
+#[cfg(feature = "prefs")]
+use crate::prefs::PrefsError;
+
+/// An error returned from user interface components.
+#[derive(thiserror::Error, Debug)]
+pub enum UiError {
+    /// Display error.
+    #[error("display error {0}")]
+    Display(DisplayError),
+    /// Graphics error.
+    #[error("graphics error {0}")]
+    Graphics(GraphicsError),
+    /// Utilities error.
+    #[error("utilities error {0}")]
+    Utilities(UtilitiesError),
+    /// Too many buttons added to a message box.
+    #[error("message box supports up to {max} buttons, got {actual}")]
+    TooManyButtons {
+        /// The maximum number of buttons.
+        max: usize,
+        /// The actual number of buttons.
+        actual: usize,
+    },
+    /// Command does not exist.
+    #[error("command {0} not found")]
+    CommandNotFound(String),
+    /// Shortcut is not registered.
+    #[error("shortcut {0} not found")]
+    ShortcutNotFound(String),
+    /// Key combination is already used by another shortcut.
+    #[error("shortcut {shortcut} is already used by {id}")]
+    ShortcutConflict {
+        /// The key combination.
+        shortcut: String,
+        /// The identifier of the shortcut which uses the combination.
+        id: String,
+    },
+    /// Shortcuts can't be loaded or saved.
+    #[cfg(feature = "prefs")]
+    #[error("preferences error {0}")]
+    Prefs(PrefsError),
+}
+
+impl From<DisplayError> for UiError {
+    fn from(value: DisplayError) -> Self {
+        Self::Display(value)
+    }
+}
+
+impl From<GraphicsError> for UiError {
+    fn from(value: GraphicsError) -> Self {
+        Self::Graphics(value)
+    }
+}
+
+impl From<UtilitiesError> for UiError {
+    fn from(value: UtilitiesError) -> Self {
+        Self::Utilities(value)
+    }
+}
+
+#[cfg(feature = "prefs")]
+impl From<PrefsError> for UiError {
+    fn from(value: PrefsError) -> Self {
+        Self::Prefs(value)
+    }
+}
@@ -0,0 +1,223 @@
+use crate::api::display::gravity::GravityRect;
+use crate::api::display::{
+    self, create_window_with_decoration, get_screen_bounds_global, Coord, EventState, KeyFlags,
+    MouseStatus, Rect, Size, WheelAxis, WindowDecoration, WindowHandler, WindowHandlerRecord,
+    WindowId, WindowLayer,
+};
+use crate::api::utilities::VirtualKey;
+
+use super::Result;
+
+/// Default distance between an overlay and the screen edges in boxels.
+pub const DEFAULT_MARGIN: i32 = 16;
+
+/// A screen corner an overlay is anchored to.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum OverlayCorner {
+    /// The top left screen corner.
+    #[default]
+    TopLeft,
+    /// The top right screen corner.
+    TopRight,
+    /// The bottom left screen corner.
+    BottomLeft,
+    /// The bottom right screen corner.
+    BottomRight,
+}
+
+impl OverlayCorner {
+    /// Returns the window gravity which keeps the window in the corner.
+    fn gravity(&self) -> GravityRect {
+        let (x, y) = match self {
+            Self::TopLeft => (0.0, 1.0),
+            Self::TopRight => (1.0, 1.0),
+            Self::BottomLeft => (0.0, 0.0),
+            Self::BottomRight => (1.0, 0.0),
+        };
+        GravityRect::new(x, y, x, y)
+    }
+
+    /// Returns the window geometry in the corner of the screen bounds.
+    fn place(&self, screen: &Rect, size: &Size, margin: i32) -> Rect {
+        let left = match self {
+            Self::TopLeft | Self::BottomLeft => screen.left + margin,
+            Self::TopRight | Self::BottomRight => screen.right - margin - size.width,
+        };
+        let top = match self {
+            Self::TopLeft | Self::TopRight => screen.top - margin,
+            Self::BottomLeft | Self::BottomRight => screen.bottom + margin + size.height,
+        };
+        Rect::new(left, top, left + size.width, top - size.height)
+    }
+}
+
+/// An undecorated window anchored to a screen corner, e.g. for a frame rate or status display.
+///
+/// The overlay is shown in the [`WindowLayer::FlightOverlay`] layer and never handles
+/// mouse events, so clicks reach the windows and the cockpit beneath it. It is moved back
+/// to its corner whenever the global screen bounds change, e.g. when the X-Plane window is
+/// resized or a monitor is added.
+///
+/// ```ignore
+/// let overlay = GaugeOverlay::new(Size::new(120, 24))
+///     .corner(OverlayCorner::TopRight)
+///     .show(|rect| {
+///         let _ = graphics::draw_string(
+///             fps(),
+///             Font::Basic,
+///             &Color::white(),
+///             &Coord::new(rect.left + 4, rect.bottom + 8),
+///         );
+///     })?;
+/// ```
+pub struct GaugeOverlay {
+    size: Size,
+    corner: OverlayCorner,
+    margin: i32,
+}
+
+impl GaugeOverlay {
+    /// Creates an overlay configuration anchored to the top left corner.
+    ///
+    /// # Arguments
+    /// * `size` - an overlay size in boxels.
+    ///
+    /// # Returns
+    /// Returns a new [`GaugeOverlay`] instance.
+    pub fn new(size: Size) -> Self {
+        Self {
+            size,
+            corner: OverlayCorner::default(),
+            margin: DEFAULT_MARGIN,
+        }
+    }
+
+    /// Sets the screen corner.
+    ///
+    /// # Arguments
+    /// * `corner` - a screen corner. See [`OverlayCorner`] for more details.
+    ///
+    /// # Returns
+    /// Returns a modified overlay configuration.
+    pub fn corner(mut self, corner: OverlayCorner) -> Self {
+        self.corner = corner;
+        self
+    }
+
+    /// Sets the distance to the screen edges.
+    ///
+    /// # Arguments
+    /// * `margin` - a margin in boxels, see [`DEFAULT_MARGIN`].
+    ///
+    /// # Returns
+    /// Returns a modified overlay configuration.
+    pub fn margin(mut self, margin: i32) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    /// Creates and shows the overlay window.
+    ///
+    /// # Arguments
+    /// * `draw` - a closure which draws the overlay contents, it receives the window geometry.
+    ///
+    /// # Returns
+    /// Returns a [`GaugeOverlayHandle`] which keeps the overlay alive on success.
+    /// Otherwise returns [`super::UiError`].
+    pub fn show<F: FnMut(&Rect) + 'static>(self, draw: F) -> Result<GaugeOverlayHandle> {
+        let screen = get_screen_bounds_global();
+        let rect = self.corner.place(&screen, &self.size, self.margin);
+        let handler = GaugeOverlayWindow {
+            size: self.size,
+            corner: self.corner,
+            margin: self.margin,
+            screen,
+            draw: Box::new(draw),
+        };
+
+        let record = create_window_with_decoration(
+            &rect,
+            WindowLayer::FlightOverlay,
+            WindowDecoration::None,
+            handler,
+        )?;
+        display::set_window_gravity(&record.id, &self.corner.gravity());
+        display::set_window_visible(&record.id);
+        Ok(GaugeOverlayHandle { record })
+    }
+}
+
+/// A shown gauge overlay. The overlay window is destroyed when dropped.
+pub struct GaugeOverlayHandle {
+    record: WindowHandlerRecord,
+}
+
+impl GaugeOverlayHandle {
+    /// Returns the overlay window identifier.
+    pub fn id(&self) -> &WindowId {
+        &self.record.id
+    }
+
+    /// Shows or hides the overlay.
+    ///
+    /// # Arguments
+    /// * `visible` - `true` to show the overlay.
+    pub fn set_visible(&self, visible: bool) {
+        if visible {
+            display::set_window_visible(&self.record.id);
+        } else {
+            display::set_window_hidden(&self.record.id);
+        }
+    }
+
+    /// Checks whether the overlay is visible.
+    pub fn is_visible(&self) -> bool {
+        display::get_window_is_visible(&self.record.id)
+    }
+}
+
+struct GaugeOverlayWindow {
+    size: Size,
+    corner: OverlayCorner,
+    margin: i32,
+    screen: Rect,
+    draw: Box<dyn FnMut(&Rect)>,
+}
+
+impl WindowHandler for GaugeOverlayWindow {
+    fn draw(&mut self, id: &WindowId) {
+        let screen = get_screen_bounds_global();
+        if screen != self.screen {
+            self.screen = screen;
+            let rect = self.corner.place(&screen, &self.size, self.margin);
+            display::set_window_geometry(id, &rect);
+        }
+
+        (self.draw)(&display::get_window_geometry(id));
+    }
+
+    fn mouse_click(&mut self, _id: &WindowId, _coord: Coord, _status: MouseStatus) -> EventState {
+        EventState::Propagate
+    }
+
+    fn handle_key(
+        &mut self,
+        _id: &WindowId,
+        _key: char,
+        _virtual_key: VirtualKey,
+        _flags: KeyFlags,
+    ) {
+    }
+
+    fn handle_cursor(&mut self, _id: &WindowId, _coord: Coord) {}
+
+    fn handle_mouse_wheel(
+        &mut self,
+        _id: &WindowId,
+        _coord: Coord,
+        _wheel_axis: WheelAxis,
+        _clicks: i32,
+    ) -> EventState {
+        EventState::Propagate
+    }
+}
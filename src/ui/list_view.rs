@@ -0,0 +1,390 @@
+use crate::api::display::{Color, Coord, EventState, KeyFlags, MouseStatus, Rect, WheelAxis};
+use crate::api::graphics::{self, font_metrics, push_clip_rect, Font};
+use crate::api::utilities::VirtualKey;
+
+use super::Theme;
+
+/// Default number of rows scrolled by a single mouse wheel click.
+pub const DEFAULT_WHEEL_ROWS: i32 = 3;
+
+const PADDING: i32 = 4;
+const SCROLLBAR_WIDTH: i32 = 4;
+const SCROLLBAR_MIN_HEIGHT: i32 = 12;
+
+/// A callback which receives the index and the text of the selected row.
+pub type SelectCallback = Box<dyn FnMut(usize, &str)>;
+
+/// A scrollable list of text rows drawn inside a window, e.g. for data ref browsers,
+/// log viewers or airport pickers.
+///
+/// The list is not a window itself, it is owned by a [`crate::api::display::WindowHandler`]
+/// which forwards its draw, mouse and key events. Only the visible rows are drawn and the
+/// drawing is clipped to the list rectangle.
+///
+/// ```ignore
+/// impl WindowHandler for Browser {
+///     fn draw(&mut self, id: &WindowId) {
+///         self.list.draw(&display::get_window_geometry(id));
+///     }
+///
+///     fn mouse_click(&mut self, _id: &WindowId, coord: Coord, status: MouseStatus) -> EventState {
+///         self.list.mouse_click(&coord, status)
+///     }
+///
+///     fn handle_mouse_wheel(&mut self, _id: &WindowId, coord: Coord, axis: WheelAxis, clicks: i32) -> EventState {
+///         self.list.mouse_wheel(&coord, axis, clicks)
+///     }
+///     // ...
+/// }
+/// ```
+pub struct ListView {
+    rows: Vec<String>,
+    font: Font,
+    row_height: i32,
+    wheel_rows: i32,
+    scroll: i32,
+    selected: Option<usize>,
+    rect: Rect,
+    text: Color,
+    selected_text: Color,
+    selection: Color,
+    scrollbar: Color,
+    on_select: Vec<SelectCallback>,
+}
+
+impl ListView {
+    /// Creates an empty list drawn with the [`Font::Proportional`] font and the
+    /// [`Theme::default`] colors.
+    ///
+    /// # Returns
+    /// Returns a new [`ListView`] instance.
+    pub fn new() -> Self {
+        Self {
+            rows: Vec::new(),
+            font: Font::Proportional,
+            row_height: font_metrics(Font::Proportional).char_height + PADDING,
+            wheel_rows: DEFAULT_WHEEL_ROWS,
+            scroll: 0,
+            selected: None,
+            rect: Rect::default(),
+            text: Color::white(),
+            selected_text: Color::white(),
+            selection: Color::white(),
+            scrollbar: Color::white(),
+            on_select: Vec::new(),
+        }
+        .theme(&Theme::default())
+    }
+
+    /// Sets the font and adjusts the row height to it.
+    ///
+    /// # Arguments
+    /// * `font` - a font. See [`Font`] for more details.
+    ///
+    /// # Returns
+    /// Returns a modified list.
+    pub fn font(mut self, font: Font) -> Self {
+        self.font = font;
+        self.row_height = font_metrics(font).char_height + PADDING;
+        self
+    }
+
+    /// Sets the row height.
+    ///
+    /// # Arguments
+    /// * `height` - a row height in boxels.
+    ///
+    /// # Returns
+    /// Returns a modified list.
+    pub fn row_height(mut self, height: i32) -> Self {
+        self.row_height = height.max(1);
+        self
+    }
+
+    /// Sets the number of rows scrolled by a single mouse wheel click.
+    ///
+    /// # Arguments
+    /// * `rows` - a number of rows, see [`DEFAULT_WHEEL_ROWS`].
+    ///
+    /// # Returns
+    /// Returns a modified list.
+    pub fn wheel_rows(mut self, rows: i32) -> Self {
+        self.wheel_rows = rows;
+        self
+    }
+
+    /// Takes the colors from a UI theme.
+    ///
+    /// # Arguments
+    /// * `theme` - a theme. See [`Theme`] for more details.
+    ///
+    /// # Returns
+    /// Returns a modified list.
+    pub fn theme(mut self, theme: &Theme) -> Self {
+        self.set_theme(theme);
+        self
+    }
+
+    /// Changes the colors, e.g. from a [`super::ThemeWatcher`] callback.
+    ///
+    /// # Arguments
+    /// * `theme` - a theme. See [`Theme`] for more details.
+    pub fn set_theme(&mut self, theme: &Theme) {
+        self.text = theme.menu_text;
+        self.selected_text = theme.menu_text;
+        self.selection = theme.menu_hilite;
+        self.scrollbar = theme.menu_lite;
+    }
+
+    /// Registers a callback which is called when a row is selected by the user.
+    ///
+    /// # Arguments
+    /// * `callback` - a callback which receives the row index and text.
+    pub fn on_select<F: FnMut(usize, &str) + 'static>(&mut self, callback: F) {
+        self.on_select.push(Box::new(callback));
+    }
+
+    /// Replaces the rows. The selection is cleared and the list is scrolled to the top.
+    ///
+    /// # Arguments
+    /// * `rows` - new rows.
+    pub fn set_rows<I, T>(&mut self, rows: I)
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        self.rows = rows.into_iter().map(Into::into).collect();
+        self.selected = None;
+        self.scroll = 0;
+    }
+
+    /// Appends a row. A list scrolled to the bottom stays at the bottom, e.g. in a log viewer.
+    ///
+    /// # Arguments
+    /// * `row` - a row text.
+    pub fn push<T: Into<String>>(&mut self, row: T) {
+        let at_bottom = self.scroll >= self.max_scroll();
+        self.rows.push(row.into());
+        if at_bottom {
+            self.scroll_to_bottom();
+        }
+    }
+
+    /// Removes all rows.
+    pub fn clear(&mut self) {
+        self.set_rows(Vec::<String>::new());
+    }
+
+    /// Returns the rows.
+    pub fn rows(&self) -> &[String] {
+        &self.rows
+    }
+
+    /// Returns the index of the selected row.
+    pub fn selected(&self) -> Option<usize> {
+        self.selected
+    }
+
+    /// Selects a row and scrolls it into view without calling the callbacks.
+    ///
+    /// # Arguments
+    /// * `index` - a row index or [`None`] to clear the selection.
+    pub fn set_selected(&mut self, index: Option<usize>) {
+        self.selected = index.filter(|index| *index < self.rows.len());
+        if let Some(index) = self.selected {
+            self.scroll_to(index);
+        }
+    }
+
+    /// Scrolls the list so the row becomes visible.
+    ///
+    /// # Arguments
+    /// * `index` - a row index.
+    pub fn scroll_to(&mut self, index: usize) {
+        let top = index as i32 * self.row_height;
+        let bottom = top + self.row_height;
+        if top < self.scroll {
+            self.scroll = top;
+        } else if bottom > self.scroll + self.visible_height() {
+            self.scroll = bottom - self.visible_height();
+        }
+        self.clamp_scroll();
+    }
+
+    /// Scrolls the list to the last row.
+    pub fn scroll_to_bottom(&mut self) {
+        self.scroll = self.max_scroll();
+    }
+
+    /// Draws the visible rows. Should be called from the window draw callback.
+    ///
+    /// # Arguments
+    /// * `rect` - a list rectangle in window coordinates, usually the window geometry.
+    pub fn draw(&mut self, rect: &Rect) {
+        self.rect = *rect;
+        self.clamp_scroll();
+
+        let _clip = push_clip_rect(rect);
+        let first = (self.scroll / self.row_height) as usize;
+        let visible = (self.visible_height() / self.row_height + 2) as usize;
+        let text_offset = (self.row_height - font_metrics(self.font).char_height) / 2 + 2;
+        for (index, row) in self.rows.iter().enumerate().skip(first).take(visible) {
+            let row_rect = self.row_rect(index);
+            let color = if self.selected == Some(index) {
+                graphics::shapes::draw_rect_filled(&row_rect, &self.selection);
+                &self.selected_text
+            } else {
+                &self.text
+            };
+
+            let coord = Coord::new(row_rect.left + PADDING, row_rect.bottom + text_offset);
+            if let Err(err) = graphics::draw_string(row, self.font, color, &coord) {
+                crate::error!("{}", err);
+            }
+        }
+
+        if let Some(thumb) = self.scrollbar_rect() {
+            graphics::shapes::draw_rect_filled(&thumb, &self.scrollbar);
+        }
+    }
+
+    /// Scrolls the list. Should be called from the window mouse wheel callback.
+    ///
+    /// # Arguments
+    /// * `coord` - a cursor position in window coordinates.
+    /// * `wheel_axis` - a wheel axis, only the vertical axis scrolls the list.
+    /// * `clicks` - a number of wheel clicks, positive values scroll up.
+    ///
+    /// # Returns
+    /// Returns [`EventState::Consume`] if the event was handled by the list.
+    pub fn mouse_wheel(&mut self, coord: &Coord, wheel_axis: WheelAxis, clicks: i32) -> EventState {
+        if wheel_axis != WheelAxis::Vertical || !self.rect.contains(coord) {
+            return EventState::Propagate;
+        }
+
+        self.scroll -= clicks * self.wheel_rows * self.row_height;
+        self.clamp_scroll();
+        EventState::Consume
+    }
+
+    /// Selects a clicked row. Should be called from the window mouse click callback.
+    ///
+    /// # Arguments
+    /// * `coord` - a cursor position in window coordinates.
+    /// * `status` - a mouse status. See [`MouseStatus`] for more details.
+    ///
+    /// # Returns
+    /// Returns [`EventState::Consume`] if the event was handled by the list.
+    pub fn mouse_click(&mut self, coord: &Coord, status: MouseStatus) -> EventState {
+        if !self.rect.contains(coord) {
+            return EventState::Propagate;
+        }
+
+        if status == MouseStatus::Down {
+            if let Some(index) = self.row_at(coord) {
+                self.select(index);
+            }
+        }
+
+        EventState::Consume
+    }
+
+    /// Moves the selection with the arrow, page, home and end keys.
+    /// Should be called from the window key callback.
+    ///
+    /// # Arguments
+    /// * `virtual_key` - a pressed key.
+    /// * `flags` - key flags. See [`KeyFlags`] for more details.
+    ///
+    /// # Returns
+    /// Returns `true` if the key was handled by the list.
+    pub fn handle_key(&mut self, virtual_key: VirtualKey, flags: KeyFlags) -> bool {
+        if self.rows.is_empty() || !flags.is_down() {
+            return false;
+        }
+
+        let last = self.rows.len() - 1;
+        let page = (self.visible_height() / self.row_height).max(1) as usize;
+        let current = self.selected;
+        let index = match virtual_key {
+            VirtualKey::Up => current.map_or(last, |index| index.saturating_sub(1)),
+            VirtualKey::Down => current.map_or(0, |index| (index + 1).min(last)),
+            VirtualKey::Prior => current.map_or(0, |index| index.saturating_sub(page)),
+            VirtualKey::Next => current.map_or(0, |index| (index + page).min(last)),
+            VirtualKey::Home => 0,
+            VirtualKey::End => last,
+            _ => return false,
+        };
+
+        if current != Some(index) {
+            self.select(index);
+        }
+
+        true
+    }
+
+    fn select(&mut self, index: usize) {
+        self.selected = Some(index);
+        self.scroll_to(index);
+        let row = &self.rows[index];
+        for callback in self.on_select.iter_mut() {
+            callback(index, row);
+        }
+    }
+
+    fn visible_height(&self) -> i32 {
+        self.rect.top - self.rect.bottom
+    }
+
+    fn content_height(&self) -> i32 {
+        self.rows.len() as i32 * self.row_height
+    }
+
+    fn max_scroll(&self) -> i32 {
+        (self.content_height() - self.visible_height()).max(0)
+    }
+
+    fn clamp_scroll(&mut self) {
+        self.scroll = self.scroll.clamp(0, self.max_scroll());
+    }
+
+    fn row_rect(&self, index: usize) -> Rect {
+        let top = self.rect.top + self.scroll - index as i32 * self.row_height;
+        Rect::new(
+            self.rect.left,
+            top,
+            self.rect.right - SCROLLBAR_WIDTH,
+            top - self.row_height,
+        )
+    }
+
+    fn row_at(&self, coord: &Coord) -> Option<usize> {
+        let offset = self.rect.top + self.scroll - coord.y;
+        let index = (offset / self.row_height) as usize;
+        (offset >= 0 && index < self.rows.len()).then_some(index)
+    }
+
+    /// Returns the scrollbar thumb or [`None`] if all rows are visible.
+    fn scrollbar_rect(&self) -> Option<Rect> {
+        let visible = self.visible_height();
+        let content = self.content_height();
+        if content <= visible || visible <= 0 {
+            return None;
+        }
+
+        let height = (visible * visible / content).max(SCROLLBAR_MIN_HEIGHT);
+        let top = self.rect.top - (visible - height) * self.scroll / self.max_scroll();
+        Some(Rect::new(
+            self.rect.right - SCROLLBAR_WIDTH,
+            top,
+            self.rect.right,
+            top - height,
+        ))
+    }
+}
+
+impl Default for ListView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,299 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use crate::api::display::{
+    self, create_window_in_layer, get_screen_bounds_global, get_window_geometry, Color, Coord,
+    EventState, KeyFlags, MouseStatus, Rect, WheelAxis, WindowHandler, WindowHandlerRecord,
+    WindowId, WindowLayer,
+};
+use crate::api::graphics::{self, font_metrics, measure_string, Font};
+use crate::api::utilities::VirtualKey;
+
+use super::{Result, UiError};
+
+/// The maximum number of message box buttons.
+pub const MAX_BUTTONS: usize = 3;
+
+const FONT: Font = Font::Proportional;
+const WIDTH: i32 = 400;
+const PADDING: i32 = 12;
+const BUTTON_HEIGHT: i32 = 24;
+const BUTTON_MIN_WIDTH: i32 = 80;
+const BUTTON_SPACING: i32 = 8;
+
+/// A callback which receives the index of the chosen button.
+pub type ChoiceCallback = Box<dyn FnOnce(usize)>;
+
+/// A modal dialog with a title, a word-wrapped message and up to [`MAX_BUTTONS`] buttons.
+/// The dialog is shown in the [`WindowLayer::Modal`] layer, so windows beneath it don't
+/// receive clicks until a button is chosen.
+///
+/// The `Enter` key chooses the first button and the `Escape` key chooses the last one.
+///
+/// ```ignore
+/// let dialog = MessageBox::new("Delete preset", "The preset will be removed permanently.")
+///     .button("Delete")
+///     .button("Cancel")
+///     .show(|choice| if choice == 0 { delete_preset() })?;
+/// ```
+pub struct MessageBox {
+    title: String,
+    message: String,
+    buttons: Vec<String>,
+}
+
+impl MessageBox {
+    /// Creates a new message box without buttons.
+    /// A single "OK" button is shown if no buttons are added.
+    ///
+    /// # Arguments
+    /// * `title` - a window title.
+    /// * `message` - a message text.
+    ///
+    /// # Returns
+    /// Returns a new [`MessageBox`] instance.
+    pub fn new<T: Into<String>, M: Into<String>>(title: T, message: M) -> Self {
+        Self {
+            title: title.into(),
+            message: message.into(),
+            buttons: Vec::new(),
+        }
+    }
+
+    /// Adds a button. Buttons are laid out from left to right in the order they are added.
+    ///
+    /// # Arguments
+    /// * `label` - a button label.
+    ///
+    /// # Returns
+    /// Returns a modified message box.
+    pub fn button<T: Into<String>>(mut self, label: T) -> Self {
+        self.buttons.push(label.into());
+        self
+    }
+
+    /// Shows the message box centered on the main screen.
+    ///
+    /// # Arguments
+    /// * `on_choice` - a callback which receives the index of the chosen button.
+    ///
+    /// # Returns
+    /// Returns a [`MessageBoxHandle`] which keeps the dialog alive.
+    /// Otherwise returns [`UiError`].
+    pub fn show<F: FnOnce(usize) + 'static>(mut self, on_choice: F) -> Result<MessageBoxHandle> {
+        if self.buttons.len() > MAX_BUTTONS {
+            return Err(UiError::TooManyButtons {
+                max: MAX_BUTTONS,
+                actual: self.buttons.len(),
+            });
+        }
+
+        if self.buttons.is_empty() {
+            self.buttons.push("OK".to_string());
+        }
+
+        let lines = wrap_text(&self.message, WIDTH - 2 * PADDING)?;
+        let line_height = font_metrics(FONT).char_height + 2;
+        let height = PADDING * 3 + lines.len() as i32 * line_height + BUTTON_HEIGHT;
+        let screen = get_screen_bounds_global();
+        let center = screen.center();
+        let rect = Rect::new(
+            center.x - WIDTH / 2,
+            center.y + height / 2,
+            center.x + WIDTH / 2,
+            center.y - height / 2,
+        );
+
+        let choice = Rc::new(Cell::new(None));
+        let handler = MessageBoxWindow {
+            lines,
+            line_height,
+            buttons: self.buttons,
+            button_rects: Vec::new(),
+            pressed: None,
+            choice: choice.clone(),
+            on_choice: Some(Box::new(on_choice)),
+        };
+
+        let record = create_window_in_layer(&rect, WindowLayer::Modal, handler)?;
+        display::set_window_title(&record.id, self.title)?;
+        display::set_window_visible(&record.id);
+        display::take_keyboard_focus(&record.id);
+        Ok(MessageBoxHandle { record, choice })
+    }
+}
+
+/// A shown message box. The dialog is closed when dropped.
+pub struct MessageBoxHandle {
+    record: WindowHandlerRecord,
+    choice: Rc<Cell<Option<usize>>>,
+}
+
+impl MessageBoxHandle {
+    /// Returns the index of the chosen button or [`None`] if the dialog is still open.
+    pub fn choice(&self) -> Option<usize> {
+        self.choice.get()
+    }
+
+    /// Checks whether a button was chosen and the dialog was hidden.
+    pub fn is_closed(&self) -> bool {
+        self.choice.get().is_some()
+    }
+
+    /// Returns the dialog window identifier.
+    pub fn window(&self) -> &WindowId {
+        &self.record.id
+    }
+}
+
+struct MessageBoxWindow {
+    lines: Vec<String>,
+    line_height: i32,
+    buttons: Vec<String>,
+    button_rects: Vec<Rect>,
+    pressed: Option<usize>,
+    choice: Rc<Cell<Option<usize>>>,
+    on_choice: Option<ChoiceCallback>,
+}
+
+impl MessageBoxWindow {
+    fn choose(&mut self, id: &WindowId, index: usize) {
+        if self.choice.get().is_some() {
+            return;
+        }
+
+        // A hidden modal window no longer blocks the windows beneath it.
+        display::set_window_hidden(id);
+        if display::has_keyboard_focus(id) {
+            display::remove_keyboard_focus();
+        }
+        self.choice.set(Some(index));
+        if let Some(callback) = self.on_choice.take() {
+            callback(index);
+        }
+    }
+
+    fn layout_buttons(&mut self, geometry: &Rect) {
+        self.button_rects.clear();
+        let mut right = geometry.right - PADDING;
+        let bottom = geometry.bottom + PADDING;
+        for label in self.buttons.iter().rev() {
+            let label_width = measure_string(FONT, label).unwrap_or_default() as i32;
+            let width = (label_width + 2 * PADDING).max(BUTTON_MIN_WIDTH);
+            self.button_rects.push(Rect::new(
+                right - width,
+                bottom + BUTTON_HEIGHT,
+                right,
+                bottom,
+            ));
+            right -= width + BUTTON_SPACING;
+        }
+        self.button_rects.reverse();
+    }
+
+    fn button_at(&self, coord: &Coord) -> Option<usize> {
+        self.button_rects
+            .iter()
+            .position(|rect| rect.contains(coord))
+    }
+}
+
+impl WindowHandler for MessageBoxWindow {
+    fn draw(&mut self, id: &WindowId) {
+        let geometry = get_window_geometry(id);
+        self.layout_buttons(&geometry);
+
+        let text_color = Color::white();
+        let mut coord = Coord::new(geometry.left + PADDING, geometry.top - PADDING);
+        for line in &self.lines {
+            coord.y -= self.line_height;
+            if let Err(err) = graphics::draw_string(line, FONT, &text_color, &coord) {
+                crate::error!("{}", err);
+            }
+        }
+
+        let button_color = Color::from_rgb(70, 70, 70);
+        let pressed_color = Color::from_rgb(40, 90, 150);
+        for (index, (label, rect)) in self.buttons.iter().zip(&self.button_rects).enumerate() {
+            let color = if self.pressed == Some(index) {
+                &pressed_color
+            } else {
+                &button_color
+            };
+            graphics::shapes::draw_rect_filled(rect, color);
+            let label_width = measure_string(FONT, label).unwrap_or_default() as i32;
+            let label_coord = Coord::new(
+                rect.left + (rect.right - rect.left - label_width) / 2,
+                rect.bottom + (BUTTON_HEIGHT - self.line_height) / 2 + 2,
+            );
+            if let Err(err) = graphics::draw_string(label, FONT, &text_color, &label_coord) {
+                crate::error!("{}", err);
+            }
+        }
+    }
+
+    fn mouse_click(&mut self, id: &WindowId, coord: Coord, status: MouseStatus) -> EventState {
+        match status {
+            MouseStatus::Down => self.pressed = self.button_at(&coord),
+            MouseStatus::Drag => {}
+            MouseStatus::Up => {
+                if let Some(index) = self.pressed.take() {
+                    if self.button_at(&coord) == Some(index) {
+                        self.choose(id, index);
+                    }
+                }
+            }
+        }
+
+        EventState::Consume
+    }
+
+    fn handle_key(&mut self, id: &WindowId, _key: char, virtual_key: VirtualKey, flags: KeyFlags) {
+        if !flags.is_down() {
+            return;
+        }
+
+        match virtual_key {
+            VirtualKey::Return | VirtualKey::Enter | VirtualKey::NumpadEnter => self.choose(id, 0),
+            VirtualKey::Escape => self.choose(id, self.buttons.len() - 1),
+            _ => {}
+        }
+    }
+
+    fn handle_cursor(&mut self, _id: &WindowId, _coord: Coord) {}
+
+    fn handle_mouse_wheel(
+        &mut self,
+        _id: &WindowId,
+        _coord: Coord,
+        _wheel_axis: WheelAxis,
+        _clicks: i32,
+    ) -> EventState {
+        EventState::Consume
+    }
+}
+
+/// Splits a text into lines which fit into the given width.
+/// Explicit line breaks are preserved.
+fn wrap_text(text: &str, width: i32) -> Result<Vec<String>> {
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            let candidate = if line.is_empty() {
+                word.to_string()
+            } else {
+                format!("{} {}", line, word)
+            };
+
+            if line.is_empty() || measure_string(FONT, &candidate)? as i32 <= width {
+                line = candidate;
+            } else {
+                lines.push(std::mem::replace(&mut line, word.to_string()));
+            }
+        }
+        lines.push(line);
+    }
+
+    Ok(lines)
+}
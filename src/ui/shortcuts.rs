@@ -0,0 +1,372 @@
+use std::cell::RefCell;
+use std::fmt;
+use std::path::Path;
+use std::rc::Rc;
+
+use serde_json::Value;
+
+use crate::api::display::{register_hot_key, HotKeyRecord, KeyFlags};
+use crate::api::utilities::{command_once, find_command, Command, VirtualKey};
+use crate::prefs::Preferences;
+
+use super::{Result, UiError};
+
+/// A key combination.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Shortcut {
+    /// The virtual key.
+    pub key: VirtualKey,
+    /// The modifier keys, only shift, option or alt and control are taken into account.
+    pub modifiers: KeyFlags,
+}
+
+impl Shortcut {
+    /// Creates a shortcut without modifiers.
+    ///
+    /// # Arguments
+    /// * `key` - a virtual key.
+    ///
+    /// # Returns
+    /// Returns a new [`Shortcut`] instance.
+    pub fn new(key: VirtualKey) -> Self {
+        Self {
+            key,
+            modifiers: KeyFlags::empty(),
+        }
+    }
+
+    /// Adds modifier keys.
+    ///
+    /// # Arguments
+    /// * `modifiers` - a modifier key or keys, e.g. [`KeyFlags::CONTROL`].
+    ///
+    /// # Returns
+    /// Returns a modified shortcut.
+    pub fn with<F: Into<KeyFlags>>(mut self, modifiers: F) -> Self {
+        self.modifiers = self.modifiers.with(modifiers);
+        self
+    }
+
+    fn normalized(self) -> Self {
+        let modifiers =
+            self.modifiers & (KeyFlags::SHIFT | KeyFlags::OPTION_ALT | KeyFlags::CONTROL);
+        Self { modifiers, ..self }
+    }
+
+    fn to_value(self, id: &str) -> Value {
+        serde_json::json!({
+            "id": id,
+            "key": self.key as u32,
+            "modifiers": self.modifiers.bits(),
+        })
+    }
+
+    fn from_value(value: &Value) -> Option<(String, Self)> {
+        let id = value.get("id")?.as_str()?;
+        let key = u8::try_from(value.get("key")?.as_u64()?).ok()?;
+        let key = VirtualKey::try_from(key as ::std::os::raw::c_char).ok()?;
+        let modifiers = value.get("modifiers")?.as_i64()?.try_into().ok()?;
+        let shortcut = Self {
+            key,
+            modifiers: KeyFlags::from_bits(modifiers),
+        };
+        Some((id.to_string(), shortcut.normalized()))
+    }
+}
+
+impl fmt::Display for Shortcut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.is_empty() {
+            write!(f, "{:?}", self.key)
+        } else {
+            write!(f, "{}+{:?}", self.modifiers, self.key)
+        }
+    }
+}
+
+/// An action triggered by a shortcut.
+pub enum ShortcutAction {
+    /// Calls a closure.
+    Closure(Box<dyn FnMut()>),
+    /// Runs a command once.
+    Command(Command),
+}
+
+impl ShortcutAction {
+    /// Creates an action which calls a closure.
+    ///
+    /// # Arguments
+    /// * `closure` - a closure to call.
+    ///
+    /// # Returns
+    /// Returns [`ShortcutAction::Closure`].
+    pub fn closure<F: FnMut() + 'static>(closure: F) -> Self {
+        Self::Closure(Box::new(closure))
+    }
+
+    /// Creates an action which runs a command.
+    ///
+    /// # Arguments
+    /// * `name` - a command name.
+    ///
+    /// # Returns
+    /// Returns [`ShortcutAction::Command`] on success. Otherwise returns [`UiError`].
+    pub fn command(name: &str) -> Result<Self> {
+        find_command(name)?
+            .map(Self::Command)
+            .ok_or_else(|| UiError::CommandNotFound(name.to_string()))
+    }
+
+    fn run(&mut self) {
+        match self {
+            Self::Closure(closure) => closure(),
+            Self::Command(command) => command_once(command),
+        }
+    }
+}
+
+struct Binding {
+    id: String,
+    description: String,
+    default: Shortcut,
+    shortcut: Shortcut,
+    action: Rc<RefCell<ShortcutAction>>,
+    _record: HotKeyRecord,
+}
+
+/// A registry of keyboard shortcuts registered as X-Plane hot keys.
+///
+/// Every shortcut has a stable identifier and a default key combination. Combinations
+/// changed by the user are persisted as JSON [`Preferences`] in the X-Plane preferences
+/// folder and take precedence over the defaults. Available with the `prefs` feature enabled.
+///
+/// ```ignore
+/// let mut shortcuts = Shortcuts::new("my_plugin_shortcuts.json")?;
+/// shortcuts.add(
+///     "toggle_window",
+///     "Toggle my plugin window",
+///     Shortcut::new(VirtualKey::W).with(KeyFlags::CONTROL),
+///     ShortcutAction::closure(move || window.toggle()),
+/// )?;
+/// ```
+pub struct Shortcuts {
+    prefs: Preferences,
+    customized: Vec<(String, Shortcut)>,
+    bindings: Vec<Binding>,
+}
+
+impl Shortcuts {
+    /// Loads customized shortcuts from a file in the X-Plane preferences folder.
+    ///
+    /// # Arguments
+    /// * `file_name` - a file name unique to the plugin, e.g. `my_plugin_shortcuts.json`.
+    ///
+    /// # Returns
+    /// Returns [`Shortcuts`] on success. Otherwise returns [`UiError`].
+    pub fn new<F: AsRef<Path>>(file_name: F) -> Result<Self> {
+        Self::with_prefs(Preferences::new(file_name)?)
+    }
+
+    /// Loads customized shortcuts from a file. A missing file yields no customizations.
+    ///
+    /// # Arguments
+    /// * `path` - a shortcuts file path.
+    ///
+    /// # Returns
+    /// Returns [`Shortcuts`] on success. Otherwise returns [`UiError`].
+    pub fn with_path<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::with_prefs(Preferences::with_path(path.as_ref()))
+    }
+
+    fn with_prefs(prefs: Preferences) -> Result<Self> {
+        let customized = prefs
+            .load::<Value>()?
+            .as_ref()
+            .and_then(Value::as_array)
+            .map(|shortcuts| shortcuts.iter().filter_map(Shortcut::from_value).collect())
+            .unwrap_or_default();
+
+        Ok(Self {
+            prefs,
+            customized,
+            bindings: Vec::new(),
+        })
+    }
+
+    /// Registers a shortcut. The combination customized by the user is used if there is one.
+    ///
+    /// # Arguments
+    /// * `id` - a stable shortcut identifier.
+    /// * `description` - a description shown in the X-Plane keyboard settings.
+    /// * `default` - a default key combination.
+    /// * `action` - an action to trigger. See [`ShortcutAction`] for more details.
+    ///
+    /// # Returns
+    /// Returns empty result on success. Otherwise returns [`UiError::ShortcutConflict`]
+    /// if the combination is already used by another shortcut or other [`UiError`].
+    pub fn add<I, D>(
+        &mut self,
+        id: I,
+        description: D,
+        default: Shortcut,
+        action: ShortcutAction,
+    ) -> Result<()>
+    where
+        I: Into<String>,
+        D: Into<String>,
+    {
+        let id = id.into();
+        let default = default.normalized();
+        let shortcut = self
+            .customized
+            .iter()
+            .find(|(customized, _)| *customized == id)
+            .map(|(_, shortcut)| *shortcut)
+            .unwrap_or(default);
+
+        self.check_conflict(&id, shortcut)?;
+        let description = description.into();
+        let action = Rc::new(RefCell::new(action));
+        let record = register(shortcut, &description, &action)?;
+        self.remove(&id);
+        self.bindings.push(Binding {
+            id,
+            description,
+            default,
+            shortcut,
+            action,
+            _record: record,
+        });
+        Ok(())
+    }
+
+    /// Unregisters a shortcut. The customized combination is kept.
+    ///
+    /// # Arguments
+    /// * `id` - a shortcut identifier.
+    pub fn remove(&mut self, id: &str) {
+        self.bindings.retain(|binding| binding.id != id);
+    }
+
+    /// Changes a shortcut combination and persists it.
+    ///
+    /// # Arguments
+    /// * `id` - a shortcut identifier.
+    /// * `shortcut` - a new key combination.
+    ///
+    /// # Returns
+    /// Returns empty result on success. Otherwise returns [`UiError::ShortcutConflict`]
+    /// if the combination is already used by another shortcut or other [`UiError`].
+    pub fn rebind(&mut self, id: &str, shortcut: Shortcut) -> Result<()> {
+        let shortcut = shortcut.normalized();
+        self.check_conflict(id, shortcut)?;
+        let binding = self
+            .bindings
+            .iter_mut()
+            .find(|binding| binding.id == id)
+            .ok_or_else(|| UiError::ShortcutNotFound(id.to_string()))?;
+
+        if binding.shortcut != shortcut {
+            binding._record = register(shortcut, &binding.description, &binding.action)?;
+            binding.shortcut = shortcut;
+        }
+
+        self.customized.retain(|(customized, _)| customized != id);
+        if shortcut != binding.default {
+            self.customized.push((id.to_string(), shortcut));
+        }
+
+        self.store()
+    }
+
+    /// Restores the default combination of a shortcut and persists it.
+    ///
+    /// # Arguments
+    /// * `id` - a shortcut identifier.
+    ///
+    /// # Returns
+    /// Returns empty result on success. Otherwise returns [`UiError`].
+    pub fn reset(&mut self, id: &str) -> Result<()> {
+        let default = self
+            .bindings
+            .iter()
+            .find(|binding| binding.id == id)
+            .map(|binding| binding.default)
+            .ok_or_else(|| UiError::ShortcutNotFound(id.to_string()))?;
+        self.rebind(id, default)
+    }
+
+    /// Returns the current combination of a shortcut.
+    ///
+    /// # Arguments
+    /// * `id` - a shortcut identifier.
+    ///
+    /// # Returns
+    /// Returns [`Shortcut`] or [`None`] if the shortcut is not registered.
+    pub fn shortcut(&self, id: &str) -> Option<Shortcut> {
+        self.bindings
+            .iter()
+            .find(|binding| binding.id == id)
+            .map(|binding| binding.shortcut)
+    }
+
+    /// Finds the shortcut which uses a key combination.
+    ///
+    /// # Arguments
+    /// * `shortcut` - a key combination.
+    ///
+    /// # Returns
+    /// Returns the shortcut identifier or [`None`] if the combination is free.
+    pub fn find(&self, shortcut: Shortcut) -> Option<&str> {
+        let shortcut = shortcut.normalized();
+        self.bindings
+            .iter()
+            .find(|binding| binding.shortcut == shortcut)
+            .map(|binding| binding.id.as_str())
+    }
+
+    /// Lists the registered shortcuts, e.g. to show them in a settings window.
+    ///
+    /// # Returns
+    /// Returns an iterator over identifiers, descriptions and combinations.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str, Shortcut)> {
+        self.bindings.iter().map(|binding| {
+            (
+                binding.id.as_str(),
+                binding.description.as_str(),
+                binding.shortcut,
+            )
+        })
+    }
+
+    fn check_conflict(&self, id: &str, shortcut: Shortcut) -> Result<()> {
+        match self.find(shortcut) {
+            Some(existing) if existing != id => Err(UiError::ShortcutConflict {
+                shortcut: shortcut.to_string(),
+                id: existing.to_string(),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    fn store(&self) -> Result<()> {
+        let shortcuts = self
+            .customized
+            .iter()
+            .map(|(id, shortcut)| shortcut.to_value(id))
+            .collect::<Vec<_>>();
+        Ok(self.prefs.save(&shortcuts)?)
+    }
+}
+
+fn register(
+    shortcut: Shortcut,
+    description: &str,
+    action: &Rc<RefCell<ShortcutAction>>,
+) -> Result<HotKeyRecord> {
+    let action = action.clone();
+    let record = register_hot_key(shortcut.key, shortcut.modifiers, description, move || {
+        action.borrow_mut().run()
+    })?;
+    Ok(record)
+}
@@ -0,0 +1,225 @@
+use std::time::{Duration, Instant};
+
+use crate::api::data_access::{find_data_ref_opt, get_data_vf, DataRef};
+use crate::api::display::Color;
+
+/// Default interval between two theme checks of [`ThemeWatcher::poll`].
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Names of the X-Plane UI color data refs, each is an array of three floats.
+const COLOR_DATA_REFS: [&str; 10] = [
+    "sim/graphics/colors/background_rgb",
+    "sim/graphics/colors/menu_dark_rgb",
+    "sim/graphics/colors/menu_hilite_rgb",
+    "sim/graphics/colors/menu_lite_rgb",
+    "sim/graphics/colors/menu_text_rgb",
+    "sim/graphics/colors/menu_text_disabled_rgb",
+    "sim/graphics/colors/subtitle_text_rgb",
+    "sim/graphics/colors/tab_front_rgb",
+    "sim/graphics/colors/tab_back_rgb",
+    "sim/graphics/colors/caption_text_rgb",
+];
+
+/// The X-Plane UI color palette, so plugin windows can match the simulator UI
+/// instead of hard-coding their colors.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Theme {
+    /// Window background.
+    pub background: Color,
+    /// Dark menu and panel surfaces.
+    pub menu_dark: Color,
+    /// Highlighted menu items and selections.
+    pub menu_hilite: Color,
+    /// Light menu and panel surfaces.
+    pub menu_lite: Color,
+    /// Menu and regular text.
+    pub menu_text: Color,
+    /// Disabled text.
+    pub menu_text_disabled: Color,
+    /// Subtitles and secondary text.
+    pub subtitle_text: Color,
+    /// The active tab.
+    pub tab_front: Color,
+    /// Inactive tabs.
+    pub tab_back: Color,
+    /// Window captions.
+    pub caption_text: Color,
+}
+
+impl Theme {
+    /// Reads the current palette. Colors whose data refs are missing keep
+    /// the [`Theme::default`] values.
+    ///
+    /// # Returns
+    /// Returns the current [`Theme`].
+    pub fn current() -> Self {
+        ThemeReader::new().read()
+    }
+
+    /// Checks wether the theme is dark, i.e. the background is darker than the text.
+    pub fn is_dark(&self) -> bool {
+        luminance(&self.background) < luminance(&self.menu_text)
+    }
+
+    /// Returns the colors in the order of [`COLOR_DATA_REFS`].
+    fn colors_mut(&mut self) -> [&mut Color; 10] {
+        [
+            &mut self.background,
+            &mut self.menu_dark,
+            &mut self.menu_hilite,
+            &mut self.menu_lite,
+            &mut self.menu_text,
+            &mut self.menu_text_disabled,
+            &mut self.subtitle_text,
+            &mut self.tab_front,
+            &mut self.tab_back,
+            &mut self.caption_text,
+        ]
+    }
+}
+
+impl Default for Theme {
+    /// Returns a dark palette close to the X-Plane 12 defaults.
+    fn default() -> Self {
+        Self {
+            background: Color::from_rgb(34, 40, 48),
+            menu_dark: Color::from_rgb(24, 28, 34),
+            menu_hilite: Color::from_rgb(45, 110, 190),
+            menu_lite: Color::from_rgb(60, 68, 78),
+            menu_text: Color::from_rgb(230, 232, 235),
+            menu_text_disabled: Color::from_rgb(120, 125, 130),
+            subtitle_text: Color::from_rgb(170, 176, 184),
+            tab_front: Color::from_rgb(60, 68, 78),
+            tab_back: Color::from_rgb(34, 40, 48),
+            caption_text: Color::from_rgb(230, 232, 235),
+        }
+    }
+}
+
+/// Color data refs looked up once.
+struct ThemeReader {
+    data_refs: Vec<Option<DataRef>>,
+}
+
+impl ThemeReader {
+    fn new() -> Self {
+        Self {
+            data_refs: COLOR_DATA_REFS
+                .iter()
+                .map(|name| find_data_ref_opt(*name))
+                .collect(),
+        }
+    }
+
+    fn read(&self) -> Theme {
+        let mut theme = Theme::default();
+        for (color, data_ref) in theme.colors_mut().into_iter().zip(&self.data_refs) {
+            let Some(data_ref) = data_ref else {
+                continue;
+            };
+
+            let mut rgb = [0.0; 3];
+            if get_data_vf(data_ref, 0, &mut rgb) == rgb.len() {
+                *color = Color::new(rgb[0], rgb[1], rgb[2]);
+            }
+        }
+
+        theme
+    }
+}
+
+type ThemeChangedCallback = Box<dyn FnMut(&Theme)>;
+
+/// Detects UI theme changes, e.g. when the user switches the X-Plane 12 UI theme.
+pub struct ThemeWatcher {
+    reader: ThemeReader,
+    current: Theme,
+    interval: Duration,
+    next_check: Instant,
+    callbacks: Vec<ThemeChangedCallback>,
+}
+
+impl ThemeWatcher {
+    /// Creates a new watcher and reads the current palette.
+    ///
+    /// # Returns
+    /// Returns a new [`ThemeWatcher`] instance.
+    pub fn new() -> Self {
+        let reader = ThemeReader::new();
+        Self {
+            current: reader.read(),
+            reader,
+            interval: DEFAULT_POLL_INTERVAL,
+            next_check: Instant::now() + DEFAULT_POLL_INTERVAL,
+            callbacks: Vec::new(),
+        }
+    }
+
+    /// Sets the interval between two checks of [`ThemeWatcher::poll`].
+    ///
+    /// # Arguments
+    /// * `interval` - a check interval, see [`DEFAULT_POLL_INTERVAL`].
+    ///
+    /// # Returns
+    /// Returns a modified watcher.
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Returns the palette read by the last check.
+    pub fn current(&self) -> &Theme {
+        &self.current
+    }
+
+    /// Registers a callback which is called every time the palette changes.
+    ///
+    /// # Arguments
+    /// * `callback` - a callback which receives the new palette.
+    pub fn on_theme_changed<F: FnMut(&Theme) + 'static>(&mut self, callback: F) {
+        self.callbacks.push(Box::new(callback));
+    }
+
+    /// Checks the palette once the interval has passed.
+    /// Should be called periodically, e.g. from a flight loop or a draw callback.
+    ///
+    /// # Returns
+    /// Returns `true` if the palette has changed.
+    pub fn poll(&mut self) -> bool {
+        let now = Instant::now();
+        if now < self.next_check {
+            return false;
+        }
+
+        self.next_check = now + self.interval;
+        self.check()
+    }
+
+    /// Reads the palette and calls the callbacks if it has changed.
+    ///
+    /// # Returns
+    /// Returns `true` if the palette has changed.
+    pub fn check(&mut self) -> bool {
+        let theme = self.reader.read();
+        if theme == self.current {
+            return false;
+        }
+
+        for callback in self.callbacks.iter_mut() {
+            callback(&theme);
+        }
+
+        self.current = theme;
+        true
+    }
+}
+
+impl Default for ThemeWatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn luminance(color: &Color) -> f32 {
+    0.2126 * color.r + 0.7152 * color.g + 0.0722 * color.b
+}
@@ -0,0 +1,251 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use crate::api::display::{
+    get_screen_size, register_draw_callback, Color, Coord, DrawCallbackRecord, DrawPhase, Rect,
+};
+use crate::api::graphics::{self, font_metrics, measure_string, Font};
+
+const FONT: Font = Font::Proportional;
+const PADDING: i32 = 8;
+const MARGIN: i32 = 16;
+const SPACING: i32 = 6;
+const DEFAULT_DURATION: Duration = Duration::from_secs(4);
+const DEFAULT_FADE: Duration = Duration::from_millis(600);
+const DEFAULT_MAX_VISIBLE: usize = 3;
+/// Text can't be drawn translucent, so it is hidden once the toast is mostly faded out.
+const TEXT_VISIBILITY_THRESHOLD: f32 = 0.3;
+
+/// A corner of the screen toasts are stacked in.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum ToastCorner {
+    /// The top left screen corner.
+    TopLeft,
+    /// The top right screen corner.
+    #[default]
+    TopRight,
+    /// The bottom left screen corner.
+    BottomLeft,
+    /// The bottom right screen corner.
+    BottomRight,
+}
+
+struct ToastEntry {
+    message: String,
+    shown_at: Option<Instant>,
+}
+
+struct ToastState {
+    corner: ToastCorner,
+    duration: Duration,
+    fade: Duration,
+    max_visible: usize,
+    background: Color,
+    text: Color,
+    queue: VecDeque<ToastEntry>,
+}
+
+impl ToastState {
+    /// Removes expired toasts and starts the timers of the toasts which became visible.
+    fn update(&mut self, now: Instant) {
+        let duration = self.duration;
+        self.queue.retain(|toast| {
+            !toast
+                .shown_at
+                .is_some_and(|shown_at| now.duration_since(shown_at) >= duration)
+        });
+
+        for toast in self.queue.iter_mut().take(self.max_visible) {
+            toast.shown_at.get_or_insert(now);
+        }
+    }
+
+    /// Returns the opacity of a toast in range 0.0..=1.0.
+    fn opacity(&self, shown_at: Instant, now: Instant) -> f32 {
+        let remaining = self.duration.saturating_sub(now.duration_since(shown_at));
+        if remaining >= self.fade || self.fade.is_zero() {
+            1.0
+        } else {
+            remaining.as_secs_f32() / self.fade.as_secs_f32()
+        }
+    }
+
+    fn draw(&mut self) {
+        let now = Instant::now();
+        self.update(now);
+
+        let screen = get_screen_size();
+        let line_height = font_metrics(FONT).char_height;
+        let height = line_height + 2 * PADDING;
+        let mut offset = MARGIN;
+        for toast in self.queue.iter().take(self.max_visible) {
+            let Some(shown_at) = toast.shown_at else {
+                continue;
+            };
+
+            let width =
+                measure_string(FONT, &toast.message).unwrap_or_default() as i32 + 2 * PADDING;
+            let (left, top) = match self.corner {
+                ToastCorner::TopLeft => (MARGIN, screen.height - offset),
+                ToastCorner::TopRight => (screen.width - MARGIN - width, screen.height - offset),
+                ToastCorner::BottomLeft => (MARGIN, offset + height),
+                ToastCorner::BottomRight => (screen.width - MARGIN - width, offset + height),
+            };
+            let rect = Rect::new(left, top, left + width, top - height);
+
+            let opacity = self.opacity(shown_at, now);
+            let mut background = self.background;
+            background.a *= opacity;
+            graphics::shapes::draw_rect_filled(&rect, &background);
+            if opacity > TEXT_VISIBILITY_THRESHOLD {
+                let coord = Coord::new(left + PADDING, rect.bottom + PADDING);
+                if let Err(err) = graphics::draw_string(&toast.message, FONT, &self.text, &coord) {
+                    crate::error!("{}", err);
+                }
+            }
+
+            offset += height + SPACING;
+        }
+    }
+}
+
+/// Shows transient translucent messages stacked in a corner of the screen.
+///
+/// Messages are queued, at most [`ToastManager::max_visible`] toasts are shown at once and
+/// each toast fades out at the end of its duration. Toasts are drawn from a draw callback,
+/// so they are visible over the sim but don't receive clicks. Dropping the manager hides
+/// all toasts.
+pub struct ToastManager {
+    state: Rc<RefCell<ToastState>>,
+    _record: DrawCallbackRecord,
+}
+
+impl ToastManager {
+    /// Creates a new toast manager and registers its draw callback.
+    ///
+    /// # Returns
+    /// Returns a new [`ToastManager`] instance.
+    pub fn new() -> Self {
+        let state = Rc::new(RefCell::new(ToastState {
+            corner: ToastCorner::default(),
+            duration: DEFAULT_DURATION,
+            fade: DEFAULT_FADE,
+            max_visible: DEFAULT_MAX_VISIBLE,
+            background: Color::new_rgba(0.1, 0.1, 0.1, 0.8),
+            text: Color::white(),
+            queue: VecDeque::new(),
+        }));
+
+        let draw_state = state.clone();
+        let record = register_draw_callback(DrawPhase::Window, false, move |_, _| {
+            if let Ok(mut state) = draw_state.try_borrow_mut() {
+                state.draw();
+            }
+            true
+        });
+
+        Self {
+            state,
+            _record: record,
+        }
+    }
+
+    /// Sets the screen corner toasts are stacked in.
+    ///
+    /// # Arguments
+    /// * `value` - a screen corner.
+    ///
+    /// # Returns
+    /// Returns a modified toast manager.
+    pub fn corner(self, value: ToastCorner) -> Self {
+        self.state.borrow_mut().corner = value;
+        self
+    }
+
+    /// Sets how long each toast is shown, including the fade out.
+    ///
+    /// # Arguments
+    /// * `value` - a toast duration.
+    ///
+    /// # Returns
+    /// Returns a modified toast manager.
+    pub fn duration(self, value: Duration) -> Self {
+        self.state.borrow_mut().duration = value;
+        self
+    }
+
+    /// Sets the fade out duration at the end of each toast.
+    ///
+    /// # Arguments
+    /// * `value` - a fade out duration.
+    ///
+    /// # Returns
+    /// Returns a modified toast manager.
+    pub fn fade(self, value: Duration) -> Self {
+        self.state.borrow_mut().fade = value;
+        self
+    }
+
+    /// Sets the maximum number of toasts shown at once. Other toasts wait in the queue.
+    ///
+    /// # Arguments
+    /// * `value` - a maximum number of visible toasts, at least 1.
+    ///
+    /// # Returns
+    /// Returns a modified toast manager.
+    pub fn max_visible(self, value: usize) -> Self {
+        self.state.borrow_mut().max_visible = value.max(1);
+        self
+    }
+
+    /// Sets the toast colors.
+    ///
+    /// # Arguments
+    /// * `background` - a background color, its alpha channel is respected.
+    /// * `text` - a text color.
+    ///
+    /// # Returns
+    /// Returns a modified toast manager.
+    pub fn colors(self, background: Color, text: Color) -> Self {
+        {
+            let mut state = self.state.borrow_mut();
+            state.background = background;
+            state.text = text;
+        }
+        self
+    }
+
+    /// Queues a toast.
+    ///
+    /// # Arguments
+    /// * `message` - a message to show.
+    pub fn show<T: Into<String>>(&self, message: T) {
+        self.state.borrow_mut().queue.push_back(ToastEntry {
+            message: message.into(),
+            shown_at: None,
+        });
+    }
+
+    /// Removes all visible and queued toasts.
+    pub fn clear(&self) {
+        self.state.borrow_mut().queue.clear();
+    }
+
+    /// Returns the number of visible and queued toasts.
+    pub fn len(&self) -> usize {
+        self.state.borrow().queue.len()
+    }
+
+    /// Checks whether there are no visible or queued toasts.
+    pub fn is_empty(&self) -> bool {
+        self.state.borrow().queue.is_empty()
+    }
+}
+
+impl Default for ToastManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,31 @@
+//! Checks the values command handlers return to X-Plane.
+
+use std::os::raw::c_int;
+
+use xplm::api::utilities::CommandOutcome;
+
+#[test]
+fn pass_through_lets_x_plane_process_the_command() {
+    assert_eq!(c_int::from(CommandOutcome::PassThrough), 1);
+    assert!(!CommandOutcome::PassThrough.is_consumed());
+}
+
+#[test]
+fn consume_stops_the_command_processing() {
+    assert_eq!(c_int::from(CommandOutcome::Consume), 0);
+    assert!(CommandOutcome::Consume.is_consumed());
+}
+
+#[test]
+fn raw_values_map_back_to_outcomes() {
+    assert_eq!(CommandOutcome::from(0), CommandOutcome::Consume);
+    assert_eq!(CommandOutcome::from(1), CommandOutcome::PassThrough);
+    assert_eq!(CommandOutcome::from(-1), CommandOutcome::PassThrough);
+}
+
+#[test]
+fn outcomes_round_trip() {
+    for outcome in [CommandOutcome::PassThrough, CommandOutcome::Consume] {
+        assert_eq!(CommandOutcome::from(c_int::from(outcome)), outcome);
+    }
+}
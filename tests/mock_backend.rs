@@ -0,0 +1,183 @@
+//! Headless tests of the crate logic against the in-memory fake of X-Plane.
+//! Run with `cargo test --features mock`.
+
+#![cfg(feature = "mock")]
+
+use std::fs;
+use std::rc::Rc;
+
+use xplm::api::backend::fake::{self, FakeData};
+use xplm::api::data_access::{find_cached, invalidate_lookup_cache, DataAccessError, DataRefArray};
+use xplm::api::display::{
+    self, Coord, EventState, KeyFlags, MouseStatus, Rect, WheelAxis, WindowHandler, WindowId,
+    WindowLayer,
+};
+use xplm::api::utilities::{
+    audit_command_balance, command_begin, command_end, command_once, find_command, VirtualKey,
+};
+use xplm::sim::aircraft::{list_liveries, set_livery};
+
+const ARRAY: &str = "test/array";
+const COMMAND: &str = "test/command";
+
+#[test]
+fn array_read_past_the_end_is_rejected() {
+    fake::reset();
+    fake::define_data_ref(ARRAY, FakeData::FloatArray(vec![1.0, 2.0, 3.0, 4.0]));
+    let array = DataRefArray::<f32>::find(ARRAY).unwrap();
+    let mut values = [0.0; 2];
+
+    assert_eq!(array.read_at(2, &mut values).unwrap(), 2);
+    assert_eq!(values, [3.0, 4.0]);
+    assert_eq!(array.read_at(4, &mut values).unwrap(), 0);
+    assert!(matches!(
+        array.read_at(5, &mut values),
+        Err(DataAccessError::OffsetOutOfBounds { .. })
+    ));
+}
+
+#[test]
+fn array_write_past_the_end_is_rejected() {
+    fake::reset();
+    fake::define_data_ref(ARRAY, FakeData::FloatArray(vec![0.0; 4]));
+    let array = DataRefArray::<f32>::find(ARRAY).unwrap();
+
+    array.write_at(2, &[5.0, 6.0]).unwrap();
+    assert!(matches!(
+        array.write_at(3, &[7.0, 8.0]),
+        Err(DataAccessError::OffsetOutOfBounds { .. })
+    ));
+    assert_eq!(
+        fake::data(ARRAY),
+        Some(FakeData::FloatArray(vec![0.0, 0.0, 5.0, 6.0]))
+    );
+}
+
+#[test]
+fn find_cached_reuses_the_lookup() {
+    fake::reset();
+    invalidate_lookup_cache();
+    fake::define_data_ref(ARRAY, FakeData::FloatArray(vec![0.0]));
+
+    let first = find_cached(ARRAY).unwrap();
+    let second = find_cached(ARRAY).unwrap();
+    assert!(Rc::ptr_eq(&first, &second));
+
+    invalidate_lookup_cache();
+    let third = find_cached(ARRAY).unwrap();
+    assert!(!Rc::ptr_eq(&first, &third));
+    assert_eq!(**first, **third);
+}
+
+#[test]
+fn find_cached_does_not_cache_missing_data_refs() {
+    fake::reset();
+    invalidate_lookup_cache();
+
+    assert!(find_cached(ARRAY).is_err());
+    fake::define_data_ref(ARRAY, FakeData::Int(1));
+    assert!(find_cached(ARRAY).is_ok());
+}
+
+#[test]
+fn unbalanced_commands_are_reported() {
+    fake::reset();
+    fake::define_command(COMMAND);
+    let command = find_command(COMMAND).unwrap().unwrap();
+
+    command_begin(&command);
+    command_begin(&command);
+    command_end(&command);
+    command_once(&command);
+
+    let stats = fake::command_stats(COMMAND).unwrap();
+    assert_eq!((stats.begin, stats.end, stats.once), (2, 1, 1));
+    assert!(stats.is_active());
+    if cfg!(debug_assertions) {
+        assert_eq!(audit_command_balance(), 1);
+        assert!(fake::log_messages()
+            .iter()
+            .any(|message| message.contains("began 1 times without end")));
+    }
+
+    command_end(&command);
+    assert!(!fake::command_stats(COMMAND).unwrap().is_active());
+    assert_eq!(audit_command_balance(), 0);
+}
+
+struct EmptyWindow;
+
+impl WindowHandler for EmptyWindow {
+    fn draw(&mut self, _id: &WindowId) {}
+
+    fn mouse_click(&mut self, _id: &WindowId, _coord: Coord, _status: MouseStatus) -> EventState {
+        EventState::Propagate
+    }
+
+    fn handle_key(&mut self, _id: &WindowId, _key: char, _key_code: VirtualKey, _flags: KeyFlags) {}
+
+    fn handle_cursor(&mut self, _id: &WindowId, _coord: Coord) {}
+
+    fn handle_mouse_wheel(
+        &mut self,
+        _id: &WindowId,
+        _coord: Coord,
+        _wheel_axis: WheelAxis,
+        _clicks: i32,
+    ) -> EventState {
+        EventState::Propagate
+    }
+}
+
+#[test]
+fn window_geometry_round_trips() {
+    fake::reset();
+    let rect = Rect::new(10, 200, 110, 100);
+    let record =
+        display::create_window_in_layer(&rect, WindowLayer::FloatingWindows, EmptyWindow).unwrap();
+
+    assert_eq!(fake::window_count(), 1);
+    assert_eq!(display::get_window_geometry(&record.id), rect);
+    assert!(!fake::is_window_visible(&record.id));
+
+    let moved = Rect::new(20, 220, 140, 120);
+    display::set_window_geometry(&record.id, &moved);
+    assert_eq!(fake::window_geometry(&record.id), Some(moved));
+    assert_eq!(display::get_window_geometry(&record.id), moved);
+
+    drop(record);
+    assert_eq!(fake::window_count(), 0);
+}
+
+#[test]
+fn liveries_follow_the_livery_index_order() {
+    fake::reset();
+    let aircraft_dir = std::env::temp_dir().join(format!("xplm_liveries_{}", std::process::id()));
+    let liveries_dir = aircraft_dir.join("liveries");
+    for livery in ["b", "A", "C"] {
+        fs::create_dir_all(liveries_dir.join(livery)).unwrap();
+    }
+    fs::write(liveries_dir.join("notes.txt"), "").unwrap();
+    fake::set_aircraft_path(aircraft_dir.join("test.acf"));
+    fake::define_data_ref("sim/aircraft/view/acf_livery_index", FakeData::Int(0));
+
+    let names: Vec<_> = list_liveries()
+        .unwrap()
+        .iter()
+        .map(|livery| livery.file_name().unwrap().to_string_lossy().into_owned())
+        .collect();
+    assert_eq!(names, ["A", "b", "C"]);
+
+    set_livery(Some("b")).unwrap();
+    assert_eq!(
+        fake::data("sim/aircraft/view/acf_livery_index"),
+        Some(FakeData::Int(2))
+    );
+    set_livery(None::<&str>).unwrap();
+    assert_eq!(
+        fake::data("sim/aircraft/view/acf_livery_index"),
+        Some(FakeData::Int(0))
+    );
+
+    fs::remove_dir_all(aircraft_dir).unwrap();
+}
@@ -0,0 +1,194 @@
+//! Procedural macros for the `xplm` crate, re-exported by it with the `macros` feature enabled.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
+use syn::{Expr, FnArg, ImplItem, ItemImpl, Lit, MetaNameValue, ReturnType, Token};
+
+const PHASES: [&str; 3] = ["on_begin", "on_continue", "on_end"];
+
+/// Implements `CommandHandler` for a type from annotated methods of an inherent impl block.
+///
+/// Methods annotated with `#[on_begin]`, `#[on_continue]` and `#[on_end]` take `&mut self`
+/// and return either `CommandOutcome` or nothing, in which case the command passes through.
+/// Phases without a method pass the command through.
+///
+/// With `command` and optional `description` arguments a `register(self)` method is
+/// generated, which creates the command and registers the handler before X-Plane.
+///
+/// ```ignore
+/// struct GearToggle { down: bool }
+///
+/// #[command_handler(command = "my_plugin/gear/toggle", description = "Toggle gear")]
+/// impl GearToggle {
+///     #[on_begin]
+///     fn toggle(&mut self) -> CommandOutcome {
+///         self.down = !self.down;
+///         CommandOutcome::Consume
+///     }
+/// }
+///
+/// let record = GearToggle { down: true }.register()?;
+/// ```
+#[proc_macro_attribute]
+pub fn command_handler(args: TokenStream, input: TokenStream) -> TokenStream {
+    expand(args.into(), input.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(args: TokenStream2, input: TokenStream2) -> syn::Result<TokenStream2> {
+    let args = Punctuated::<MetaNameValue, Token![,]>::parse_terminated.parse2(args)?;
+    let mut item = syn::parse2::<ItemImpl>(input)?;
+    if let Some((_, path, _)) = &item.trait_ {
+        return Err(syn::Error::new_spanned(
+            path,
+            "command_handler expects an inherent impl block",
+        ));
+    }
+
+    let mut command = None;
+    let mut description = None;
+    for arg in &args {
+        let value = string_value(&arg.value)?;
+        if arg.path.is_ident("command") {
+            command = Some(value);
+        } else if arg.path.is_ident("description") {
+            description = Some(value);
+        } else {
+            return Err(syn::Error::new_spanned(
+                &arg.path,
+                "expected `command` or `description`",
+            ));
+        }
+    }
+
+    let mut phases: [Option<TokenStream2>; 3] = Default::default();
+    for impl_item in &mut item.items {
+        let ImplItem::Fn(method) = impl_item else {
+            continue;
+        };
+
+        let mut phase = None;
+        let mut error = None;
+        method.attrs.retain(|attr| {
+            match PHASES.iter().position(|name| attr.path().is_ident(name)) {
+                Some(index) if phase.is_some() => {
+                    error = Some(syn::Error::new_spanned(
+                        attr,
+                        format!(
+                            "method is already bound to a phase, remove #[{}]",
+                            PHASES[index]
+                        ),
+                    ));
+                    false
+                }
+                Some(index) => {
+                    phase = Some(index);
+                    false
+                }
+                None => true,
+            }
+        });
+
+        if let Some(error) = error {
+            return Err(error);
+        }
+
+        let Some(index) = phase else {
+            continue;
+        };
+
+        if phases[index].is_some() {
+            return Err(syn::Error::new_spanned(
+                &method.sig.ident,
+                format!("more than one #[{}] method", PHASES[index]),
+            ));
+        }
+
+        let takes_mut_self = matches!(
+            method.sig.inputs.first(),
+            Some(FnArg::Receiver(receiver)) if receiver.mutability.is_some() && receiver.reference.is_some()
+        );
+        if !takes_mut_self || method.sig.inputs.len() != 1 {
+            return Err(syn::Error::new_spanned(
+                &method.sig,
+                "command phase methods must take only `&mut self`",
+            ));
+        }
+
+        let ident = &method.sig.ident;
+        phases[index] = Some(match method.sig.output {
+            ReturnType::Default => quote! {
+                self.#ident();
+                ::xplm::api::utilities::CommandOutcome::PassThrough
+            },
+            ReturnType::Type(..) => quote! { self.#ident() },
+        });
+    }
+
+    if phases.iter().all(Option::is_none) {
+        return Err(syn::Error::new_spanned(
+            &item.self_ty,
+            "expected at least one #[on_begin], #[on_continue] or #[on_end] method",
+        ));
+    }
+
+    let [begin, continue_, end] = phases.map(|body| {
+        body.unwrap_or_else(|| quote! { ::xplm::api::utilities::CommandOutcome::PassThrough })
+    });
+    let self_ty = &item.self_ty;
+    let (impl_generics, _, where_clause) = item.generics.split_for_impl();
+    let register = command.map(|command| {
+        let description = description.unwrap_or_default();
+        quote! {
+            impl #impl_generics #self_ty #where_clause {
+                /// Creates the command and registers the handler before X-Plane.
+                ///
+                /// # Returns
+                /// Returns a record which keeps the handler registered on success.
+                /// Otherwise returns [`::xplm::XplmError`].
+                pub fn register(self) -> ::xplm::Result<::xplm::api::utilities::CommandHandlerRecord> {
+                    let command = ::xplm::api::utilities::create_command(#command, #description)?;
+                    Ok(::xplm::api::utilities::register_command_handler(
+                        &command,
+                        ::xplm::api::utilities::CommandExecutionTime::BeforeXPlane,
+                        self,
+                    ))
+                }
+            }
+        }
+    });
+
+    Ok(quote! {
+        #item
+
+        impl #impl_generics ::xplm::api::utilities::CommandHandler for #self_ty #where_clause {
+            fn command_begin(&mut self) -> ::xplm::api::utilities::CommandOutcome {
+                #begin
+            }
+
+            fn command_continue(&mut self) -> ::xplm::api::utilities::CommandOutcome {
+                #continue_
+            }
+
+            fn command_end(&mut self) -> ::xplm::api::utilities::CommandOutcome {
+                #end
+            }
+        }
+
+        #register
+    })
+}
+
+fn string_value(expr: &Expr) -> syn::Result<String> {
+    match expr {
+        Expr::Lit(expr) => match &expr.lit {
+            Lit::Str(value) => Ok(value.value()),
+            lit => Err(syn::Error::new_spanned(lit, "expected a string literal")),
+        },
+        expr => Err(syn::Error::new_spanned(expr, "expected a string literal")),
+    }
+}